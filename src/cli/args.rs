@@ -1,10 +1,17 @@
-use clap::{Parser, ValueEnum};
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
 use anyhow::{Context, Result};
 use thiserror::Error;
-use log::{info, warn, debug};
+use log::{info, warn, debug, LevelFilter};
+use std::collections::BTreeMap;
 use std::path::Path;
+use std::time::SystemTime;
 use crate::core::traversal::TraversalMode;
-use crate::core::config::FileSearchConfig;
+use crate::filters::{date, ContentKind, DateZone, EntryType, IgnoreFilter, RegexSyntax, SizeConstraint, TypeGroupFilter};
+use crate::filters::audit::AuditRule;
+use crate::commands::ConfigFormat;
+use crate::core::config::{BinaryMode, ConfigSource, DocType, FileSearchConfig, InputOrder, OutputFormat, ProgressDisplay, SortOrder, Walker};
+use crate::output::{ColorChoice, GroupBy, HistogramKind, ReportFormat, Template, ThemePreset};
+use crate::utils::documents::DocumentExtractors;
 use regex;
 
 /// Errors related to command-line argument processing
@@ -30,8 +37,12 @@ pub enum ArgsError {
 #[command(version = "1.0.0")]
 #[command(about = "High-performance file search utility")]
 #[command(disable_help_flag = true)]
-#[command(override_usage = "oqab [QUERY]\n       oqab [OPTIONS] [QUERY]\n       oqab --grep PATTERN [OPTIONS]")]
+#[command(override_usage = "oqab [QUERY]\n       oqab [OPTIONS] [QUERY]\n       oqab --grep PATTERN [OPTIONS]\n       oqab serve --http ADDR [OPTIONS]")]
 pub struct Args {
+    /// Subcommand to run instead of a one-off search
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+    
     /// Search query (file name pattern or text to search for)
     /// Examples: 'main.rs', '*.txt', 'config', etc.
     #[arg(index = 1)]
@@ -41,15 +52,27 @@ pub struct Args {
     #[arg(short = 'h', long = "help")]
     pub help: bool,
 
-    /// Directory to search (defaults to current directory, use '/' for root)
+    /// Directory to search (defaults to the current directory)
     #[arg(short = 'p', long = "path")]
     pub path: Option<String>,
 
+    /// Search the whole filesystem from its root instead of the current directory
+    #[arg(long = "system", conflicts_with = "path")]
+    pub system: bool,
+
+    /// Search the whole filesystem without asking for confirmation first
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
+
     /// File extension to search for (alternative to specifying in QUERY)
     /// Only use this if you want to override extension detection from QUERY
     #[arg(short = 'e', long = "ext")]
     pub extension: Option<String>,
 
+    /// Match file extensions case-sensitively (by default '.RS' matches 'rs')
+    #[arg(long = "case-sensitive-ext")]
+    pub case_sensitive_ext: bool,
+
     /// File name pattern to search for (alternative to using QUERY)
     /// Only use this if you need more complex patterns than QUERY allows
     #[arg(short = 'n', long = "name")]
@@ -59,30 +82,106 @@ pub struct Args {
     #[arg(short = 'g', long = "grep")]
     pub pattern: Option<String>,
     
-    /// Case insensitive search
+    /// Ignore case in content and in --ext, even with --case-sensitive-ext (--name always ignores case)
     #[arg(short = 'i', long = "ignore-case")]
     pub ignore_case: bool,
     
+    /// Regular expression syntax: default, literal, basic or extended
+    #[arg(long = "regex-syntax")]
+    pub regex_syntax: Option<SyntaxType>,
+    
     /// Show line numbers in search results
     #[arg(long = "line-number")]
     pub line_number: bool,
     
+    /// Treat NUL rather than newline as the record separator when searching content
+    #[arg(long = "null-data")]
+    pub null_data: bool,
+    
+    /// Search binary files, reporting only whether each one matches
+    #[arg(long = "binary", overrides_with = "text")]
+    pub binary: bool,
+    
+    /// Search binary files as if they were text
+    #[arg(long = "text", overrides_with = "binary")]
+    pub text: bool,
+    
+    /// Group matches under a heading per file (the default on a terminal)
+    #[arg(long = "heading", overrides_with = "no_heading")]
+    pub heading: bool,
+    
+    /// Print matches as file:line:text records (the default when piped)
+    #[arg(long = "no-heading", overrides_with = "heading")]
+    pub no_heading: bool,
+    
+    /// Leave file names out of match output
+    #[arg(long = "no-filename")]
+    pub no_filename: bool,
+    
+    /// Print every line of the searched files, flagging the matching ones
+    #[arg(long = "passthru", conflicts_with = "files_with_matches")]
+    pub passthru: bool,
+    
     /// Show only filenames of files containing the pattern
     #[arg(long = "files-with-matches")]
     pub files_with_matches: bool,
     
+    /// Print search statistics after a content search
+    #[arg(long = "stats")]
+    pub stats: bool,
+    
+    /// Break the statistics down by file size or age (implies --stats)
+    #[arg(long = "histogram", value_delimiter = ',')]
+    pub histogram: Vec<HistogramType>,
+    
+    /// Skip files larger than this when searching content (e.g., "10mb")
+    #[arg(long = "max-filesize", value_parser = parse_size)]
+    pub max_filesize: Option<u64>,
+    
+    /// Document types to extract text from when searching content (requires the `pdf`/`docx` features)
+    #[arg(long = "doc-types", value_delimiter = ',')]
+    pub doc_types: Vec<DocTypeArg>,
+    
+    /// Print content matches as files finish rather than in a stable order
+    #[arg(long = "unordered")]
+    pub unordered: bool,
+    
+    /// Content index file to build or use (default: per-directory file in the user cache)
+    #[arg(long = "index")]
+    pub index: Option<String>,
+    
+    /// Do not use a content index when searching file contents
+    #[arg(long = "no-index")]
+    pub no_index: bool,
+    
     /// Use advanced search algorithm
     #[arg(short = 'a', long = "advanced")]
     pub advanced: bool,
     
-    /// Suppress progress output
+    /// Suppress progress output and all diagnostics, printing only results
     #[arg(short = 's', long = "silent")]
     pub silent: bool,
+
+    /// Increase diagnostic output (-v informational, -vv debug)
+    #[arg(short = 'v', long = "verbose", action = ArgAction::Count)]
+    pub verbose: u8,
     
     /// Number of worker threads
     #[arg(short = 'w', long = "workers")]
     pub workers: Option<usize>,
     
+    /// Directory walker used by the advanced finder
+    #[arg(long = "walker")]
+    pub walker: Option<WalkerType>,
+    
+    /// How progress is drawn on a terminal
+    #[arg(long = "progress", value_name = "STYLE")]
+    pub progress: Option<ProgressType>,
+    
+    /// Count the directories to search first, for an exact percentage and time left
+    #[arg(long = "prescan")]
+    pub prescan: bool,
+    
     /// Load configuration from file
     #[arg(short = 'c', long = "config")]
     pub config_file: Option<String>,
@@ -90,18 +189,149 @@ pub struct Args {
     /// Save current configuration to file
     #[arg(long = "save-config")]
     pub save_config_file: Option<String>,
+    
+    /// Print the effective configuration and where each value came from, then exit
+    #[arg(long = "print-config", value_name = "FORMAT", num_args = 0..=1, default_missing_value = "toml")]
+    pub print_config: Option<ConfigFormatType>,
 
     /// Traversal type
     #[arg(short = 't', long = "traversal")]
     pub traversal: Option<TraversalType>,
 
-    /// Quiet mode
+    /// Quiet mode (errors only)
     #[arg(short = 'q', long = "quiet")]
     pub quiet: bool,
 
+    /// Suppress messages about unreadable paths (they are still counted)
+    #[arg(long = "no-messages")]
+    pub no_messages: bool,
+
+    /// Skip files and directories matching a gitignore-style pattern, such as `target/` or `*.min.js`
+    #[arg(long = "exclude", value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+
+    /// Also search node_modules, target, .git and other directories skipped by default
+    #[arg(long = "no-default-excludes")]
+    pub no_default_excludes: bool,
+
+    /// Don't apply the patterns of the global ignore file (~/.config/oqab/ignore)
+    #[arg(long = "no-ignore-global")]
+    pub no_ignore_global: bool,
+
+    /// Skip files and directories ignored by .gitignore files and .git/info/exclude
+    #[arg(long = "git-ignore")]
+    pub git_ignore: bool,
+
+    /// Fail if any directory or file could not be read
+    #[arg(long = "strict")]
+    pub strict: bool,
+
+    /// Output format for results
+    #[arg(long = "format")]
+    pub format: Option<FormatType>,
+    
+    /// Print bare paths ending in NUL instead of newline, for `xargs -0`
+    #[arg(short = '0', long = "print0")]
+    pub print0: bool,
+    
+    /// Print each result with a template such as '%p %s %t\n'
+    #[arg(long = "printf", value_name = "TEMPLATE")]
+    pub printf: Option<String>,
+    
+    /// List permissions, owner, size and modification time with each file, like `ls -l`
+    #[arg(short = 'l', long = "long", conflicts_with = "pattern")]
+    pub long: bool,
+    
+    /// Draw results as a tree under their directories
+    #[arg(long = "tree", conflicts_with = "long")]
+    pub tree: bool,
+
+    /// Order in which file results are printed
+    #[arg(long = "sort")]
+    pub sort: Option<SortType>,
+
+    /// Order in which candidate files are searched for content
+    #[arg(long = "sort-input")]
+    pub sort_input: Option<SortInputType>,
+
+    /// Print per-group counts and total sizes instead of listing results
+    #[arg(long = "group-by")]
+    pub group_by: Option<GroupByType>,
+
+    /// Browse results in a terminal UI as they are found (`tui` feature)
+    #[arg(short = 'I', long = "interactive",
+          conflicts_with_all = ["pattern", "fuzzy", "fuzzy_query", "group_by", "du", "report"])]
+    pub interactive: bool,
+
+    /// Report the N (default 10) largest directories and files by the total size of their matches
+    #[arg(long = "du", value_name = "N", num_args = 0..=1, require_equals = true, default_missing_value = "10",
+          conflicts_with_all = ["pattern", "fuzzy", "fuzzy_query", "select", "group_by", "tree", "long"])]
+    pub du: Option<usize>,
+
+    /// Write a Markdown or HTML report of the results, to --output if given
+    #[arg(long = "report", value_name = "FORMAT",
+          conflicts_with_all = ["pattern", "fuzzy", "fuzzy_query", "select", "group_by", "du"])]
+    pub report: Option<ReportFormatType>,
+
+    /// Wait for a key press after every N lines of results on a terminal
+    #[arg(long = "page-size", value_name = "N")]
+    pub page_size: Option<usize>,
+
+    /// Print results directly instead of piping long listings into $PAGER
+    #[arg(long = "no-pager")]
+    pub no_pager: bool,
+
+    /// Color scheme of text output
+    #[arg(long = "theme")]
+    pub theme: Option<ThemeType>,
+
+    /// When to color output
+    #[arg(long = "color", value_name = "WHEN")]
+    pub color: Option<ColorType>,
+
+    /// Print result paths as clickable file:// links on terminals that support them
+    #[arg(long = "hyperlinks")]
+    pub hyperlinks: bool,
+
+    /// File to write results into for file-based formats such as parquet, or a --report
+    #[arg(short = 'o', long = "output")]
+    pub output: Option<String>,
+
+    /// Also write results into a SQLite database, new or written by an earlier export
+    #[arg(long = "output-sqlite")]
+    pub output_sqlite: Option<String>,
+
     /// No recursive search
     #[arg(short = 'r', long = "no-recursive")]
     pub no_recursive: bool,
+    
+    /// Report nothing deeper than N levels below the search path; 1 is the path's own entries
+    #[arg(long = "max-depth", value_name = "N")]
+    pub max_depth: Option<usize>,
+    
+    /// Report nothing shallower than N levels below the search path, still searching through them
+    #[arg(long = "min-depth", value_name = "N")]
+    pub min_depth: Option<usize>,
+
+    /// Report only the first match in each directory
+    #[arg(long = "one-per-dir", conflicts_with = "max_per_dir")]
+    pub one_per_dir: bool,
+
+    /// Report at most N matches in each directory, skipping its remaining files
+    #[arg(long = "max-per-dir", value_name = "N")]
+    pub max_per_dir: Option<usize>,
+
+    /// Stop searching once N results have been found
+    #[arg(long = "max-results", value_name = "N")]
+    pub max_results: Option<usize>,
+
+    /// Match the query as a plain file name substring, without glob/regex/extension detection
+    #[arg(long = "literal")]
+    pub literal: bool,
+    
+    /// Match --name and re: queries against the whole path instead of the file name
+    #[arg(long = "full-path")]
+    pub full_path: bool,
 
     /// Enable fuzzy matching for file names
     #[arg(short = 'z', long = "fuzzy")]
@@ -111,25 +341,222 @@ pub struct Args {
     #[arg(long = "fuzzy-threshold")]
     pub fuzzy_threshold: Option<u8>,
 
+    /// Text to rank file names against in fuzzy mode (implies --fuzzy; the positional query by default)
+    #[arg(long = "fuzzy-query", value_name = "QUERY")]
+    pub fuzzy_query: Option<String>,
+    
+    /// Pick from the fuzzy matches and print only the chosen paths (implies --fuzzy,
+    /// except with --interactive, where Enter chooses the selected result)
+    #[arg(long = "select")]
+    pub select: bool,
+
     /// Follow symlinks
     #[arg(short = 'f', long = "follow-symlinks")]
     pub follow_symlinks: bool,
     
+    /// Report each file once under its canonical path, dropping links and hard links to it
+    #[arg(long = "unique")]
+    pub unique: bool,
+    
+    /// Stay on the filesystem of the search path, never entering mount points, like `find -xdev`
+    #[arg(long = "one-file-system")]
+    pub one_file_system: bool,
+    
+    /// Include hidden files and directories
+    #[arg(short = 'H', long = "hidden", overrides_with = "no_hidden")]
+    pub hidden: bool,
+    
+    /// Skip hidden files and directories, even when the config file includes them
+    #[arg(long = "no-hidden", overrides_with = "hidden")]
+    pub no_hidden: bool,
+    
     /// Filter by minimum file size (e.g., "10kb", "5mb")
-    #[arg(long = "min-size")]
-    pub min_size: Option<String>,
+    #[arg(long = "min-size", value_parser = parse_size)]
+    pub min_size: Option<u64>,
     
     /// Filter by maximum file size (e.g., "10kb", "5mb")
-    #[arg(long = "max-size")]
-    pub max_size: Option<String>,
+    #[arg(long = "max-size", value_parser = parse_size)]
+    pub max_size: Option<u64>,
+    
+    /// Filter by size find-style: "+10M" (larger), "-1G" (smaller) or "4k" (exactly)
+    #[arg(long = "size", value_parser = parse_size_constraint, allow_hyphen_values = true,
+          conflicts_with_all = ["min_size", "max_size"])]
+    pub size: Option<SizeConstraint>,
+    
+    /// Measure sizes by allocated disk space, as `du` does, instead of apparent length
+    #[arg(long = "disk-size")]
+    pub disk_size: bool,
+    
+    /// Match zero-byte files, and with `--type d` directories with no entries
+    #[arg(long = "empty")]
+    pub empty: bool,
     
-    /// Filter by modified after date (YYYY-MM-DD)
-    #[arg(long = "newer-than")]
+    /// Match entries with exactly N hard links (Unix only)
+    #[arg(long = "links", value_name = "N")]
+    pub links: Option<u64>,
+    
+    /// Match entries with at least N hard links, such as 2 for files also reachable under another name (Unix only)
+    #[arg(long = "min-links", value_name = "N", conflicts_with = "links")]
+    pub min_links: Option<u64>,
+    
+    /// Filter by modified after date (YYYY-MM-DD, or an RFC 3339 time such as 2024-06-01T14:30:00+02:00)
+    #[arg(long = "newer-than", value_parser = parse_date)]
     pub newer_than: Option<String>,
     
-    /// Filter by modified before date (YYYY-MM-DD)
-    #[arg(long = "older-than")]
+    /// Filter by modified before date (YYYY-MM-DD, or an RFC 3339 time)
+    #[arg(long = "older-than", value_parser = parse_date)]
     pub older_than: Option<String>,
+    
+    /// Filter by created after date, where the platform records creation times
+    #[arg(long = "created-after", value_name = "DATE", value_parser = parse_date)]
+    pub created_after: Option<String>,
+    
+    /// Filter by created before date, where the platform records creation times
+    #[arg(long = "created-before", value_name = "DATE", value_parser = parse_date)]
+    pub created_before: Option<String>,
+    
+    /// Filter by last read after date
+    #[arg(long = "accessed-after", value_name = "DATE", value_parser = parse_date)]
+    pub accessed_after: Option<String>,
+    
+    /// Filter by last read before date
+    #[arg(long = "accessed-before", value_name = "DATE", value_parser = parse_date)]
+    pub accessed_before: Option<String>,
+    
+    /// Read dates and times without an offset in UTC instead of local time
+    #[arg(long = "utc")]
+    pub utc: bool,
+    
+    /// Kinds of entries to report: f (files), d (directories), l (symlinks), s (sockets), p (pipes)
+    #[arg(long = "type", value_delimiter = ',', value_name = "TYPES")]
+    pub entry_type: Vec<EntryTypeArg>,
+    
+    /// Only files in these extension groups: image, video, audio, archive, code, doc
+    #[arg(long = "type-group", value_delimiter = ',', value_name = "GROUPS")]
+    pub type_group: Vec<String>,
+    
+    /// Only files whose content is of these MIME types, such as image/* or application/pdf
+    #[arg(long = "mime", value_delimiter = ',', value_name = "TYPES")]
+    pub mime: Vec<String>,
+    
+    /// Only text files, classified like grep does before searching them
+    #[arg(long = "text-only", overrides_with = "binary_only")]
+    pub text_only: bool,
+    
+    /// Only binary files, those grep would skip
+    #[arg(long = "binary-only", overrides_with = "text_only")]
+    pub binary_only: bool,
+    
+    /// Filter by modified more recently than a reference file, like `find -newer`
+    #[arg(long = "newer-than-file", value_name = "FILE")]
+    pub newer_than_file: Option<String>,
+    
+    /// Filter by modified before a reference file
+    #[arg(long = "older-than-file", value_name = "FILE")]
+    pub older_than_file: Option<String>,
+
+    /// Only photos taken or recordings made on or after this date (YYYY-MM-DD, requires the `media` feature)
+    #[arg(long = "exif-date-after", value_parser = parse_date)]
+    pub exif_date_after: Option<String>,
+    
+    /// Only photos taken or recordings made before this date (YYYY-MM-DD, requires the `media` feature)
+    #[arg(long = "exif-date-before", value_parser = parse_date)]
+    pub exif_date_before: Option<String>,
+    
+    /// Only photos whose camera model contains this text (requires the `media` feature)
+    #[arg(long = "camera-model")]
+    pub camera_model: Option<String>,
+    
+    /// Only recordings whose artist contains this text (requires the `media` feature)
+    #[arg(long = "artist")]
+    pub artist: Option<String>,
+
+    /// Read the files to search from a list ("-" for stdin) instead of traversing
+    #[arg(long = "files-from")]
+    pub files_from: Option<String>,
+
+    /// Only search files tracked by git
+    #[arg(long = "git-tracked", conflicts_with = "git_modified")]
+    pub git_tracked: bool,
+
+    /// Only search tracked files with uncommitted modifications
+    #[arg(long = "git-modified")]
+    pub git_modified: bool,
+
+    /// Rhai script defining custom filters and match actions (requires the `scripting` feature)
+    #[arg(long = "script")]
+    pub script: Option<String>,
+}
+
+/// Subcommands that run something other than a one-off search
+#[derive(Subcommand, Debug, Clone)]
+pub enum CliCommand {
+    /// Serve searches over HTTP (requires the `http` feature)
+    Serve {
+        /// Address to listen on, e.g. 127.0.0.1:7700
+        #[arg(long = "http", default_value = "127.0.0.1:7700")]
+        http: String,
+    },
+    /// Manage the index used to speed up repeated searches
+    Index {
+        #[command(subcommand)]
+        action: IndexAction,
+    },
+    /// Record that paths were chosen from the results, for --sort frecency
+    Record {
+        /// Paths that were selected
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Run the searches defined in a schedule file and report their results
+    Schedule {
+        /// JSON file listing the jobs to run
+        file: String,
+        /// Keep running and repeat every job on its interval
+        #[arg(long = "daemon")]
+        daemon: bool,
+    },
+    /// Check a directory tree for common security problems
+    Audit {
+        /// Directory to audit (default: the search path or current directory)
+        path: Option<String>,
+        /// Only run these rules (default: all)
+        #[arg(long = "rule", value_delimiter = ',')]
+        rules: Vec<AuditRuleType>,
+    },
+    /// Find files whose content nearly duplicates a reference file
+    Similar {
+        /// Reference file to compare candidates against
+        file: String,
+        /// Minimum similarity in percent
+        #[arg(long = "threshold", default_value_t = 80, value_parser = clap::value_parser!(u8).range(0..=100))]
+        threshold: u8,
+    },
+}
+
+/// Operations on the search index
+#[derive(Subcommand, Debug, Clone)]
+pub enum IndexAction {
+    /// Build or refresh the index for the search path
+    Build {
+        /// Index file contents so --grep only opens files that can match
+        #[arg(long = "content")]
+        content: bool,
+    },
+    /// Write the index to a portable file that can be imported elsewhere
+    Export {
+        /// File to write the export to
+        file: String,
+    },
+    /// Replace the index for the search path with an exported one
+    Import {
+        /// Export to read
+        file: String,
+    },
+    /// Drop stale entries and reclaim space left by incremental updates
+    Compact,
+    /// Check the index for damage
+    Check,
 }
 
 /// Available traversal strategies for directory searching
@@ -152,11 +579,495 @@ impl From<TraversalType> for TraversalMode {
     }
 }
 
+/// Available output formats for search results
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum FormatType {
+    /// Human-readable output
+    #[value(name = "text")]
+    Text,
+    /// Editor-friendly file:line:col:text records
+    #[value(name = "vimgrep")]
+    Vimgrep,
+    /// Parquet file of result rows (requires the `parquet` feature and --output)
+    #[value(name = "parquet")]
+    Parquet,
+    /// JSON Lines records
+    #[value(name = "json")]
+    Json,
+}
+
+impl From<FormatType> for OutputFormat {
+    fn from(value: FormatType) -> Self {
+        match value {
+            FormatType::Text => OutputFormat::Text,
+            FormatType::Vimgrep => OutputFormat::Vimgrep,
+            FormatType::Parquet => OutputFormat::Parquet,
+            FormatType::Json => OutputFormat::Json,
+        }
+    }
+}
+
+/// Available orders for file search results
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SortType {
+    /// Sort by path
+    #[value(name = "path")]
+    Path,
+    /// Sort by path, with numbers compared by value
+    #[value(name = "natural")]
+    Natural,
+    /// Most frequently and recently selected paths first
+    #[value(name = "frecency")]
+    Frecency,
+}
+
+impl From<SortType> for SortOrder {
+    fn from(value: SortType) -> Self {
+        match value {
+            SortType::Path => SortOrder::Path,
+            SortType::Natural => SortOrder::Natural,
+            SortType::Frecency => SortOrder::Frecency,
+        }
+    }
+}
+
+/// Available orders for the files a content search reads
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SortInputType {
+    /// Sort by path
+    #[value(name = "path")]
+    Path,
+    /// Most recently modified files first
+    #[value(name = "mtime")]
+    Mtime,
+}
+
+impl From<SortInputType> for InputOrder {
+    fn from(value: SortInputType) -> Self {
+        match value {
+            SortInputType::Path => InputOrder::Path,
+            SortInputType::Mtime => InputOrder::Mtime,
+        }
+    }
+}
+
+/// Available directory walkers
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum WalkerType {
+    /// Oqab's own walker
+    #[value(name = "builtin")]
+    Builtin,
+    /// The walkdir crate's walker
+    #[value(name = "walkdir")]
+    Walkdir,
+    /// The ignore crate's parallel walker
+    #[value(name = "ignore")]
+    Ignore,
+}
+
+impl From<WalkerType> for Walker {
+    fn from(value: WalkerType) -> Self {
+        match value {
+            WalkerType::Builtin => Walker::Builtin,
+            WalkerType::Walkdir => Walker::Walkdir,
+            WalkerType::Ignore => Walker::Ignore,
+        }
+    }
+}
+
+/// Kinds of entries accepted by `--type`, named as `find -type` names them
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum EntryTypeArg {
+    /// Regular files
+    #[value(name = "f", alias = "file")]
+    File,
+    /// Directories
+    #[value(name = "d", alias = "dir")]
+    Directory,
+    /// Symbolic links
+    #[value(name = "l", alias = "symlink")]
+    Symlink,
+    /// Sockets
+    #[value(name = "s", alias = "socket")]
+    Socket,
+    /// Named pipes
+    #[value(name = "p", alias = "fifo")]
+    Fifo,
+}
+
+impl From<EntryTypeArg> for EntryType {
+    fn from(value: EntryTypeArg) -> Self {
+        match value {
+            EntryTypeArg::File => EntryType::File,
+            EntryTypeArg::Directory => EntryType::Directory,
+            EntryTypeArg::Symlink => EntryType::Symlink,
+            EntryTypeArg::Socket => EntryType::Socket,
+            EntryTypeArg::Fifo => EntryType::Fifo,
+        }
+    }
+}
+
+/// Progress displays accepted by `--progress`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ProgressType {
+    /// Status line naming the current directory
+    #[value(name = "line")]
+    Line,
+    /// Animated progress bar
+    #[value(name = "bar")]
+    Bar,
+}
+
+impl From<ProgressType> for ProgressDisplay {
+    fn from(value: ProgressType) -> Self {
+        match value {
+            ProgressType::Line => ProgressDisplay::Line,
+            ProgressType::Bar => ProgressDisplay::Bar,
+        }
+    }
+}
+
+/// Formats accepted by `--print-config`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ConfigFormatType {
+    /// Annotated `key = value` lines
+    #[value(name = "toml")]
+    Toml,
+    /// The configuration and its sources as JSON
+    #[value(name = "json")]
+    Json,
+}
+
+impl From<ConfigFormatType> for ConfigFormat {
+    fn from(value: ConfigFormatType) -> Self {
+        match value {
+            ConfigFormatType::Toml => ConfigFormat::Toml,
+            ConfigFormatType::Json => ConfigFormat::Json,
+        }
+    }
+}
+
+/// Histograms accepted by `--histogram`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum HistogramType {
+    /// Searched files by size
+    #[value(name = "size")]
+    Size,
+    /// Searched files by time since last modification
+    #[value(name = "mtime")]
+    Mtime,
+}
+
+impl From<HistogramType> for HistogramKind {
+    fn from(value: HistogramType) -> Self {
+        match value {
+            HistogramType::Size => HistogramKind::Size,
+            HistogramType::Mtime => HistogramKind::Mtime,
+        }
+    }
+}
+
+/// Keys accepted by `--group-by`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum GroupByType {
+    /// Group by file extension
+    #[value(name = "ext")]
+    Ext,
+    /// Group by containing directory
+    #[value(name = "dir")]
+    Dir,
+}
+
+impl From<GroupByType> for GroupBy {
+    fn from(value: GroupByType) -> Self {
+        match value {
+            GroupByType::Ext => GroupBy::Ext,
+            GroupByType::Dir => GroupBy::Dir,
+        }
+    }
+}
+
+/// Report formats accepted by `--report`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ReportFormatType {
+    /// Markdown with pipe tables
+    #[value(name = "md")]
+    Markdown,
+    /// Standalone HTML page
+    #[value(name = "html")]
+    Html,
+}
+
+impl From<ReportFormatType> for ReportFormat {
+    fn from(value: ReportFormatType) -> Self {
+        match value {
+            ReportFormatType::Markdown => ReportFormat::Markdown,
+            ReportFormatType::Html => ReportFormat::Html,
+        }
+    }
+}
+
+/// Color choices accepted on the command line
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ColorType {
+    /// Color output on terminals, unless NO_COLOR is set
+    #[value(name = "auto")]
+    Auto,
+    /// Always color output
+    #[value(name = "always")]
+    Always,
+    /// Never color output
+    #[value(name = "never")]
+    Never,
+}
+
+impl From<ColorType> for ColorChoice {
+    fn from(value: ColorType) -> Self {
+        match value {
+            ColorType::Auto => ColorChoice::Auto,
+            ColorType::Always => ColorChoice::Always,
+            ColorType::Never => ColorChoice::Never,
+        }
+    }
+}
+
+/// Built-in color schemes accepted on the command line
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ThemeType {
+    /// The standard colors
+    #[value(name = "default")]
+    Default,
+    /// Bright colors and highlighted matches
+    #[value(name = "high-contrast")]
+    HighContrast,
+    /// Underlines in place of bold text
+    #[value(name = "no-bold")]
+    NoBold,
+}
+
+impl From<ThemeType> for ThemePreset {
+    fn from(value: ThemeType) -> Self {
+        match value {
+            ThemeType::Default => ThemePreset::Default,
+            ThemeType::HighContrast => ThemePreset::HighContrast,
+            ThemeType::NoBold => ThemePreset::NoBold,
+        }
+    }
+}
+
+/// Built-in audit rules accepted on the command line
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum AuditRuleType {
+    /// Files and non-sticky directories writable by everyone
+    #[value(name = "world-writable")]
+    WorldWritable,
+    /// Files with the setuid or setgid bit
+    #[value(name = "setuid")]
+    Setuid,
+    /// Symbolic links to missing targets
+    #[value(name = "dangling-symlink")]
+    DanglingSymlink,
+    /// Files owned by users that no longer exist
+    #[value(name = "orphaned-owner")]
+    OrphanedOwner,
+    /// SSH keys and configuration accessible to others
+    #[value(name = "ssh-permissions")]
+    SshPermissions,
+}
+
+impl From<AuditRuleType> for AuditRule {
+    fn from(value: AuditRuleType) -> Self {
+        match value {
+            AuditRuleType::WorldWritable => AuditRule::WorldWritable,
+            AuditRuleType::Setuid => AuditRule::Setuid,
+            AuditRuleType::DanglingSymlink => AuditRule::DanglingSymlink,
+            AuditRuleType::OrphanedOwner => AuditRule::OrphanedOwner,
+            AuditRuleType::SshPermissions => AuditRule::SshPermissions,
+        }
+    }
+}
+
+/// Document types accepted by --doc-types
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum DocTypeArg {
+    /// PDF documents
+    #[value(name = "pdf")]
+    Pdf,
+    /// Word documents
+    #[value(name = "docx")]
+    Docx,
+}
+
+impl From<DocTypeArg> for DocType {
+    fn from(value: DocTypeArg) -> Self {
+        match value {
+            DocTypeArg::Pdf => DocType::Pdf,
+            DocTypeArg::Docx => DocType::Docx,
+        }
+    }
+}
+
+/// Regular expression dialects accepted on the command line
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SyntaxType {
+    /// Rust regex syntax
+    #[value(name = "default")]
+    Default,
+    /// Match the pattern as plain text
+    #[value(name = "literal")]
+    Literal,
+    /// POSIX basic regular expressions (grep, sed)
+    #[value(name = "basic")]
+    Basic,
+    /// POSIX extended regular expressions (grep -E)
+    #[value(name = "extended")]
+    Extended,
+}
+
+impl From<SyntaxType> for RegexSyntax {
+    fn from(value: SyntaxType) -> Self {
+        match value {
+            SyntaxType::Default => RegexSyntax::Default,
+            SyntaxType::Literal => RegexSyntax::Literal,
+            SyntaxType::Basic => RegexSyntax::Basic,
+            SyntaxType::Extended => RegexSyntax::Extended,
+        }
+    }
+}
+
+/// Parse a human-readable size string into bytes
+fn parse_size(size_str: &str) -> Result<u64> {
+    let size_str = size_str.trim().to_lowercase();
+    
+    // Regular expression to match a number followed by an optional unit
+    let re = regex::Regex::new(r"^(\d+(?:\.\d+)?)\s*([kmgt]?b?)?$").unwrap();
+    
+    if let Some(caps) = re.captures(&size_str) {
+        let value: f64 = caps.get(1)
+            .map_or("0", |m| m.as_str())
+            .parse()
+            .unwrap_or(0.0);
+            
+        let unit = caps.get(2).map_or("", |m| m.as_str());
+        
+        let multiplier: u64 = match unit {
+            "k" | "kb" => 1_024,
+            "m" | "mb" => 1_024 * 1_024,
+            "g" | "gb" => 1_024 * 1_024 * 1_024,
+            "t" | "tb" => 1_099_511_627_776, // 1024^4
+            _ => 1, // Default to bytes
+        };
+        
+        Ok((value * multiplier as f64) as u64)
+    } else {
+        Err(ArgsError::InvalidValue(format!("Invalid size format: {}", size_str)).into())
+    }
+}
+
+/// Parse a find-style `--size` constraint: `+N` larger than, `-N` smaller than, `N` exactly
+fn parse_size_constraint(spec: &str) -> Result<SizeConstraint> {
+    let spec = spec.trim();
+    if let Some(size) = spec.strip_prefix('+') {
+        Ok(SizeConstraint::LargerThan(parse_size(size)?))
+    } else if let Some(size) = spec.strip_prefix('-') {
+        Ok(SizeConstraint::SmallerThan(parse_size(size)?))
+    } else {
+        Ok(SizeConstraint::Exactly(parse_size(spec)?))
+    }
+}
+
+/// Check a date argument, which is only resolved once the zone to read it in is known
+fn parse_date(date_str: &str) -> Result<String> {
+    date::start_of(date_str, DateZone::Utc)
+        .map(|_| date_str.to_string())
+        // Clap already names the flag and the rejected value
+        .map_err(|_| anyhow::anyhow!("expected a date in YYYY-MM-DD format or an RFC 3339 time"))
+}
+
+/// Resolve a date argument in a zone, as the earliest or latest instant it covers
+fn resolve_date(
+    date_str: Option<&str>,
+    zone: DateZone,
+    bound: fn(&str, DateZone) -> Result<SystemTime, chrono::ParseError>,
+) -> Option<SystemTime> {
+    // Arguments were checked when they were parsed
+    date_str.and_then(|date_str| bound(date_str, zone).ok())
+}
+
 impl Args {
     /// Parse command line arguments
     pub fn parse() -> Result<Self> {
         Self::try_parse()
-            .map_err(|e| ArgsError::ParseError(e.to_string()).into())
+            .map_err(|e| {
+                // --version is reported through the error path but is not a failure
+                if e.kind() == clap::error::ErrorKind::DisplayVersion {
+                    e.exit();
+                }
+                ArgsError::ParseError(e.to_string()).into()
+            })
+    }
+    
+    /// Log level selected by the verbosity flags, if any were given
+    ///
+    /// `--silent` wins over everything, then `--quiet`, then `-v`/`-vv`. Returns
+    /// `None` when no flag was given so the caller can fall back to `RUST_LOG`.
+    pub fn log_level(&self) -> Option<LevelFilter> {
+        if self.silent {
+            Some(LevelFilter::Off)
+        } else if self.quiet {
+            Some(LevelFilter::Error)
+        } else {
+            match self.verbose {
+                0 => None,
+                1 => Some(LevelFilter::Info),
+                2 => Some(LevelFilter::Debug),
+                _ => Some(LevelFilter::Trace),
+            }
+        }
+    }
+    
+    /// Kind of content chosen with `--text-only` or `--binary-only`, if either was given
+    fn content_kind_choice(&self) -> Option<ContentKind> {
+        if self.text_only {
+            Some(ContentKind::Text)
+        } else if self.binary_only {
+            Some(ContentKind::Binary)
+        } else {
+            None
+        }
+    }
+    
+    /// Whether hidden entries were included with `--hidden` or skipped with `--no-hidden`, whichever came last
+    fn hidden_choice(&self) -> Option<bool> {
+        if self.hidden {
+            Some(true)
+        } else if self.no_hidden {
+            Some(false)
+        } else {
+            None
+        }
+    }
+    
+    /// Binary file handling chosen with `--binary` or `--text`, if either was given
+    fn binary_choice(&self) -> Option<BinaryMode> {
+        if self.binary {
+            Some(BinaryMode::Report)
+        } else if self.text {
+            Some(BinaryMode::Text)
+        } else {
+            None
+        }
+    }
+    
+    /// Heading style asked for with `--heading` or `--no-heading`, whichever came last
+    fn heading_choice(&self) -> Option<bool> {
+        if self.heading {
+            Some(true)
+        } else if self.no_heading {
+            Some(false)
+        } else {
+            None
+        }
     }
     
     /// Convert CLI arguments to a search configuration
@@ -175,42 +1086,16 @@ impl Args {
         if let Some(path) = &self.path {
             config.path = Some(path.clone());
         }
+        config.system = self.system;
         
         // Smart query processing
         if let Some(query) = &self.query {
-            // If grep pattern is explicitly provided, don't override it with query
-            if self.pattern.is_none() {
-                // Check if query contains wildcard characters or looks like a grep pattern
-                if query.contains('*') || query.contains('?') || query.contains('[') {
-                    // It's likely a file pattern search
-                    if self.name.is_none() {
-                        config.file_name = Some(query.clone());
-                    }
-                } else if query.contains('.') {
-                    // Check if it's a filename with extension
-                    let parts: Vec<&str> = query.rsplitn(2, '.').collect();
-                    if parts.len() == 2 && !parts[0].is_empty() && !parts[1].is_empty() {
-                        // Looks like filename.ext format
-                        if self.name.is_none() {
-                            config.file_name = Some(query.clone());
-                        }
-                        if self.extension.is_none() && config.file_extension.is_none() {
-                            // Only set extension if not already specified
-                            // parts[0] is the extension because we used rsplitn
-                            config.file_extension = Some(parts[0].to_string());
-                        }
-                    } else {
-                        // Just use as filename pattern
-                        if self.name.is_none() {
-                            config.file_name = Some(query.clone());
-                        }
-                    }
-                } else {
-                    // Simple text, use as filename pattern
-                    if self.name.is_none() {
-                        config.file_name = Some(query.clone());
-                    }
-                }
+            // In fuzzy mode the query ranks candidates rather than selecting them
+            if self.is_fuzzy() {
+                config.fuzzy_query = Some(query.clone());
+            } else if self.pattern.is_none() {
+                // If grep pattern is explicitly provided, don't override it with query
+                self.apply_query(query, config);
             }
         }
         
@@ -221,16 +1106,38 @@ impl Args {
         if let Some(name) = &self.name {
             config.file_name = Some(name.clone());
         }
+        config.case_sensitive_extension = self.case_sensitive_ext;
+        config.full_path = self.full_path;
         config.pattern = self.pattern.clone();
         config.ignore_case = self.ignore_case;
+        if let Some(syntax) = self.regex_syntax {
+            config.regex_syntax = syntax.into();
+        }
         config.line_number = self.line_number;
+        config.null_data = self.null_data;
+        config.binary = self.binary_choice().unwrap_or_default();
+        config.heading = self.heading_choice();
+        config.no_filename = self.no_filename;
+        config.passthru = self.passthru;
         config.files_with_matches = self.files_with_matches;
+        config.stats = self.stats || !self.histogram.is_empty();
+        config.histograms = self.histogram.iter().map(|&kind| kind.into()).collect();
+        config.max_filesize = self.max_filesize;
+        if !self.doc_types.is_empty() {
+            config.doc_types = Some(self.doc_types.iter().map(|&doc_type| doc_type.into()).collect());
+        }
+        config.unordered = self.unordered;
+        config.index_file = self.index.clone();
+        config.no_index = self.no_index;
         config.help = self.help;
         
         // Performance settings
         if let Some(threads) = self.workers {
             config.thread_count = Some(threads);
         }
+        if let Some(walker) = self.walker {
+            config.walker = walker.into();
+        }
         
         // Advanced settings
         config.advanced_search = self.advanced;
@@ -239,62 +1146,119 @@ impl Args {
         }
         
         // Size filters
-        if let Some(min_size) = &self.min_size {
-            if let Ok(size) = Self::parse_size(min_size) {
-                config.min_size = Some(size);
-            }
+        config.min_size = self.min_size;
+        config.max_size = self.max_size;
+        if let Some(size) = self.size {
+            (config.min_size, config.max_size) = size.bounds();
         }
+        config.disk_size = self.disk_size;
+        config.empty = self.empty;
+        config.links = self.links;
+        config.min_links = self.min_links;
         
-        if let Some(max_size) = &self.max_size {
-            if let Ok(size) = Self::parse_size(max_size) {
-                config.max_size = Some(size);
-            }
+        // Date filters
+        config.date_zone = if self.utc { DateZone::Utc } else { DateZone::Local };
+        config.newer_than = resolve_date(self.newer_than.as_deref(), config.date_zone, date::start_of);
+        config.exif_date_after = resolve_date(self.exif_date_after.as_deref(), config.date_zone, date::start_of);
+        config.exif_date_before = resolve_date(self.exif_date_before.as_deref(), config.date_zone, date::start_of);
+        config.camera_model = self.camera_model.clone();
+        config.artist = self.artist.clone();
+        config.older_than = resolve_date(self.older_than.as_deref(), config.date_zone, date::end_of);
+        config.created_after = resolve_date(self.created_after.as_deref(), config.date_zone, date::start_of);
+        config.created_before = resolve_date(self.created_before.as_deref(), config.date_zone, date::end_of);
+        config.accessed_after = resolve_date(self.accessed_after.as_deref(), config.date_zone, date::start_of);
+        config.accessed_before = resolve_date(self.accessed_before.as_deref(), config.date_zone, date::end_of);
+        config.newer_than_file = self.newer_than_file.clone();
+        config.older_than_file = self.older_than_file.clone();
+        if !self.type_group.is_empty() {
+            config.type_group = Some(self.type_group.clone());
         }
+        if !self.mime.is_empty() {
+            config.mime = Some(self.mime.clone());
+        }
+        config.content_kind = self.content_kind_choice();
+        config.entry_types = self.entry_type.iter().copied().map(Into::into).collect();
         
-        // Date filters
-        config.newer_than = self.newer_than.clone();
-        config.older_than = self.older_than.clone();
+        // Candidate selection
+        config.files_from = self.files_from.clone();
+        config.git_tracked = self.git_tracked;
+        config.git_modified = self.git_modified;
+        config.script = self.script.clone();
         
         // UI settings
         config.show_progress = !self.silent;
+        if let Some(progress) = self.progress {
+            config.progress = progress.into();
+        }
+        config.prescan = self.prescan;
         config.quiet_mode = self.quiet;
+        config.no_messages = self.no_messages;
+        config.no_default_excludes = self.no_default_excludes;
+        config.exclude = self.exclude.clone();
+        config.no_ignore_global = self.no_ignore_global;
+        config.git_ignore = self.git_ignore;
+        config.strict = self.strict;
+        if let Some(format) = self.format {
+            config.output_format = format.into();
+        }
+        config.print0 = self.print0;
+        config.printf = self.printf.clone();
+        config.long = self.long;
+        config.tree = self.tree;
+        if let Some(sort) = self.sort {
+            config.sort = sort.into();
+        }
+        if let Some(sort_input) = self.sort_input {
+            config.sort_input = sort_input.into();
+        }
+        config.group_by = self.group_by.map(Into::into);
+        config.interactive = self.interactive;
+        config.du = self.du;
+        config.report = self.report.map(Into::into);
+        config.page_size = self.page_size;
+        config.pager = !self.no_pager;
+        if let Some(theme) = self.theme {
+            config.theme.preset = theme.into();
+        }
+        if let Some(color) = self.color {
+            config.color = color.into();
+        }
+        config.hyperlinks = self.hyperlinks;
+        config.output_file = self.output.clone();
+        config.output_sqlite = self.output_sqlite.clone();
         config.recursive = !self.no_recursive;
+        config.max_depth = self.max_depth;
+        config.min_depth = self.min_depth;
+        config.max_per_dir = self.max_per_dir.or(self.one_per_dir.then_some(1));
+        config.max_results = self.max_results;
         config.follow_symlinks = self.follow_symlinks;
+        config.unique = self.unique;
+        config.one_file_system = self.one_file_system;
+        config.hidden = self.hidden;
         
         // Fuzzy search settings
-        config.fuzzy = self.fuzzy;
+        config.fuzzy = self.is_fuzzy();
         if let Some(threshold) = self.fuzzy_threshold {
             config.fuzzy_threshold = Some(threshold);
         }
+        if let Some(fuzzy_query) = &self.fuzzy_query {
+            config.fuzzy_query = Some(fuzzy_query.clone());
+        }
+        config.select = self.select;
     }
     
-    /// Parse a human-readable size string into bytes
-    fn parse_size(size_str: &str) -> Result<u64> {
-        let size_str = size_str.trim().to_lowercase();
-        
-        // Regular expression to match a number followed by an optional unit
-        let re = regex::Regex::new(r"^(\d+(?:\.\d+)?)\s*([kmgt]?b?)?$").unwrap();
-        
-        if let Some(caps) = re.captures(&size_str) {
-            let value: f64 = caps.get(1)
-                .map_or("0", |m| m.as_str())
-                .parse()
-                .unwrap_or(0.0);
-                
-            let unit = caps.get(2).map_or("", |m| m.as_str());
-            
-            let multiplier: u64 = match unit {
-                "k" | "kb" => 1_024,
-                "m" | "mb" => 1_024 * 1_024,
-                "g" | "gb" => 1_024 * 1_024 * 1_024,
-                "t" | "tb" => 1_099_511_627_776, // 1024^4
-                _ => 1, // Default to bytes
-            };
-            
-            Ok((value * multiplier as f64) as u64)
-        } else {
-            Err(ArgsError::InvalidValue(format!("Invalid size format: {}", size_str)).into())
-        }
+    /// Whether fuzzy mode was asked for, directly or by giving a fuzzy query or `--select`
+    ///
+    /// The results browser does its own choosing, so `--select` with it keeps the query as is.
+    fn is_fuzzy(&self) -> bool {
+        self.fuzzy || self.fuzzy_query.is_some() || (self.select && !self.interactive)
+    }
+    
+    /// Interpret the positional query according to what it looks like
+    ///
+    /// See [`FileSearchConfig::apply_query`]; explicit `--name`/`--ext` flags always win.
+    fn apply_query(&self, query: &str, config: &mut FileSearchConfig) {
+        config.apply_query(query, self.literal, self.name.is_none(), self.extension.is_none());
     }
     
     /// Process command-line arguments, loading from config file if specified
@@ -303,15 +1267,7 @@ impl Args {
         self.validate()?;
         
         // First convert CLI args to a config
-        let mut config = self.to_config();
-        
-        // Handle positional argument if present
-        if let Some(query) = &self.query {
-            // If no explicit search type is specified, use the query as file name pattern
-            if config.file_name.is_none() && config.file_extension.is_none() && config.pattern.is_none() {
-                config.file_name = Some(query.clone());
-            }
-        }
+        let mut config = self.cli_config();
         
         // If a config file is specified, load and merge it
         if let Some(config_file) = &self.config_file {
@@ -331,6 +1287,14 @@ impl Args {
             debug!("Merged configuration from file and command line arguments");
         }
         
+        // The global ignore file is read once, here, and compiled into every search's filters
+        if !config.no_ignore_global {
+            config.global_ignore = IgnoreFilter::load_global().unwrap_or_else(|e| {
+                warn!("Failed to read global ignore file: {}", e);
+                Vec::new()
+            });
+        }
+        
         // Process the save config request if present
         if let Some(save_path) = &self.save_config_file {
             debug!("Will save configuration to: {}", save_path);
@@ -368,8 +1332,111 @@ impl Args {
     
     /// Validate the generated configuration
     fn validate_config(&self, config: &FileSearchConfig) -> Result<()> {
+        // File-based formats need somewhere to write to
+        if config.output_format == OutputFormat::Parquet && config.output_file.is_none() {
+            return Err(ArgsError::InvalidValue(
+                "--format parquet requires --output <FILE>".to_string()
+            ).into());
+        }
+        
+        // Empty pages would prompt forever without showing anything
+        if config.page_size == Some(0) {
+            return Err(ArgsError::InvalidValue(
+                "--page-size must be at least 1".to_string()
+            ).into());
+        }
+        
+        // A directory limit of zero would report nothing at all
+        if config.max_per_dir == Some(0) {
+            return Err(ArgsError::InvalidValue(
+                "--max-per-dir must be at least 1".to_string()
+            ).into());
+        }
+        
+        // An empty size report would list nothing
+        if config.du == Some(0) {
+            return Err(ArgsError::InvalidValue(
+                "--du must list at least 1 entry".to_string()
+            ).into());
+        }
+        
+        // Likewise a search stopped before finding anything
+        if config.max_results == Some(0) {
+            return Err(ArgsError::InvalidValue(
+                "--max-results must be at least 1".to_string()
+            ).into());
+        }
+        
+        // Template mistakes are caught before searching rather than for every result
+        if let Some(template) = &config.printf {
+            Template::parse(template)
+                .map_err(|e| ArgsError::InvalidValue(e.to_string()))?;
+        }
+        
+        // Unknown color names would silently render unstyled
+        config.theme.validate()
+            .map_err(|e| ArgsError::InvalidValue(e.to_string()))?;
+        
+        // Broken patterns in the global ignore file would otherwise only surface mid-search
+        IgnoreFilter::new(Path::new(config.get_path()), &config.global_ignore)
+            .map_err(|e| ArgsError::InvalidValue(format!("Invalid pattern in global ignore file: {}", e)))?;
+        IgnoreFilter::new(Path::new(config.get_path()), &config.exclude)
+            .map_err(|e| ArgsError::InvalidValue(format!("Invalid --exclude pattern: {}", e)))?;
+        
+        // Every selected type group must be built in or defined in the configuration
+        if let Some(groups) = &config.type_group {
+            TypeGroupFilter::with_custom(groups, &config.type_groups)
+                .map_err(|e| ArgsError::InvalidValue(e.to_string()))?;
+        }
+        
+        // Without link counts every file would be rejected, so say so up front
+        if cfg!(not(unix)) && (config.links.is_some() || config.min_links.is_some()) {
+            return Err(ArgsError::InvalidValue("--links and --min-links are only supported on Unix".to_string()).into());
+        }
+        // One may come from a configuration file and the other from the command line
+        if config.links.is_some() && config.min_links.is_some() {
+            return Err(ArgsError::InvalidValue("--links and --min-links cannot be used together".to_string()).into());
+        }
+        
+        // Without recorded creation times every file would be rejected, so say so up front
+        if config.created_after.is_some() || config.created_before.is_some() {
+            let created = std::fs::metadata(config.get_path()).and_then(|metadata| metadata.created());
+            if let Err(e) = created {
+                if e.kind() == std::io::ErrorKind::Unsupported {
+                    return Err(ArgsError::InvalidValue(
+                        format!("creation times are not recorded for {}: {}", config.get_path(), e)
+                    ).into());
+                }
+            }
+        }
+        
+        // Scripts need the interpreter compiled in
+        if config.script.is_some() && !cfg!(feature = "scripting") {
+            return Err(ArgsError::InvalidValue(
+                "oqab was built without scripting support; rebuild with `--features scripting`".to_string()
+            ).into());
+        }
+        
+        // Requested document types need their extractors compiled in
+        if let Some(doc_types) = &config.doc_types {
+            let available = DocumentExtractors::available();
+            if let Some(missing) = doc_types.iter().find(|doc_type| !available.contains(doc_type)) {
+                let feature = format!("{:?}", missing).to_lowercase();
+                return Err(ArgsError::InvalidValue(format!(
+                    "oqab was built without {} support; rebuild with `--features {}`", feature, feature
+                )).into());
+            }
+        }
+        
+        // Metadata filters need the extractors compiled in
+        if config.has_media_criteria() && !cfg!(feature = "media") {
+            return Err(ArgsError::InvalidValue(
+                "oqab was built without media metadata support; rebuild with `--features media`".to_string()
+            ).into());
+        }
+        
         // Check if search criteria is present
-        if config.file_extension.is_none() && config.file_name.is_none() && config.pattern.is_none() && !self.help {
+        if !config.has_criteria() && !self.help && self.command.is_none() {
             warn!("No search criteria specified, behavior may be undefined");
         }
         
@@ -377,6 +1444,55 @@ impl Args {
     }
     
     /// Merge CLI arguments with a loaded configuration
+    /// Configuration given by the command line alone, including the positional query
+    fn cli_config(&self) -> FileSearchConfig {
+        let mut config = self.to_config();
+        
+        // Handle positional argument if present
+        if let Some(query) = &self.query {
+            // If no explicit search type is specified, use the query as file name pattern
+            if !config.has_criteria() {
+                config.file_name = Some(query.clone());
+            }
+        }
+        
+        config
+    }
+    
+    /// Where each value of a configuration made by [`process`](Self::process) came from
+    ///
+    /// A value counts as set on the command line when the flags alone make it differ
+    /// from the default, and as coming from the config file when the file names it.
+    pub fn config_sources(&self, config: &FileSearchConfig) -> Result<BTreeMap<String, ConfigSource>> {
+        let defaults = serde_json::to_value(FileSearchConfig::new())?;
+        let cli = serde_json::to_value(self.cli_config())?;
+        let file_keys = match &self.config_file {
+            Some(config_file) => {
+                let contents = std::fs::read_to_string(config_file)
+                    .with_context(|| format!("Failed to read configuration file: {}", config_file))?;
+                match serde_json::from_str(&contents)? {
+                    serde_json::Value::Object(values) => values.into_iter().map(|(key, _)| key).collect(),
+                    _ => Vec::new(),
+                }
+            }
+            None => Vec::new(),
+        };
+        
+        let serde_json::Value::Object(effective) = serde_json::to_value(config)? else {
+            return Ok(BTreeMap::new());
+        };
+        Ok(effective.into_iter()
+            .map(|(key, _)| {
+                let source = match &self.config_file {
+                    _ if cli.get(&key) != defaults.get(&key) => ConfigSource::CommandLine,
+                    Some(config_file) if file_keys.contains(&key) => ConfigSource::File(config_file.clone()),
+                    _ => ConfigSource::Default,
+                };
+                (key, source)
+            })
+            .collect())
+    }
+    
     fn merge_with_config(&self, loaded: FileSearchConfig) -> FileSearchConfig {
         let mut merged = loaded.clone();
         
@@ -395,16 +1511,29 @@ impl Args {
             config.path = Some(path.clone());
         }
         
+        // Whole-filesystem search - only override if specified in CLI
+        if self.system {
+            config.system = true;
+        }
+        
         // File extension - only override if specified in CLI
         if self.extension.is_some() {
             config.file_extension = self.extension.clone();
         }
         
+        if self.case_sensitive_ext {
+            config.case_sensitive_extension = true;
+        }
+        
         // File name - only override if specified in CLI
         if self.name.is_some() {
             config.file_name = self.name.clone();
         }
         
+        if self.full_path {
+            config.full_path = true;
+        }
+        
         // Pattern - only override if specified in CLI
         if self.pattern.is_some() {
             config.pattern = self.pattern.clone();
@@ -415,19 +1544,184 @@ impl Args {
             config.ignore_case = true;
         }
         
+        // Regex syntax - only override if specified in CLI
+        if let Some(syntax) = self.regex_syntax {
+            config.regex_syntax = syntax.into();
+        }
+        
         if self.line_number {
             config.line_number = true;
         }
         
+        if self.null_data {
+            config.null_data = true;
+        }
+        
+        // Binary handling - only override if specified in CLI
+        if let Some(binary) = self.binary_choice() {
+            config.binary = binary;
+        }
+        
+        if let Some(heading) = self.heading_choice() {
+            config.heading = Some(heading);
+        }
+        
+        if self.no_filename {
+            config.no_filename = true;
+        }
+        
+        // Passthru - only override if specified in CLI
+        if self.passthru {
+            config.passthru = true;
+        }
+        
         if self.files_with_matches {
             config.files_with_matches = true;
         }
         
+        if self.stats {
+            config.stats = true;
+        }
+        
+        if !self.histogram.is_empty() {
+            config.stats = true;
+            config.histograms = self.histogram.iter().map(|&kind| kind.into()).collect();
+        }
+        
+        if self.max_filesize.is_some() {
+            config.max_filesize = self.max_filesize;
+        }
+        
+        if !self.doc_types.is_empty() {
+            config.doc_types = Some(self.doc_types.iter().map(|&doc_type| doc_type.into()).collect());
+        }
+        
+        if self.unordered {
+            config.unordered = true;
+        }
+        
+        if self.index.is_some() {
+            config.index_file = self.index.clone();
+        }
+        
+        if self.no_index {
+            config.no_index = true;
+        }
+        
         // Thread count - only override if specified in CLI
         if let Some(threads) = self.workers {
             config.thread_count = Some(threads);
         }
         
+        // Fuzzy ranking - only override if specified in CLI
+        if self.is_fuzzy() {
+            config.fuzzy = true;
+            if let Some(fuzzy_query) = self.fuzzy_query.as_ref().or(self.query.as_ref()) {
+                config.fuzzy_query = Some(fuzzy_query.clone());
+            }
+        }
+        if let Some(threshold) = self.fuzzy_threshold {
+            config.fuzzy_threshold = Some(threshold);
+        }
+        if self.select {
+            config.select = true;
+        }
+        
+        // Walker - only override if specified in CLI
+        if let Some(walker) = self.walker {
+            config.walker = walker.into();
+        }
+        
+        // Progress display - only override if specified in CLI
+        if let Some(progress) = self.progress {
+            config.progress = progress.into();
+        }
+        if self.prescan {
+            config.prescan = true;
+        }
+        
+        // Output format - only override if specified in CLI
+        if let Some(format) = self.format {
+            config.output_format = format.into();
+        }
+        if self.print0 {
+            config.print0 = true;
+        }
+        if self.printf.is_some() {
+            config.printf = self.printf.clone();
+        }
+        if self.long {
+            config.long = true;
+        }
+        if self.tree {
+            config.tree = true;
+        }
+        
+        // Result order - only override if specified in CLI
+        if let Some(sort) = self.sort {
+            config.sort = sort.into();
+        }
+        
+        // Content search order - only override if specified in CLI
+        if let Some(sort_input) = self.sort_input {
+            config.sort_input = sort_input.into();
+        }
+        
+        // Grouping - only override if specified in CLI
+        if let Some(group_by) = self.group_by {
+            config.group_by = Some(group_by.into());
+        }
+        
+        // Results browser - only override if specified in CLI
+        if self.interactive {
+            config.interactive = true;
+        }
+        
+        // Size report - only override if specified in CLI
+        if self.du.is_some() {
+            config.du = self.du;
+        }
+        
+        // Report - only override if specified in CLI
+        if let Some(report) = self.report {
+            config.report = Some(report.into());
+        }
+        
+        // Pagination - only override if specified in CLI
+        if self.page_size.is_some() {
+            config.page_size = self.page_size;
+        }
+        
+        // External pager - only override if disabled in CLI
+        if self.no_pager {
+            config.pager = false;
+        }
+        
+        // Color scheme - only override if specified in CLI, keeping any custom colors
+        if let Some(theme) = self.theme {
+            config.theme.preset = theme.into();
+        }
+        
+        // Color choice - only override if specified in CLI
+        if let Some(color) = self.color {
+            config.color = color.into();
+        }
+        
+        // Hyperlinks - only override if specified in CLI
+        if self.hyperlinks {
+            config.hyperlinks = true;
+        }
+        
+        // Output file - only override if specified in CLI
+        if self.output.is_some() {
+            config.output_file = self.output.clone();
+        }
+        
+        // SQLite export - only override if specified in CLI
+        if self.output_sqlite.is_some() {
+            config.output_sqlite = self.output_sqlite.clone();
+        }
+        
         // Traversal strategy - only override if specified in CLI
         if let Some(traversal_type) = self.traversal {
             config.traversal_mode = traversal_type.into();
@@ -450,15 +1744,187 @@ impl Args {
             config.show_progress = false;
         }
         
+        // Path error messages - override if no-messages flag is set
+        if self.no_messages {
+            config.no_messages = true;
+        }
+        
+        // Default excludes - override if no-default-excludes flag is set
+        if self.no_default_excludes {
+            config.no_default_excludes = true;
+        }
+        
+        // Exclusions - added to those of the configuration file
+        config.exclude.extend(self.exclude.iter().cloned());
+        
+        // Global ignore file - override if no-ignore-global flag is set
+        if self.no_ignore_global {
+            config.no_ignore_global = true;
+        }
+        
+        // Git ignore files - override if git-ignore flag is set
+        if self.git_ignore {
+            config.git_ignore = true;
+        }
+        
+        // Strict mode - override if strict flag is set
+        if self.strict {
+            config.strict = true;
+        }
+        
         // Recursive - override if no-recursive flag is set
         if self.no_recursive {
             config.recursive = false;
         }
         
+        // Depth limits - only override if specified in CLI
+        if self.max_depth.is_some() {
+            config.max_depth = self.max_depth;
+        }
+        
+        if self.min_depth.is_some() {
+            config.min_depth = self.min_depth;
+        }
+        
+        // Per-directory limit - only override if specified in CLI
+        if let Some(limit) = self.max_per_dir.or(self.one_per_dir.then_some(1)) {
+            config.max_per_dir = Some(limit);
+        }
+        
+        // Result limit - only override if specified in CLI
+        if let Some(limit) = self.max_results {
+            config.max_results = Some(limit);
+        }
+        
         // Follow symlinks - override if follow-symlinks flag is set
         if self.follow_symlinks {
             config.follow_symlinks = true;
         }
+        
+        // Unique results - override if unique flag is set
+        if self.unique {
+            config.unique = true;
+        }
+        
+        // Filesystem boundaries - override if one-file-system flag is set
+        if self.one_file_system {
+            config.one_file_system = true;
+        }
+        
+        // Hidden files - only override if --hidden or --no-hidden is specified in CLI
+        if let Some(hidden) = self.hidden_choice() {
+            config.hidden = hidden;
+        }
+        
+        // File list - only override if specified in CLI
+        if self.files_from.is_some() {
+            config.files_from = self.files_from.clone();
+        }
+        
+        // Size and date bounds - only override if specified in CLI
+        if self.min_size.is_some() {
+            config.min_size = self.min_size;
+        }
+        
+        if self.max_size.is_some() {
+            config.max_size = self.max_size;
+        }
+        
+        if let Some(size) = self.size {
+            (config.min_size, config.max_size) = size.bounds();
+        }
+        
+        if self.disk_size {
+            config.disk_size = true;
+        }
+        
+        if self.empty {
+            config.empty = true;
+        }
+        
+        // Link counts - only override if specified in CLI
+        if self.links.is_some() {
+            config.links = self.links;
+        }
+        
+        if self.min_links.is_some() {
+            config.min_links = self.min_links;
+        }
+        
+        // Date zone - only override if specified in CLI, before dates are read in it
+        if self.utc {
+            config.date_zone = DateZone::Utc;
+        }
+        
+        if self.newer_than.is_some() {
+            config.newer_than = resolve_date(self.newer_than.as_deref(), config.date_zone, date::start_of);
+        }
+        
+        // Media metadata criteria - only override if specified in CLI
+        if self.exif_date_after.is_some() {
+            config.exif_date_after = resolve_date(self.exif_date_after.as_deref(), config.date_zone, date::start_of);
+        }
+        if self.exif_date_before.is_some() {
+            config.exif_date_before = resolve_date(self.exif_date_before.as_deref(), config.date_zone, date::start_of);
+        }
+        if self.camera_model.is_some() {
+            config.camera_model = self.camera_model.clone();
+        }
+        if self.artist.is_some() {
+            config.artist = self.artist.clone();
+        }
+        
+        if self.older_than.is_some() {
+            config.older_than = resolve_date(self.older_than.as_deref(), config.date_zone, date::end_of);
+        }
+        
+        if self.created_after.is_some() {
+            config.created_after = resolve_date(self.created_after.as_deref(), config.date_zone, date::start_of);
+        }
+        if self.created_before.is_some() {
+            config.created_before = resolve_date(self.created_before.as_deref(), config.date_zone, date::end_of);
+        }
+        if self.accessed_after.is_some() {
+            config.accessed_after = resolve_date(self.accessed_after.as_deref(), config.date_zone, date::start_of);
+        }
+        if self.accessed_before.is_some() {
+            config.accessed_before = resolve_date(self.accessed_before.as_deref(), config.date_zone, date::end_of);
+        }
+        
+        if self.newer_than_file.is_some() {
+            config.newer_than_file = self.newer_than_file.clone();
+        }
+        
+        if !self.type_group.is_empty() {
+            config.type_group = Some(self.type_group.clone());
+        }
+        
+        if !self.mime.is_empty() {
+            config.mime = Some(self.mime.clone());
+        }
+        
+        if let Some(kind) = self.content_kind_choice() {
+            config.content_kind = Some(kind);
+        }
+        
+        if !self.entry_type.is_empty() {
+            config.entry_types = self.entry_type.iter().copied().map(Into::into).collect();
+        }
+        
+        if self.older_than_file.is_some() {
+            config.older_than_file = self.older_than_file.clone();
+        }
+        
+        // Git selection - override if either git flag is set
+        if self.git_tracked || self.git_modified {
+            config.git_tracked = self.git_tracked;
+            config.git_modified = self.git_modified;
+        }
+        
+        // Script - only override if specified in CLI
+        if self.script.is_some() {
+            config.script = self.script.clone();
+        }
     }
     
     /// Save current configuration to a file
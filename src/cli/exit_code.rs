@@ -0,0 +1,51 @@
+//! Process exit codes
+//!
+//! | Code | Meaning                                                     |
+//! |------|-------------------------------------------------------------|
+//! | 0    | The search found at least one match                         |
+//! | 1    | The search completed but found nothing                      |
+//! | 2    | Usage error, or a failure that prevented the search running |
+//! | 3    | Partial results: some paths could not be read               |
+//! | 130  | Interrupted with Ctrl-C                                     |
+//! | 141  | Standard output was closed before all results were written  |
+
+use crate::commands::{IncompleteSearchError, Outcome};
+use crate::output::BrokenPipeError;
+
+/// At least one match was found
+pub const MATCHES: i32 = 0;
+
+/// The search completed without finding anything
+pub const NO_MATCHES: i32 = 1;
+
+/// Invalid arguments, or an error that stopped the search
+pub const USAGE: i32 = 2;
+
+/// Some paths could not be read, so the results may be incomplete
+pub const PARTIAL: i32 = 3;
+
+/// The run was interrupted by SIGINT (128 + 2)
+pub const INTERRUPTED: i32 = 130;
+
+/// The reader of standard output went away, as if killed by SIGPIPE (128 + 13)
+pub const BROKEN_PIPE: i32 = 141;
+
+/// Exit code for a command that ran to completion
+pub fn for_outcome(outcome: Outcome) -> i32 {
+    match outcome {
+        Outcome::Success => MATCHES,
+        Outcome::NoMatches => NO_MATCHES,
+        Outcome::Partial => PARTIAL,
+    }
+}
+
+/// Exit code for a command that failed
+pub fn for_error(error: &anyhow::Error) -> i32 {
+    if error.downcast_ref::<IncompleteSearchError>().is_some() {
+        PARTIAL
+    } else if error.downcast_ref::<BrokenPipeError>().is_some() {
+        BROKEN_PIPE
+    } else {
+        USAGE
+    }
+}
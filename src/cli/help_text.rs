@@ -16,7 +16,8 @@ oqab --grep PATTERN [OPTIONS]
 
 {}
 {} Display this help message
-{} Directory to search in (default: root directory)
+{} Directory to search in (default: current directory)
+{} Search the whole filesystem from its root
 {} File extension to search for (e.g., 'rs' or '.rs')
 {} Filter by file name pattern
 {} Search for text pattern within files (grep-like functionality)
@@ -30,7 +31,7 @@ oqab --grep PATTERN [OPTIONS]
 {} Save current settings to a configuration file
 
 {}
-# Simple file search by name (searches from root directory)
+# Simple file search by name (searches from the current directory, or the root with --system)
 {}
 
 # Find all Rust files in current directory
@@ -63,6 +64,7 @@ oqab --grep PATTERN [OPTIONS]
         section_title("OPTIONS:"),
         option("-h, --help                  "),
         option("-p, --path <DIR>            "),
+        option("--system                    "),
         option("-e, --ext <EXT>             "),
         option("-n, --name <PATTERN>        "),
         option("-g, --grep <PATTERN>        "),
@@ -1,4 +1,5 @@
 pub mod args;
+pub mod exit_code;
 pub mod help_text;
 
 pub use args::Args;
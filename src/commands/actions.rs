@@ -0,0 +1,48 @@
+use std::path::Path;
+use anyhow::Result;
+
+use crate::core::FileSearchConfig;
+
+/// Per-match actions defined by the `on_match` functions of a user script
+///
+/// Without the `scripting` feature, or without a script, every action is a no-op.
+pub(crate) struct MatchActions {
+    #[cfg(feature = "scripting")]
+    script: Option<crate::filters::Script>,
+}
+
+impl MatchActions {
+    /// Load the actions of the configured script, failing early if it does not compile
+    #[cfg(feature = "scripting")]
+    pub fn load(config: &FileSearchConfig) -> Result<Self> {
+        let script = config.script.as_ref()
+            .map(crate::filters::Script::load)
+            .transpose()?;
+        Ok(Self { script })
+    }
+    
+    #[cfg(not(feature = "scripting"))]
+    pub fn load(_config: &FileSearchConfig) -> Result<Self> {
+        Ok(Self {})
+    }
+    
+    /// Run the action for a file found by a file search
+    pub fn file(&self, _path: &Path) -> Result<()> {
+        #[cfg(feature = "scripting")]
+        if let Some(script) = self.script.as_ref().filter(|script| script.defines("on_match", 1)) {
+            script.on_file(_path)?;
+        }
+        Ok(())
+    }
+    
+    /// Run the action for each matching line of a content search
+    pub fn lines(&self, _path: &Path, _matches: &[(usize, String)]) -> Result<()> {
+        #[cfg(feature = "scripting")]
+        if let Some(script) = self.script.as_ref().filter(|script| script.defines("on_match", 3)) {
+            for (line_number, line) in _matches {
+                script.on_line(_path, *line_number, line)?;
+            }
+        }
+        Ok(())
+    }
+}
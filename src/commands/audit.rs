@@ -0,0 +1,160 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use console::style;
+use log::{info, warn};
+use serde_json::json;
+use walkdir::WalkDir;
+
+use crate::output::{outln, outrec, styled_path};
+use crate::commands::{ensure_complete, Command, Outcome};
+use crate::core::{FileSearchConfig, OutputFormat};
+use crate::core::observer::{SearchObserver, TrackingObserver};
+use crate::filters::audit::{AuditRule, Severity};
+use crate::filters::{Filter, FilterResult};
+
+/// Pseudo-filesystems whose entries say nothing about the files on disk
+const SKIPPED_ROOTS: [&str; 3] = ["/proc", "/sys", "/dev"];
+
+/// A path flagged by an audit rule
+#[derive(Debug, Clone)]
+struct Finding {
+    rule: AuditRule,
+    path: PathBuf,
+}
+
+/// Command that checks a directory tree against built-in security rules
+///
+/// Hidden files are always included and symbolic links are never followed, since
+/// both matter for security. Findings are printed in text or, with `--format json`,
+/// as JSON Lines records.
+pub struct AuditCommand<'a> {
+    config: &'a FileSearchConfig,
+    root: PathBuf,
+    rules: Vec<AuditRule>,
+}
+
+impl<'a> AuditCommand<'a> {
+    /// Create a new audit of `root` with the given rules (all rules if empty)
+    pub fn new(config: &'a FileSearchConfig, root: &Path, rules: &[AuditRule]) -> Self {
+        let rules = if rules.is_empty() { AuditRule::ALL.to_vec() } else { rules.to_vec() };
+        Self { config, root: root.to_path_buf(), rules }
+    }
+    
+    /// Walk the tree and collect every finding
+    fn audit(&self, observer: &dyn SearchObserver) -> Vec<Finding> {
+        let filters: Vec<(AuditRule, Box<dyn Filter>)> = self.rules.iter()
+            .map(|&rule| (rule, rule.filter()))
+            .collect();
+        let mut findings = Vec::new();
+        
+        let walker = WalkDir::new(&self.root)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !SKIPPED_ROOTS.iter().any(|root| entry.path() == Path::new(root)));
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    let path = e.path().unwrap_or(&self.root).to_path_buf();
+                    observer.path_error(&path, &e);
+                    if !self.config.no_messages {
+                        info!("Skipping {}: {}", path.display(), e);
+                    }
+                    continue;
+                }
+            };
+            
+            if entry.file_type().is_dir() {
+                observer.directory_processed(entry.path());
+            } else {
+                observer.file_found(entry.path());
+            }
+            
+            for (rule, filter) in &filters {
+                if filter.filter(entry.path()) == FilterResult::Accept {
+                    findings.push(Finding { rule: *rule, path: entry.path().to_path_buf() });
+                }
+            }
+        }
+        
+        findings
+    }
+    
+    fn display_findings(&self, findings: &[Finding], observer: &dyn SearchObserver) -> Result<()> {
+        let mut by_severity: BTreeMap<Severity, usize> = BTreeMap::new();
+        for finding in findings {
+            *by_severity.entry(finding.rule.severity()).or_insert(0) += 1;
+        }
+        
+        if self.config.print0 {
+            // A file with several findings is only listed once
+            let mut listed = BTreeSet::new();
+            for finding in findings {
+                if listed.insert(&finding.path) {
+                    outrec!('\0', "{}", finding.path.display())?;
+                }
+            }
+            return Ok(());
+        }
+        
+        if self.config.output_format == OutputFormat::Json {
+            for finding in findings {
+                outln!("{}", json!({
+                    "type": "finding",
+                    "rule": finding.rule,
+                    "severity": finding.rule.severity(),
+                    "path": finding.path,
+                    "message": finding.rule.message(),
+                }))?;
+            }
+            outln!("{}", json!({
+                "type": "summary",
+                "findings": findings.len(),
+                "by_severity": by_severity,
+                "files_checked": observer.files_count(),
+                "directories_checked": observer.directories_count(),
+            }))?;
+            return Ok(());
+        }
+        
+        let path_style = self.config.theme.path();
+        for finding in findings {
+            let severity = finding.rule.severity();
+            let label = match severity {
+                Severity::High => style(format!("[{}]", severity)).bold().red(),
+                Severity::Medium => style(format!("[{}]", severity)).bold().yellow(),
+                Severity::Low => style(format!("[{}]", severity)).bold().dim(),
+            };
+            outln!("{:<10} {:<18} {}  {}", label, finding.rule.name(), styled_path(&path_style, &finding.path), style(finding.rule.message()).dim())?;
+        }
+        
+        if self.config.show_progress {
+            if findings.is_empty() {
+                eprintln!("\nNo findings");
+            } else {
+                let counts: Vec<String> = by_severity.iter().rev()
+                    .map(|(severity, count)| format!("{} {}", count, severity.to_string().to_lowercase()))
+                    .collect();
+                eprintln!("\n{} finding(s): {}", findings.len(), counts.join(", "));
+            }
+            eprintln!("Checked {} files in {} directories", observer.files_count(), observer.directories_count());
+        }
+        Ok(())
+    }
+}
+
+impl Command for AuditCommand<'_> {
+    fn execute(&self) -> Result<Outcome> {
+        if !cfg!(unix) {
+            warn!("Permission and ownership rules only apply on Unix systems");
+        }
+        
+        let observer = TrackingObserver::new();
+        let findings = self.audit(&observer);
+        self.display_findings(&findings, &observer)?;
+        ensure_complete(self.config, observer.path_errors())?;
+        
+        Ok(Outcome::from_counts(findings.len(), observer.errors_count()))
+    }
+}
@@ -1,16 +1,23 @@
-use anyhow::Result;
-use log::{info, debug};
+use anyhow::{Context, Result};
+use std::borrow::Cow;
+use std::io::{self, IsTerminal};
+use console::Term;
+use log::{info, debug, warn};
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use std::path::PathBuf;
 use std::time::Instant;
+use serde_json::json;
 
-use crate::commands::Command;
-use crate::core::config::FileSearchConfig;
-use crate::core::observer::NullObserver;
+use crate::commands::{ensure_complete, Command, Outcome};
+use crate::core::config::{FileSearchConfig, OutputFormat};
+use crate::core::observer::{SearchObserver, SilentObserver};
+use crate::output::{flush_pending, outln, outrec, styled_path, write_file_output};
+use crate::utils::frecency::FrecencyStore;
 use crate::utils::standard_search;
 
-
+/// Most matches offered to choose from with `--select`
+const SELECT_CHOICES: usize = 20;
 
 /// Command for fuzzy file searching
 pub struct FuzzyCommand<'a> {
@@ -23,17 +30,33 @@ impl<'a> FuzzyCommand<'a> {
         Self { config }
     }
 
-    /// Process files with fuzzy matching
-    fn process_files(&self, files: &[PathBuf]) -> Result<()> {
+    /// Text to rank file names against, and the configuration selecting the candidates
+    ///
+    /// Without a fuzzy query the name criterion is used as the query, as it was before
+    /// the query had its own setting; it then no longer restricts the candidates. Neither
+    /// does the result limit, which applies to the ranked matches.
+    fn query(&self) -> (Option<&str>, Cow<'_, FileSearchConfig>) {
+        let query = self.config.fuzzy_query.as_deref().or(self.config.file_name.as_deref());
+        if self.config.fuzzy_query.is_some() && self.config.max_results.is_none() {
+            return (query, Cow::Borrowed(self.config));
+        }
+        
+        let mut candidates = self.config.clone();
+        if self.config.fuzzy_query.is_none() {
+            candidates.file_name = None;
+        }
+        candidates.max_results = None;
+        (query, Cow::Owned(candidates))
+    }
+
+    /// Print the files matching the fuzzy pattern and return how many matched
+    fn process_files(&self, files: &[PathBuf], pattern: Option<&str>) -> Result<usize> {
         // Create a fuzzy matcher with appropriate settings
         let matcher = SkimMatcherV2::default();
         
-        // Get the search pattern
-        let pattern = if let Some(name) = &self.config.file_name {
-            name
-        } else {
+        let Some(pattern) = pattern else {
             // If no pattern specified, nothing to match against
-            return Ok(());
+            return Ok(0);
         };
         
         // Get threshold from config or use default
@@ -58,48 +81,140 @@ impl<'a> FuzzyCommand<'a> {
         }
         
         // Sort matches by score (highest first)
-        matches.sort_by(|a, b| b.1.cmp(&a.1));
+        matches.sort_by_key(|m| std::cmp::Reverse(m.1));
+        if let Some(limit) = self.config.max_results {
+            matches.truncate(limit);
+        }
+        
+        if self.config.select {
+            return select(&matches, self.config);
+        }
         
         // Display results
-        if !matches.is_empty() {
-            println!("Found {} fuzzy matching file(s):", matches.len());
+        let paths: Vec<PathBuf> = matches.iter().map(|(path, _)| path.clone()).collect();
+        if write_file_output(self.config, &paths)? {
+            return Ok(paths.len());
+        }
+        
+        if self.config.print0 {
+            for (path, _) in matches {
+                outrec!('\0', "{}", path.display())?;
+            }
+        } else if self.config.output_format == OutputFormat::Vimgrep {
+            for (path, _) in matches {
+                outln!("{}:1:1:", path.display())?;
+            }
+        } else if self.config.output_format == OutputFormat::Json {
+            for (path, score) in matches {
+                outln!("{}", json!({"type": "file", "path": path, "score": score}))?;
+            }
+        } else if !matches.is_empty() {
+            if self.config.show_progress {
+                eprintln!("Found {} fuzzy matching file(s):", matches.len());
+            }
+            let path_style = self.config.theme.path();
             for (path, score) in matches {
                 // Calculate match quality as a percentage (0-100)
                 let quality = ((score as f64) / 100.0).min(1.0) * 100.0;
-                println!("  {} (match quality: {:.0}%)", path.display(), quality);
+                outln!("  {} (match quality: {:.0}%)", styled_path(&path_style, &path), quality)?;
             }
-        } else {
-            println!("No fuzzy matches found.");
+        } else if self.config.show_progress {
+            eprintln!("No fuzzy matches found.");
         }
         
-        Ok(())
+        Ok(paths.len())
     }
 }
 
 impl<'a> Command for FuzzyCommand<'a> {
-    fn execute(&self) -> Result<()> {
+    fn execute(&self) -> Result<Outcome> {
         let start_time = Instant::now();
         let search_path = PathBuf::from(self.config.get_path());
         info!("Starting fuzzy search in {}", search_path.display());
         
-        // Use standard search to collect files, then apply fuzzy matching
-        let results = standard_search::search_directory(
-            &search_path,
-            self.config,
-            &NullObserver,
-        )?;
+        // Use standard search to collect candidates with the other criteria, then rank them
+        let (query, candidates) = self.query();
+        let observer = SilentObserver::new();
+        let results = standard_search::collect_files(&candidates, &observer)?;
         
         debug!("Found {} files to process for fuzzy matching", results.len());
         
         // Process the collected files with fuzzy matching
-        self.process_files(&results)?;
+        let matches = self.process_files(&results, query)?;
+        
+        if self.config.output_format == OutputFormat::Text && self.config.show_progress && !self.config.select {
+            // Display performance metrics, after any results held back for the pager
+            flush_pending()?;
+            let elapsed = start_time.elapsed();
+            eprintln!("\nPerformance:");
+            eprintln!("  Time taken: {:.2} seconds", elapsed.as_secs_f64());
+            eprintln!("  Files processed: {}", results.len());
+        }
         
-        // Display performance metrics
-        let elapsed = start_time.elapsed();
-        println!("\nPerformance:");
-        println!("  Time taken: {:.2} seconds", elapsed.as_secs_f64());
-        println!("  Files processed: {}", results.len());
+        ensure_complete(self.config, observer.path_errors())?;
         
-        Ok(())
+        Ok(Outcome::from_counts(matches, observer.errors_count()))
+    }
+}
+
+/// Print only the chosen matches, one path per line, and return how many were chosen
+///
+/// This is the contract shell integration relies on, as in `cd "$(dirname "$(oqab --select main)")"`:
+/// standard output carries nothing but the chosen paths, and nothing at all when no
+/// file matches or the choice is cancelled. On a terminal the best matches are listed
+/// on standard error to choose from; otherwise the best match is chosen. Chosen paths
+/// are recorded in the frecency store, so that `--sort frecency` ranks them higher.
+fn select(matches: &[(PathBuf, i64)], config: &FileSearchConfig) -> Result<usize> {
+    if matches.is_empty() && config.show_progress {
+        eprintln!("No fuzzy matches found.");
+    }
+    
+    let chosen = if matches.len() > 1 && io::stdin().is_terminal() && io::stderr().is_terminal() {
+        pick(&matches[..matches.len().min(SELECT_CHOICES)])?
+    } else {
+        matches.iter().take(1).map(|(path, _)| path.clone()).collect()
+    };
+    
+    // A store that cannot be used must not keep the choice from being printed
+    if !chosen.is_empty() && let Err(e) = FrecencyStore::record_chosen(&chosen) {
+        warn!("Failed to record selected paths: {:#}", e);
+    }
+    for path in &chosen {
+        outrec!(config.record_terminator(), "{}", path.display())?;
+    }
+    Ok(chosen.len())
+}
+
+/// Ask on the terminal which of the matches to choose
+///
+/// Several numbers choose several paths, enter alone chooses the best match and `q`
+/// chooses nothing.
+fn pick(matches: &[(PathBuf, i64)]) -> Result<Vec<PathBuf>> {
+    let term = Term::stderr();
+    for (number, (path, _)) in matches.iter().enumerate() {
+        term.write_line(&format!("{:>3}) {}", number + 1, path.display()))
+            .context("Failed to write to the terminal")?;
+    }
+    
+    loop {
+        term.write_str(&format!("Select 1-{} (several separated by spaces, enter for 1, q to cancel): ", matches.len()))
+            .context("Failed to write to the terminal")?;
+        let answer = term.read_line().context("Failed to read from the terminal")?;
+        let answer = answer.trim();
+        match answer {
+            "" => return Ok(vec![matches[0].0.clone()]),
+            "q" | "Q" => return Ok(Vec::new()),
+            _ => {}
+        }
+        
+        let numbers: Option<Vec<usize>> = answer
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|number| !number.is_empty())
+            .map(|number| number.parse().ok().filter(|number| (1..=matches.len()).contains(number)))
+            .collect();
+        match numbers {
+            Some(numbers) => return Ok(numbers.into_iter().map(|number| matches[number - 1].0.clone()).collect()),
+            None => term.write_line(&format!("Not a choice: {}", answer)).context("Failed to write to the terminal")?,
+        }
     }
 }
@@ -1,16 +1,253 @@
 use anyhow::{Result, Context};
-use std::time::{Duration, Instant};
+use std::collections::{BTreeMap, HashSet};
+use std::time::Instant;
 use std::cell::RefCell;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, IsTerminal};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use rayon::prelude::*;
 use regex::RegexBuilder;
-use console::style;
-use log::debug;
+use console::{style, Style};
+use log::{debug, log_enabled, warn, Level};
+use serde::Serialize;
+use serde_json::json;
 
-use crate::commands::Command;
-use crate::core::{ConfigManager, FileSearchConfig};
-use crate::utils::search_directory;
+use crate::commands::{ensure_complete, Command, Outcome};
+use crate::commands::actions::MatchActions;
+use crate::core::{AppConfig, ConfigManager, FileSearchConfig, FinderFactory, OutputFormat, SearchReport, WorkerStats};
+use crate::core::config::{BinaryMode, Handler, InputOrder};
+use crate::index::{ContentIndex, TrigramQuery};
+use crate::core::observer::{create_observer, SearchObserver, SilentObserver};
+use crate::output::{flush_pending, outln, outrec, sort_by_mtime, styled_path, write_file_output, Histogram, HistogramKind, SqliteExporter, Template};
+use crate::output::stdout::write_text;
+use crate::utils::collect_files;
+use crate::utils::content::{starts_binary, BINARY_SAMPLE_SIZE};
+use crate::utils::documents::DocumentExtractors;
+use crate::utils::handlers::{run_command, HandlerRegistry};
+use crate::utils::standard_search::report_path_error;
+
+/// Summary statistics for a content search
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GrepStats {
+    /// Files whose content was searched
+    pub files_searched: usize,
+    /// Files with at least one matching line
+    pub files_matched: usize,
+    /// Matching lines across all files
+    pub matches: usize,
+    /// Binary files reported as matching without printing their lines
+    pub binary_matched: usize,
+    /// Bytes read from searched files
+    pub bytes_scanned: u64,
+    /// Candidate files that were not searched
+    pub skipped: SkippedFiles,
+    /// Matching lines per file extension (`""` for files without one)
+    pub matches_by_extension: BTreeMap<String, usize>,
+    /// Searched files by the properties asked for with `--histogram`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub histograms: Vec<Histogram>,
+    /// Wall-clock time of the whole run in milliseconds
+    pub elapsed_ms: u128,
+    /// Files searched per second
+    pub files_per_sec: f64,
+}
+
+/// Candidate files skipped by a content search, by reason
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SkippedFiles {
+    /// Files that look binary
+    pub binary: usize,
+    /// Files larger than `--max-filesize`
+    pub too_large: usize,
+    /// Files that could not be opened or read
+    pub unreadable: usize,
+    /// Files the content index proved cannot match
+    pub indexed: usize,
+    /// Files a configured handler says to leave out
+    pub handled: usize,
+}
+
+impl SkippedFiles {
+    /// Total number of skipped files
+    pub fn total(&self) -> usize {
+        self.binary + self.too_large + self.unreadable + self.indexed + self.handled
+    }
+}
+
+/// Outcome of searching a single file
+enum FileScan {
+    /// The file was searched; holds its size, matching lines and, for `--passthru`, every line
+    Searched { bytes: u64, matches: Vec<(usize, String)>, lines: Vec<(usize, String)> },
+    /// A binary file searched with `--binary` matched; holds its size and matching line count
+    BinaryMatch { bytes: u64, matches: usize },
+    /// The file looks binary, or a handler says it is, and was not searched
+    Binary,
+    /// A handler says to leave the file out, or it is not a regular file
+    Skipped,
+    /// The file exceeds `--max-filesize` and was not searched
+    TooLarge,
+    /// The file could not be opened or read
+    Unreadable,
+}
+
+/// Search one file for lines matching the pattern
+///
+/// Runs on the grep worker pool, so failures only go to the thread-safe observer
+/// and statistics are left to the caller.
+fn search_file(
+    path: &Path,
+    regex: &regex::Regex,
+    config: &FileSearchConfig,
+    extractors: &DocumentExtractors,
+    handlers: &HandlerRegistry,
+    observer: &dyn SearchObserver,
+) -> FileScan {
+    // Only regular files have contents to search, and opening a named pipe would wait for a writer
+    if !path.is_file() {
+        return FileScan::Skipped;
+    }
+    
+    let handler = handlers.find(path);
+    match handler {
+        Some(Handler::Skip) => return FileScan::Skipped,
+        Some(Handler::Binary) if config.binary == BinaryMode::Skip => return FileScan::Binary,
+        _ => {}
+    }
+    
+    // Try to open the file, counting and skipping files we cannot read
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => {
+            report_path_error(config, observer, path, &e);
+            return FileScan::Unreadable;
+        }
+    };
+    let file_size = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+    
+    if let Some(limit) = config.max_filesize {
+        if file_size > limit {
+            debug!("Skipping {} ({} bytes exceeds --max-filesize)", path.display(), file_size);
+            return FileScan::TooLarge;
+        }
+    }
+    
+    // Documents are searched through their extracted text rather than their raw bytes,
+    // as are files whose handler converts them to text
+    let extracted = match handler {
+        Some(Handler::Extract(doc_type)) => handlers.extractor(*doc_type).map(|extractor| extractor.extract(path)),
+        Some(Handler::Command(command)) => Some(run_command(command, path)),
+        Some(Handler::Text | Handler::Binary) => None,
+        _ => extractors.find(path).map(|extractor| extractor.extract(path)),
+    };
+    if let Some(extracted) = extracted {
+        return match extracted {
+            Ok(text) => {
+                let lines = text.lines().enumerate().map(|(index, line)| (index + 1, line.to_string()));
+                scan_lines(lines, regex, config.passthru, file_size)
+            }
+            Err(e) => {
+                if !config.no_messages {
+                    warn!("{:#}", e);
+                }
+                FileScan::Unreadable
+            }
+        };
+    }
+    
+    let mut reader = BufReader::with_capacity(BINARY_SAMPLE_SIZE, file);
+    
+    // NUL bytes separate records in --null-data mode instead of marking binary files
+    if config.null_data {
+        return search_records(path, reader, b'\0', regex, config.passthru, file_size);
+    }
+    
+    // A text handler vouches for files that merely look binary
+    let binary = match starts_binary(&mut reader) {
+        Ok(_) if handler == Some(&Handler::Text) => false,
+        Ok(looks_binary) => looks_binary || handler == Some(&Handler::Binary),
+        Err(e) => {
+            report_path_error(config, observer, path, &e);
+            return FileScan::Unreadable;
+        }
+    };
+    if binary {
+        // Binary content is rarely valid UTF-8, so lines are decoded lossily
+        return match config.binary {
+            BinaryMode::Skip => {
+                debug!("Skipping binary file {}", path.display());
+                FileScan::Binary
+            }
+            BinaryMode::Report => match search_records(path, reader, b'\n', regex, false, file_size) {
+                FileScan::Searched { bytes, matches, .. } if !matches.is_empty() => {
+                    FileScan::BinaryMatch { bytes, matches: matches.len() }
+                }
+                scan => scan,
+            },
+            BinaryMode::Text => search_records(path, reader, b'\n', regex, config.passthru, file_size),
+        };
+    }
+    
+    let lines = reader.lines().enumerate().filter_map(|(line_num, line_result)| match line_result {
+        Ok(line) => Some((line_num + 1, line)),
+        Err(e) => {
+            // Skip any errors when reading lines
+            // This handles encoding issues, invalid arguments, and other errors
+            debug!("Skipping line in file {} due to error: {}", path.display(), e);
+            None
+        }
+    });
+    
+    scan_lines(lines, regex, config.passthru, file_size)
+}
+
+/// Search the records of a file ending in `terminator`, numbering matches by record
+fn search_records(path: &Path, reader: impl BufRead, terminator: u8, regex: &regex::Regex, passthru: bool, file_size: u64) -> FileScan {
+    let records = reader.split(terminator)
+        .map_while(|record_result| match record_result {
+            Ok(record) => Some(String::from_utf8_lossy(&record).into_owned()),
+            Err(e) => {
+                // Unlike decoding errors, read errors would repeat on every further record
+                debug!("Stopping search of {} due to error: {}", path.display(), e);
+                None
+            }
+        })
+        .enumerate()
+        .map(|(record_num, record)| (record_num + 1, record));
+    
+    scan_lines(records, regex, passthru, file_size)
+}
+
+/// Keep the numbered lines matching the pattern, and every line when passing them all through
+fn scan_lines(lines: impl Iterator<Item = (usize, String)>, regex: &regex::Regex, passthru: bool, file_size: u64) -> FileScan {
+    let mut matches = Vec::new();
+    let mut all_lines = Vec::new();
+    
+    for (line_num, line) in lines {
+        if regex.is_match(&line) {
+            matches.push((line_num, line.clone()));
+        }
+        if passthru {
+            all_lines.push((line_num, line));
+        }
+    }
+    
+    FileScan::Searched { bytes: file_size, matches, lines: all_lines }
+}
+
+/// Style every match of the pattern within a line
+fn highlight(line: &str, regex: &regex::Regex, style: &Style) -> String {
+    let mut highlighted = String::with_capacity(line.len());
+    let mut last = 0;
+    for found in regex.find_iter(line) {
+        highlighted.push_str(&line[last..found.start()]);
+        highlighted.push_str(&style.apply_to(found.as_str()).to_string());
+        last = found.end();
+    }
+    highlighted.push_str(&line[last..]);
+    highlighted
+}
 
 /// GrepCommand implements text pattern searching within files
 /// 
@@ -21,7 +258,8 @@ pub struct GrepCommand<'a> {
     start_time: Instant,
     total_files: RefCell<usize>,
     total_dirs: RefCell<usize>,
-    matches_found: RefCell<usize>,
+    workers: RefCell<Vec<WorkerStats>>,
+    stats: RefCell<GrepStats>,
 }
 
 impl<'a> GrepCommand<'a> {
@@ -31,116 +269,490 @@ impl<'a> GrepCommand<'a> {
             start_time: Instant::now(),
             total_files: RefCell::new(0),
             total_dirs: RefCell::new(0),
-            matches_found: RefCell::new(0),
+            workers: RefCell::new(Vec::new()),
+            stats: RefCell::new(GrepStats {
+                histograms: config.histograms.iter().map(|&kind| Histogram::new(kind)).collect(),
+                ..GrepStats::default()
+            }),
         }
     }
 
-    
-    fn search_file(&self, path: &Path, regex: &regex::Regex) -> Result<Vec<(usize, String)>> {
-        // Try to open the file, silently skip if permission denied
-        let file = match File::open(path) {
-            Ok(file) => file,
-            Err(e) => {
-                // Skip files we don't have permission to access
-                if e.kind() == std::io::ErrorKind::PermissionDenied {
-                    return Ok(Vec::new());
-                }
-                // For other errors, return with context
-                return Err(e).with_context(|| format!("Failed to open file: {}", path.display()));
-            }
-        };
+
+    fn process_files(
+        &self,
+        files: &[PathBuf],
+        config: &FileSearchConfig,
+        observer: &dyn SearchObserver,
+        actions: &MatchActions,
+    ) -> Result<usize> {
+        let template = config.printf.as_deref().map(Template::parse).transpose()?;
         
-        let reader = BufReader::new(file);
-        let mut matches = Vec::new();
+        // Create regex pattern from the config
+        let pattern = config.pattern.as_deref().unwrap_or("");
+        let regex = RegexBuilder::new(&config.regex_syntax.translate(pattern))
+            .case_insensitive(config.ignore_case)
+            .build()
+            .with_context(|| format!("Failed to compile regex pattern: {}", pattern))?;
+            
+        let mut total_matches = 0;
+        let extractors = DocumentExtractors::for_config(config);
+        let handlers = HandlerRegistry::for_config(config)?;
         
-        for (line_num, line_result) in reader.lines().enumerate() {
-            let line = match line_result {
-                Ok(line) => line,
-                Err(e) => {
-                    // Skip any errors when reading lines
-                    // This handles encoding issues, invalid arguments, and other errors
-                    debug!("Skipping line in file {} due to error: {}", path.display(), e);
-                    continue;
+        // Optionally mirror results into a SQLite database
+        let exporter = config.output_sqlite.as_ref()
+            .map(SqliteExporter::create)
+            .transpose()?;
+        
+        // File-based formats collect matched files instead of printing them
+        let writes_file = config.output_format == OutputFormat::Parquet;
+        let mut matched_files = Vec::new();
+        
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(config.thread_count.unwrap_or_else(num_cpus::get))
+            .build()
+            .context("Failed to create grep worker pool")?;
+        let (sender, receiver) = mpsc::channel();
+        
+        // Files are searched on the pool while results are printed on this thread,
+        // either as they complete or reordered back into candidate order
+        thread::scope(|scope| -> Result<()> {
+            scope.spawn(|| {
+                pool.install(|| {
+                    // Sending only fails once output has stopped, such as when stdout is closed,
+                    // so the remaining files are left unsearched
+                    let _ = files.par_iter().enumerate().try_for_each_with(sender, |sender, (index, path)| {
+                        sender.send((index, search_file(path, &regex, config, &extractors, &handlers, observer)))
+                    });
+                });
+            });
+            
+            let mut handle = |index: usize, scan: FileScan| -> Result<()> {
+                let file_path = &files[index];
+                self.record_scan(file_path, &scan);
+                
+                if let FileScan::BinaryMatch { matches, .. } = scan {
+                    total_matches += matches;
+                    if writes_file {
+                        matched_files.push(file_path.clone());
+                    }
+                    self.emit_binary_match(file_path, config, template.as_ref(), exporter.as_ref(), writes_file)?;
+                } else if let FileScan::Searched { matches, lines, .. } = scan {
+                    if !matches.is_empty() {
+                        total_matches += matches.len();
+                        if writes_file {
+                            matched_files.push(file_path.clone());
+                        }
+                        actions.lines(file_path, &matches)?;
+                        self.emit_matches(file_path, &matches, &lines, &regex, config, template.as_ref(), exporter.as_ref(), writes_file)?;
+                    } else if !writes_file && config.output_format == OutputFormat::Text && template.is_none() {
+                        // Files without matches are still passed through
+                        self.emit_passthru(file_path, &lines, &regex, config)?;
+                    }
                 }
+                Ok(())
             };
             
-            if regex.is_match(&line) {
-                matches.push((line_num + 1, line));
-                *self.matches_found.borrow_mut() += 1;
+            // Hanging up the channel once enough files matched stops the remaining searches
+            let limit_reached = || config.max_results.is_some_and(|limit| self.stats.borrow().files_matched >= limit);
+            let mut pending = BTreeMap::new();
+            let mut next_index = 0;
+            'results: for (index, scan) in receiver {
+                if config.unordered {
+                    handle(index, scan)?;
+                    if limit_reached() {
+                        break;
+                    }
+                    continue;
+                }
+                
+                pending.insert(index, scan);
+                while let Some(scan) = pending.remove(&next_index) {
+                    handle(next_index, scan)?;
+                    next_index += 1;
+                    if limit_reached() {
+                        break 'results;
+                    }
+                }
             }
+            Ok(())
+        })?;
+        
+        if let Some(exporter) = exporter {
+            exporter.finish()?;
+        }
+        
+        if writes_file {
+            write_file_output(config, &matched_files)?;
         }
         
-        Ok(matches)
+        let stats = self.finish_stats();
+        
+        // Print summary if showing progress or asked for statistics; machine-readable
+        // output only carries statistics as a final JSON record
+        match config.output_format {
+            OutputFormat::Text if config.show_progress || config.stats => {
+                // Results held back for the pager come before the summary
+                flush_pending()?;
+                eprintln!("\nFound {} matches in {} files", 
+                    style(total_matches).bold().green(),
+                    style(stats.files_matched).bold());
+                self.display_performance_metrics(&stats, observer.errors_count());
+            }
+            OutputFormat::Json if config.stats => {
+                outln!("{}", json!({"type": "summary", "stats": stats}))?;
+            }
+            _ => {}
+        }
+        
+        Ok(total_matches)
     }
     
-    fn process_files(&self, files: &[PathBuf], config: &FileSearchConfig) -> Result<()> {
-        // Create regex pattern from the config
-        let pattern = config.pattern.as_deref().unwrap_or("");
-        let regex = RegexBuilder::new(pattern)
-            .case_insensitive(config.ignore_case)
-            .build()
-            .with_context(|| format!("Failed to compile regex pattern: {}", pattern))?;
-            
-        let mut total_matches = 0;
+    /// Select the files to search for the pattern
+    ///
+    /// Explicit file lists are filtered directly; otherwise candidates come from the
+    /// same finder pipeline as `--advanced`, so every filter flag applies to grep too.
+    fn find_candidates(&self, config: &FileSearchConfig) -> Result<(Vec<PathBuf>, SearchReport)> {
+        // The result limit counts matching files, which only the content search can tell
+        let config = &FileSearchConfig { max_results: None, ..config.clone() };
+        
+        if config.files_from.is_some() {
+            let start = Instant::now();
+            let observer = create_observer(config);
+            let files = collect_files(config, &*observer)?;
+            return Ok((files, SearchReport::from_observer(&*observer, start.elapsed())));
+        }
+        
+        let app_config = AppConfig::from(config);
+        let finder = FinderFactory::from_search_config(config)?;
+        let (matches, report) = finder.find(&app_config.root_dir)
+            .with_context(|| format!("Failed to search directory: {}", app_config.root_dir.display()))?;
+        let mut files: Vec<PathBuf> = matches.into_iter().map(|found| found.path).collect();
+        
+        // Workers finish in any order, so sort to keep the output stable between runs
+        files.sort();
+        
+        Ok((files, report))
+    }
+    
+    /// Drop candidates that a content index proves cannot contain the pattern
+    fn prune_with_index(&self, config: &FileSearchConfig, files: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+        // --passthru prints files without a match too, so none may be left out
+        if config.no_index || config.files_from.is_some() || config.passthru {
+            return Ok(files);
+        }
+        
+        let root = AppConfig::from(config).root_dir;
+        let index = match &config.index_file {
+            Some(path) => Some(ContentIndex::open_existing(Path::new(path))?),
+            // An unusable cached index only costs speed, so fall back to a full search
+            None => ContentIndex::find_for(&root).unwrap_or_else(|e| {
+                warn!("Ignoring content index: {:#}", e);
+                None
+            }),
+        };
+        let Some(index) = index else {
+            return Ok(files);
+        };
+        
+        // The index holds the raw bytes, but files converted by a handler are searched
+        // through the converted text, so only the others can be ruled out
+        let handlers = HandlerRegistry::for_config(config)?;
+        let converted = |file: &Path| matches!(handlers.find(file), Some(Handler::Command(_) | Handler::Extract(_)));
+        let indexed: Vec<PathBuf> = files.iter().filter(|file| !converted(file)).cloned().collect();
+        
+        let pattern = config.regex_syntax.translate(config.pattern.as_deref().unwrap_or(""));
+        let query = TrigramQuery::from_regex(&pattern, config.ignore_case);
+        let possible: HashSet<PathBuf> = index.prune(indexed, &root, &query)?.into_iter().collect();
+        let candidates = files.len();
+        let files: Vec<PathBuf> = files.into_iter()
+            .filter(|file| possible.contains(file) || converted(file))
+            .collect();
+        self.stats.borrow_mut().skipped.indexed = candidates - files.len();
+        
+        Ok(files)
+    }
+    
+    /// Export and print the matching lines of one file
+    #[allow(clippy::too_many_arguments)]
+    fn emit_matches(
+        &self,
+        file_path: &Path,
+        matches: &[(usize, String)],
+        lines: &[(usize, String)],
+        regex: &regex::Regex,
+        config: &FileSearchConfig,
+        template: Option<&Template>,
+        exporter: Option<&SqliteExporter>,
+        writes_file: bool,
+    ) -> Result<()> {
+        if let Some(exporter) = exporter {
+            let file_id = exporter.add_file(file_path)?;
+            for (line_num, line) in matches {
+                exporter.add_match(file_id, *line_num, line)?;
+            }
+        }
+        
+        if writes_file {
+            return Ok(());
+        }
+        
+        // Templates print once per matching line if they show lines, and once per file otherwise
+        if let Some(template) = template {
+            if template.uses_lines() {
+                for (line_num, line) in matches {
+                    write_text(&template.render(file_path, Some((*line_num, line))))?;
+                }
+            } else {
+                write_text(&template.render(file_path, None))?;
+            }
+            return Ok(());
+        }
         
-        for file_path in files {
-            let matches = self.search_file(file_path, &regex)?;
+        let terminator = config.record_terminator();
+        if config.output_format == OutputFormat::Json {
+            if config.files_with_matches {
+                outln!("{}", json!({"type": "file", "path": file_path}))?;
+            } else {
+                for (line_num, line) in matches {
+                    outln!("{}", json!({
+                        "type": "match",
+                        "path": file_path,
+                        "line_number": line_num,
+                        "text": line,
+                    }))?;
+                }
+            }
+        } else if config.output_format == OutputFormat::Vimgrep && !config.files_with_matches {
+            // One file:line:col:text record per match
+            for (line_num, line) in matches {
+                for found in regex.find_iter(line) {
+                    outln!("{}:{}:{}:{}", file_path.display(), line_num, found.start() + 1, line)?;
+                }
+            }
+        } else if config.files_with_matches {
+            // Only print the filename
+            outrec!(terminator, "{}", styled_path(&config.theme.path(), file_path))?;
+        } else if config.passthru {
+            self.emit_passthru(file_path, lines, regex, config)?;
+        } else if config.heading.unwrap_or_else(|| io::stdout().is_terminal()) {
+            // Print filename header and matches
+            if !config.no_filename {
+                outrec!(terminator, "{}", styled_path(&config.theme.heading(), file_path))?;
+            }
             
-            if !matches.is_empty() {
-                if config.files_with_matches {
-                    // Only print the filename
-                    println!("{}", file_path.display());
-                    total_matches += matches.len();
+            let line_number_style = config.theme.line_number();
+            let matched_style = config.theme.matched();
+            for (line_num, line) in matches {
+                let line = highlight(line, regex, &matched_style);
+                if config.line_number {
+                    outrec!(terminator, "{}: {}", line_number_style.apply_to(line_num), line)?;
                 } else {
-                    // Print filename header and matches
-                    println!("{}", style(file_path.display()).bold().cyan());
-                    
-                    // Use a reference to avoid moving matches
-                    for (line_num, line) in &matches {
-                        if config.line_number {
-                            println!("{}: {}", style(line_num).green(), line);
-                        } else {
-                            println!("{}", line);
-                        }
-                    }
-                    
-                    println!(); // Empty line between files
-                    total_matches += matches.len();
+                    outrec!(terminator, "{}", line)?;
                 }
             }
+            
+            outrec!(terminator, "")?; // Empty record between files
+        } else {
+            // One self-contained file:line:text record per match, as grep prints them
+            let path = styled_path(&config.theme.path(), file_path);
+            let line_number_style = config.theme.line_number();
+            let matched_style = config.theme.matched();
+            for (line_num, line) in matches {
+                let line = highlight(line, regex, &matched_style);
+                match (config.no_filename, config.line_number) {
+                    (false, true) => outrec!(terminator, "{}:{}:{}", path, line_number_style.apply_to(line_num), line)?,
+                    (false, false) => outrec!(terminator, "{}:{}", path, line)?,
+                    (true, true) => outrec!(terminator, "{}:{}", line_number_style.apply_to(line_num), line)?,
+                    (true, false) => outrec!(terminator, "{}", line)?,
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Report a matching binary file for `--binary` in place of its lines
+    fn emit_binary_match(
+        &self,
+        file_path: &Path,
+        config: &FileSearchConfig,
+        template: Option<&Template>,
+        exporter: Option<&SqliteExporter>,
+        writes_file: bool,
+    ) -> Result<()> {
+        if let Some(exporter) = exporter {
+            exporter.add_file(file_path)?;
+        }
+        
+        if writes_file {
+            return Ok(());
         }
         
-        // Print summary if showing progress
-        if config.show_progress {
-            let elapsed = self.start_time.elapsed();
-            println!("\nFound {} matches in {} files", 
-                style(total_matches).bold().green(),
-                style(files.len()).bold());
-            self.display_performance_metrics(total_matches, elapsed);
+        // Binary files have no lines to show, so line fields are left empty
+        if let Some(template) = template {
+            return write_text(&template.render(file_path, None));
         }
         
+        let terminator = config.record_terminator();
+        if config.output_format == OutputFormat::Json {
+            outln!("{}", json!({"type": "binary", "path": file_path}))?;
+        } else if config.files_with_matches {
+            outrec!(terminator, "{}", styled_path(&config.theme.path(), file_path))?;
+        } else {
+            outrec!(terminator, "Binary file {} matches", styled_path(&config.theme.path(), file_path))?;
+        }
         Ok(())
     }
     
-    fn display_performance_metrics(&self, matches_count: usize, elapsed: Duration) {
-        let elapsed_secs = elapsed.as_secs_f64();
-        let files_per_sec = if elapsed_secs > 0.0 && *self.total_files.borrow() > 0 {
-            *self.total_files.borrow() as f64 / elapsed_secs
+    /// Print every line of one file for `--passthru`, flagging and highlighting the matching ones
+    ///
+    /// As in grep, matching lines are separated from their file name and line number
+    /// by `:` and the other lines by `-`.
+    fn emit_passthru(&self, file_path: &Path, lines: &[(usize, String)], regex: &regex::Regex, config: &FileSearchConfig) -> Result<()> {
+        if lines.is_empty() {
+            return Ok(());
+        }
+        
+        let terminator = config.record_terminator();
+        let heading = config.heading.unwrap_or_else(|| io::stdout().is_terminal());
+        if heading && !config.no_filename {
+            outrec!(terminator, "{}", styled_path(&config.theme.heading(), file_path))?;
+        }
+        
+        let path = styled_path(&config.theme.path(), file_path);
+        let line_number_style = config.theme.line_number();
+        let matched_style = config.theme.matched();
+        for (line_num, line) in lines {
+            let (separator, text) = if regex.is_match(line) {
+                (':', highlight(line, regex, &matched_style))
+            } else {
+                ('-', line.clone())
+            };
+            let line_num = line_number_style.apply_to(line_num);
+            match (heading, config.no_filename, config.line_number) {
+                (true, _, true) => outrec!(terminator, "{}{} {}", line_num, separator, text)?,
+                (false, false, true) => outrec!(terminator, "{}{}{}{}{}", path, separator, line_num, separator, text)?,
+                (false, false, false) => outrec!(terminator, "{}{}{}", path, separator, text)?,
+                (false, true, true) => outrec!(terminator, "{}{}{}", line_num, separator, text)?,
+                (_, _, false) => outrec!(terminator, "{}", text)?,
+            }
+        }
+        
+        if heading {
+            outrec!(terminator, "")?; // Empty record between files
+        }
+        Ok(())
+    }
+    
+    /// Count a scanned file and its matches in the run statistics
+    fn record_scan(&self, path: &Path, scan: &FileScan) {
+        let mut stats = self.stats.borrow_mut();
+        let (bytes, matches) = match scan {
+            FileScan::Searched { bytes, matches, .. } => (*bytes, matches.len()),
+            FileScan::BinaryMatch { bytes, matches } => {
+                stats.binary_matched += 1;
+                (*bytes, *matches)
+            }
+            FileScan::Binary => return stats.skipped.binary += 1,
+            FileScan::Skipped => return stats.skipped.handled += 1,
+            FileScan::TooLarge => return stats.skipped.too_large += 1,
+            FileScan::Unreadable => return stats.skipped.unreadable += 1,
+        };
+        
+        stats.files_searched += 1;
+        stats.bytes_scanned += bytes;
+        if !stats.histograms.is_empty() {
+            if let Ok(metadata) = fs::metadata(path) {
+                for histogram in &mut stats.histograms {
+                    histogram.add(&metadata);
+                }
+            }
+        }
+        
+        if matches > 0 {
+            let extension = path.extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            stats.files_matched += 1;
+            stats.matches += matches;
+            *stats.matches_by_extension.entry(extension).or_insert(0) += matches;
+        }
+    }
+    
+    /// Stamp the run statistics with the elapsed time and return a copy
+    fn finish_stats(&self) -> GrepStats {
+        let mut stats = self.stats.borrow_mut();
+        let elapsed_secs = self.start_time.elapsed().as_secs_f64();
+        stats.elapsed_ms = self.start_time.elapsed().as_millis();
+        stats.files_per_sec = if elapsed_secs > 0.0 {
+            stats.files_searched as f64 / elapsed_secs
         } else {
             0.0
         };
-        
-        println!("\nPerformance:");
-        println!("  Time taken: {:.2} seconds", elapsed_secs);
-        println!("  Matches found: {}", matches_count);
-        println!("  Files searched: {}", *self.total_files.borrow());
-        println!("  Directories searched: {}", *self.total_dirs.borrow());
-        println!("  Processing rate: {:.2} files/sec", files_per_sec);
+        stats.clone()
+    }
+    
+    fn display_performance_metrics(&self, stats: &GrepStats, errors_count: usize) {
+        eprintln!("\nPerformance:");
+        eprintln!("  Time taken: {:.2} seconds", stats.elapsed_ms as f64 / 1000.0);
+        eprintln!("  Matches found: {}", stats.matches);
+        eprintln!("  Candidate files: {}", *self.total_files.borrow());
+        eprintln!("  Files searched: {}", stats.files_searched);
+        eprintln!("  Directories searched: {}", *self.total_dirs.borrow());
+        eprintln!("  Bytes scanned: {}", stats.bytes_scanned);
+        eprintln!("  Processing rate: {:.2} files/sec", stats.files_per_sec);
+        if stats.skipped.total() > 0 {
+            eprintln!("  Files skipped: {} (binary: {}, too large: {}, unreadable: {}, ruled out by index: {}, by handler: {})",
+                stats.skipped.total(),
+                stats.skipped.binary,
+                stats.skipped.too_large,
+                stats.skipped.unreadable,
+                stats.skipped.indexed,
+                stats.skipped.handled);
+        }
+        if stats.binary_matched > 0 {
+            eprintln!("  Binary files matched: {}", stats.binary_matched);
+        }
+        if errors_count > 0 {
+            eprintln!("  Unreadable paths: {}", errors_count);
+        }
+        if !stats.matches_by_extension.is_empty() {
+            eprintln!("\nMatches by extension:");
+            for (extension, count) in &stats.matches_by_extension {
+                let label = if extension.is_empty() { "(none)".to_string() } else { format!(".{}", extension) };
+                eprintln!("  {:<12} {}", label, count);
+            }
+        }
+        // Worker telemetry is only of interest when diagnosing how a search scales
+        let workers = self.workers.borrow();
+        if log_enabled!(Level::Debug) && !workers.is_empty() {
+            eprintln!("\nWorkers:");
+            eprintln!("  {:<4} {:>11} {:>7} {:>10} {:>10} {:>10}", "id", "directories", "files", "busy", "idle", "queue wait");
+            for worker in workers.iter() {
+                eprintln!("  {:<4} {:>11} {:>7} {:>10} {:>10} {:>10}",
+                    worker.id,
+                    worker.directories,
+                    worker.files,
+                    format!("{:.3}s", worker.busy.as_secs_f64()),
+                    format!("{:.3}s", worker.idle.as_secs_f64()),
+                    format!("{:.3}s", worker.queue_wait.as_secs_f64()));
+            }
+        }
+        for histogram in &stats.histograms {
+            let title = match histogram.kind {
+                HistogramKind::Size => "Files searched by size",
+                HistogramKind::Mtime => "Files searched by time since modification",
+            };
+            eprintln!("\n{}:", title);
+            for line in histogram.chart() {
+                eprintln!("  {}", line);
+            }
+        }
     }
 }
 
 impl Command for GrepCommand<'_> {
-    fn execute(&self) -> Result<()> {
+    fn execute(&self) -> Result<Outcome> {
         // Get the latest configuration from the singleton if available
         let config = if ConfigManager::instance().is_initialized() {
             ConfigManager::instance().get_config()
@@ -148,29 +760,37 @@ impl Command for GrepCommand<'_> {
             self.config.clone()
         };
         
-        // Create observer for file traversal
-        let observer = crate::core::observer::create_observer(config.show_progress);
+        // Compile any script up front so mistakes are reported before searching
+        let actions = MatchActions::load(&config)?;
         
         // Find all files that match the file criteria
-        let search_path = std::path::PathBuf::from(config.get_path());
-        let files = search_directory(
-            &search_path,
-            &config,
-            &*observer
-        ).with_context(|| format!("Failed to search directory: {}", search_path.display()))?;
+        let (files, report) = self.find_candidates(&config)?;
+        let mut files = self.prune_with_index(&config, files)?;
+        if config.sort_input == InputOrder::Mtime {
+            sort_by_mtime(&mut files);
+        }
         
         // Update metrics
-        *self.total_files.borrow_mut() = observer.files_count();
-        *self.total_dirs.borrow_mut() = observer.directories_count();
+        *self.total_files.borrow_mut() = report.files_matched;
+        *self.total_dirs.borrow_mut() = report.directories_scanned;
+        *self.workers.borrow_mut() = report.workers;
+        
+        // Files that turn out unreadable while searching join the ones the walk could not read
+        let observer = SilentObserver::new();
+        for error in &report.errors {
+            observer.path_error(&error.path, &error.message);
+        }
         
         // Process the files to find text matches
-        if let Err(e) = self.process_files(&files, &config) {
+        let total_matches = match self.process_files(&files, &config, &observer, &actions) {
+            Ok(total_matches) => total_matches,
             // Only report errors that aren't permission related
-            if !e.to_string().contains("permission denied") {
-                return Err(e);
-            }
-        }
+            Err(e) if !e.to_string().contains("permission denied") => return Err(e),
+            Err(_) => 0,
+        };
         
-        Ok(())
+        ensure_complete(&config, observer.path_errors())?;
+        
+        Ok(Outcome::from_counts(total_matches, observer.errors_count()))
     }
 }
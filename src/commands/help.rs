@@ -1,6 +1,7 @@
 use anyhow::Result;
 use console::style;
-use crate::commands::Command;
+use crate::output::outln;
+use crate::commands::{Command, Outcome};
 
 /// Command for displaying help information
 pub struct HelpCommand;
@@ -12,16 +13,16 @@ impl HelpCommand {
     }
     
     /// Display the application banner
-    fn display_banner() {
-        println!("  ____                 _     ");
-        println!(" / __ \\               | |    ");
-        println!("| |  | | __ _  __ _ _ | |__  ");
-        println!("| |  | |/ _` |/ _` | '| '_ \\ ");
-        println!("| |__| | (_| | (_| | || |_) |");
-        println!(" \\____/ \\__, |\\__,_|_||_.__/ ");
-        println!("         __/ |                ");
-        println!("        |___/  Search Utility ");
-        println!();
+    fn display_banner() -> Result<()> {
+        outln!("  ____                 _     ")?;
+        outln!(" / __ \\               | |    ")?;
+        outln!("| |  | | __ _  __ _ _ | |__  ")?;
+        outln!("| |  | |/ _` |/ _` | '| '_ \\ ")?;
+        outln!("| |__| | (_| | (_| | || |_) |")?;
+        outln!(" \\____/ \\__, |\\__,_|_||_.__/ ")?;
+        outln!("         __/ |                ")?;
+        outln!("        |___/  Search Utility ")?;
+        outln!()
     }
 }
 
@@ -32,77 +33,208 @@ impl Default for HelpCommand {
 }
 
 impl Command for HelpCommand {
-    fn execute(&self) -> Result<()> {
+    fn execute(&self) -> Result<Outcome> {
         // Display banner first
-        Self::display_banner();
+        Self::display_banner()?;
         
         // Then display help text with colorful formatting
         // We're implementing our own colorful help text directly
         // instead of using the get_help_text() function
         
         // Print usage section with colors
-        println!("{}", style("USAGE:").bold().green());
-        println!("oqab [QUERY]                   # Search for files by name or pattern");
-        println!("oqab --grep PATTERN [OPTIONS]  # Search for text within files
-");
+        outln!("{}", style("USAGE:").bold().green())?;
+        outln!("oqab [QUERY]                   # Search for files by name or pattern")?;
+        outln!("oqab --grep PATTERN [OPTIONS]  # Search for text within files
+")?;
         
         // Print options section with colors
-        println!("{}", style("OPTIONS:").bold().green());
-        println!("{} Display this help message", style("-h, --help                  ").yellow());
-        println!("{} Directory to search in (default: root directory)", style("-p, --path <DIR>            ").yellow());
-        println!("{} File extension to search for (e.g., 'rs' or '.rs')", style("-e, --ext <EXT>             ").yellow());
-        println!("{} Filter by file name pattern", style("-n, --name <PATTERN>        ").yellow());
-        println!("{} Search for text pattern within files (grep-like functionality)", style("-g, --grep <PATTERN>        ").yellow());
-        println!("{} Case insensitive search", style("-i, --ignore-case          ").yellow());
-        println!("{} Show line numbers in search results", style("--line-number               ").yellow());
-        println!("{} Show only filenames of files containing the pattern", style("--files-with-matches        ").yellow());
-        println!("{} Enable fuzzy matching for file names", style("-z, --fuzzy                 ").yellow());
-        println!("{} Fuzzy match threshold (0-100, higher is stricter)", style("--fuzzy-threshold <NUM>     ").yellow());
-        println!("{} Suppress progress output", style("-s, --silent                ").yellow());
-        println!("{} Quiet mode (less verbose output)", style("-q, --quiet                 ").yellow());
-        println!("{} Number of worker threads (default: CPU cores)", style("-w, --workers <NUM>         ").yellow());
-        println!("{} Load settings from a configuration file", style("-c, --config <FILE>         ").yellow());
-        println!("{} Save current settings to a configuration file
-", style("--save-config <FILE>        ").yellow());
+        outln!("{}", style("OPTIONS:").bold().green())?;
+        outln!("{} Display this help message", style("-h, --help                  ").yellow())?;
+        outln!("{} Directory to search in (default: current directory)", style("-p, --path <DIR>            ").yellow())?;
+        outln!("{} Search the whole filesystem from its root", style("--system                    ").yellow())?;
+        outln!("{} Don't ask before searching the whole filesystem", style("-y, --yes                   ").yellow())?;
+        outln!("{} File extension to search for (e.g., 'rs' or '.rs')", style("-e, --ext <EXT>             ").yellow())?;
+        outln!("{} Match file extensions case-sensitively", style("--case-sensitive-ext        ").yellow())?;
+        outln!("{} Filter by file name pattern", style("-n, --name <PATTERN>        ").yellow())?;
+        outln!("{} Treat QUERY as a plain name, not a glob/regex/extension", style("--literal                   ").yellow())?;
+        outln!("{} Match --name and re: queries against the whole path", style("--full-path                 ").yellow())?;
+        outln!("{} Search for text pattern within files (grep-like functionality)", style("-g, --grep <PATTERN>        ").yellow())?;
+        outln!("{} Ignore case in content and --ext (--name always does)", style("-i, --ignore-case          ").yellow())?;
+        outln!("{} Regex syntax: default, literal, basic (grep/sed) or extended (grep -E)", style("--regex-syntax <SYNTAX>     ").yellow())?;
+        outln!("{} Show line numbers in search results", style("--line-number               ").yellow())?;
+        outln!("{} Records in files and grep output end in NUL, not newline", style("--null-data                 ").yellow())?;
+        outln!("{} Search binary files, only reporting that they match", style("--binary                    ").yellow())?;
+        outln!("{} Search binary files as text", style("--text                      ").yellow())?;
+        outln!("{} Print every line, flagging the matching ones", style("--passthru                  ").yellow())?;
+        outln!("{} Show only filenames of files containing the pattern", style("--files-with-matches        ").yellow())?;
+        outln!("{} Group matches under file headings (default on a terminal)", style("--heading, --no-heading     ").yellow())?;
+        outln!("{} Leave file names out of match output", style("--no-filename               ").yellow())?;
+        outln!("{} Print search statistics (a final record with --format json)", style("--stats                     ").yellow())?;
+        outln!("{} Add size or mtime histograms to the statistics (implies --stats)", style("--histogram <KIND>          ").yellow())?;
+        outln!("{} Skip files larger than SIZE when searching content", style("--max-filesize <SIZE>       ").yellow())?;
+        outln!("{} Documents to search inside: pdf, docx (needs the matching feature)", style("--doc-types <TYPES>         ").yellow())?;
+        outln!("{} Print matches as files finish instead of in path order", style("--unordered                 ").yellow())?;
+        outln!("{} Content index to build or use (see `oqab index build --content`)", style("--index <FILE>              ").yellow())?;
+        outln!("{} Search every candidate file, ignoring any content index", style("--no-index                  ").yellow())?;
+        outln!("{} Enable fuzzy matching for file names", style("-z, --fuzzy                 ").yellow())?;
+        outln!("{} Fuzzy match threshold (0-100, higher is stricter)", style("--fuzzy-threshold <NUM>     ").yellow())?;
+        outln!("{} Rank candidates by this text instead of filtering by name", style("--fuzzy-query <QUERY>       ").yellow())?;
+        outln!("{} Choose among fuzzy matches (or in the browser with -I), printing only the chosen paths", style("--select                    ").yellow())?;
+        outln!("{} Size filter: +10M (larger), -1G (smaller), 4k (exactly)", style("--size <SPEC>               ").yellow())?;
+        outln!("{} Measure sizes by allocated disk space, like du", style("--disk-size                 ").yellow())?;
+        outln!("{} Zero-byte files, and with --type d directories with no entries", style("--empty                     ").yellow())?;
+        outln!("{} Entries with exactly N hard links (Unix)", style("--links <N>                 ").yellow())?;
+        outln!("{} Entries with at least N hard links (Unix)", style("--min-links <N>             ").yellow())?;
+        outln!("{} Kinds of entries: f, d, l (symlink), s (socket), p (pipe); default f", style("--type <TYPES>              ").yellow())?;
+        outln!("{} Content types by magic number, e.g. image/*, application/pdf", style("--mime <TYPES>              ").yellow())?;
+        outln!("{} Only text files, as grep classifies them", style("--text-only                 ").yellow())?;
+        outln!("{} Only binary files, those grep skips", style("--binary-only               ").yellow())?;
+        outln!("{} Extension groups: image, video, audio, archive, code, doc", style("--type-group <GROUPS>       ").yellow())?;
+        outln!("{} Files created on or after DATE, where creation times are recorded", style("--created-after <DATE>      ").yellow())?;
+        outln!("{} Files created on or before DATE", style("--created-before <DATE>     ").yellow())?;
+        outln!("{} Files last read on or after DATE", style("--accessed-after <DATE>     ").yellow())?;
+        outln!("{} Files last read on or before DATE", style("--accessed-before <DATE>    ").yellow())?;
+        outln!("{} Files modified more recently than FILE", style("--newer-than-file <FILE>    ").yellow())?;
+        outln!("{} Files modified before FILE", style("--older-than-file <FILE>    ").yellow())?;
+        outln!("{} Read dates without an offset in UTC, not local time", style("--utc                       ").yellow())?;
+        outln!("{} Photos taken/recordings made before DATE (needs the `media` feature)", style("--exif-date-before <DATE>   ").yellow())?;
+        outln!("{} Photos taken/recordings made on or after DATE", style("--exif-date-after <DATE>    ").yellow())?;
+        outln!("{} Photos whose EXIF camera model contains TEXT", style("--camera-model <TEXT>       ").yellow())?;
+        outln!("{} Recordings whose ID3 artist contains TEXT", style("--artist <TEXT>             ").yellow())?;
+        outln!("{} Include hidden files and directories", style("-H, --hidden                ").yellow())?;
+        outln!("{} Skip hidden entries, even when the config file includes them", style("--no-hidden                 ").yellow())?;
+        outln!("{} Report nothing deeper than N levels below the path", style("--max-depth <N>             ").yellow())?;
+        outln!("{} Report nothing shallower than N levels below the path", style("--min-depth <N>             ").yellow())?;
+        outln!("{} Report each file once under its canonical path, even via links", style("--unique                    ").yellow())?;
+        outln!("{} Stay on the search path's filesystem, skipping mount points", style("--one-file-system           ").yellow())?;
+        outln!("{} Report only the first match in each directory", style("--one-per-dir               ").yellow())?;
+        outln!("{} Report at most N matches in each directory", style("--max-per-dir <N>           ").yellow())?;
+        outln!("{} Stop searching once N results have been found", style("--max-results <N>           ").yellow())?;
+        outln!("{} Read files to search from a list ('-' for stdin)", style("--files-from <FILE>         ").yellow())?;
+        outln!("{} Only search files tracked by git", style("--git-tracked               ").yellow())?;
+        outln!("{} Only search files with uncommitted changes", style("--git-modified              ").yellow())?;
+        outln!("{} Rhai script with filter/on_match hooks (needs the `scripting` feature)", style("--script <FILE>             ").yellow())?;
+        outln!("{} Output format: text, json, vimgrep (file:line:col:text) or parquet", style("--format <FORMAT>           ").yellow())?;
+        outln!("{} Print bare paths ending in NUL, for xargs -0", style("-0, --print0                ").yellow())?;
+        outln!("{} Print each result with a template, e.g. '%p %s %t\\n' (path, size, mtime)", style("--printf <TEMPLATE>         ").yellow())?;
+        outln!("{} List permissions, owner, size and modification time, like ls -l", style("-l, --long                  ").yellow())?;
+        outln!("{} Draw results as a tree under their directories", style("--tree                      ").yellow())?;
+        outln!("{} Result order: path, natural (file2 before file10) or frecency", style("--sort <ORDER>              ").yellow())?;
+        outln!("{} Order files are searched in: path or mtime (newest first)", style("--sort-input <ORDER>        ").yellow())?;
+        outln!("{} Print match counts and total sizes per ext or dir instead of paths", style("--group-by <KEY>            ").yellow())?;
+        outln!("{} Browse results in a terminal UI as they are found", style("-I, --interactive           ").yellow())?;
+        outln!("{} List the N (default 10) largest directories and files, like du", style("--du[=N]                    ").yellow())?;
+        outln!("{} Write a report of the results: md or html, to --output if given", style("--report <FORMAT>           ").yellow())?;
+        outln!("{} Pause after every N lines of results on a terminal", style("--page-size <N>             ").yellow())?;
+        outln!("{} Print directly instead of piping long results into $PAGER", style("--no-pager                  ").yellow())?;
+        outln!("{} Color scheme: default, high-contrast or no-bold", style("--theme <THEME>             ").yellow())?;
+        outln!("{} When to color output: auto (honors NO_COLOR), always or never", style("--color <WHEN>              ").yellow())?;
+        outln!("{} Print result paths as clickable file:// links on supporting terminals", style("--hyperlinks                ").yellow())?;
+        outln!("{} File to write results into for the parquet format or --report", style("-o, --output <FILE>         ").yellow())?;
+        outln!("{} Write results into a SQLite database", style("--output-sqlite <FILE>      ").yellow())?;
+        outln!("{} Print only results, no progress or diagnostics", style("-s, --silent                ").yellow())?;
+        outln!("{} Progress on a terminal: line (status line) or bar (progress bar)", style("--progress <STYLE>          ").yellow())?;
+        outln!("{} Count directories before searching, for an exact percentage and time left", style("--prescan                   ").yellow())?;
+        outln!("{} Quiet mode (only report errors)", style("-q, --quiet                 ").yellow())?;
+        outln!("{} Show more diagnostics (-v info, -vv debug)", style("-v, --verbose               ").yellow())?;
+        outln!("{} Hide errors about unreadable paths", style("--no-messages               ").yellow())?;
+        outln!("{} Skip paths matching a gitignore-style pattern (repeatable)", style("--exclude <PATTERN>         ").yellow())?;
+        outln!("{} Also search node_modules, target, .git and similar", style("--no-default-excludes       ").yellow())?;
+        outln!("{} Don't apply ~/.config/oqab/ignore", style("--no-ignore-global          ").yellow())?;
+        outln!("{} Skip what .gitignore files and .git/info/exclude ignore", style("--git-ignore                ").yellow())?;
+        outln!("{} Fail if any directory or file could not be read", style("--strict                    ").yellow())?;
+        outln!("{} Number of worker threads (default: CPU cores)", style("-w, --workers <NUM>         ").yellow())?;
+        outln!("{} Walker for --advanced and --grep: builtin, walkdir or ignore", style("--walker <WALKER>           ").yellow())?;
+        outln!("{} Load settings from a configuration file", style("-c, --config <FILE>         ").yellow())?;
+        outln!("{} Save current settings to a configuration file", style("--save-config <FILE>        ").yellow())?;
+        outln!("{} Print the merged settings and their sources (toml, json)
+", style("--print-config [FORMAT]     ").yellow())?;
         
         // Print examples section with colors
-        println!("{}", style("EXAMPLES:").bold().green());
-        println!("# Simple file search by name (searches from root directory)");
-        println!("{}", style("oqab main.rs").italic());
-        println!();
-        println!("# Find files by extension");
-        println!("{}", style("oqab *.rs").italic());
-        println!();
-        println!("# Find files with specific name and extension");
-        println!("{}", style("oqab config.json").italic());
-        println!();
-        println!("# Find files in a specific directory");
-        println!("{}", style("oqab -p /home/user main.rs").italic());
-        println!();
-        println!("# Search for text within files (grep-like functionality)");
-        println!("{}", style("oqab --grep \"function\" -p .").italic());
-        println!();
-        println!("# Case-insensitive search with line numbers");
-        println!("{}", style("oqab --grep \"error\" --ignore-case --line-number").italic());
-        println!();
-        println!("# Search for text only in specific file types");
-        println!("{}", style("oqab --grep \"import\" --ext py").italic());
-        println!();
-        println!("# Use fuzzy search to find files with similar names");
-        println!("{}", style("oqab config -z").italic());
-        println!();
-        println!("# Adjust fuzzy search strictness");
-        println!("{}", style("oqab config -z --fuzzy-threshold 70").italic());
-        println!();
-        println!("# Show only filenames containing matches");
-        println!("{}", style("oqab --grep \"TODO\" --files-with-matches").italic());
-        println!();
-        println!("# Save search settings to a config file");
-        println!("{}", style("oqab -p . -e rs --save-config myconfig.json").italic());
-        println!();
-        println!("# Use settings from a config file");
-        println!("{}", style("oqab -c myconfig.json").italic());
+        outln!("{}", style("EXAMPLES:").bold().green())?;
+        outln!("# Simple file search by name (searches from the current directory, or the root with --system)")?;
+        outln!("{}", style("oqab main.rs").italic())?;
+        outln!()?;
+        outln!("# Find files by extension")?;
+        outln!("{}", style("oqab .rs").italic())?;
+        outln!()?;
+        outln!("# Find files matching a glob (quote it so the shell doesn't expand it)")?;
+        outln!("{}", style("oqab 'test_*.py'").italic())?;
+        outln!("{}", style("oqab '**/src/*.{rs,toml,md}'").italic())?;
+        outln!()?;
+        outln!("# Find files whose name matches a regular expression")?;
+        outln!("{}", style("oqab 're:^v\\d+\\.json$'").italic())?;
+        outln!()?;
+        outln!("# Find files with specific name and extension")?;
+        outln!("{}", style("oqab config.json").italic())?;
+        outln!()?;
+        outln!("# Find files in a specific directory")?;
+        outln!("{}", style("oqab -p /home/user main.rs").italic())?;
+        outln!()?;
+        outln!("# Search for text within files (grep-like functionality)")?;
+        outln!("{}", style("oqab --grep \"function\" -p .").italic())?;
+        outln!()?;
+        outln!("# Case-insensitive search with line numbers")?;
+        outln!("{}", style("oqab --grep \"error\" --ignore-case --line-number").italic())?;
+        outln!()?;
+        outln!("# Search for text only in specific file types")?;
+        outln!("{}", style("oqab --grep \"import\" --ext py").italic())?;
+        outln!()?;
+        outln!("# Use fuzzy search to find files with similar names")?;
+        outln!("{}", style("oqab config -z").italic())?;
+        outln!()?;
+        outln!("# Rank Rust files by how closely their names match a query")?;
+        outln!("{}", style("oqab -z --ext rs confg").italic())?;
+        outln!()?;
+        outln!("# Adjust fuzzy search strictness")?;
+        outln!("{}", style("oqab config -z --fuzzy-threshold 70").italic())?;
+        outln!()?;
+        outln!("# Find files larger than 100 MB")?;
+        outln!("{}", style("oqab -p . -e log --size +100M").italic())?;
+        outln!()?;
+        outln!("# Search only the files listed by another tool")?;
+        outln!("{}", style("git ls-files | oqab --grep \"TODO\" --files-from -").italic())?;
+        outln!()?;
+        outln!("# Produce output for Vim's quickfix list")?;
+        outln!("{}", style("oqab --grep \"TODO\" --format vimgrep").italic())?;
+        outln!()?;
+        outln!("# Show only filenames containing matches")?;
+        outln!("{}", style("oqab --grep \"TODO\" --files-with-matches").italic())?;
+        outln!()?;
+        outln!("# Machine-readable matches followed by run statistics")?;
+        outln!("{}", style("oqab --grep \"TODO\" --stats --format json").italic())?;
+        outln!()?;
+        outln!("# Index a repository once so repeated greps only open files that can match")?;
+        outln!("{}", style("oqab -p ~/src/monorepo index build --content").italic())?;
+        outln!()?;
+        outln!("# Rank files you open often first, recording each choice")?;
+        outln!("{}", style("oqab -e rs --sort frecency -s").italic())?;
+        outln!("{}", style("oqab record src/main.rs").italic())?;
+        outln!()?;
+        outln!("# Run the searches of a schedule file on their intervals, reporting to files or webhooks")?;
+        outln!("{}", style("oqab schedule hygiene.json --daemon").italic())?;
+        outln!()?;
+        outln!("# Check a tree for world-writable files, setuid binaries, dangling links and more")?;
+        outln!("{}", style("oqab audit /srv --rule setuid,world-writable").italic())?;
+        outln!()?;
+        outln!("# Find near-duplicates of a file (70% similar or more)")?;
+        outln!("{}", style("oqab -p docs -e md similar docs/intro.md --threshold 70").italic())?;
+        outln!()?;
+        outln!("# Save search settings to a config file")?;
+        outln!("{}", style("oqab -p . -e rs --save-config myconfig.json").italic())?;
+        outln!()?;
+        outln!("# Use settings from a config file")?;
+        outln!("{}", style("oqab -c myconfig.json").italic())?;
+        outln!()?;
         
-        Ok(())
+        // Print exit codes section with colors
+        outln!("{}", style("EXIT CODES:").bold().green())?;
+        outln!("{} Matches found", style("0   ").yellow())?;
+        outln!("{} No matches found", style("1   ").yellow())?;
+        outln!("{} Usage error, or the search could not run", style("2   ").yellow())?;
+        outln!("{} Partial results: some paths could not be read", style("3   ").yellow())?;
+        outln!("{} Interrupted", style("130 ").yellow())?;
+        
+        Ok(Outcome::Success)
     }
 } 
\ No newline at end of file
@@ -0,0 +1,152 @@
+use anyhow::{bail, Context, Result};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use log::{debug, info};
+
+use crate::commands::{Command, Outcome};
+use crate::core::{AppConfig, FileSearchConfig, FinderFactory};
+use crate::core::estimate::ScanTotals;
+use crate::index::ContentIndex;
+use crate::output::group::format_size;
+
+/// What an index command does to the content index of the search path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexOperation {
+    /// Build or refresh the index from the files a search finds
+    Build,
+    /// Write the index to a portable file
+    Export(PathBuf),
+    /// Replace the index with one read from a portable file
+    Import(PathBuf),
+    /// Drop stale entries and reclaim unused space
+    Compact,
+    /// Look for damage to the index
+    Check,
+}
+
+impl IndexOperation {
+    /// Verb describing the operation, for error messages
+    pub fn description(&self) -> &'static str {
+        match self {
+            IndexOperation::Build => "build",
+            IndexOperation::Export(_) => "export",
+            IndexOperation::Import(_) => "import",
+            IndexOperation::Compact => "compact",
+            IndexOperation::Check => "check",
+        }
+    }
+}
+
+/// Command that builds or maintains the content index for the search path
+///
+/// Files are selected with the same filters as a search, so an index can be
+/// limited to, say, `--ext rs`. Building it again only re-reads changed files.
+pub struct IndexCommand<'a> {
+    config: &'a FileSearchConfig,
+    operation: IndexOperation,
+}
+
+impl<'a> IndexCommand<'a> {
+    /// Create a new index command
+    pub fn new(config: &'a FileSearchConfig, operation: IndexOperation) -> Self {
+        Self { config, operation }
+    }
+
+    /// Location of the index for the search path
+    fn location(&self, root: &Path) -> Result<PathBuf> {
+        match &self.config.index_file {
+            Some(path) => Ok(PathBuf::from(path)),
+            None => ContentIndex::default_location(root)
+                .with_context(|| format!("No cache directory available to index {}; pass --index <FILE>", root.display())),
+        }
+    }
+
+    /// Report progress unless running quietly
+    fn report(&self, message: String) {
+        if !self.config.quiet_mode {
+            eprintln!("{}", message);
+        }
+    }
+
+    fn build(&self, root: &Path, location: &Path) -> Result<()> {
+        // An index has to cover every file, however few results searches ask for
+        let finder = FinderFactory::from_search_config(&FileSearchConfig { max_results: None, ..self.config.clone() })?;
+        let (matches, report) = finder.find(root)
+            .with_context(|| format!("Failed to list files to index in: {}", root.display()))?;
+        // Later searches of the root use the total to show how far along they are
+        if let Err(e) = ScanTotals::remember(root, report.directories_scanned) {
+            debug!("Failed to record scan total: {:#}", e);
+        }
+        let files: Vec<PathBuf> = matches.into_iter().map(|found| found.path).collect();
+
+        let mut index = ContentIndex::open(location, root)?;
+        let stats = index.update(&files)?;
+        info!("Content index written to {}", location.display());
+
+        self.report(format!("Indexed {} files ({} updated, {} removed) into {}",
+            stats.files, stats.updated, stats.removed, location.display()));
+        Ok(())
+    }
+
+    fn export(&self, location: &Path, file: &Path) -> Result<()> {
+        let index = ContentIndex::open_existing(location)?;
+        let writer = File::create(file)
+            .with_context(|| format!("Failed to create export file: {}", file.display()))?;
+        let count = index.export(BufWriter::new(writer))?;
+
+        self.report(format!("Exported {} files from {} to {}", count, location.display(), file.display()));
+        Ok(())
+    }
+
+    fn import(&self, root: &Path, location: &Path, file: &Path) -> Result<()> {
+        let reader = File::open(file)
+            .with_context(|| format!("Failed to open export file: {}", file.display()))?;
+        let mut index = ContentIndex::open(location, root)?;
+        let count = index.import(BufReader::new(reader))?;
+
+        self.report(format!("Imported {} files from {} into {}", count, file.display(), location.display()));
+        Ok(())
+    }
+
+    fn compact(&self, location: &Path) -> Result<()> {
+        let file_size = || fs::metadata(location).map(|metadata| metadata.len()).unwrap_or(0);
+        let before = file_size();
+        let dropped = ContentIndex::open_existing_writable(location)?.compact()?;
+
+        self.report(format!("Compacted {} from {} to {} ({} stale postings dropped)",
+            location.display(), format_size(before), format_size(file_size()), dropped));
+        Ok(())
+    }
+
+    fn check(&self, location: &Path) -> Result<()> {
+        let problems = ContentIndex::open_existing(location)?.check()?;
+        if !problems.is_empty() {
+            for problem in &problems {
+                eprintln!("{}", problem);
+            }
+            bail!("{} is damaged; rebuild it with `oqab index build --content`", location.display());
+        }
+
+        self.report(format!("No problems found in {}", location.display()));
+        Ok(())
+    }
+}
+
+impl Command for IndexCommand<'_> {
+    fn execute(&self) -> Result<Outcome> {
+        let app_config = AppConfig::from(self.config);
+        let root = &app_config.root_dir;
+        let location = self.location(root)?;
+
+        match &self.operation {
+            IndexOperation::Build => self.build(root, &location)?,
+            IndexOperation::Export(file) => self.export(&location, file)?,
+            IndexOperation::Import(file) => self.import(root, &location, file)?,
+            IndexOperation::Compact => self.compact(&location)?,
+            IndexOperation::Check => self.check(&location)?,
+        }
+
+        Ok(Outcome::Success)
+    }
+}
@@ -0,0 +1,43 @@
+use anyhow::{Context, Result};
+
+use crate::commands::{Command, Outcome};
+use crate::core::{AppConfig, FileSearchConfig, FinderFactory};
+use crate::output::outrec;
+use crate::tui;
+
+/// Results waiting to be taken in by the browser before the search pauses
+const STREAM_CAPACITY: usize = 4096;
+
+/// Command that browses search results in a terminal UI as they are found
+pub struct InteractiveCommand<'a> {
+    config: &'a FileSearchConfig,
+}
+
+impl<'a> InteractiveCommand<'a> {
+    /// Create a new interactive browser for the configured search
+    pub fn new(config: &'a FileSearchConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Command for InteractiveCommand<'_> {
+    fn execute(&self) -> Result<Outcome> {
+        let app_config = AppConfig::from(self.config);
+        // The browser is the only thing drawing on the terminal
+        let finder = FinderFactory::from_search_config(&FileSearchConfig { show_progress: false, ..self.config.clone() })?;
+        let results = finder.find_streaming(&app_config.root_dir, STREAM_CAPACITY)
+            .with_context(|| format!("Failed to search directory: {}", app_config.root_dir.display()))?;
+        
+        let session = tui::browse(results, self.config.select)?;
+        if !self.config.select {
+            return Ok(Outcome::from_counts(session.found, 0));
+        }
+        
+        // Like `--select` on its own, only the chosen paths reach standard output
+        let terminator = self.config.record_terminator();
+        for path in &session.chosen {
+            outrec!(terminator, "{}", path.display())?;
+        }
+        Ok(Outcome::from_counts(session.chosen.len(), 0))
+    }
+}
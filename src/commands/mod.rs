@@ -1,17 +1,95 @@
+mod actions;
+mod audit;
 mod help;
 mod search;
 mod grep;
 mod fuzzy;
+mod index;
+mod print_config;
+mod record;
+mod schedule;
+mod similar;
+mod size_report;
+#[cfg(feature = "http")]
+mod serve;
+#[cfg(feature = "tui")]
+mod interactive;
 
+pub use audit::AuditCommand;
 pub use help::HelpCommand;
 pub use search::SearchCommand;
 pub use grep::GrepCommand;
 pub use fuzzy::FuzzyCommand;
+pub use index::{IndexCommand, IndexOperation};
+pub use print_config::{ConfigFormat, PrintConfigCommand};
+pub use record::RecordCommand;
+pub use schedule::ScheduleCommand;
+pub use similar::SimilarCommand;
+pub use size_report::SizeReportCommand;
+#[cfg(feature = "http")]
+pub use serve::ServeCommand;
+#[cfg(feature = "tui")]
+pub use interactive::InteractiveCommand;
+
+use std::fmt;
 
 use anyhow::Result;
 
+use crate::core::FileSearchConfig;
+use crate::core::observer::PathError;
+
 /// Command interface for different operations
 pub trait Command {
-    /// Execute the command and return a result
-    fn execute(&self) -> Result<()>;
+    /// Execute the command and report how it went
+    fn execute(&self) -> Result<Outcome>;
+}
+
+/// How a command that ran to completion went, used to pick the exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The command found something (or had nothing to search for, like help)
+    Success,
+    /// The search completed without finding anything
+    NoMatches,
+    /// Some paths could not be read, so the results may be incomplete
+    Partial,
+}
+
+impl Outcome {
+    /// Outcome of a search from its match and unreadable path counts
+    pub fn from_counts(matches: usize, errors: usize) -> Self {
+        if errors > 0 {
+            Outcome::Partial
+        } else if matches > 0 {
+            Outcome::Success
+        } else {
+            Outcome::NoMatches
+        }
+    }
+}
+
+/// Error returned by `--strict` runs that could not read every path
+#[derive(Debug)]
+pub struct IncompleteSearchError {
+    pub errors: Vec<PathError>,
+}
+
+impl fmt::Display for IncompleteSearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} path(s) could not be read:", self.errors.len())?;
+        for error in &self.errors {
+            write!(f, "\n  {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for IncompleteSearchError {}
+
+/// Fail a `--strict` run if any path could not be read
+pub(crate) fn ensure_complete(config: &FileSearchConfig, errors: Vec<PathError>) -> Result<()> {
+    if config.strict && !errors.is_empty() {
+        return Err(IncompleteSearchError { errors }.into());
+    }
+    Ok(())
 } 
\ No newline at end of file
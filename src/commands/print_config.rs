@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use crate::commands::{Command, Outcome};
+use crate::core::FileSearchConfig;
+use crate::core::config::ConfigSource;
+use crate::output::outln;
+
+/// Formats the effective configuration can be printed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// One `key = value` line per setting, with its source as a comment
+    Toml,
+    /// An object holding the configuration, loadable with `--config`, and the sources
+    Json,
+}
+
+/// Command that prints the merged configuration and where each value came from
+pub struct PrintConfigCommand<'a> {
+    config: &'a FileSearchConfig,
+    sources: &'a BTreeMap<String, ConfigSource>,
+    format: ConfigFormat,
+}
+
+impl<'a> PrintConfigCommand<'a> {
+    /// Create a command printing a configuration with the sources of its values
+    pub fn new(config: &'a FileSearchConfig, sources: &'a BTreeMap<String, ConfigSource>, format: ConfigFormat) -> Self {
+        Self { config, sources, format }
+    }
+
+    fn source(&self, key: &str) -> String {
+        self.sources.get(key).unwrap_or(&ConfigSource::Default).to_string()
+    }
+}
+
+impl Command for PrintConfigCommand<'_> {
+    fn execute(&self) -> Result<Outcome> {
+        let values = match serde_json::to_value(self.config).context("Failed to serialize configuration")? {
+            Value::Object(values) => values,
+            _ => unreachable!("configurations serialize to objects"),
+        };
+
+        match self.format {
+            ConfigFormat::Json => {
+                let sources: BTreeMap<&String, String> = values.keys().map(|key| (key, self.source(key))).collect();
+                outln!("{}", serde_json::to_string_pretty(&json!({"config": values, "sources": sources}))?)?;
+            }
+            ConfigFormat::Toml => {
+                for (key, value) in &values {
+                    // TOML has no null, so unset values only show up as comments
+                    if value.is_null() {
+                        outln!("# {} is not set ({})", toml_key(key), self.source(key))?;
+                    } else {
+                        outln!("{} = {}  # {}", toml_key(key), toml_value(value), self.source(key))?;
+                    }
+                }
+            }
+        }
+
+        Ok(Outcome::Success)
+    }
+}
+
+// Keys made only of letters, digits, `_` and `-` can be written bare
+fn toml_key(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+// Inline TOML for a JSON value; JSON string escapes are valid in TOML basic strings
+fn toml_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(_) | Value::Number(_) | Value::String(_) => value.to_string(),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().filter(|item| !item.is_null()).map(toml_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(entries) => {
+            let entries: Vec<String> = entries.iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| format!("{} = {}", toml_key(key), toml_value(value)))
+                .collect();
+            if entries.is_empty() {
+                "{}".to_string()
+            } else {
+                format!("{{ {} }}", entries.join(", "))
+            }
+        }
+    }
+}
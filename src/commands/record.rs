@@ -0,0 +1,29 @@
+use anyhow::{Context, Result};
+use log::info;
+
+use crate::commands::{Command, Outcome};
+use crate::utils::frecency::FrecencyStore;
+
+/// Command that records paths chosen from search results in the frecency store
+///
+/// Meant to be called by pickers and editor integrations after the user opens a
+/// result, so that `--sort frecency` ranks it higher next time.
+pub struct RecordCommand<'a> {
+    paths: &'a [String],
+}
+
+impl<'a> RecordCommand<'a> {
+    /// Create a new record command for the selected paths
+    pub fn new(paths: &'a [String]) -> Self {
+        Self { paths }
+    }
+}
+
+impl Command for RecordCommand<'_> {
+    fn execute(&self) -> Result<Outcome> {
+        FrecencyStore::record_chosen(self.paths).context("Failed to record selected paths")?;
+        info!("Recorded {} selected path(s)", self.paths.len());
+        
+        Ok(Outcome::Success)
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use std::thread;
+use std::time::Instant;
+use log::{info, warn};
+
+use crate::output::outln;
+use crate::commands::{Command, Outcome};
+use crate::utils::schedule::{Report, Schedule, ScheduledJob};
+
+/// Command that runs the jobs of a schedule file
+///
+/// Each job runs once and the command exits, which suits cron; with `--daemon`
+/// the command keeps running and repeats every job on its interval.
+pub struct ScheduleCommand<'a> {
+    schedule_file: &'a str,
+    daemon: bool,
+}
+
+impl<'a> ScheduleCommand<'a> {
+    /// Create a new schedule command for a schedule file
+    pub fn new(schedule_file: &'a str, daemon: bool) -> Self {
+        Self { schedule_file, daemon }
+    }
+    
+    /// Run a job and deliver its report, returning it for the caller to inspect
+    fn run_job(job: &ScheduledJob) -> Result<Report> {
+        let report = job.run()?;
+        info!("Job '{}' found {} file(s)", job.name, report.count);
+        match &job.output {
+            Some(sink) => sink.deliver(&report)?,
+            None => outln!("{}", serde_json::to_string(&report)?)?,
+        }
+        Ok(report)
+    }
+}
+
+impl Command for ScheduleCommand<'_> {
+    fn execute(&self) -> Result<Outcome> {
+        let schedule = Schedule::load_from_file(self.schedule_file)?;
+        
+        if !self.daemon {
+            let mut found = 0;
+            for job in &schedule.jobs {
+                found += Self::run_job(job)?.count;
+            }
+            return Ok(Outcome::from_counts(found, 0));
+        }
+        
+        // Every job is due immediately, then again one interval after it last started
+        let intervals = schedule.jobs.iter()
+            .map(|job| job.interval())
+            .collect::<Result<Vec<_>>>()?;
+        let mut due = vec![Instant::now(); schedule.jobs.len()];
+        info!("Running {} scheduled job(s) until interrupted", schedule.jobs.len());
+        
+        loop {
+            for (index, job) in schedule.jobs.iter().enumerate() {
+                if due[index] > Instant::now() {
+                    continue;
+                }
+                due[index] = Instant::now() + intervals[index];
+                // A failing run must not stop the other jobs or later runs
+                if let Err(e) = Self::run_job(job) {
+                    warn!("Scheduled job '{}' failed: {:#}", job.name, e);
+                }
+            }
+            
+            if let Some(next) = due.iter().min() {
+                thread::sleep(next.saturating_duration_since(Instant::now()));
+            }
+        }
+    }
+}
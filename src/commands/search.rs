@@ -1,16 +1,24 @@
 use anyhow::{Result, Context};
 use std::time::{Duration, Instant};
 use std::cell::RefCell;
-use crate::commands::Command;
-use crate::core::{FileSearchConfig, FinderFactory};
-use crate::core::observer::{SearchObserver, SilentObserver, TrackingObserver};
-use crate::utils::search_directory;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::json;
+use crate::commands::{ensure_complete, Command, Outcome};
+use crate::commands::actions::MatchActions;
+use crate::core::{AppConfig, FileSearchConfig, FinderFactory, OutputFormat};
+use crate::core::observer::create_observer;
+use crate::output::{flush_pending, group_results, long_entries, long_lines, outln, outrec, render_report, render_tree, sort_results, styled_path, write_file_output, GroupBy, ReportSummary, SqliteExporter, Template};
+use crate::output::stdout::write_text;
+use crate::output::group::format_size;
+use crate::utils::collect_files;
 
 pub struct SearchCommand<'a> {
     config: &'a FileSearchConfig,
     start_time: Instant,
     total_files: RefCell<usize>,
     total_dirs: RefCell<usize>,
+    total_errors: RefCell<usize>,
 }
 
 impl<'a> SearchCommand<'a> {
@@ -20,84 +28,45 @@ impl<'a> SearchCommand<'a> {
             start_time: Instant::now(),
             total_files: RefCell::new(0),
             total_dirs: RefCell::new(0),
+            total_errors: RefCell::new(0),
         }
     }
 
-    fn create_app_config(&self) -> Result<crate::core::config::AppConfig> {
-        let app_config = crate::core::config::AppConfig {
-            root_dir: match &self.config.path {
-                Some(path) => std::path::PathBuf::from(path),
-                None => std::env::current_dir()?,
-            },
-            extension: self.config.file_extension.clone(),
-            name: self.config.file_name.clone(),
-            pattern: None,
-            min_size: self.config.min_size,
-            max_size: self.config.max_size,
-            newer_than: self.config.newer_than.clone(),
-            older_than: self.config.older_than.clone(),
-            size: None,
-            depth: None,
-            threads: self.config.thread_count,
-            follow_links: Some(self.config.follow_symlinks),
-            show_progress: Some(self.config.show_progress),
-            quiet: Some(self.config.quiet_mode),
-        };
-        Ok(app_config)
+    fn create_app_config(&self) -> AppConfig {
+        AppConfig::from(self.config)
     }
 }
 
 impl Command for SearchCommand<'_> {
-    fn execute(&self) -> Result<()> {
-        let app_config = self.create_app_config()?;
+    fn execute(&self) -> Result<Outcome> {
+        let app_config = self.create_app_config();
+        let actions = MatchActions::load(self.config)?;
 
-        // Choose observer type based on config
-        let observer: Box<dyn SearchObserver> = if self.config.show_progress {
-            Box::new(TrackingObserver::new())
-        } else {
-            Box::new(SilentObserver::new())
-        };
+        let observer = create_observer(self.config);
 
-        if self.config.advanced_search {
-            let finder = FinderFactory::create_standard_finder(&app_config);
+        // An explicit file list bypasses traversal, so the advanced finder has nothing to do
+        if self.config.advanced_search && self.config.files_from.is_none() {
+            let finder = FinderFactory::from_search_config(self.config)?;
             
-            let results = finder.find(&app_config.root_dir)
+            let (matches, report) = finder.find(&app_config.root_dir)
                 .with_context(|| format!("Advanced search failed in: {}", app_config.root_dir.display()))?;
+            let results: Vec<PathBuf> = matches.into_iter().map(|found| found.path).collect();
 
-            // Get metrics from finder's observer
-            if let Some(tracking_observer) = finder.get_tracking_observer() {
-                *self.total_files.borrow_mut() = tracking_observer.files_count();
-                *self.total_dirs.borrow_mut() = tracking_observer.directories_count();
-            }
+            *self.total_files.borrow_mut() = report.files_matched;
+            *self.total_dirs.borrow_mut() = report.directories_scanned;
+            *self.total_errors.borrow_mut() = report.errors.len();
                 
-            self.display_results(&results)?;
+            self.display_results(&results, &app_config.root_dir, &actions)?;
+            ensure_complete(self.config, report.errors)?;
+            
+            Ok(Outcome::from_counts(results.len(), *self.total_errors.borrow()))
         } else {
-            let search_config = FileSearchConfig {
-                path: Some(app_config.root_dir.to_string_lossy().to_string()),
-                file_extension: app_config.extension.clone(),
-                file_name: app_config.name.clone(),
-                pattern: app_config.pattern.clone(),
-                ignore_case: false,
-                line_number: false,
-                files_with_matches: false,
-                help: false,
-                advanced_search: false,
-                thread_count: app_config.threads,
-                show_progress: app_config.show_progress.unwrap_or(true),
-                recursive: true,
-                follow_symlinks: app_config.follow_links.unwrap_or(false),
-                traversal_mode: Default::default(),
-                min_size: app_config.min_size,
-                max_size: app_config.max_size,
-                newer_than: app_config.newer_than.clone(),
-                fuzzy: false,
-                fuzzy_threshold: None,
-                older_than: app_config.older_than.clone(),
-                quiet_mode: app_config.quiet.unwrap_or(false),
-            };
+            // Search with the user's configuration as-is so no filter is lost,
+            // only pinning the path to the resolved root directory
+            let mut search_config = self.config.clone();
+            search_config.path = Some(app_config.root_dir.to_string_lossy().to_string());
             
-            let results = search_directory(
-                &app_config.root_dir,
+            let results = collect_files(
                 &search_config,
                 &*observer
             ).with_context(|| format!("Standard search failed in: {}", app_config.root_dir.display()))?;
@@ -105,35 +74,143 @@ impl Command for SearchCommand<'_> {
             // Track search metrics
             *self.total_files.borrow_mut() = observer.files_count();
             *self.total_dirs.borrow_mut() = observer.directories_count();
+            *self.total_errors.borrow_mut() = observer.errors_count();
             
-            self.display_results(&results)?;
+            self.display_results(&results, &app_config.root_dir, &actions)?;
+            ensure_complete(self.config, observer.path_errors())?;
+            
+            Ok(Outcome::from_counts(results.len(), *self.total_errors.borrow()))
         }
-        
-        Ok(())
     }
 }
 
 impl SearchCommand<'_> {
-    fn display_results(&self, files: &[std::path::PathBuf]) -> Result<()> {
+    /// Show the results found below `root`, the resolved root directory they are absolute under
+    fn display_results(&self, files: &[std::path::PathBuf], root: &Path, actions: &MatchActions) -> Result<()> {
         let elapsed = self.start_time.elapsed();
         
-        if !files.is_empty() {
-            println!("\nFound {} matching file(s):", files.len());
+        let mut files = files.to_vec();
+        sort_results(&mut files, self.config.sort)?;
+        let files = files.as_slice();
+        
+        for file in files {
+            actions.file(file)?;
+        }
+        
+        if let Some(database) = &self.config.output_sqlite {
+            let exporter = SqliteExporter::create(database)?;
             for file in files {
-                println!("  {}", file.display());
+                exporter.add_file(file)?;
             }
-            
-            if self.config.show_progress {
-                self.display_performance_metrics(files.len(), elapsed);
+            exporter.finish()?;
+        }
+        
+        if write_file_output(self.config, files)? {
+            return Ok(());
+        }
+        
+        if let Some(group_by) = self.config.group_by {
+            return self.display_groups(files, group_by);
+        }
+        
+        if let Some(format) = self.config.report {
+            let summary = ReportSummary {
+                root: root.to_path_buf(),
+                directories_scanned: *self.total_dirs.borrow(),
+                errors: *self.total_errors.borrow(),
+                elapsed,
+            };
+            let report = render_report(format, &summary, files, self.config.disk_size);
+            return match &self.config.output_file {
+                Some(output) => fs::write(output, report)
+                    .with_context(|| format!("Failed to write report to {}", output)),
+                None => write_text(&report),
+            };
+        }
+        
+        if let Some(template) = &self.config.printf {
+            let template = Template::parse(template)?;
+            for file in files {
+                write_text(&template.render(file, None))?;
+            }
+            return Ok(());
+        }
+        
+        if self.config.print0 {
+            for file in files {
+                outrec!('\0', "{}", file.display())?;
+            }
+            return Ok(());
+        }
+        
+        if self.config.output_format == OutputFormat::Vimgrep {
+            // File matches have no line context, so point at the start of the file
+            for file in files {
+                outln!("{}:1:1:", file.display())?;
+            }
+            return Ok(());
+        }
+        
+        if self.config.output_format == OutputFormat::Json {
+            for file in files {
+                outln!("{}", json!({"type": "file", "path": file}))?;
+            }
+            return Ok(());
+        }
+        
+        if self.config.show_progress {
+            if files.is_empty() {
+                eprintln!("\nNo matching files found");
+            } else {
+                eprintln!("\nFound {} matching file(s):", files.len());
+            }
+        }
+        let path_style = self.config.theme.path();
+        if self.config.tree {
+            for line in render_tree(root, files, &self.config.theme.heading(), &path_style) {
+                outln!("{}", line)?;
+            }
+        } else if self.config.long {
+            // Details are looked up for all results first so the columns can be aligned
+            for line in long_lines(&long_entries(files), &path_style) {
+                outln!("  {}", line)?;
             }
         } else {
-            println!("\nNo matching files found");
-            
-            if self.config.show_progress {
-                self.display_performance_metrics(0, elapsed);
+            for file in files {
+                outln!("  {}", styled_path(&path_style, file))?;
+            }
+        }
+        
+        if self.config.show_progress {
+            // Results held back for the pager come before the summary
+            flush_pending()?;
+            self.display_performance_metrics(files.len(), elapsed);
+        }
+        
+        Ok(())
+    }
+    
+    fn display_groups(&self, files: &[PathBuf], group_by: GroupBy) -> Result<()> {
+        let groups = group_results(files, group_by, self.config.disk_size);
+        
+        if self.config.output_format == OutputFormat::Json {
+            for group in &groups {
+                outln!("{}", json!({"type": "group", "key": group.key, "count": group.count, "size": group.size}))?;
             }
+            return Ok(());
         }
         
+        if self.config.show_progress {
+            eprintln!("\nFound {} matching file(s) in {} group(s):", files.len(), groups.len());
+        }
+        let path_style = self.config.theme.path();
+        for group in &groups {
+            let key = match (group.key.is_empty(), group_by) {
+                (true, GroupBy::Ext) => "(no extension)".to_string(),
+                _ => group.key.clone(),
+            };
+            outln!("  {:>8}  {:>10}  {}", group.count, format_size(group.size), path_style.apply_to(key))?;
+        }
         Ok(())
     }
     
@@ -145,11 +222,14 @@ impl SearchCommand<'_> {
             0.0
         };
         
-        println!("\nPerformance:");
-        println!("  Time taken: {:.2} seconds", elapsed_secs);
-        println!("  Files found: {}", files_count);
-        println!("  Files searched: {}", *self.total_files.borrow());
-        println!("  Directories searched: {}", *self.total_dirs.borrow());
-        println!("  Processing rate: {:.2} files/sec", files_per_sec);
+        eprintln!("\nPerformance:");
+        eprintln!("  Time taken: {:.2} seconds", elapsed_secs);
+        eprintln!("  Files found: {}", files_count);
+        eprintln!("  Files searched: {}", *self.total_files.borrow());
+        eprintln!("  Directories searched: {}", *self.total_dirs.borrow());
+        eprintln!("  Processing rate: {:.2} files/sec", files_per_sec);
+        if *self.total_errors.borrow() > 0 {
+            eprintln!("  Unreadable paths: {}", *self.total_errors.borrow());
+        }
     }
 }
@@ -0,0 +1,31 @@
+use anyhow::Result;
+use log::info;
+
+use crate::commands::{Command, Outcome};
+use crate::core::config::FileSearchConfig;
+use crate::utils::http_server::HttpServer;
+
+/// Command that serves searches over HTTP until interrupted
+pub struct ServeCommand<'a> {
+    config: &'a FileSearchConfig,
+    address: String,
+}
+
+impl<'a> ServeCommand<'a> {
+    /// Create a new serve command listening on the given address
+    pub fn new(config: &'a FileSearchConfig, address: &str) -> Self {
+        Self {
+            config,
+            address: address.to_string(),
+        }
+    }
+}
+
+impl Command for ServeCommand<'_> {
+    fn execute(&self) -> Result<Outcome> {
+        let server = HttpServer::bind(&self.address, self.config.clone())?;
+        info!("Listening on http://{}/search", server.local_addr()?);
+        server.serve()?;
+        Ok(Outcome::Success)
+    }
+}
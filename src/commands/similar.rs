@@ -0,0 +1,104 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use log::debug;
+use rayon::prelude::*;
+use serde_json::json;
+
+use crate::output::{outln, outrec, styled_path};
+use crate::commands::{ensure_complete, Command, Outcome};
+use crate::core::{AppConfig, FileSearchConfig, FinderFactory, OutputFormat, Platform};
+use crate::utils::similarity::Fingerprint;
+
+/// Command that finds files whose content is nearly the same as a reference file
+///
+/// Candidates are selected with the usual filters, so `--ext` and friends narrow
+/// the comparison. Binary files and files over `--max-filesize` are skipped.
+pub struct SimilarCommand<'a> {
+    config: &'a FileSearchConfig,
+    reference: PathBuf,
+    threshold: f64,
+}
+
+impl<'a> SimilarCommand<'a> {
+    /// Create a new similarity search; `threshold` is the minimum similarity in percent
+    pub fn new(config: &'a FileSearchConfig, reference: &Path, threshold: u8) -> Self {
+        Self {
+            config,
+            reference: reference.to_path_buf(),
+            threshold: f64::from(threshold.min(100)) / 100.0,
+        }
+    }
+    
+    /// Fingerprint the candidates in parallel and keep those similar enough
+    fn compare(&self, reference: &Fingerprint, files: &[PathBuf]) -> Result<Vec<(PathBuf, f64)>> {
+        // The reference itself is left out, however the walk spelled its path
+        let reference_path = Platform::path_key(&self.reference);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.config.thread_count.unwrap_or_else(num_cpus::get))
+            .build()
+            .context("Failed to create worker pool")?;
+        
+        let mut similar: Vec<(PathBuf, f64)> = pool.install(|| {
+            files.par_iter()
+                .filter(|file| Platform::path_key(file) != reference_path)
+                .filter(|file| match self.config.max_filesize {
+                    Some(limit) => fs::metadata(file).is_ok_and(|metadata| metadata.len() <= limit),
+                    None => true,
+                })
+                .filter_map(|file| {
+                    let content = fs::read(file)
+                        .inspect_err(|e| debug!("Skipping {}: {}", file.display(), e))
+                        .ok()?;
+                    let similarity = Fingerprint::from_content(&content)?.similarity(reference);
+                    (similarity >= self.threshold).then(|| (file.clone(), similarity))
+                })
+                .collect()
+        });
+        
+        // Most similar first, then by path for stable output
+        similar.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(similar)
+    }
+}
+
+impl Command for SimilarCommand<'_> {
+    fn execute(&self) -> Result<Outcome> {
+        let reference = Fingerprint::from_file(&self.reference)?;
+        
+        let app_config = AppConfig::from(self.config);
+        // The result limit applies to the most similar files, not to the candidates
+        let finder = FinderFactory::from_search_config(&FileSearchConfig { max_results: None, ..self.config.clone() })?;
+        let (matches, report) = finder.find(&app_config.root_dir)
+            .with_context(|| format!("Failed to search directory: {}", app_config.root_dir.display()))?;
+        let files: Vec<PathBuf> = matches.into_iter().map(|found| found.path).collect();
+        
+        let mut similar = self.compare(&reference, &files)?;
+        if let Some(limit) = self.config.max_results {
+            similar.truncate(limit);
+        }
+        
+        if self.config.print0 {
+            for (path, _) in &similar {
+                outrec!('\0', "{}", path.display())?;
+            }
+        } else if self.config.output_format == OutputFormat::Json {
+            for (path, similarity) in &similar {
+                outln!("{}", json!({"type": "file", "path": path, "similarity": similarity}))?;
+            }
+        } else if similar.is_empty() {
+            if self.config.show_progress {
+                eprintln!("No files similar to {} found", self.reference.display());
+            }
+        } else {
+            let path_style = self.config.theme.path();
+            for (path, similarity) in &similar {
+                outln!("{:>6.1}%  {}", similarity * 100.0, styled_path(&path_style, path))?;
+            }
+        }
+        
+        let errors = report.errors.len();
+        ensure_complete(self.config, report.errors)?;
+        Ok(Outcome::from_counts(similar.len(), errors))
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::json;
+
+use crate::output::{outln, styled_path};
+use crate::output::group::format_size;
+use crate::commands::{ensure_complete, Command, Outcome};
+use crate::core::{AppConfig, FileSearchConfig, FinderFactory, OutputFormat};
+use crate::filters::size::file_size;
+
+/// Command that reports where the space taken by the matching files goes, like `du`
+///
+/// Every matching file counts towards its own directory and each directory above it
+/// up to the search root, so a directory's total covers its whole subtree. The
+/// largest directories and files are listed, biggest first.
+pub struct SizeReportCommand<'a> {
+    config: &'a FileSearchConfig,
+    top: usize,
+}
+
+/// Total size and number of the matching files at or below a path
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Usage {
+    path: PathBuf,
+    size: u64,
+    count: usize,
+}
+
+impl<'a> SizeReportCommand<'a> {
+    /// Create a new size report listing the `top` largest directories and files
+    pub fn new(config: &'a FileSearchConfig, top: usize) -> Self {
+        Self { config, top }
+    }
+
+    /// Measure the files and add them up per directory, both largest first
+    fn measure(&self, root: &Path, files: &[PathBuf]) -> (Vec<Usage>, Vec<Usage>) {
+        let mut directories: HashMap<&Path, Usage> = HashMap::new();
+        let mut sizes = Vec::with_capacity(files.len());
+        for file in files {
+            // Unreadable files still count towards their directories, with no size
+            let size = fs::metadata(file).map(|metadata| file_size(&metadata, self.config.disk_size)).unwrap_or(0);
+            sizes.push(Usage { path: file.clone(), size, count: 1 });
+
+            // Files from outside the root, as with --files-from, only count towards their parent
+            for directory in file.ancestors().skip(1) {
+                let usage = directories.entry(directory)
+                    .or_insert_with(|| Usage { path: directory.to_path_buf(), size: 0, count: 0 });
+                usage.size += size;
+                usage.count += 1;
+                if directory == root || !directory.starts_with(root) {
+                    break;
+                }
+            }
+        }
+
+        let mut directories: Vec<Usage> = directories.into_values().collect();
+        for usages in [&mut directories, &mut sizes] {
+            usages.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+            usages.truncate(self.top);
+        }
+        (directories, sizes)
+    }
+
+    fn display(&self, kind: &str, heading: &str, usages: &[Usage]) -> Result<()> {
+        if self.config.output_format == OutputFormat::Json {
+            for usage in usages {
+                outln!("{}", json!({"type": kind, "path": usage.path, "size": usage.size, "count": usage.count}))?;
+            }
+            return Ok(());
+        }
+
+        outln!("{}", self.config.theme.heading().apply_to(heading))?;
+        let path_style = self.config.theme.path();
+        for usage in usages {
+            outln!("  {:>10}  {}", format_size(usage.size), styled_path(&path_style, &usage.path))?;
+        }
+        Ok(())
+    }
+}
+
+impl Command for SizeReportCommand<'_> {
+    fn execute(&self) -> Result<Outcome> {
+        let app_config = AppConfig::from(self.config);
+        // The report covers every match, whatever the result limit
+        let finder = FinderFactory::from_search_config(&FileSearchConfig { max_results: None, ..self.config.clone() })?;
+        let (matches, report) = finder.find(&app_config.root_dir)
+            .with_context(|| format!("Failed to search directory: {}", app_config.root_dir.display()))?;
+        let files: Vec<PathBuf> = matches.into_iter().map(|found| found.path).collect();
+
+        if files.is_empty() {
+            if self.config.show_progress {
+                eprintln!("No matching files found");
+            }
+        } else {
+            let (directories, largest) = self.measure(&app_config.root_dir, &files);
+            self.display("dir", "Largest directories:", &directories)?;
+            self.display("file", "Largest files:", &largest)?;
+        }
+
+        let errors = report.errors.len();
+        ensure_complete(self.config, report.errors)?;
+        Ok(Outcome::from_counts(files.len(), errors))
+    }
+}
@@ -2,11 +2,12 @@ use std::sync::Arc;
 
 use crate::{
     core::{
+        config::Walker,
         finder::{FinderConfig, FileFinder},
         registry::{FilterRegistry, ObserverRegistry},
-        traversal::{DefaultTraversalStrategy, TraversalStrategy},
+        traversal::{CompositeTraversalStrategy, DefaultTraversalStrategy, TraversalStrategy},
     },
-    filters::Filter,
+    filters::{Filter, TypeFilter},
 };
 
 /// Builder for FileFinder
@@ -40,6 +41,16 @@ impl FileFinderBuilder {
         self
     }
 
+    /// Add a traversal strategy alongside the current one
+    ///
+    /// Paths are only processed if both strategies allow them, so the built-in
+    /// handling of hidden entries and patterns is kept.
+    pub fn with_additional_traversal_strategy(mut self, strategy: Box<dyn TraversalStrategy + 'static>) -> Self {
+        let current = Arc::clone(&self.traversal_strategy);
+        self.traversal_strategy = Arc::new(CompositeTraversalStrategy::new(vec![Box::new(current), strategy]));
+        self
+    }
+
     /// Set the number of threads to use for search
     pub fn with_threads(mut self, num_threads: usize) -> Self {
         self.config.num_threads = num_threads;
@@ -52,12 +63,72 @@ impl FileFinderBuilder {
         self
     }
 
+    /// Set whether to stay on the filesystem of the root
+    ///
+    /// The walkdir and ignore walkers skip other filesystems themselves; the
+    /// built-in one relies on a [`SameFileSystemFilter`](crate::filters::SameFileSystemFilter)
+    /// being registered as well.
+    pub fn with_one_file_system(mut self, one_file_system: bool) -> Self {
+        self.config.one_file_system = one_file_system;
+        self
+    }
+
     /// Set the maximum search depth
     pub fn with_max_depth(mut self, max_depth: usize) -> Self {
         self.config.max_depth = Some(max_depth);
         self
     }
 
+    /// Set the maximum number of files found per directory
+    ///
+    /// Once a directory has this many matches its remaining files are not examined;
+    /// its subdirectories are still searched.
+    pub fn with_max_per_dir(mut self, max_per_dir: usize) -> Self {
+        self.config.max_per_dir = Some(max_per_dir);
+        self
+    }
+
+    /// Set the number of matches after which the search stops
+    ///
+    /// Directories that were not read yet are abandoned, so which matches are found
+    /// depends on the order the walk happens to take.
+    pub fn with_max_results(mut self, max_results: usize) -> Self {
+        self.config.max_results = Some(max_results);
+        self
+    }
+
+    /// Set the kinds of entries reported, such as directories or symbolic links
+    ///
+    /// Only regular files are reported unless other kinds are asked for.
+    pub fn with_types(mut self, types: TypeFilter) -> Self {
+        self.config.types = types;
+        self
+    }
+
+    /// Report each file once, under its canonical path, however many paths reach it
+    ///
+    /// Paths are compared by file identity, so symbolic links followed with
+    /// `follow_links` and hard links to a file already reported are dropped.
+    pub fn with_unique(mut self, unique: bool) -> Self {
+        self.config.unique = unique;
+        self
+    }
+
+    /// Set the directory walker to traverse with
+    ///
+    /// Every walker applies the same traversal strategy, filters and limits, so they
+    /// can be compared on the same tree.
+    pub fn with_walker(mut self, walker: Walker) -> Self {
+        self.config.walker = walker;
+        self
+    }
+
+    /// Set whether to suppress messages about unreadable paths
+    pub fn with_no_messages(mut self, no_messages: bool) -> Self {
+        self.config.no_messages = no_messages;
+        self
+    }
+
     /// Add a filter to the filter registry
     pub fn with_filter<F: Filter + 'static>(mut self, name: &str, filter: F) -> Self {
         {
@@ -1,4 +1,6 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::time::SystemTime;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use anyhow::{Context, Result};
@@ -6,6 +8,8 @@ use std::fs;
 use std::path::Path;
 
 use crate::core::traversal::TraversalMode;
+use crate::filters::{ContentKind, DateZone, EntryType, RegexSyntax};
+use crate::output::{ColorChoice, GroupBy, HistogramKind, ReportFormat, Theme};
 
 /// Errors that can occur during configuration operations
 #[derive(Error, Debug)]
@@ -21,35 +25,112 @@ pub enum ConfigError {
 }
 
 /// Configuration for file search operations
+///
+/// Date bounds written without an offset in config files are read in the file's
+/// `date_zone`, as they are on the command line.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct FileSearchConfig {
     /// Path to search in (defaults to current directory)
     #[serde(default)]
     pub path: Option<String>,
     
+    /// Search from the filesystem root when no path is given
+    #[serde(default)]
+    pub system: bool,
+    
     /// File extension to filter by
     #[serde(default)]
     pub file_extension: Option<String>,
     
+    /// Whether file extensions are compared case-sensitively
+    #[serde(default)]
+    pub case_sensitive_extension: bool,
+    
     /// File name pattern to filter by
     #[serde(default)]
     pub file_name: Option<String>,
     
+    /// Shell-style glob the file name (or path, if it contains '/') must match
+    #[serde(default)]
+    pub name_glob: Option<String>,
+    
+    /// Regular expression the file name must match
+    #[serde(default)]
+    pub name_regex: Option<String>,
+    
+    /// Whether the name and name regex are matched against the whole path instead of the file name
+    #[serde(default)]
+    pub full_path: bool,
+    
     /// Text pattern to search for within files (grep-like functionality)
     #[serde(default)]
     pub pattern: Option<String>,
     
-    /// Whether to use case-insensitive search
+    /// Whether to ignore case in content and extensions, even with `case_sensitive_extension`
     #[serde(default)]
     pub ignore_case: bool,
     
+    /// Syntax in which content and file name regular expressions are written
+    #[serde(default)]
+    pub regex_syntax: RegexSyntax,
+    
     /// Whether to show line numbers in search results
     #[serde(default)]
     pub line_number: bool,
     
+    /// Whether records in searched files and in grep output end in NUL instead of newline
+    #[serde(default)]
+    pub null_data: bool,
+    
+    /// How grep treats files that look binary
+    #[serde(default)]
+    pub binary: BinaryMode,
+    
+    /// Whether grep output groups matches under file name headings (automatic on a terminal if not set)
+    #[serde(default)]
+    pub heading: Option<bool>,
+    
+    /// Whether to leave file names out of grep match output
+    #[serde(default)]
+    pub no_filename: bool,
+    
+    /// Whether grep prints every line of the searched files, flagging the matching ones
+    #[serde(default)]
+    pub passthru: bool,
+    
     /// Whether to show only filenames of files containing the pattern
     #[serde(default)]
     pub files_with_matches: bool,
+    
+    /// Whether to print search statistics at the end of a content search
+    #[serde(default)]
+    pub stats: bool,
+    
+    /// Histograms added to the statistics of a content search
+    #[serde(default)]
+    pub histograms: Vec<HistogramKind>,
+    
+    /// Document types whose text is extracted for content search (all available if not set)
+    #[serde(default)]
+    pub doc_types: Option<Vec<DocType>>,
+    
+    /// Largest file in bytes that a content search will read
+    #[serde(default)]
+    pub max_filesize: Option<u64>,
+    
+    /// Whether to print content matches as soon as each file finishes
+    /// instead of in candidate order
+    #[serde(default)]
+    pub unordered: bool,
+    
+    /// Content index to use instead of the one found for the search path
+    #[serde(default)]
+    pub index_file: Option<String>,
+    
+    /// Whether to ignore any content index and open every candidate file
+    #[serde(default)]
+    pub no_index: bool,
 
     /// Whether to use fuzzy matching for file names
     #[serde(default)]
@@ -59,6 +140,14 @@ pub struct FileSearchConfig {
     #[serde(default)]
     pub fuzzy_threshold: Option<u8>,
     
+    /// Text fuzzy mode ranks file names against; the other criteria only select candidates
+    #[serde(default)]
+    pub fuzzy_query: Option<String>,
+    
+    /// Whether fuzzy mode picks matches and prints only the chosen paths, for shell integration
+    #[serde(default)]
+    pub select: bool,
+    
     /// Whether to display help information
     #[serde(default)]
     pub help: bool,
@@ -71,22 +160,162 @@ pub struct FileSearchConfig {
     #[serde(default)]
     pub thread_count: Option<usize>,
     
+    /// Directory walker the advanced finder uses
+    #[serde(default)]
+    pub walker: Walker,
+    
     /// Whether to show progress during search
     #[serde(default = "default_show_progress")]
     pub show_progress: bool,
     
+    /// How progress is drawn on a terminal
+    #[serde(default)]
+    pub progress: ProgressDisplay,
+    
+    /// Whether to count the directories to search first, for exact progress
+    #[serde(default)]
+    pub prescan: bool,
+    
     /// Whether to use quiet mode (less verbose output)
     #[serde(default)]
     pub quiet_mode: bool,
     
+    /// Whether to suppress messages about unreadable paths (they are still counted)
+    #[serde(default)]
+    pub no_messages: bool,
+    
+    /// Whether to also enter `node_modules`, `target`, `.git` and the other directories skipped by default
+    #[serde(default)]
+    pub no_default_excludes: bool,
+    
+    /// Gitignore-style patterns of files and directories to skip
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    
+    /// Whether to skip the patterns of the global ignore file
+    #[serde(default)]
+    pub no_ignore_global: bool,
+    
+    /// Whether to skip what `.gitignore` files and `.git/info/exclude` ignore
+    #[serde(default)]
+    pub git_ignore: bool,
+    
+    /// Patterns read from the global ignore file at startup
+    #[serde(skip)]
+    pub global_ignore: Vec<String>,
+    
+    /// Whether to fail the run if any path could not be read
+    #[serde(default)]
+    pub strict: bool,
+    
+    /// Format used to print results
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    
+    /// Whether results are printed as bare paths ending in NUL, for `xargs -0`
+    #[serde(default)]
+    pub print0: bool,
+    
+    /// Template each result is printed with instead of the output format, as in `find -printf`
+    #[serde(default)]
+    pub printf: Option<String>,
+    
+    /// Whether file results list permissions, owner, size and modification time, as in `ls -l`
+    #[serde(default)]
+    pub long: bool,
+    
+    /// Whether file results are drawn as a tree under their directories
+    #[serde(default)]
+    pub tree: bool,
+    
+    /// Order in which file results are printed
+    #[serde(default)]
+    pub sort: SortOrder,
+    
+    /// Order in which candidate files are searched for content
+    #[serde(default)]
+    pub sort_input: InputOrder,
+    
+    /// Aggregate results into per-group counts and sizes instead of listing them
+    #[serde(default)]
+    pub group_by: Option<GroupBy>,
+    
+    /// Whether results are browsed in a terminal UI instead of being printed
+    #[serde(default)]
+    pub interactive: bool,
+    
+    /// Number of largest directories and files to list in a size report, if one was asked for
+    #[serde(default)]
+    pub du: Option<usize>,
+    
+    /// Format of a report written instead of the result listing, if one was asked for
+    #[serde(default)]
+    pub report: Option<ReportFormat>,
+    
+    /// Lines of results shown before waiting for a key press on a terminal
+    #[serde(default)]
+    pub page_size: Option<usize>,
+    
+    /// Whether results longer than the screen are piped into `$PAGER` on a terminal
+    #[serde(default = "default_pager")]
+    pub pager: bool,
+    
+    /// Colors of text output
+    #[serde(default)]
+    pub theme: Theme,
+    
+    /// When text output is colored
+    #[serde(default)]
+    pub color: ColorChoice,
+    
+    /// Whether result paths are printed as clickable links on terminals that support them
+    #[serde(default)]
+    pub hyperlinks: bool,
+    
+    /// File to write results into for file-based output formats
+    #[serde(default)]
+    pub output_file: Option<String>,
+    
+    /// SQLite database to write results into
+    #[serde(default)]
+    pub output_sqlite: Option<String>,
+    
     /// Whether to search recursively in subdirectories
     #[serde(default = "default_recursive")]
     pub recursive: bool,
     
+    /// Report nothing deeper than this many levels below the search root
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    
+    /// Report nothing shallower than this many levels below the search root
+    #[serde(default)]
+    pub min_depth: Option<usize>,
+    
+    /// Report at most this many matches per directory
+    #[serde(default)]
+    pub max_per_dir: Option<usize>,
+    
+    /// Stop searching once this many results have been found
+    #[serde(default)]
+    pub max_results: Option<usize>,
+    
     /// Whether to follow symbolic links
     #[serde(default)]
     pub follow_symlinks: bool,
     
+    /// Whether each file is reported once, under its canonical path, however many paths reach it
+    #[serde(default)]
+    pub unique: bool,
+    
+    /// Whether to stay on the filesystem of the search path
+    #[serde(default)]
+    pub one_file_system: bool,
+    
+    /// Whether to include hidden files and directories
+    #[serde(default)]
+    pub hidden: bool,
+    
     /// Traversal strategy to use
     #[serde(default)]
     pub traversal_mode: TraversalMode,
@@ -99,44 +328,352 @@ pub struct FileSearchConfig {
     #[serde(default)]
     pub max_size: Option<u64>,
     
-    /// Modified after this date (ISO format: YYYY-MM-DD)
+    /// Whether sizes are measured by allocated disk space instead of apparent length
+    #[serde(default)]
+    pub disk_size: bool,
+    
+    /// Only zero-byte files and, when directories are reported, directories with no entries
+    #[serde(default)]
+    pub empty: bool,
+    
+    /// Only entries with exactly this many hard links
+    #[serde(default)]
+    pub links: Option<u64>,
+    
+    /// Only entries with at least this many hard links, unless `links` is also set
+    #[serde(default)]
+    pub min_links: Option<u64>,
+    
+    /// Modified after this time (stored in config files as an RFC 3339 time, or
+    /// YYYY-MM-DD for the start of a day in `date_zone`)
+    #[serde(default, with = "date_format::start_of_day")]
+    pub newer_than: Option<SystemTime>,
+    
+    /// Modified before this time (stored in config files as an RFC 3339 time, or
+    /// YYYY-MM-DD for the end of a day in `date_zone`)
+    #[serde(default, with = "date_format::end_of_day")]
+    pub older_than: Option<SystemTime>,
+    
+    /// Created after this time, stored like `newer_than`
+    #[serde(default, with = "date_format::start_of_day")]
+    pub created_after: Option<SystemTime>,
+    
+    /// Created before this time, stored like `older_than`
+    #[serde(default, with = "date_format::end_of_day")]
+    pub created_before: Option<SystemTime>,
+    
+    /// Last read after this time, stored like `newer_than`
+    #[serde(default, with = "date_format::start_of_day")]
+    pub accessed_after: Option<SystemTime>,
+    
+    /// Last read before this time, stored like `older_than`
+    #[serde(default, with = "date_format::end_of_day")]
+    pub accessed_before: Option<SystemTime>,
+    
+    /// Zone in which dates without an offset are read, on the command line and in config files
+    #[serde(default)]
+    pub date_zone: DateZone,
+    
+    /// Only files whose extension is in one of these groups, such as `image` or `code`
+    #[serde(default)]
+    pub type_group: Option<Vec<String>>,
+    
+    /// Only files whose content is of one of these MIME types, such as `image/*`
+    #[serde(default)]
+    pub mime: Option<Vec<String>>,
+    
+    /// Only text files, or only binary ones, as grep tells them apart
+    #[serde(default)]
+    pub content_kind: Option<ContentKind>,
+    
+    /// Kinds of entries reported, such as directories or symbolic links; regular files when empty
+    #[serde(default)]
+    pub entry_types: Vec<EntryType>,
+    
+    /// Extension groups adding to, or replacing, the built-in ones of the same name
+    #[serde(default)]
+    pub type_groups: BTreeMap<String, Vec<String>>,
+    
+    /// How content search treats files, keyed by extension or MIME type such as `text/*`
+    #[serde(default)]
+    pub handlers: BTreeMap<String, Handler>,
+    
+    /// Modified more recently than this reference file
     #[serde(default)]
-    pub newer_than: Option<String>,
+    pub newer_than_file: Option<String>,
     
-    /// Modified before this date (ISO format: YYYY-MM-DD)
+    /// Modified before this reference file
     #[serde(default)]
-    pub older_than: Option<String>,
+    pub older_than_file: Option<String>,
+    
+    /// Photos taken or recordings made on or after this date (stored like `newer_than`)
+    #[serde(default, with = "date_format::start_of_day")]
+    pub exif_date_after: Option<SystemTime>,
+    
+    /// Photos taken or recordings made before this date (stored like `newer_than`)
+    #[serde(default, with = "date_format::start_of_day")]
+    pub exif_date_before: Option<SystemTime>,
+    
+    /// Text the camera model in a photo's EXIF data must contain
+    #[serde(default)]
+    pub camera_model: Option<String>,
+    
+    /// Text the artist in a recording's ID3 tag must contain
+    #[serde(default)]
+    pub artist: Option<String>,
+    
+    /// Read candidate files from this list ("-" for stdin) instead of traversing
+    #[serde(default)]
+    pub files_from: Option<String>,
+    
+    /// Only consider files tracked by git
+    #[serde(default)]
+    pub git_tracked: bool,
+    
+    /// Only consider tracked files with uncommitted modifications
+    #[serde(default)]
+    pub git_modified: bool,
+    
+    /// Rhai script defining custom filters and match actions
+    #[serde(default)]
+    pub script: Option<String>,
+}
+
+/// Output formats for search results
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum OutputFormat {
+    /// Human-readable output with headings and summaries
+    #[default]
+    Text,
+    /// `file:line:col:text` records understood by Vim's `grepprg` and similar integrations
+    Vimgrep,
+    /// Parquet file of result rows written to the configured output file
+    Parquet,
+    /// One JSON object per line, tagged with a `type` field
+    Json,
+}
+
+/// Where a value of the effective configuration came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// Built-in default
+    Default,
+    /// Configuration file, by path
+    File(String),
+    /// Command-line flag or argument
+    CommandLine,
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::File(path) => write!(f, "config file {}", path),
+            ConfigSource::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+/// How content search treats a kind of file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Handler {
+    /// Leave the file out of the search
+    Skip,
+    /// Treat the file as binary, whatever its content
+    Binary,
+    /// Search the file as text, even if it looks binary
+    Text,
+    /// Search the text extracted from a document format
+    Extract(DocType),
+    /// Search what a command writes to standard output when given the file's path as
+    /// its last argument, such as `["gzip", "-dc"]`
+    Command(Vec<String>),
+}
+
+/// Document formats whose text can be extracted for content search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocType {
+    /// PDF documents (requires the `pdf` feature)
+    Pdf,
+    /// Word documents (requires the `docx` feature)
+    Docx,
+}
+
+/// Orders for file search results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Keep the order in which files were found
+    #[default]
+    None,
+    /// Sort by path
+    Path,
+    /// Sort by path with embedded numbers compared by value and letters ignoring case
+    Natural,
+    /// Most frequently and recently selected paths first
+    Frecency,
+}
+
+/// Orders in which candidate files are searched for content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum InputOrder {
+    /// Sort by path
+    #[default]
+    Path,
+    /// Most recently modified files first
+    Mtime,
+}
+
+/// How content search treats files that look binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BinaryMode {
+    /// Leave binary files unsearched
+    #[default]
+    Skip,
+    /// Search binary files, but only report that they match
+    Report,
+    /// Search binary files as text, printing their matching lines
+    Text,
+}
+
+/// Directory walkers the advanced finder can delegate traversal to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Walker {
+    /// Oqab's own walker, run on its worker pool
+    #[default]
+    Builtin,
+    /// The `walkdir` crate's single-threaded walker
+    Walkdir,
+    /// The `ignore` crate's parallel walker, with its own ignore rules turned off
+    Ignore,
+}
+
+/// How progress is drawn on a terminal while a search runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressDisplay {
+    /// A plain status line naming the directory being read
+    #[default]
+    Line,
+    /// An animated progress bar with the elapsed time
+    Bar,
 }
 
 // Helper functions for serde defaults
 fn default_show_progress() -> bool { true }
 fn default_recursive() -> bool { true }
+fn default_pager() -> bool { true }
 
 impl FileSearchConfig {
     /// Create a new configuration with default values
     pub fn new() -> Self {
         Self {
             path: None,
+            system: false,
             file_extension: None,
+            case_sensitive_extension: false,
             file_name: None,
+            name_glob: None,
+            name_regex: None,
+            full_path: false,
             pattern: None,
             ignore_case: false,
+            regex_syntax: RegexSyntax::default(),
             line_number: false,
+            null_data: false,
+            binary: BinaryMode::default(),
+            heading: None,
+            no_filename: false,
+            passthru: false,
             files_with_matches: false,
+            stats: false,
+            histograms: Vec::new(),
+            doc_types: None,
+            max_filesize: None,
+            unordered: false,
+            index_file: None,
+            no_index: false,
             help: false,
             advanced_search: false,
             thread_count: None,
+            walker: Walker::default(),
             show_progress: true,
+            progress: ProgressDisplay::default(),
+            prescan: false,
             quiet_mode: false,
+            no_messages: false,
+            no_default_excludes: false,
+            exclude: Vec::new(),
+            no_ignore_global: false,
+            git_ignore: false,
+            global_ignore: Vec::new(),
+            strict: false,
+            output_format: OutputFormat::default(),
+            print0: false,
+            printf: None,
+            long: false,
+            tree: false,
+            sort: SortOrder::default(),
+            sort_input: InputOrder::default(),
+            group_by: None,
+            interactive: false,
+            du: None,
+            report: None,
+            page_size: None,
+            pager: true,
+            theme: Theme::default(),
+            color: ColorChoice::default(),
+            hyperlinks: false,
+            output_file: None,
+            output_sqlite: None,
             recursive: true,
+            max_depth: None,
+            min_depth: None,
+            max_per_dir: None,
+            max_results: None,
             follow_symlinks: false,
+            one_file_system: false,
+            unique: false,
+            hidden: false,
             traversal_mode: TraversalMode::default(),
             min_size: None,
             max_size: None,
+            disk_size: false,
+            empty: false,
+            links: None,
+            min_links: None,
             newer_than: None,
             older_than: None,
+            created_after: None,
+            created_before: None,
+            accessed_after: None,
+            accessed_before: None,
+            date_zone: DateZone::default(),
+            newer_than_file: None,
+            older_than_file: None,
+            type_group: None,
+            mime: None,
+            content_kind: None,
+            entry_types: Vec::new(),
+            type_groups: BTreeMap::new(),
+            handlers: BTreeMap::new(),
+            exif_date_after: None,
+            exif_date_before: None,
+            camera_model: None,
+            artist: None,
             fuzzy: false,
             fuzzy_threshold: None,
+            fuzzy_query: None,
+            select: false,
+            files_from: None,
+            git_tracked: false,
+            git_modified: false,
+            script: None,
         }
     }
     
@@ -166,10 +703,77 @@ impl FileSearchConfig {
         Ok(())
     }
     
+    /// Character ending each record of printed results
+    pub fn record_terminator(&self) -> char {
+        if self.null_data || self.print0 { '\0' } else { '\n' }
+    }
+    
+    /// Set the criteria a smart query asks for, according to what it looks like
+    ///
+    /// `re:PATTERN` is a regular expression on the file name, anything containing
+    /// `*`, `?`, `[` or `{` is a glob, `.ext` selects an extension, and `name.ext`
+    /// selects both. Anything else, or any `literal` query, is matched as a file name
+    /// substring. Name criteria are only set with `set_name` and the extension only
+    /// with `set_extension`, so that explicitly given ones win.
+    pub fn apply_query(&mut self, query: &str, literal: bool, set_name: bool, set_extension: bool) {
+        if literal {
+            if set_name {
+                self.file_name = Some(query.to_string());
+            }
+        } else if let Some(regex) = query.strip_prefix("re:") {
+            if set_name {
+                self.name_regex = Some(regex.to_string());
+            }
+        } else if query.contains(['*', '?', '[', '{']) {
+            if set_name {
+                self.name_glob = Some(query.to_string());
+            }
+        } else if let Some(extension) = extension_shorthand(query) {
+            if set_extension {
+                self.file_extension = Some(extension.to_string());
+            }
+        } else if let Some((name, extension)) = query.rsplit_once('.').filter(|(name, ext)| !name.is_empty() && !ext.is_empty()) {
+            // Looks like filename.ext format
+            if set_name {
+                self.file_name = Some(format!("{}.{}", name, extension));
+            }
+            if set_extension {
+                self.file_extension = Some(extension.to_string());
+            }
+        } else if set_name {
+            self.file_name = Some(query.to_string());
+        }
+    }
+    
     /// Get the search path or the default "." path
     pub fn get_path(&self) -> &str {
         self.path.as_deref().unwrap_or(".")
     }
+    
+    /// Whether any criterion selecting files or content has been given
+    pub fn has_criteria(&self) -> bool {
+        self.file_extension.is_some()
+            || self.file_name.is_some()
+            || self.name_glob.is_some()
+            || self.name_regex.is_some()
+            || self.pattern.is_some()
+            || self.fuzzy_query.is_some()
+            || !self.entry_types.is_empty()
+            || self.empty
+            || self.links.is_some()
+            || self.min_links.is_some()
+            || self.mime.is_some()
+            || self.content_kind.is_some()
+            || self.has_media_criteria()
+    }
+    
+    /// Whether any criterion on embedded media metadata has been given
+    pub fn has_media_criteria(&self) -> bool {
+        self.exif_date_after.is_some()
+            || self.exif_date_before.is_some()
+            || self.camera_model.is_some()
+            || self.artist.is_some()
+    }
 }
 
 impl Default for FileSearchConfig {
@@ -178,6 +782,12 @@ impl Default for FileSearchConfig {
     }
 }
 
+// The extension in a `.ext` query, if the query is nothing but an extension
+fn extension_shorthand(query: &str) -> Option<&str> {
+    query.strip_prefix('.')
+        .filter(|ext| !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+}
+
 /// Application runtime configuration
 #[derive(Debug, Clone)]
 pub struct AppConfig {
@@ -187,9 +797,21 @@ pub struct AppConfig {
     /// File extension to filter by
     pub extension: Option<String>,
     
+    /// Whether file extensions are compared case-sensitively
+    pub case_sensitive_extension: Option<bool>,
+    
     /// File name to filter by
     pub name: Option<String>,
     
+    /// Shell-style glob to filter file names (or paths) by
+    pub name_glob: Option<String>,
+    
+    /// Regular expression to filter file names by
+    pub name_regex: Option<String>,
+    
+    /// Whether names are matched against the whole path
+    pub full_path: Option<bool>,
+    
     /// Regular expression pattern to filter by
     pub pattern: Option<String>,
     
@@ -199,11 +821,68 @@ pub struct AppConfig {
     /// Maximum file size in bytes
     pub max_size: Option<u64>,
     
-    /// Modified after this date (ISO format: YYYY-MM-DD)
-    pub newer_than: Option<String>,
+    /// Whether sizes are measured by allocated disk space
+    pub disk_size: Option<bool>,
+    
+    /// Only empty files and directories
+    pub empty: Option<bool>,
+    
+    /// Only entries with exactly this many hard links
+    pub links: Option<u64>,
+    
+    /// Only entries with at least this many hard links
+    pub min_links: Option<u64>,
+    
+    /// Modified after this time
+    pub newer_than: Option<SystemTime>,
+    
+    /// Modified before this time
+    pub older_than: Option<SystemTime>,
+    
+    /// Created after this time
+    pub created_after: Option<SystemTime>,
+    
+    /// Created before this time
+    pub created_before: Option<SystemTime>,
+    
+    /// Last read after this time
+    pub accessed_after: Option<SystemTime>,
+    
+    /// Last read before this time
+    pub accessed_before: Option<SystemTime>,
+    
+    /// Only files whose extension is in one of these groups
+    pub type_group: Option<Vec<String>>,
+    
+    /// Only files whose content is of one of these MIME types
+    pub mime: Option<Vec<String>>,
+    
+    /// Only text files, or only binary ones
+    pub content_kind: Option<ContentKind>,
+    
+    /// Kinds of entries reported; regular files when empty
+    pub entry_types: Vec<EntryType>,
+    
+    /// Extension groups adding to, or replacing, the built-in ones
+    pub type_groups: BTreeMap<String, Vec<String>>,
+    
+    /// Modified more recently than this reference file
+    pub newer_than_file: Option<PathBuf>,
+    
+    /// Modified before this reference file
+    pub older_than_file: Option<PathBuf>,
+    
+    /// Photos taken or recordings made at or after this time
+    pub exif_date_after: Option<SystemTime>,
     
-    /// Modified before this date (ISO format: YYYY-MM-DD)
-    pub older_than: Option<String>,
+    /// Photos taken or recordings made before this time
+    pub exif_date_before: Option<SystemTime>,
+    
+    /// Text the camera model of a photo must contain
+    pub camera_model: Option<String>,
+    
+    /// Text the artist of a recording must contain
+    pub artist: Option<String>,
     
     /// Size to filter by (legacy)
     pub size: Option<u64>,
@@ -211,17 +890,71 @@ pub struct AppConfig {
     /// Maximum depth to search
     pub depth: Option<usize>,
     
+    /// Minimum depth of reported entries
+    pub min_depth: Option<usize>,
+    
+    /// Maximum number of matches reported per directory
+    pub max_per_dir: Option<usize>,
+    
+    /// Maximum number of matches found before the search stops
+    pub max_results: Option<usize>,
+    
     /// Number of threads to use
     pub threads: Option<usize>,
     
+    /// Directory walker to traverse with
+    pub walker: Option<Walker>,
+    
     /// Whether to follow symbolic links
     pub follow_links: Option<bool>,
     
+    /// Whether files reached through several paths are reported once
+    pub unique: Option<bool>,
+    
+    /// Whether to stay on the filesystem of the search path
+    pub one_file_system: Option<bool>,
+    
+    /// Whether to include hidden files and directories
+    pub hidden: Option<bool>,
+    
+    /// Syntax in which the name regex and path pattern are written
+    pub regex_syntax: Option<RegexSyntax>,
+    
     /// Whether to show progress during search
     pub show_progress: Option<bool>,
     
+    /// How progress is drawn on a terminal
+    pub progress: Option<ProgressDisplay>,
+    
+    /// Whether to count the directories to search first, for exact progress
+    pub prescan: Option<bool>,
+    
     /// Whether to use quiet mode (less verbose output)
     pub quiet: Option<bool>,
+    
+    /// Whether to suppress messages about unreadable paths
+    pub no_messages: Option<bool>,
+    
+    /// Whether to enter directories skipped by default
+    pub no_default_excludes: Option<bool>,
+    
+    /// Gitignore-style patterns of files and directories to skip
+    pub exclude: Vec<String>,
+    
+    /// Gitignore-style patterns applied to every search
+    pub global_ignore: Vec<String>,
+    
+    /// Skip what git ignores
+    pub git_ignore: Option<bool>,
+    
+    /// Only consider files tracked by git
+    pub git_tracked: Option<bool>,
+    
+    /// Only consider tracked files with uncommitted modifications
+    pub git_modified: Option<bool>,
+    
+    /// Rhai script whose `filter` function selects files
+    pub script: Option<PathBuf>,
 }
 
 impl Default for AppConfig {
@@ -229,18 +962,265 @@ impl Default for AppConfig {
         Self {
             root_dir: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
             extension: None,
+            case_sensitive_extension: Some(false),
             name: None,
+            name_glob: None,
+            name_regex: None,
+            full_path: Some(false),
             pattern: None,
             min_size: None,
             max_size: None,
+            disk_size: Some(false),
+            empty: Some(false),
+            links: None,
+            min_links: None,
             newer_than: None,
             older_than: None,
+            created_after: None,
+            created_before: None,
+            accessed_after: None,
+            accessed_before: None,
+            newer_than_file: None,
+            older_than_file: None,
+            type_group: None,
+            mime: None,
+            content_kind: None,
+            entry_types: Vec::new(),
+            type_groups: BTreeMap::new(),
+            exif_date_after: None,
+            exif_date_before: None,
+            camera_model: None,
+            artist: None,
             size: None,
             depth: None,
+            min_depth: None,
+            max_per_dir: None,
+            max_results: None,
             threads: Some(num_cpus::get()),
+            walker: Some(Walker::default()),
             follow_links: Some(false),
+            one_file_system: Some(false),
+            unique: Some(false),
+            hidden: Some(false),
+            regex_syntax: Some(RegexSyntax::default()),
             show_progress: Some(true),
+            progress: Some(ProgressDisplay::default()),
+            prescan: Some(false),
             quiet: Some(false),
+            no_messages: Some(false),
+            no_default_excludes: Some(false),
+            exclude: Vec::new(),
+            global_ignore: Vec::new(),
+            git_ignore: Some(false),
+            git_tracked: Some(false),
+            git_modified: Some(false),
+            script: None,
         }
     }
-} 
\ No newline at end of file
+} 
+impl From<&FileSearchConfig> for AppConfig {
+    /// Convert a user-facing search configuration into the runtime configuration
+    /// consumed by `FinderFactory`, carrying over every filter that has an equivalent.
+    ///
+    /// The grep `pattern` is intentionally not mapped: in `AppConfig` the pattern is a
+    /// path regex, whereas in `FileSearchConfig` it is a content pattern handled by `GrepCommand`.
+    fn from(config: &FileSearchConfig) -> Self {
+        Self {
+            root_dir: match &config.path {
+                Some(path) => PathBuf::from(path),
+                None => std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            },
+            extension: config.file_extension.clone(),
+            // Ignoring case applies to extensions as much as to content
+            case_sensitive_extension: Some(config.case_sensitive_extension && !config.ignore_case),
+            name: config.file_name.clone(),
+            name_glob: config.name_glob.clone(),
+            name_regex: config.name_regex.clone(),
+            full_path: Some(config.full_path),
+            pattern: None,
+            min_size: config.min_size,
+            max_size: config.max_size,
+            disk_size: Some(config.disk_size),
+            empty: Some(config.empty),
+            links: config.links,
+            min_links: config.min_links,
+            newer_than: config.newer_than,
+            older_than: config.older_than,
+            created_after: config.created_after,
+            created_before: config.created_before,
+            accessed_after: config.accessed_after,
+            accessed_before: config.accessed_before,
+            newer_than_file: config.newer_than_file.as_ref().map(PathBuf::from),
+            older_than_file: config.older_than_file.as_ref().map(PathBuf::from),
+            type_group: config.type_group.clone(),
+            mime: config.mime.clone(),
+            content_kind: config.content_kind,
+            entry_types: config.entry_types.clone(),
+            type_groups: config.type_groups.clone(),
+            exif_date_after: config.exif_date_after,
+            exif_date_before: config.exif_date_before,
+            camera_model: config.camera_model.clone(),
+            artist: config.artist.clone(),
+            size: None,
+            // A non-recursive search only looks at the root directory itself
+            depth: config.max_depth.into_iter().chain((!config.recursive).then_some(1)).min(),
+            min_depth: config.min_depth,
+            max_per_dir: config.max_per_dir,
+            max_results: config.max_results,
+            threads: config.thread_count,
+            walker: Some(config.walker),
+            follow_links: Some(config.follow_symlinks),
+            one_file_system: Some(config.one_file_system),
+            unique: Some(config.unique),
+            hidden: Some(config.hidden),
+            regex_syntax: Some(config.regex_syntax),
+            show_progress: Some(config.show_progress),
+            progress: Some(config.progress),
+            prescan: Some(config.prescan),
+            quiet: Some(config.quiet_mode),
+            no_messages: Some(config.no_messages),
+            no_default_excludes: Some(config.no_default_excludes),
+            exclude: config.exclude.clone(),
+            global_ignore: config.global_ignore.clone(),
+            git_ignore: Some(config.git_ignore),
+            git_tracked: Some(config.git_tracked),
+            git_modified: Some(config.git_modified),
+            script: config.script.as_ref().map(PathBuf::from),
+        }
+    }
+}
+
+// Plain dates are resolved in the config's zone before the fields are read, and
+// written back in it, which the fields cannot do on their own
+impl Serialize for FileSearchConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut value = FileSearchConfig::serialize(self, serde_json::value::Serializer)
+            .map_err(serde::ser::Error::custom)?;
+        if let serde_json::Value::Object(fields) = &mut value {
+            date_format::simplify(fields);
+        }
+        value.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FileSearchConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        if let serde_json::Value::Object(fields) = &mut value {
+            date_format::resolve(fields)?;
+        }
+        FileSearchConfig::deserialize(value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serde helpers storing date bounds in config files
+///
+/// Each field is stored on its own as an RFC 3339 time. The config as a whole
+/// stores bounds falling on the edge of a day in its `date_zone` as plain
+/// `YYYY-MM-DD` dates, and reads plain dates in that zone.
+mod date_format {
+    use std::time::SystemTime;
+    use chrono::{DateTime, Local, NaiveTime, SecondsFormat, Utc};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use serde_json::{Map, Value};
+    use crate::filters::{date, DateZone};
+
+    /// Date bound fields, and whether each is the start of a range
+    const FIELDS: [(&str, bool); 8] = [
+        ("newer_than", true),
+        ("older_than", false),
+        ("created_after", true),
+        ("created_before", false),
+        ("accessed_after", true),
+        ("accessed_before", false),
+        ("exif_date_after", true),
+        ("exif_date_before", true),
+    ];
+
+    fn day_edge(start: bool) -> NaiveTime {
+        if start { NaiveTime::MIN } else { NaiveTime::from_hms_opt(23, 59, 59).unwrap() }
+    }
+
+    fn zone_of<E: de::Error>(fields: &Map<String, Value>) -> Result<DateZone, E> {
+        fields.get("date_zone")
+            .map(|zone| DateZone::deserialize(zone).map_err(E::custom))
+            .unwrap_or(Ok(DateZone::default()))
+    }
+
+    /// Rewrite the plain dates of a config as the exact times they stand for in its zone
+    pub fn resolve<E: de::Error>(fields: &mut Map<String, Value>) -> Result<(), E> {
+        let zone = zone_of(fields)?;
+        for (key, start) in FIELDS {
+            let Some(Value::String(text)) = fields.get(key) else {
+                continue;
+            };
+            let bound = if start { date::start_of } else { date::end_of };
+            let time = bound(text, zone).map_err(|e| E::custom(format!("invalid date '{}': {}", text, e)))?;
+            fields.insert(key.to_string(), Value::String(rfc3339(time)));
+        }
+        Ok(())
+    }
+
+    /// Rewrite the times of a config falling on the edge of a day in its zone as plain dates
+    pub fn simplify(fields: &mut Map<String, Value>) {
+        let Ok(zone) = zone_of::<serde_json::Error>(fields) else {
+            return;
+        };
+        for (key, start) in FIELDS {
+            let Some(time) = fields.get(key).and_then(Value::as_str).and_then(|text| date::start_of(text, zone).ok()) else {
+                continue;
+            };
+            let time = match zone {
+                DateZone::Local => DateTime::<Local>::from(time).naive_local(),
+                DateZone::Utc => DateTime::<Utc>::from(time).naive_utc(),
+            };
+            if time.time() == day_edge(start) {
+                fields.insert(key.to_string(), Value::String(time.format("%Y-%m-%d").to_string()));
+            }
+        }
+    }
+
+    fn rfc3339(time: SystemTime) -> String {
+        DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::AutoSi, true)
+    }
+
+    fn serialize<S: Serializer>(time: &Option<SystemTime>, serializer: S) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => serializer.serialize_some(&rfc3339(*time)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D, start: bool) -> Result<Option<SystemTime>, D::Error> {
+        let bound = if start { date::start_of } else { date::end_of };
+        Option::<String>::deserialize(deserializer)?
+            .map(|text| bound(&text, DateZone::Utc).map_err(|e| de::Error::custom(format!("invalid date '{}': {}", text, e))))
+            .transpose()
+    }
+
+    /// Lower bounds cover the whole of their day
+    pub mod start_of_day {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(time: &Option<SystemTime>, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize(time, serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<SystemTime>, D::Error> {
+            super::deserialize(deserializer, true)
+        }
+    }
+
+    /// Upper bounds also cover the whole of their day
+    pub mod end_of_day {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(time: &Option<SystemTime>, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize(time, serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<SystemTime>, D::Error> {
+            super::deserialize(deserializer, false)
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Small JSON file keeping state between runs, such as frecency scores
+///
+/// The contents start out as their default when the file does not exist yet, and
+/// are only written back by [`save`](Self::save). `what` names the contents in
+/// error messages.
+#[derive(Debug)]
+pub struct DataFile<T> {
+    location: PathBuf,
+    what: &'static str,
+    contents: T,
+}
+
+/// Location of the data file named `name` inside the user's data directory
+pub fn data_location(name: &str) -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("oqab").join(name))
+}
+
+impl<T: Serialize + DeserializeOwned + Default> DataFile<T> {
+    /// Open the file named `name` inside the user's data directory
+    pub fn open_default(name: &str, what: &'static str) -> Result<Self> {
+        let location = data_location(name)
+            .with_context(|| format!("No data directory available for the {}", what))?;
+        Self::open(location, what)
+    }
+
+    /// Open the file at `location`, starting empty if it does not exist yet
+    pub fn open<P: AsRef<Path>>(location: P, what: &'static str) -> Result<Self> {
+        let location = location.as_ref().to_path_buf();
+        let contents = match fs::read_to_string(&location) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}: {}", what, location.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => T::default(),
+            Err(e) => return Err(e)
+                .with_context(|| format!("Failed to read {}: {}", what, location.display())),
+        };
+
+        Ok(Self { location, what, contents })
+    }
+
+    /// Contents as last read or changed
+    pub fn contents(&self) -> &T {
+        &self.contents
+    }
+
+    /// Contents to change before saving
+    pub fn contents_mut(&mut self) -> &mut T {
+        &mut self.contents
+    }
+
+    /// Write the contents back to disk
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.location.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let serialized = serde_json::to_string(&self.contents)
+            .with_context(|| format!("Failed to serialize {}", self.what))?;
+        fs::write(&self.location, serialized)
+            .with_context(|| format!("Failed to write {}: {}", self.what, self.location.display()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::Result;
+use walkdir::WalkDir;
+
+use crate::core::data_file::{data_location, DataFile};
+use crate::core::Platform;
+use crate::core::traversal::is_hidden;
+
+/// Leaf directories a walk reaches before its size is estimated from their paths
+pub const SAMPLE_LEAVES: usize = 64;
+
+/// Name of the scan totals file inside the user's data directory
+const TOTALS_FILE_NAME: &str = "scan-totals.json";
+
+/// What the scan totals are called in error messages
+const TOTALS_WHAT: &str = "scan totals";
+
+/// Directory counts of earlier walks, by root, used to estimate later ones
+///
+/// Totals are approximate: they are recorded by whichever search last walked a
+/// root, with whatever depth limit it had.
+#[derive(Debug)]
+pub struct ScanTotals {
+    totals: DataFile<HashMap<PathBuf, usize>>,
+}
+
+impl ScanTotals {
+    /// Location of the totals inside the user's data directory
+    pub fn default_location() -> Option<PathBuf> {
+        data_location(TOTALS_FILE_NAME)
+    }
+
+    /// Open the totals kept in the user's data directory
+    pub fn open_default() -> Result<Self> {
+        Ok(Self { totals: DataFile::open_default(TOTALS_FILE_NAME, TOTALS_WHAT)? })
+    }
+
+    /// Open totals kept at `location`
+    pub fn open<P: AsRef<Path>>(location: P) -> Result<Self> {
+        Ok(Self { totals: DataFile::open(location, TOTALS_WHAT)? })
+    }
+
+    /// Number of directories last walked under a root
+    pub fn get(&self, root: &Path) -> Option<usize> {
+        self.totals.contents().get(&canonical(root)).copied()
+    }
+
+    /// Remember how many directories a walk of a root read
+    pub fn record(&mut self, root: &Path, directories: usize) {
+        self.totals.contents_mut().insert(canonical(root), directories);
+    }
+
+    /// Write the totals back to disk
+    pub fn save(&self) -> Result<()> {
+        self.totals.save()
+    }
+
+    /// Record a total in the default store
+    pub fn remember(root: &Path, directories: usize) -> Result<()> {
+        let mut totals = Self::open_default()?;
+        totals.record(root, directories);
+        totals.save()
+    }
+}
+
+// Roots are stored absolute so that totals apply whichever directory oqab runs from
+fn canonical(path: &Path) -> PathBuf {
+    Platform::path_key(path)
+}
+
+/// A path from the root of a walk, carrying the branching factors met along it
+///
+/// Following one path down a tree and multiplying the number of subdirectories
+/// at each level gives Knuth's unbiased estimate of the size of the tree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Probe {
+    width: f64,
+    size: f64,
+}
+
+impl Probe {
+    /// Probe standing at the root
+    pub fn root() -> Self {
+        Self { width: 1.0, size: 1.0 }
+    }
+
+    /// Probe for one of the subdirectories of the directory this probe stands at
+    pub fn descend(self, subdirectories: usize) -> Self {
+        let width = self.width * subdirectories as f64;
+        Self { width, size: self.size + width }
+    }
+}
+
+/// Estimates the number of directories in a tree from the paths to its first leaves
+#[derive(Debug, Clone, Default)]
+pub struct TreeSizeSampler {
+    samples: usize,
+    sum: f64,
+}
+
+impl TreeSizeSampler {
+    /// Record a probe that reached a directory without subdirectories
+    pub fn leaf(&mut self, probe: Probe) {
+        if self.samples < SAMPLE_LEAVES {
+            self.samples += 1;
+            self.sum += probe.size;
+        }
+    }
+
+    /// Average estimate of the probes, once enough leaves were reached
+    pub fn estimate(&self) -> Option<usize> {
+        (self.samples >= SAMPLE_LEAVES).then(|| (self.sum / self.samples as f64).round() as usize)
+    }
+}
+
+/// How far a running walk has got
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Share of directories read, below 1 until the walk ends
+    pub fraction: f64,
+    /// Time left at the pace so far
+    pub remaining: Duration,
+}
+
+impl Progress {
+    /// Work out progress from the directories read and queued and the expected total
+    pub fn from_counts(read: usize, queued: usize, expected_total: usize, elapsed: Duration) -> Option<Self> {
+        if read == 0 {
+            return None;
+        }
+        // The tree may be larger than expected
+        let total = expected_total.max(read + queued) as f64;
+        let read = read as f64;
+        Some(Self {
+            fraction: (read / total).min(0.99),
+            remaining: elapsed.mul_f64((total - read) / read),
+        })
+    }
+}
+
+/// Count the directories a recursive walk of `root` would read, for an exact progress total
+///
+/// Hidden directories are left out unless `hidden` is set, as the search does. The
+/// count can still be a little high, since the search also skips directories such as
+/// `node_modules` and those excluded by ignore files.
+pub fn count_directories(root: &Path, hidden: bool, follow_links: bool) -> usize {
+    WalkDir::new(root)
+        .follow_links(follow_links)
+        .into_iter()
+        .filter_entry(|entry| hidden || entry.depth() == 0 || !is_hidden(entry.path()))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_dir())
+        .count()
+}
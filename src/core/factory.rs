@@ -1,14 +1,20 @@
 use crate::{
     core::{
         builder::FileFinderBuilder,
-        config::AppConfig,
+        config::{AppConfig, FileSearchConfig},
+        estimate::count_directories,
         finder::{FinderConfig, FileFinder},
-        observer::NullObserver,
+        observer::{progress_observer, TrackingObserver},
         registry::ObserverRegistry,
-        traversal::{DefaultTraversalStrategy, RegexTraversalStrategy, TraversalStrategy},
+        traversal::{CompositeTraversalStrategy, DefaultTraversalStrategy, GitAwareTraversalStrategy, IgnoreFileTraversalStrategy, RegexTraversalStrategy, TraversalStrategy},
     },
-    filters::{ExtensionFilter, NameFilter, RegexFilter, SizeFilter, date::DateFilter},
+    filters::{ContentFilter, DepthFilter, EmptyFilter, ExtensionFilter, GitFilter, GlobFilter, IgnoreFilter, LinksFilter, MimeFilter, NameFilter, PruneFilter, SameFileSystemFilter, TypeFilter, TypeGroupFilter, RegexFilter, SizeFilter, Timestamp, date::DateFilter},
 };
+use anyhow::{bail, Result};
+use log::warn;
+use std::fmt::Display;
+use std::io::{self, IsTerminal};
+use std::sync::Arc;
 
 /// Factory for creating pre-configured FileFinder instances
 pub struct FinderFactory;
@@ -16,65 +22,58 @@ pub struct FinderFactory;
 impl FinderFactory {
     /// Create a new finder for standard search
     pub fn create_standard_finder(config: &AppConfig) -> FileFinder {
-        let observer_registry = ObserverRegistry::new();
-        observer_registry.register(NullObserver);
+        Self::apply_filters(Self::standard_builder(config), config, false)
+            .expect("filters are skipped rather than failing when not strict")
+            .build()
+    }
 
-        let mut builder = FileFinderBuilder::new()
-            .with_threads(config.threads.unwrap_or_else(num_cpus::get))
-            .with_follow_links(config.follow_links.unwrap_or(false))
-            .with_traversal_strategy(Box::new(DefaultTraversalStrategy::new(true)));
+    /// Create a finder applying every file criterion of a search configuration
+    ///
+    /// Saves converting to `AppConfig` by hand. Unlike `create_standard_finder`, a
+    /// criterion that cannot be applied, such as an invalid glob, is an error
+    /// rather than being skipped with a warning.
+    pub fn from_search_config(config: &FileSearchConfig) -> Result<FileFinder> {
+        Ok(Self::builder_from_search_config(config)?.build())
+    }
 
-        // Add extension filter if specified
-        if let Some(ref ext) = config.extension {
-            builder = builder.with_filter("extension", ExtensionFilter::new(ext));
-        }
+    /// Builder set up like [`from_search_config`](Self::from_search_config), left
+    /// open so that embedders can add their own traversal strategy, filters or
+    /// observers before building
+    pub fn builder_from_search_config(config: &FileSearchConfig) -> Result<FileFinderBuilder> {
+        let app_config = AppConfig::from(config);
+        Self::apply_filters(Self::standard_builder(&app_config), &app_config, true)
+    }
 
-        // Add name filter if specified
-        if let Some(ref name) = config.name {
-            builder = builder.with_filter("name", NameFilter::new(name));
+    // Builder with the standard traversal and observers, before any filters
+    fn standard_builder(config: &AppConfig) -> FileFinderBuilder {
+        // The tracking observer collects results and reports metrics and path errors
+        let observer_registry = ObserverRegistry::new();
+        observer_registry.register(TrackingObserver::new());
+        // Draws the status line or progress bar for people watching a terminal
+        if config.show_progress.unwrap_or(false) && io::stderr().is_terminal() {
+            let total = (config.prescan.unwrap_or(false) && config.depth.is_none()).then(|| {
+                count_directories(&config.root_dir, config.hidden.unwrap_or(false), config.follow_links.unwrap_or(false))
+            });
+            let display = config.progress.unwrap_or_default();
+            observer_registry.register_arc(Arc::from(progress_observer(display, &config.root_dir, total)));
         }
 
-        // Add regex pattern filter if specified
-        if let Some(ref pattern) = config.pattern {
-            if let Ok(filter) = RegexFilter::new(pattern) {
-                builder = builder.with_filter("pattern", filter);
-            }
-        }
+        let builder = FileFinderBuilder::new()
+            .with_threads(config.threads.unwrap_or_else(num_cpus::get))
+            .with_follow_links(config.follow_links.unwrap_or(false))
+            .with_traversal_strategy(Box::new(DefaultTraversalStrategy::new(!config.hidden.unwrap_or(false))))
+            .with_observer_registry(observer_registry);
+        Self::with_ignore_files(builder, config)
+    }
 
-        // Add size filter if specified
-        if let Some(size) = config.size {
-            builder = builder.with_filter("size", SizeFilter::min(size));
+    // Add the strategies applying .oqabignore files and, if asked for, what git ignores
+    fn with_ignore_files(builder: FileFinderBuilder, config: &AppConfig) -> FileFinderBuilder {
+        let builder = builder.with_additional_traversal_strategy(Box::new(IgnoreFileTraversalStrategy::new(&config.root_dir)));
+        if config.git_ignore.unwrap_or(false) {
+            builder.with_additional_traversal_strategy(Box::new(GitAwareTraversalStrategy::new(&config.root_dir)))
         } else {
-            // Add min size filter if specified
-            if let Some(min_size) = config.min_size {
-                builder = builder.with_filter("min_size", SizeFilter::min(min_size));
-            }
-            
-            // Add max size filter if specified
-            if let Some(max_size) = config.max_size {
-                builder = builder.with_filter("max_size", SizeFilter::max(max_size));
-            }
-        }
-        
-        // Add date filters if specified
-        if let Some(ref newer_than) = config.newer_than {
-            if let Ok(filter) = DateFilter::newer_than(newer_than) {
-                builder = builder.with_filter("newer_than", filter);
-            }
-        }
-        
-        if let Some(ref older_than) = config.older_than {
-            if let Ok(filter) = DateFilter::older_than(older_than) {
-                builder = builder.with_filter("older_than", filter);
-            }
+            builder
         }
-
-        // Set maximum depth if specified
-        if let Some(depth) = config.depth {
-            builder = builder.with_max_depth(depth);
-        }
-
-        builder.build()
     }
 
     /// Create a new finder for advanced search with regex patterns
@@ -83,81 +82,271 @@ impl FinderFactory {
         include_pattern: Option<&str>,
         exclude_pattern: Option<&str>,
     ) -> Result<FileFinder, regex::Error> {
+        let hidden_strategy = DefaultTraversalStrategy::new(!config.hidden.unwrap_or(false));
         let traversal_strategy: Box<dyn TraversalStrategy + 'static> = if include_pattern.is_some() || exclude_pattern.is_some() {
-            Box::new(RegexTraversalStrategy::new(include_pattern, exclude_pattern)?)
+            // Hidden entries are skipped the same way whether or not patterns are given
+            Box::new(CompositeTraversalStrategy::new(vec![
+                Box::new(hidden_strategy),
+                Box::new(RegexTraversalStrategy::new(include_pattern, exclude_pattern)?),
+            ]))
         } else {
-            Box::new(DefaultTraversalStrategy::new(true))
+            Box::new(hidden_strategy)
         };
 
+        // The tracking observer collects results and reports metrics and path errors
         let observer_registry = ObserverRegistry::new();
-        observer_registry.register(NullObserver);
+        observer_registry.register(TrackingObserver::new());
 
-        let mut builder = FileFinderBuilder::new()
+        let builder = FileFinderBuilder::new()
             .with_threads(config.threads.unwrap_or_else(num_cpus::get))
             .with_follow_links(config.follow_links.unwrap_or(false))
-            .with_traversal_strategy(traversal_strategy);
+            .with_traversal_strategy(traversal_strategy)
+            .with_observer_registry(observer_registry);
+
+        Ok(Self::apply_filters(Self::with_ignore_files(builder, config), config, false)
+            .expect("filters are skipped rather than failing when not strict")
+            .build())
+    }
+
+    /// Create a custom finder with the specified configuration
+    pub fn create_custom_finder(
+        config: FinderConfig,
+        traversal_strategy: Box<dyn TraversalStrategy + 'static>,
+    ) -> FileFinder {
+        FileFinderBuilder::new()
+            .with_config(config)
+            .with_traversal_strategy(traversal_strategy)
+            .build()
+    }
+
+    /// Register every filter described by the configuration on the builder
+    ///
+    /// Criteria that cannot be applied fail the build when `strict` is set and are
+    /// skipped with a warning otherwise, except git selections, which then match
+    /// nothing rather than every file.
+    fn apply_filters(mut builder: FileFinderBuilder, config: &AppConfig, strict: bool) -> Result<FileFinderBuilder> {
+        let skip = |criterion: &str, error: &dyn Display| -> Result<()> {
+            if strict {
+                bail!("Cannot apply {}: {}", criterion, error);
+            }
+            warn!("Ignoring {}: {}", criterion, error);
+            Ok(())
+        };
 
         // Add extension filter if specified
         if let Some(ref ext) = config.extension {
-            builder = builder.with_filter("extension", ExtensionFilter::new(ext));
+            let filter = ExtensionFilter::new(ext)
+                .with_case_sensitive(config.case_sensitive_extension.unwrap_or(false));
+            builder = builder.with_filter("extension", filter);
+        }
+
+        // Add type group filter if specified
+        if let Some(ref groups) = config.type_group {
+            match TypeGroupFilter::with_custom(groups, &config.type_groups) {
+                Ok(filter) => builder = builder.with_filter("type_group", filter),
+                Err(e) => skip("--type-group", &e)?,
+            }
+        }
+
+        // Recognise files by their content
+        if let Some(ref types) = config.mime {
+            builder = builder.with_filter("mime", MimeFilter::new(types));
+        }
+
+        // Tell text files from binary ones by their content
+        if let Some(kind) = config.content_kind {
+            builder = builder.with_filter("content", ContentFilter::new(kind));
+        }
+
+        // Report other kinds of entries than regular files if asked to
+        if !config.entry_types.is_empty() {
+            builder = builder.with_types(TypeFilter::new(config.entry_types.iter().copied()));
         }
 
         // Add name filter if specified
         if let Some(ref name) = config.name {
-            builder = builder.with_filter("name", NameFilter::new(name));
+            builder = builder.with_filter("name", NameFilter::contains(name).with_full_path(config.full_path.unwrap_or(false)));
+        }
+
+        // Add glob filter if specified
+        if let Some(ref glob) = config.name_glob {
+            match GlobFilter::new(glob) {
+                Ok(filter) => builder = builder.with_filter("glob", filter),
+                Err(e) => skip(&format!("glob '{}'", glob), &e)?,
+            }
+        }
+
+        // Add file name regex filter if specified
+        if let Some(ref name_regex) = config.name_regex {
+            match RegexFilter::file_name_with_syntax(name_regex, config.regex_syntax.unwrap_or_default()) {
+                Ok(filter) => builder = builder.with_filter("name_regex", filter.with_full_path(config.full_path.unwrap_or(false))),
+                Err(e) => skip(&format!("name regex '{}'", name_regex), &e)?,
+            }
         }
 
         // Add regex pattern filter if specified
         if let Some(ref pattern) = config.pattern {
-            if let Ok(filter) = RegexFilter::new(pattern) {
-                builder = builder.with_filter("pattern", filter);
+            match RegexFilter::with_syntax(pattern, config.regex_syntax.unwrap_or_default()) {
+                Ok(filter) => builder = builder.with_filter("pattern", filter),
+                Err(e) => skip(&format!("pattern '{}'", pattern), &e)?,
             }
         }
 
         // Add size filter if specified
+        let disk_size = config.disk_size.unwrap_or(false);
         if let Some(size) = config.size {
-            builder = builder.with_filter("size", SizeFilter::min(size));
+            builder = builder.with_filter("size", SizeFilter::min(size).with_disk_size(disk_size));
         } else {
             // Add min size filter if specified
             if let Some(min_size) = config.min_size {
-                builder = builder.with_filter("min_size", SizeFilter::min(min_size));
+                builder = builder.with_filter("min_size", SizeFilter::min(min_size).with_disk_size(disk_size));
             }
             
             // Add max size filter if specified
             if let Some(max_size) = config.max_size {
-                builder = builder.with_filter("max_size", SizeFilter::max(max_size));
+                builder = builder.with_filter("max_size", SizeFilter::max(max_size).with_disk_size(disk_size));
             }
         }
         
-        // Add date filters if specified
-        if let Some(ref newer_than) = config.newer_than {
-            if let Ok(filter) = DateFilter::newer_than(newer_than) {
-                builder = builder.with_filter("newer_than", filter);
-            }
+        // Only empty files and directories
+        if config.empty.unwrap_or(false) {
+            builder = builder.with_filter("empty", EmptyFilter::new());
+        }
+        
+        // Files with a number of hard links, where an exact count makes a minimum moot
+        if let Some(count) = config.links {
+            builder = builder.with_filter("links", LinksFilter::exactly(count));
+        } else if let Some(count) = config.min_links {
+            builder = builder.with_filter("links", LinksFilter::at_least(count));
         }
         
-        if let Some(ref older_than) = config.older_than {
-            if let Ok(filter) = DateFilter::older_than(older_than) {
-                builder = builder.with_filter("older_than", filter);
+        // Add date filter if either bound is specified
+        if config.newer_than.is_some() || config.older_than.is_some() {
+            builder = builder.with_filter("date", DateFilter::between(config.newer_than, config.older_than));
+        }
+
+        // Compare creation and access times the same way, where they are recorded
+        if config.created_after.is_some() || config.created_before.is_some() {
+            let filter = DateFilter::between(config.created_after, config.created_before).with_timestamp(Timestamp::Created);
+            builder = builder.with_filter("created", filter);
+        }
+        if config.accessed_after.is_some() || config.accessed_before.is_some() {
+            let filter = DateFilter::between(config.accessed_after, config.accessed_before).with_timestamp(Timestamp::Accessed);
+            builder = builder.with_filter("accessed", filter);
+        }
+
+        // Compare against the modification times of reference files
+        if config.newer_than_file.is_some() || config.older_than_file.is_some() {
+            match DateFilter::between_files(config.newer_than_file.as_deref(), config.older_than_file.as_deref()) {
+                Ok(filter) => builder = builder.with_filter("reference_date", filter),
+                Err(e) => skip("reference file", &e)?,
             }
         }
 
-        // Set maximum depth if specified
+        // Set maximum depth if specified, and report only entries within the depth limits
         if let Some(depth) = config.depth {
             builder = builder.with_max_depth(depth);
         }
+        if config.depth.is_some() || config.min_depth.is_some() {
+            builder = builder.with_filter("depth", DepthFilter::new(&config.root_dir, config.min_depth, config.depth));
+        }
+        
+        // Stop examining files in a directory once it has enough matches
+        if let Some(limit) = config.max_per_dir {
+            builder = builder.with_max_per_dir(limit);
+        }
+        
+        // Abandon the walk once enough matches are found
+        if let Some(limit) = config.max_results {
+            builder = builder.with_max_results(limit);
+        }
+        
+        // Report each file once however many paths lead to it
+        builder = builder.with_unique(config.unique.unwrap_or(false));
+        builder = builder.with_no_messages(config.no_messages.unwrap_or(false));
+        builder = builder.with_walker(config.walker.unwrap_or_default());
+        
+        // Never leave the filesystem the search started on
+        if config.one_file_system.unwrap_or(false) {
+            builder = builder.with_one_file_system(true);
+            match SameFileSystemFilter::new(&config.root_dir) {
+                Ok(filter) => builder = builder.with_filter("one_file_system", filter),
+                Err(e) => skip("--one-file-system", &e)?,
+            }
+        }
+        
+        // Skip dependency, build and version control directories unless asked not to
+        if !config.no_default_excludes.unwrap_or(false) {
+            builder = builder.with_filter("default_excludes", PruneFilter::default_excludes());
+        }
+        
+        // Skip what the user excluded, never entering excluded directories
+        if !config.exclude.is_empty() {
+            match IgnoreFilter::new(&config.root_dir, &config.exclude) {
+                Ok(filter) => builder = builder.with_filter("exclude", filter),
+                Err(e) => skip("--exclude", &e)?,
+            }
+        }
+        
+        // Apply the patterns of the global ignore file
+        if !config.global_ignore.is_empty() {
+            match IgnoreFilter::new(&config.root_dir, &config.global_ignore) {
+                Ok(filter) => builder = builder.with_filter("global_ignore", filter),
+                Err(e) => skip("global ignore file", &e)?,
+            }
+        }
+        
+        // Restrict candidates to files known to git if requested
+        let git = if config.git_modified.unwrap_or(false) {
+            Some(("--git-modified", GitFilter::modified(&config.root_dir)))
+        } else if config.git_tracked.unwrap_or(false) {
+            Some(("--git-tracked", GitFilter::tracked(&config.root_dir)))
+        } else {
+            None
+        };
+        if let Some((criterion, filter)) = git {
+            let filter = match filter {
+                Ok(filter) => filter,
+                Err(e) if strict => bail!("Cannot apply {}: {}", criterion, e),
+                // Skipping a selection would report every file instead of the selected ones
+                Err(e) => {
+                    warn!("Matching no files for {}: {}", criterion, e);
+                    GitFilter::none()
+                }
+            };
+            builder = builder.with_filter("git", filter);
+        }
 
-        Ok(builder.build())
-    }
+        // Match photos and recordings by their embedded metadata
+        #[cfg(feature = "media")]
+        if config.exif_date_after.is_some() || config.exif_date_before.is_some()
+            || config.camera_model.is_some() || config.artist.is_some()
+        {
+            let mut filter = crate::filters::MediaFilter::new();
+            if let Some(after) = config.exif_date_after {
+                filter = filter.taken_after(after);
+            }
+            if let Some(before) = config.exif_date_before {
+                filter = filter.taken_before(before);
+            }
+            if let Some(ref model) = config.camera_model {
+                filter = filter.camera_model(model);
+            }
+            if let Some(ref artist) = config.artist {
+                filter = filter.artist(artist);
+            }
+            builder = builder.with_filter("media", filter);
+        }
 
-    /// Create a custom finder with the specified configuration
-    pub fn create_custom_finder(
-        config: FinderConfig,
-        traversal_strategy: Box<dyn TraversalStrategy + 'static>,
-    ) -> FileFinder {
-        FileFinderBuilder::new()
-            .with_config(config)
-            .with_traversal_strategy(traversal_strategy)
-            .build()
+        // Let a user script select files
+        #[cfg(feature = "scripting")]
+        if let Some(ref script) = config.script {
+            match crate::filters::Script::load(script) {
+                Ok(script) => builder = builder.with_filter("script", crate::filters::ScriptFilter::new(std::sync::Arc::new(script))),
+                Err(e) => skip("--script", &format!("{:#}", e))?,
+            }
+        }
+
+        Ok(builder)
     }
-} 
\ No newline at end of file
+}
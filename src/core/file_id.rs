@@ -0,0 +1,61 @@
+use std::io;
+use std::path::Path;
+
+/// Identity of a file on disk, shared by every path and hard link that reaches it
+///
+/// Made of the device and inode numbers on Unix, and the volume serial number and
+/// file index on Windows. Two paths name the same file exactly when their ids are
+/// equal, which path comparison cannot tell through symbolic links, hard links or
+/// names differing in case. Ids are only meaningful while the file exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId {
+    volume: u64,
+    index: u64,
+}
+
+impl FileId {
+    /// Identity of the file a path resolves to, following symbolic links
+    pub fn of(path: &Path) -> io::Result<Self> {
+        file_id(path)
+    }
+
+    /// Identity of the device or volume holding the file
+    pub fn volume(&self) -> u64 {
+        self.volume
+    }
+}
+
+#[cfg(unix)]
+fn file_id(path: &Path) -> io::Result<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = std::fs::metadata(path)?;
+    Ok(FileId { volume: metadata.dev(), index: metadata.ino() })
+}
+
+#[cfg(windows)]
+fn file_id(path: &Path) -> io::Result<FileId> {
+    use std::fs::OpenOptions;
+    use std::os::windows::{fs::OpenOptionsExt, io::AsRawHandle};
+    use windows_sys::Win32::Storage::FileSystem::{GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION, FILE_FLAG_BACKUP_SEMANTICS};
+
+    // Directories can only be opened with backup semantics
+    let file = OpenOptions::new()
+        .read(true)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)?;
+    // SAFETY: the structure is plain data, for which all zeroes is a valid value
+    let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+    // SAFETY: the handle stays open for the duration of the call and `info` is writable
+    if unsafe { GetFileInformationByHandle(file.as_raw_handle() as _, &mut info) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(FileId {
+        volume: u64::from(info.dwVolumeSerialNumber),
+        index: (u64::from(info.nFileIndexHigh) << 32) | u64::from(info.nFileIndexLow),
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+fn file_id(_path: &Path) -> io::Result<FileId> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "file identities are not available on this platform"))
+}
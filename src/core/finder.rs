@@ -1,18 +1,25 @@
 use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{atomic::{AtomicUsize, Ordering}, mpsc::{self, Receiver, RecvTimeoutError}, Arc, Mutex},
     io,
+    thread,
+    time::{Duration, Instant},
 };
-use log::{debug, error, warn};
+use log::{debug, error, info, warn};
 use anyhow::{Context, Result};
 use crate::{
     core::{
+        config::Walker,
         registry::{FilterRegistry, ObserverRegistry},
         traversal::TraversalStrategy,
-        worker::WorkerPool,
-        observer::TrackingObserver,
+        file_id::FileId,
+        worker::{WorkerPool, WorkerStats},
+        estimate::{Probe, TreeSizeSampler},
+    observer::{Heartbeat, PathError, SearchEvent, SearchObserver, StreamingObserver, TrackingObserver, HEARTBEAT_INTERVAL},
     },
-    filters::FilterResult,
+    filters::{FilterResult, TypeFilter},
 };
 #[derive(Debug, thiserror::Error)]
 pub enum FinderError {
@@ -23,18 +30,75 @@ pub enum FinderError {
     #[error("Worker pool error: {0}")]
     WorkerPool(String),
 }
+/// A file accepted by the finder's filters
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileMatch {
+    pub path: PathBuf,
+}
+impl From<PathBuf> for FileMatch {
+    fn from(path: PathBuf) -> Self {
+        FileMatch { path }
+    }
+}
+/// Summary of a finished search, returned with its matches
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchReport {
+    /// Number of files accepted by the filters
+    pub files_matched: usize,
+    /// Number of directories read
+    pub directories_scanned: usize,
+    /// Paths that could not be read
+    pub errors: Vec<PathError>,
+    /// Directories that were not entered, such as unfollowed symbolic links
+    pub skipped: Vec<PathBuf>,
+    /// Wall-clock time the search took
+    pub elapsed: Duration,
+    /// What each worker thread did; empty for single-threaded searches
+    pub workers: Vec<WorkerStats>,
+}
+impl SearchReport {
+    /// Build a report from what an observer saw during a search
+    pub fn from_observer(observer: &dyn SearchObserver, elapsed: Duration) -> Self {
+        SearchReport {
+            files_matched: observer.files_count(),
+            directories_scanned: observer.directories_count(),
+            errors: observer.path_errors(),
+            skipped: observer.skipped_paths(),
+            elapsed,
+            workers: Vec::new(),
+        }
+    }
+    /// Whether every path in the tree could be read
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
 #[derive(Debug, Clone)]
 pub struct FinderConfig {
     pub num_threads: usize,
     pub follow_links: bool,
+    pub one_file_system: bool,
     pub max_depth: Option<usize>,
+    pub no_messages: bool,
+    pub max_per_dir: Option<usize>,
+    pub max_results: Option<usize>,
+    pub unique: bool,
+    pub types: TypeFilter,
+    pub walker: Walker,
 }
 impl Default for FinderConfig {
     fn default() -> Self {
         FinderConfig {
             num_threads: num_cpus::get(),
             follow_links: false,
+            one_file_system: false,
             max_depth: None,
+            no_messages: false,
+            max_per_dir: None,
+            max_results: None,
+            unique: false,
+            types: TypeFilter::files(),
+            walker: Walker::default(),
         }
     }
 }
@@ -58,11 +122,65 @@ impl FileFinder {
             observer_registry,
         }
     }
-    pub fn find(&self, root_dir: &Path) -> Result<Vec<PathBuf>> {
-    // Main file search method
-        let traversal = Arc::clone(&self.traversal_strategy);
-        let filters = Arc::clone(&self.filter_registry);
-        let observers = Arc::clone(&self.observer_registry);
+    /// Search a directory tree and report how the search went
+    ///
+    /// Every registered observer still sees the run, but callers get the counts,
+    /// unreadable paths and skipped directories from the returned report instead.
+    pub fn find(&self, root_dir: &Path) -> Result<(Vec<FileMatch>, SearchReport)> {
+        Self::validate_root(root_dir)?;
+        let start = Instant::now();
+        
+        // A fresh observer per run keeps the report from mixing in earlier searches
+        let tracker = Arc::new(TrackingObserver::new());
+        let observers = (*self.observer_registry).clone();
+        observers.register_arc(tracker.clone());
+        let finder = FileFinder::new(
+            self.config.clone(),
+            Arc::clone(&self.traversal_strategy),
+            Arc::clone(&self.filter_registry),
+            Arc::new(observers),
+        );
+        let workers = finder.walk(root_dir);
+        
+        let matches: Vec<FileMatch> = tracker.lock_found_files()?
+            .iter()
+            .cloned()
+            .map(FileMatch::from)
+            .collect();
+        let report = SearchReport {
+            workers,
+            ..SearchReport::from_observer(&*tracker, start.elapsed())
+        };
+        debug!("Found {} matching files in {:?}", matches.len(), report.elapsed);
+        Ok((matches, report))
+    }
+    /// Stream matches over a bounded channel while the search is still running
+    ///
+    /// The walk runs on a background thread and blocks whenever `capacity` matches are
+    /// waiting, so a slow consumer applies backpressure instead of buffering everything.
+    /// The channel closes when the walk is complete; the finder's own observers still
+    /// see every event, so metrics are available from them afterwards.
+    pub fn find_streaming(&self, root_dir: &Path, capacity: usize) -> Result<Receiver<FileMatch>> {
+        Self::validate_root(root_dir)?;
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        
+        let observers = (*self.observer_registry).clone();
+        observers.register(StreamingObserver::new(sender));
+        let finder = FileFinder::new(
+            self.config.clone(),
+            Arc::clone(&self.traversal_strategy),
+            Arc::clone(&self.filter_registry),
+            Arc::new(observers),
+        );
+        let root_dir = root_dir.to_path_buf();
+        thread::Builder::new()
+            .name("oqab-finder".to_string())
+            .spawn(move || { finder.walk(&root_dir); })
+            .context("Failed to start search thread")?;
+        
+        Ok(receiver)
+    }
+    fn validate_root(root_dir: &Path) -> Result<()> {
         if !root_dir.exists() {
             return Err(FinderError::InvalidPath(format!(
                 "Root directory does not exist: {}",
@@ -75,7 +193,59 @@ impl FileFinder {
                 root_dir.display()
             )).into());
         }
+        Ok(())
+    }
+    // Walk the tree, reporting matches and periodic heartbeats to the observers
+    fn walk(&self, root_dir: &Path) -> Vec<WorkerStats> {
+        let progress = Arc::new(WalkProgress::new(root_dir, self.config.max_results, self.config.unique));
+        let (stop, stopped) = mpsc::channel::<()>();
+        let heartbeat = {
+            let progress = Arc::clone(&progress);
+            let observers = Arc::clone(&self.observer_registry);
+            thread::Builder::new()
+                .name("oqab-heartbeat".to_string())
+                .spawn(move || {
+                    // Beat until the walk hangs up its end of the channel
+                    while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(HEARTBEAT_INTERVAL) {
+                        observers.publish(SearchEvent::Heartbeat(&progress.heartbeat()));
+                    }
+                })
+                .inspect_err(|e| warn!("Failed to start heartbeat thread: {}", e))
+                .ok()
+        };
+        
+        let start = Instant::now();
+        let workers = self.walk_tree(root_dir, &progress);
+        if progress.finished() {
+            debug!("Stopped the search after {} matches", progress.found());
+        }
+        
+        drop(stop);
+        if let Some(heartbeat) = heartbeat {
+            let _ = heartbeat.join();
+        }
+        if !workers.is_empty() {
+            self.observer_registry.publish(SearchEvent::Workers(&workers));
+        }
+        self.observer_registry.publish(SearchEvent::Completed(&self.observer_registry.stats(start.elapsed())));
+        workers
+    }
+    fn walk_tree(&self, root_dir: &Path, progress: &Arc<WalkProgress>) -> Vec<WorkerStats> {
+        let traversal = Arc::clone(&self.traversal_strategy);
+        let filters = Arc::clone(&self.filter_registry);
+        let observers = Arc::clone(&self.observer_registry);
         debug!("Searching in {}", root_dir.display());
+        match self.config.walker {
+            Walker::Builtin => {}
+            Walker::Walkdir => {
+                self.walk_with_walkdir(root_dir, progress);
+                return Vec::new();
+            }
+            Walker::Ignore => {
+                self.walk_with_ignore(root_dir, progress);
+                return Vec::new();
+            }
+        }
         if self.config.num_threads <= 1 {
             debug!("Using single-threaded mode");
             let mut current_depth = Vec::new();
@@ -85,10 +255,14 @@ impl FileFinder {
                 &filters,
                 &observers,
                 &self.config,
+                progress,
+                Probe::root(),
                 &mut current_depth,
+                &mut Vec::new(),
             ) {
                 warn!("Error processing directory: {}", e);
             }
+            Vec::new()
         } else {
             debug!("Using {} worker threads", self.config.num_threads);
             let worker_pool = WorkerPool::new(
@@ -98,6 +272,7 @@ impl FileFinder {
                     let filters = Arc::clone(&filters);
                     let observers = Arc::clone(&observers);
                     let config = self.config.clone();
+                    let progress = Arc::clone(progress);
                     move |dir_path| {
                         let mut current_depth = Vec::new();
                         if let Err(e) = process_directory(
@@ -106,7 +281,10 @@ impl FileFinder {
                             &filters,
                             &observers,
                             &config,
+                            &progress,
+                            Probe::root(),
                             &mut current_depth,
+                            &mut Vec::new(),
                         ) {
                             error!("Failed to process {}: {}", dir_path.display(), e);
                         }
@@ -115,9 +293,10 @@ impl FileFinder {
                 {
                     let filters = Arc::clone(&filters);
                     let observers = Arc::clone(&observers);
+                    let progress = Arc::clone(progress);
                     move |file_path| {
-                        if filters.apply_all(&file_path) == FilterResult::Accept {
-                            observers.notify_file_found(&file_path);
+                        if filters.apply_all_to_entry(&file_path) == FilterResult::Accept {
+                            report_match(&file_path, &progress, &observers);
                         }
                     }
                 },
@@ -126,121 +305,157 @@ impl FileFinder {
                 warn!("Failed to submit directory to worker pool");
             }
             worker_pool.complete();
-            worker_pool.join();
-        }
-        if let Some(tracking_observer) = Self::find_tracking_observer(&observers) {
-            match tracking_observer.lock_found_files() {
-                Ok(files_guard) => {
-                    let mut result = Vec::with_capacity(files_guard.len());
-                    for path in files_guard.iter() {
-                        result.push(path.clone());
-                    }
-                    debug!("Found {} matching files", result.len());
-                    Ok(result)
-                },
-                Err(e) => {
-                    warn!("Failed to lock found files: {}", e);
-                    #[allow(deprecated)]
-                    let files = tracking_observer.get_found_files();
-                    debug!("Using fallback method - found {} files", files.len());
-                    Ok(files)
-                }
-            }
-        } else {
-            debug!("No tracking observer found, using direct collection");
-            let mut results = Vec::new();
-            if let Err(e) = Self::collect_files_direct(
-                root_dir,
-                &*traversal,
-                &filters,
-                &mut results,
-                self.config.max_depth.unwrap_or(usize::MAX),
-                0
-            ) {
-                warn!("Direct collection error: {}", e);
+            let workers = worker_pool.join();
+            for worker in &workers {
+                debug!("Worker {}: {} directories, {} files, busy {:?}, idle {:?}, waiting for queues {:?}",
+                    worker.id, worker.directories, worker.files, worker.busy, worker.idle, worker.queue_wait);
             }
-            debug!("Found {} matching files", results.len());
-            Ok(results)
+            workers
         }
     }
-    fn find_tracking_observer(observer_registry: &ObserverRegistry) -> Option<Arc<TrackingObserver>> {
-        observer_registry.get_observer_of_type::<TrackingObserver>()
-    }
-    fn collect_files_direct(
-        dir: &Path,
-        traversal: &dyn TraversalStrategy,
-        filters: &FilterRegistry,
-        results: &mut Vec<PathBuf>,
-        max_depth: usize,
-        current_depth: usize,
-    ) -> Result<()> {
-        if current_depth >= max_depth || !traversal.should_process_directory(dir) {
-            return Ok(());
+    // Walk with the walkdir crate, one directory at a time
+    fn walk_with_walkdir(&self, root_dir: &Path, progress: &WalkProgress) {
+        debug!("Using the walkdir walker");
+        let visitor = EntryVisitor::new(self, progress);
+        let mut walker = walkdir::WalkDir::new(root_dir)
+            .follow_links(self.config.follow_links)
+            .same_file_system(self.config.one_file_system);
+        if let Some(max_depth) = self.config.max_depth {
+            walker = walker.max_depth(max_depth);
         }
-        let entries = std::fs::read_dir(dir)
-            .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
-        for entry_result in entries {
-            let entry = match entry_result {
-                Ok(entry) => entry,
-                Err(e) => {
-                    warn!("Failed to read directory entry: {}", e);
-                    continue;
-                }
-            };
-            let path = entry.path();
-            let file_type = match entry.file_type() {
-                Ok(ft) => ft,
+        let entries = walker
+            .into_iter()
+            .filter_entry(|entry| !entry.file_type().is_dir() || visitor.enter(entry.path(), entry.depth()));
+        for entry in entries {
+            if progress.finished() {
+                break;
+            }
+            match entry {
+                Ok(entry) if entry.depth() > 0 => visitor.entry(entry.path(), &entry.file_type()),
+                Ok(_) => {}
                 Err(e) => {
-                    warn!("Failed to determine file type for {}: {}", path.display(), e);
-                    continue;
-                }
-            };
-            if file_type.is_dir() {
-                if let Err(e) = Self::collect_files_direct(
-                    &path,
-                    traversal,
-                    filters,
-                    results,
-                    max_depth,
-                    current_depth + 1,
-                ) {
-                    warn!("Error collecting files in subdirectory {}: {}", path.display(), e);
+                    let path = e.path().unwrap_or(root_dir).to_path_buf();
+                    if e.loop_ancestor().is_some() {
+                        visitor.cycle(&path);
+                    } else {
+                        let error = e.into_io_error().unwrap_or_else(|| io::Error::other("unknown walkdir error"));
+                        report_path_error(&self.observer_registry, &self.config, &path, &error);
+                    }
                 }
-            } else if file_type.is_file() && traversal.should_process_file(&path) && filters.apply_all(&path) == FilterResult::Accept {
-                results.push(path);
             }
         }
-        Ok(())
+    }
+    // Walk with the ignore crate's parallel walker, with all of its own filtering turned off
+    fn walk_with_ignore(&self, root_dir: &Path, progress: &WalkProgress) {
+        debug!("Using the ignore walker with {} threads", self.config.num_threads);
+        let visitor = EntryVisitor::new(self, progress);
+        ignore::WalkBuilder::new(root_dir)
+            .standard_filters(false)
+            .follow_links(self.config.follow_links)
+            .same_file_system(self.config.one_file_system)
+            .max_depth(self.config.max_depth)
+            .threads(self.config.num_threads)
+            .build_parallel()
+            .run(|| Box::new(|entry| {
+                if progress.finished() {
+                    return ignore::WalkState::Quit;
+                }
+                match entry {
+                    Ok(entry) => match entry.file_type() {
+                        Some(file_type) if file_type.is_dir() && !visitor.enter(entry.path(), entry.depth()) => {
+                            return ignore::WalkState::Skip;
+                        }
+                        Some(file_type) if entry.depth() > 0 => visitor.entry(entry.path(), &file_type),
+                        _ => {}
+                    },
+                    Err(e) => {
+                        let (path, cycle) = ignore_error_path(&e);
+                        let path = path.unwrap_or(root_dir);
+                        if cycle {
+                            visitor.cycle(path);
+                        } else {
+                            let error = e.io_error()
+                                .map(|error| io::Error::new(error.kind(), error.to_string()))
+                                .unwrap_or_else(|| io::Error::other(e.to_string()));
+                            report_path_error(&self.observer_registry, &self.config, path, &error);
+                        }
+                    }
+                }
+                ignore::WalkState::Continue
+            }));
+    }
+    fn find_tracking_observer(observer_registry: &ObserverRegistry) -> Option<Arc<TrackingObserver>> {
+        observer_registry.get_observer_of_type::<TrackingObserver>()
     }
     pub fn get_tracking_observer(&self) -> Option<Arc<TrackingObserver>> {
         Self::find_tracking_observer(&self.observer_registry)
     }
 }
 // Process directory for file search
+#[allow(clippy::too_many_arguments)]
 fn process_directory(
     dir_path: &Path,
     traversal_strategy: &Arc<dyn TraversalStrategy>,
     filter_registry: &Arc<FilterRegistry>,
     observer_registry: &Arc<ObserverRegistry>,
     config: &FinderConfig,
+    progress: &WalkProgress,
+    probe: Probe,
     current_depth: &mut Vec<String>,
+    ancestors: &mut Vec<FileId>,
 ) -> Result<()> {
+    progress.dequeue();
+    // Directories still waiting once enough matches are found are abandoned
+    if progress.finished() {
+        return Ok(());
+    }
     if let Some(max_depth) = config.max_depth {
         if current_depth.len() >= max_depth {
             return Ok(());
         }
     }
-    if !traversal_strategy.should_process_directory(dir_path) {
+    // The root was asked for explicitly, so only its descendants are subject to the strategy
+    if !current_depth.is_empty() && !traversal_strategy.should_process_directory(dir_path) {
+        observer_registry.publish(SearchEvent::Skipped(dir_path));
         return Ok(());
     }
-    observer_registry.notify_directory_processed(dir_path);
-    let entries = std::fs::read_dir(dir_path)
-        .with_context(|| format!("Failed to read directory entries for: {}", dir_path.display()))?;
+    observer_registry.publish(SearchEvent::DirEntered(dir_path));
+    progress.reading(dir_path);
+    let entries: Vec<_> = match std::fs::read_dir(dir_path) {
+        Ok(entries) => entries.collect(),
+        Err(e) => {
+            report_path_error(observer_registry, config, dir_path, &e);
+            return Ok(());
+        }
+    };
+    // Subdirectories beyond the depth limit are never read, so they are left out of estimates
+    let subdirectories = if config.max_depth.is_none_or(|max_depth| current_depth.len() + 1 < max_depth) {
+        entries.iter()
+            .filter_map(|entry| entry.as_ref().ok())
+            .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+            .filter(|entry| traversal_strategy.should_process_directory(&entry.path()) && !filter_registry.prunes(&entry.path()))
+            .count()
+    } else {
+        0
+    };
+    progress.enqueue(subdirectories);
+    if subdirectories == 0 {
+        progress.leaf(probe);
+    }
+    // Only links can lead back to a directory being walked, so plain walks skip the lookup
+    let id = config.follow_links.then(|| FileId::of(dir_path).ok()).flatten();
+    ancestors.extend(id);
+    let probe = probe.descend(subdirectories);
+    let mut found = 0;
+    let satisfied = |found: usize| config.max_per_dir.is_some_and(|limit| found >= limit);
     for entry_result in entries {
+        if progress.finished() {
+            break;
+        }
         let entry = match entry_result {
             Ok(entry) => entry,
             Err(e) => {
-                warn!("Failed to read directory entry: {}", e);
+                report_path_error(observer_registry, config, dir_path, &e);
                 continue;
             }
         };
@@ -248,15 +463,28 @@ fn process_directory(
         let file_type = match entry.file_type() {
             Ok(ft) => ft,
             Err(e) => {
-                warn!("Failed to determine file type for {}: {}", path.display(), e);
+                report_path_error(observer_registry, config, &path, &e);
                 continue;
             }
         };
         if file_type.is_dir() {
             if file_type.is_symlink() && !config.follow_links {
-                debug!("Skipping symbolic link to directory: {}", path.display());
+                info!("Skipping symbolic link to directory: {}", path.display());
+                observer_registry.publish(SearchEvent::Skipped(&path));
                 continue;
             }
+            if filter_registry.prunes(&path) {
+                debug!("Pruning directory: {}", path.display());
+                observer_registry.publish(SearchEvent::Skipped(&path));
+                continue;
+            }
+            // Directories asked for are reported before their contents, as find does
+            if !satisfied(found) && traversal_strategy.should_process_directory(&path)
+                && is_candidate(&path, &file_type, config, filter_registry)
+                && report_match(&path, progress, observer_registry)
+            {
+                found += 1;
+            }
             if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
                 current_depth.push(dir_name.to_string());
                 if let Err(e) = process_directory(
@@ -265,17 +493,32 @@ fn process_directory(
                     filter_registry,
                     observer_registry,
                     config,
-                    current_depth
+                    progress,
+                    probe,
+                    current_depth,
+                    ancestors,
                 ) {
                     warn!("Error processing subdirectory {}: {}", path.display(), e);
                 }
                 current_depth.pop();
             }
         } else if file_type.is_file() && traversal_strategy.should_process_file(&path) {
-            if filter_registry.apply_all(&path) == FilterResult::Accept {
-                observer_registry.notify_file_found(&path);
+            if !satisfied(found) && is_candidate(&path, &file_type, config, filter_registry)
+                && report_match(&path, progress, observer_registry)
+            {
+                found += 1;
+            }
+        } else {
+            // Links, sockets and pipes are only results when asked for
+            if !satisfied(found) && traversal_strategy.should_process_file(&path)
+                && is_candidate(&path, &file_type, config, filter_registry)
+                && report_match(&path, progress, observer_registry)
+            {
+                found += 1;
+            }
+            if !file_type.is_symlink() || !config.follow_links {
+                continue;
             }
-        } else if file_type.is_symlink() && config.follow_links {
             match std::fs::read_link(&path) {
                 Ok(target) => {
                     let target_path = if target.is_absolute() {
@@ -286,7 +529,13 @@ fn process_directory(
                     };
                     match std::fs::metadata(&target_path) {
                         Ok(metadata) => {
-                            if metadata.is_dir() {
+                            if metadata.is_dir() && filter_registry.prunes(&path) {
+                                debug!("Pruning directory: {}", path.display());
+                                observer_registry.publish(SearchEvent::Skipped(&path));
+                            } else if metadata.is_dir() && is_ancestor(&target_path, ancestors) {
+                                info!("Skipping symbolic link cycle: {}", path.display());
+                                observer_registry.publish(SearchEvent::Skipped(&path));
+                            } else if metadata.is_dir() {
                                 if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
                                     current_depth.push(dir_name.to_string());
                                     if let Err(e) = process_directory(
@@ -295,7 +544,10 @@ fn process_directory(
                                         filter_registry,
                                         observer_registry,
                                         config,
-                                        current_depth
+                                        progress,
+                                        probe,
+                                        current_depth,
+                                        ancestors,
                                     ) {
                                         warn!("Error processing symlinked directory {}: {}",
                                               target_path.display(), e);
@@ -303,24 +555,224 @@ fn process_directory(
                                     current_depth.pop();
                                 }
                             } else if metadata.is_file() && traversal_strategy.should_process_file(&target_path) {
-                                if filter_registry.apply_all(&target_path) == FilterResult::Accept {
-                                    observer_registry.notify_file_found(&target_path);
+                                if !satisfied(found) && is_candidate(&target_path, &metadata.file_type(), config, filter_registry)
+                                    && report_match(&target_path, progress, observer_registry)
+                                {
+                                    found += 1;
                                 }
                             }
                         }
                         Err(e) => {
-                            warn!("Failed to get metadata for symlink target {}: {}",
-                                  target_path.display(), e);
+                            report_path_error(observer_registry, config, &target_path, &e);
                         }
                     }
                 }
                 Err(e) => {
-                    warn!("Failed to read symlink {}: {}", path.display(), e);
+                    report_path_error(observer_registry, config, &path, &e);
                 }
             }
         }
     }
+    if id.is_some() {
+        ancestors.pop();
+    }
     Ok(())
 }
+// Whether an entry is a result: of a kind asked for and accepted by every filter
+fn is_candidate(path: &Path, file_type: &std::fs::FileType, config: &FinderConfig, filter_registry: &FilterRegistry) -> bool {
+    config.types.accepts(file_type) && filter_registry.apply_all_to_entry(path) == FilterResult::Accept
+}
+// Report a result, unless another path to it was reported or the walk already has all the results it may report
+fn report_match(path: &Path, progress: &WalkProgress, observer_registry: &ObserverRegistry) -> bool {
+    match progress.identify(path) {
+        Some(reported) if progress.claim() => {
+            observer_registry.publish(SearchEvent::FileFound(&reported));
+            true
+        }
+        _ => false,
+    }
+}
+/// Whether a directory is one of the directories being walked, reached again through a link
+pub(crate) fn is_ancestor(dir_path: &Path, ancestors: &[FileId]) -> bool {
+    FileId::of(dir_path).is_ok_and(|id| ancestors.contains(&id))
+}
+// What a running walk is doing, sampled by its heartbeat, and how many matches it has found
+struct WalkProgress {
+    directory: Mutex<PathBuf>,
+    pending: AtomicUsize,
+    sampler: Mutex<TreeSizeSampler>,
+    limit: Option<usize>,
+    found: AtomicUsize,
+    // Files already reported, when each file is only reported once
+    seen: Option<Mutex<HashSet<FileId>>>,
+}
+impl WalkProgress {
+    // The root starts out as the only directory waiting to be read
+    fn new(root_dir: &Path, limit: Option<usize>, unique: bool) -> Self {
+        WalkProgress {
+            directory: Mutex::new(root_dir.to_path_buf()),
+            pending: AtomicUsize::new(1),
+            sampler: Mutex::new(TreeSizeSampler::default()),
+            limit,
+            found: AtomicUsize::new(0),
+            seen: unique.then(|| Mutex::new(HashSet::new())),
+        }
+    }
+    // Path to report an accepted file under, or none if another path to the same file was reported
+    //
+    // When files are only reported once, links and hard links to a reported file are
+    // dropped and files are reported under their canonical paths. Files whose identity
+    // cannot be read are reported as found.
+    fn identify<'p>(&self, path: &'p Path) -> Option<Cow<'p, Path>> {
+        let Some(seen) = &self.seen else {
+            return Some(Cow::Borrowed(path));
+        };
+        if let Ok(id) = FileId::of(path) {
+            if !seen.lock().unwrap_or_else(|e| e.into_inner()).insert(id) {
+                debug!("Skipping another path to a reported file: {}", path.display());
+                return None;
+            }
+        }
+        Some(path.canonicalize().map_or(Cow::Borrowed(path), Cow::Owned))
+    }
+    // Count an accepted file as a match, unless the walk already has all the matches it may report
+    fn claim(&self) -> bool {
+        let Some(limit) = self.limit else {
+            return true;
+        };
+        self.found.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |found| (found < limit).then_some(found + 1)).is_ok()
+    }
+    // Whether the walk has found as many matches as it may report, and can stop
+    fn finished(&self) -> bool {
+        self.limit.is_some_and(|limit| self.found() >= limit)
+    }
+    fn found(&self) -> usize {
+        self.found.load(Ordering::Relaxed)
+    }
+    fn enqueue(&self, directories: usize) {
+        self.pending.fetch_add(directories, Ordering::Relaxed);
+    }
+    fn dequeue(&self) {
+        // Symbolic links to directories are entered without having been counted
+        let _ = self.pending.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |pending| pending.checked_sub(1));
+    }
+    fn leaf(&self, probe: Probe) {
+        if let Ok(mut sampler) = self.sampler.lock() {
+            sampler.leaf(probe);
+        }
+    }
+    fn reading(&self, dir_path: &Path) {
+        if let Ok(mut directory) = self.directory.lock() {
+            dir_path.clone_into(&mut directory);
+        }
+    }
+    fn heartbeat(&self) -> Heartbeat {
+        Heartbeat {
+            directory: self.directory.lock().map(|directory| directory.clone()).unwrap_or_default(),
+            queue_depth: self.pending.load(Ordering::Relaxed),
+            estimated_total: self.sampler.lock().ok().and_then(|sampler| sampler.estimate()),
+        }
+    }
+}
+/// Applies a finder's traversal strategy, filters and limits to the entries of another walker
+struct EntryVisitor<'a> {
+    finder: &'a FileFinder,
+    progress: &'a WalkProgress,
+    // Files accepted so far in each directory, when their number is limited
+    found: Mutex<HashMap<PathBuf, usize>>,
+}
+impl<'a> EntryVisitor<'a> {
+    fn new(finder: &'a FileFinder, progress: &'a WalkProgress) -> Self {
+        EntryVisitor { finder, progress, found: Mutex::new(HashMap::new()) }
+    }
+    // Whether to keep a directory at the given depth below the root, announcing it if it is read
+    //
+    // Directories at the depth limit are kept as possible results, and the walker
+    // itself stops before reading them.
+    fn enter(&self, dir_path: &Path, depth: usize) -> bool {
+        let finder = self.finder;
+        if self.progress.finished() {
+            return false;
+        }
+        // The root was asked for explicitly, so only its descendants are subject to the strategy
+        if depth > 0 && (!finder.traversal_strategy.should_process_directory(dir_path) || finder.filter_registry.prunes(dir_path)) {
+            debug!("Pruning directory: {}", dir_path.display());
+            finder.observer_registry.publish(SearchEvent::Skipped(dir_path));
+            return false;
+        }
+        if finder.config.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            return true;
+        }
+        self.progress.dequeue();
+        self.progress.reading(dir_path);
+        finder.observer_registry.publish(SearchEvent::DirEntered(dir_path));
+        true
+    }
+    // Consider an entry below the root as a result; directories were already entered
+    fn entry(&self, path: &Path, file_type: &std::fs::FileType) {
+        let finder = self.finder;
+        if !finder.config.types.accepts(file_type) {
+            return;
+        }
+        let allowed = if file_type.is_dir() {
+            finder.traversal_strategy.should_process_directory(path)
+        } else {
+            finder.traversal_strategy.should_process_file(path)
+        };
+        if !allowed {
+            return;
+        }
+        let accept = || {
+            is_candidate(path, file_type, &finder.config, &finder.filter_registry)
+                && report_match(path, self.progress, &finder.observer_registry)
+        };
+        match finder.config.max_per_dir {
+            None => {
+                accept();
+            }
+            Some(limit) => {
+                // Held while filtering so parallel walkers cannot overshoot the limit
+                let mut found = self.found.lock().unwrap_or_else(|e| e.into_inner());
+                let count = found.entry(path.parent().unwrap_or(Path::new("")).to_path_buf()).or_default();
+                if *count < limit && accept() {
+                    *count += 1;
+                }
+            }
+        }
+    }
+    fn cycle(&self, path: &Path) {
+        info!("Skipping symbolic link cycle: {}", path.display());
+        self.finder.observer_registry.publish(SearchEvent::Skipped(path));
+    }
+}
+// Path an error from the ignore crate is about, and whether it is a symbolic link cycle
+fn ignore_error_path(error: &ignore::Error) -> (Option<&Path>, bool) {
+    match error {
+        ignore::Error::Loop { child, .. } => (Some(child), true),
+        ignore::Error::WithPath { path, err } => {
+            let (inner, cycle) = ignore_error_path(err);
+            (inner.or(Some(path)), cycle)
+        }
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => ignore_error_path(err),
+        _ => (None, false),
+    }
+}
+// Count an unreadable path and log it unless messages are suppressed
+fn report_path_error(
+    observer_registry: &ObserverRegistry,
+    config: &FinderConfig,
+    path: &Path,
+    error: &io::Error,
+) {
+    observer_registry.publish(SearchEvent::Error { path, error });
+    if config.no_messages {
+        return;
+    }
+    if error.kind() == io::ErrorKind::PermissionDenied {
+        info!("Skipping {}: {}", path.display(), error);
+    } else {
+        warn!("Failed to read {}: {}", path.display(), error);
+    }
+}
 
 
@@ -1,6 +1,9 @@
 pub mod builder;
 pub mod config;
+pub mod data_file;
+pub mod estimate;
 pub mod factory;
+pub mod file_id;
 pub mod finder;
 pub mod observer;
 pub mod platform;
@@ -11,11 +14,13 @@ pub mod worker;
 
 // Re-export commonly used types
 pub use self::builder::FileFinderBuilder;
-pub use self::config::{AppConfig, FileSearchConfig};
+pub use self::config::{AppConfig, FileSearchConfig, OutputFormat, SortOrder};
 pub use self::factory::FinderFactory;
-pub use self::finder::FileFinder;
-pub use self::observer::{NullObserver, ProgressReporter, SearchObserver, SilentObserver};
+pub use self::file_id::FileId;
+pub use self::finder::{FileFinder, FileMatch, SearchReport};
+pub use self::observer::{progress_observer, NullObserver, ProgressBarObserver, ProgressReporter, SearchEvent, SearchObserver, SearchStats, SilentObserver, StreamingObserver};
 pub use self::platform::Platform;
 pub use self::registry::{FilterRegistry, ObserverRegistry};
 pub use self::singleton::ConfigManager;
-pub use self::traversal::{DefaultTraversalStrategy, TraversalMode, TraversalStrategy}; 
\ No newline at end of file
+pub use self::traversal::{CompositeTraversalStrategy, DefaultTraversalStrategy, GitAwareTraversalStrategy, IgnoreFileTraversalStrategy, RegexTraversalStrategy, TraversalMode, TraversalStrategy}; 
+pub use self::worker::WorkerStats;
\ No newline at end of file
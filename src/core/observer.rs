@@ -2,12 +2,20 @@ use std::{
     path::Path,
     path::PathBuf,
     sync::atomic::{AtomicUsize, Ordering},
-    time::Instant,
-    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+    io::{self, IsTerminal},
+    sync::{mpsc::SyncSender, Mutex, MutexGuard},
     any::Any,
+    fmt::{self, Display},
 };
-use log::warn;
+use console::{truncate_str, Term};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use log::{debug, warn};
 use anyhow::Result;
+use crate::core::config::{FileSearchConfig, ProgressDisplay};
+use crate::core::estimate::{count_directories, Progress, ScanTotals};
+use crate::core::finder::FileMatch;
+use crate::core::worker::WorkerStats;
 pub trait SearchObserver: Send + Sync {
     // Observer for file search operations
     fn file_found(&self, file_path: &Path);
@@ -15,6 +23,102 @@ pub trait SearchObserver: Send + Sync {
     fn files_count(&self) -> usize;
     fn directories_count(&self) -> usize;
     fn as_any(&self) -> &dyn Any;
+    // Called for every path that could not be read; the default ignores it
+    fn path_error(&self, _path: &Path, _error: &dyn Display) {}
+    fn errors_count(&self) -> usize { 0 }
+    fn path_errors(&self) -> Vec<PathError> { Vec::new() }
+    // Called for every directory the search chose not to enter; the default ignores it
+    fn path_skipped(&self, _path: &Path) {}
+    fn skipped_paths(&self) -> Vec<PathBuf> { Vec::new() }
+    // Called periodically while a search runs, and once when it is over
+    fn heartbeat(&self, _heartbeat: &Heartbeat) {}
+    fn search_finished(&self) {}
+    // Called for every event published on the bus; the default hands each kind to
+    // the matching method above and ignores kinds it does not know
+    fn on_event(&self, event: &SearchEvent<'_>) {
+        match *event {
+            SearchEvent::FileFound(path) => self.file_found(path),
+            SearchEvent::DirEntered(path) => self.directory_processed(path),
+            SearchEvent::Error { path, error } => self.path_error(path, error),
+            SearchEvent::Skipped(path) => self.path_skipped(path),
+            SearchEvent::Heartbeat(heartbeat) => self.heartbeat(heartbeat),
+            SearchEvent::Completed(_) => self.search_finished(),
+            SearchEvent::Workers(_) => {}
+        }
+    }
+}
+/// Something that happened during a search, published to every subscribed observer
+///
+/// New kinds may be added; subscribers matching on events directly should ignore
+/// the ones they do not handle.
+#[derive(Clone, Copy)]
+#[non_exhaustive]
+pub enum SearchEvent<'a> {
+    /// A file was accepted by the filters
+    FileFound(&'a Path),
+    /// A directory was read
+    DirEntered(&'a Path),
+    /// A path could not be read
+    Error { path: &'a Path, error: &'a dyn Display },
+    /// A directory was not entered, such as an unfollowed symbolic link
+    Skipped(&'a Path),
+    /// Periodic sign of life from the walk
+    Heartbeat(&'a Heartbeat),
+    /// The search is over
+    Completed(&'a SearchStats),
+    /// What each worker thread did, published before `Completed` by multi-threaded walks
+    Workers(&'a [WorkerStats]),
+}
+impl fmt::Debug for SearchEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchEvent::FileFound(path) => f.debug_tuple("FileFound").field(path).finish(),
+            SearchEvent::DirEntered(path) => f.debug_tuple("DirEntered").field(path).finish(),
+            SearchEvent::Error { path, error } => f.debug_struct("Error")
+                .field("path", path)
+                .field("error", &error.to_string())
+                .finish(),
+            SearchEvent::Skipped(path) => f.debug_tuple("Skipped").field(path).finish(),
+            SearchEvent::Heartbeat(heartbeat) => f.debug_tuple("Heartbeat").field(heartbeat).finish(),
+            SearchEvent::Completed(stats) => f.debug_tuple("Completed").field(stats).finish(),
+            SearchEvent::Workers(workers) => f.debug_tuple("Workers").field(workers).finish(),
+        }
+    }
+}
+/// Totals of a finished search, as seen by the observers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Number of files accepted by the filters
+    pub files: usize,
+    /// Number of directories read
+    pub directories: usize,
+    /// Number of paths that could not be read
+    pub errors: usize,
+    /// Wall-clock time the search took
+    pub elapsed: Duration,
+}
+/// How often a running search reports a heartbeat
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(250);
+/// Periodic sign of life from a running search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heartbeat {
+    /// Directory being read
+    pub directory: PathBuf,
+    /// Directories found but not read yet
+    pub queue_depth: usize,
+    /// Number of directories the walk is expected to read, once there is an estimate
+    pub estimated_total: Option<usize>,
+}
+/// A path that could not be read during a search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathError {
+    pub path: PathBuf,
+    pub message: String,
+}
+impl Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
 }
 #[derive(Debug)]
 pub struct NullObserver;
@@ -30,20 +134,63 @@ impl Clone for NullObserver {
         Self
     }
 }
+/// Reports progress on standard error while a search runs
+///
+/// On a terminal, heartbeats are drawn as a single status line that is replaced
+/// in place and cleared when the search finishes. Elsewhere, a line is printed
+/// every 100 files and every 50 directories instead.
+///
+/// A reporter created [`for_root`](Self::for_root) shows a percentage and time
+/// left, based on the number of directories the last walk of the root read, and
+/// records the number this walk read for next time. Without an earlier total,
+/// the walk's own estimate of its size is used once it has one. A total counted
+/// beforehand can be given with [`with_total`](Self::with_total) instead.
 #[derive(Debug)]
 pub struct ProgressReporter {
     files_count: AtomicUsize,
     dirs_count: AtomicUsize,
+    errors: Mutex<Vec<PathError>>,
     start_time: Instant,
+    status_line: bool,
+    root: Option<PathBuf>,
+    known_total: Option<usize>,
 }
 impl ProgressReporter {
     pub fn new() -> Self {
         ProgressReporter {
             files_count: AtomicUsize::new(0),
             dirs_count: AtomicUsize::new(0),
+            errors: Mutex::new(Vec::new()),
             start_time: Instant::now(),
+            status_line: io::stderr().is_terminal(),
+            root: None,
+            known_total: None,
         }
     }
+    /// Create a reporter for a walk of `root` that remembers its total between runs
+    pub fn for_root(root: &Path) -> Self {
+        let known_total = ScanTotals::open_default()
+            .inspect_err(|e| debug!("No earlier scan totals: {:#}", e))
+            .ok()
+            .and_then(|totals| totals.get(root));
+        ProgressReporter {
+            root: Some(root.to_path_buf()),
+            known_total,
+            ..Self::new()
+        }
+    }
+    /// Use a number of directories counted before the walk as its total
+    pub fn with_total(self, total: usize) -> Self {
+        ProgressReporter {
+            known_total: Some(total),
+            ..self
+        }
+    }
+    /// Progress of the walk, if its size is known or has been estimated
+    pub fn progress(&self, heartbeat: &Heartbeat) -> Option<Progress> {
+        let expected_total = self.known_total.or(heartbeat.estimated_total)?;
+        Progress::from_counts(self.directories_count(), heartbeat.queue_depth, expected_total, self.elapsed_time())
+    }
     pub fn elapsed_time(&self) -> std::time::Duration {
         self.start_time.elapsed()
     }
@@ -56,15 +203,15 @@ impl Default for ProgressReporter {
 impl SearchObserver for ProgressReporter {
     fn file_found(&self, file_path: &Path) {
         let count = self.files_count.fetch_add(1, Ordering::Relaxed) + 1;
-        if count % 100 == 0 {
-            println!("Found {} files so far... (latest: {})",
+        if !self.status_line && count.is_multiple_of(100) {
+            eprintln!("Found {} files so far... (latest: {})",
                 count, file_path.display());
         }
     }
     fn directory_processed(&self, dir_path: &Path) {
         let count = self.dirs_count.fetch_add(1, Ordering::Relaxed) + 1;
-        if count % 50 == 0 {
-            println!("Processed {} directories so far... (latest: {})",
+        if !self.status_line && count.is_multiple_of(50) {
+            eprintln!("Processed {} directories so far... (latest: {})",
                 count, dir_path.display());
         }
     }
@@ -74,32 +221,219 @@ impl SearchObserver for ProgressReporter {
     fn directories_count(&self) -> usize {
         self.dirs_count.load(Ordering::Relaxed)
     }
+    fn path_error(&self, path: &Path, error: &dyn Display) {
+        record_errors(&self.errors, vec![PathError {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        }]);
+    }
+    fn errors_count(&self) -> usize {
+        self.path_errors().len()
+    }
+    fn path_errors(&self) -> Vec<PathError> {
+        match self.errors.lock() {
+            Ok(errors) => errors.clone(),
+            Err(_e) => {
+                warn!("Failed to acquire lock for path errors, returning empty vector");
+                Vec::new()
+            }
+        }
+    }
+    fn heartbeat(&self, heartbeat: &Heartbeat) {
+        if !self.status_line {
+            return;
+        }
+        let mut status = format!(
+            "{} found, {} directories ({}), {} queued: {}",
+            self.files_count(), self.directories_count(), format_rate(self.directories_count(), self.elapsed_time()),
+            heartbeat.queue_depth, heartbeat.directory.display()
+        );
+        if let Some(progress) = self.progress(heartbeat) {
+            // Estimates from the shape of the tree are rougher than earlier totals
+            let approximate = if self.known_total.is_some() { "" } else { "~" };
+            status = format!(
+                "{}{:.0}%, {}{} left, {}",
+                approximate, progress.fraction * 100.0, approximate, format_remaining(progress.remaining), status
+            );
+        }
+        let term = Term::stderr();
+        let width = term.size().1 as usize;
+        let _ = term.clear_line();
+        let _ = term.write_str(&truncate_str(&status, width.saturating_sub(1), "…"));
+    }
+    fn search_finished(&self) {
+        if self.status_line {
+            let _ = Term::stderr().clear_line();
+        }
+        if let Some(root) = &self.root {
+            if let Err(e) = ScanTotals::remember(root, self.directories_count()) {
+                debug!("Failed to record scan total: {:#}", e);
+            }
+        }
+    }
     fn as_any(&self) -> &dyn Any { self }
 }
 impl Clone for ProgressReporter {
     fn clone(&self) -> Self {
-        let new_reporter = ProgressReporter::new();
-        let files_count = self.files_count();
-        if files_count > 0 {
-            new_reporter.files_count.store(files_count, Ordering::Relaxed);
+        ProgressReporter {
+            files_count: AtomicUsize::new(self.files_count()),
+            dirs_count: AtomicUsize::new(self.directories_count()),
+            errors: Mutex::new(self.path_errors()),
+            start_time: self.start_time,
+            status_line: self.status_line,
+            root: self.root.clone(),
+            known_total: self.known_total,
         }
-        let dirs_count = self.directories_count();
-        if dirs_count > 0 {
-            new_reporter.dirs_count.store(dirs_count, Ordering::Relaxed);
+    }
+}
+/// Draws an animated progress bar on standard error while a search runs
+///
+/// Until the size of the walk is known, a spinner shows the elapsed time, the
+/// number of directories read and files found, and how fast directories are read.
+/// Once there is a total for the root, from an earlier walk, a count made with
+/// [`with_total`](Self::with_total) or the walk's own estimate, it becomes a bar
+/// filling up as directories are read, with the time left. The bar is cleared
+/// when the search finishes.
+#[derive(Debug)]
+pub struct ProgressBarObserver {
+    files_count: AtomicUsize,
+    dirs_count: AtomicUsize,
+    errors: Mutex<Vec<PathError>>,
+    bar: ProgressBar,
+    root: Option<PathBuf>,
+    known_total: Option<usize>,
+}
+impl ProgressBarObserver {
+    pub fn new() -> Self {
+        let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr());
+        bar.set_style(Self::spinner_style());
+        bar.enable_steady_tick(Duration::from_millis(100));
+        ProgressBarObserver {
+            files_count: AtomicUsize::new(0),
+            dirs_count: AtomicUsize::new(0),
+            errors: Mutex::new(Vec::new()),
+            bar,
+            root: None,
+            known_total: None,
+        }
+    }
+    /// Create a progress bar for a walk of `root` that remembers its total between runs
+    pub fn for_root(root: &Path) -> Self {
+        let known_total = ScanTotals::open_default()
+            .inspect_err(|e| debug!("No earlier scan totals: {:#}", e))
+            .ok()
+            .and_then(|totals| totals.get(root));
+        let observer = ProgressBarObserver {
+            root: Some(root.to_path_buf()),
+            known_total,
+            ..Self::new()
+        };
+        if let Some(total) = known_total {
+            observer.show_bar(total);
+        }
+        observer
+    }
+    /// Use a number of directories counted before the walk as its total
+    pub fn with_total(self, total: usize) -> Self {
+        self.show_bar(total);
+        ProgressBarObserver {
+            known_total: Some(total),
+            ..self
+        }
+    }
+    fn spinner_style() -> ProgressStyle {
+        ProgressStyle::with_template("{spinner} [{elapsed_precise}] {msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_spinner())
+    }
+    // Switch from the spinner to a bar once the number of directories is known
+    fn show_bar(&self, total: usize) {
+        if self.bar.length().is_none() {
+            self.bar.set_style(ProgressStyle::with_template("{spinner} [{elapsed_precise}] [{wide_bar}] {percent:>3}%, {eta} left, {msg}")
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("=> "));
+        }
+        self.bar.set_length(total as u64);
+    }
+    fn update_message(&self) {
+        self.bar.set_message(format!(
+            "{} directories ({}), {} found",
+            self.directories_count(), format_rate(self.directories_count(), self.bar.elapsed()), self.files_count()
+        ));
+    }
+}
+impl Default for ProgressBarObserver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl SearchObserver for ProgressBarObserver {
+    fn file_found(&self, _file_path: &Path) {
+        self.files_count.fetch_add(1, Ordering::Relaxed);
+        self.update_message();
+    }
+    fn directory_processed(&self, _dir_path: &Path) {
+        let count = self.dirs_count.fetch_add(1, Ordering::Relaxed) + 1;
+        // The walk can read more directories than were expected
+        if self.bar.length().is_some_and(|length| count as u64 > length) {
+            self.bar.set_length(count as u64);
+        }
+        self.bar.set_position(count as u64);
+        self.update_message();
+    }
+    fn files_count(&self) -> usize {
+        self.files_count.load(Ordering::Relaxed)
+    }
+    fn directories_count(&self) -> usize {
+        self.dirs_count.load(Ordering::Relaxed)
+    }
+    fn path_error(&self, path: &Path, error: &dyn Display) {
+        record_errors(&self.errors, vec![PathError {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        }]);
+    }
+    fn errors_count(&self) -> usize {
+        self.path_errors().len()
+    }
+    fn path_errors(&self) -> Vec<PathError> {
+        match self.errors.lock() {
+            Ok(errors) => errors.clone(),
+            Err(_e) => {
+                warn!("Failed to acquire lock for path errors, returning empty vector");
+                Vec::new()
+            }
+        }
+    }
+    fn heartbeat(&self, heartbeat: &Heartbeat) {
+        if self.known_total.is_none() {
+            if let Some(estimate) = heartbeat.estimated_total {
+                self.show_bar(estimate.max(self.directories_count()));
+            }
         }
-        new_reporter
     }
+    fn search_finished(&self) {
+        self.bar.finish_and_clear();
+        if let Some(root) = &self.root {
+            if let Err(e) = ScanTotals::remember(root, self.directories_count()) {
+                debug!("Failed to record scan total: {:#}", e);
+            }
+        }
+    }
+    fn as_any(&self) -> &dyn Any { self }
 }
+
 #[derive(Debug)]
 pub struct SilentObserver {
     files_count: AtomicUsize,
     dirs_count: AtomicUsize,
+    errors: Mutex<Vec<PathError>>,
 }
 impl SilentObserver {
     pub fn new() -> Self {
         SilentObserver {
             files_count: AtomicUsize::new(0),
             dirs_count: AtomicUsize::new(0),
+            errors: Mutex::new(Vec::new()),
         }
     }
 }
@@ -121,6 +455,24 @@ impl SearchObserver for SilentObserver {
     fn directories_count(&self) -> usize {
         self.dirs_count.load(Ordering::Relaxed)
     }
+    fn path_error(&self, path: &Path, error: &dyn Display) {
+        record_errors(&self.errors, vec![PathError {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        }]);
+    }
+    fn errors_count(&self) -> usize {
+        self.path_errors().len()
+    }
+    fn path_errors(&self) -> Vec<PathError> {
+        match self.errors.lock() {
+            Ok(errors) => errors.clone(),
+            Err(_e) => {
+                warn!("Failed to acquire lock for path errors, returning empty vector");
+                Vec::new()
+            }
+        }
+    }
     fn as_any(&self) -> &dyn Any { self }
 }
 impl Clone for SilentObserver {
@@ -128,6 +480,7 @@ impl Clone for SilentObserver {
         SilentObserver {
             files_count: AtomicUsize::new(self.files_count()),
             dirs_count: AtomicUsize::new(self.directories_count()),
+            errors: Mutex::new(self.path_errors()),
         }
     }
 }
@@ -135,6 +488,8 @@ impl Clone for SilentObserver {
 pub struct TrackingObserver {
     files_count: AtomicUsize,
     dirs_count: AtomicUsize,
+    errors: Mutex<Vec<PathError>>,
+    skipped: Mutex<Vec<PathBuf>>,
     found_files: Mutex<Vec<PathBuf>>,
 }
 impl TrackingObserver {
@@ -142,6 +497,8 @@ impl TrackingObserver {
         TrackingObserver {
             files_count: AtomicUsize::new(0),
             dirs_count: AtomicUsize::new(0),
+            errors: Mutex::new(Vec::new()),
+            skipped: Mutex::new(Vec::new()),
             found_files: Mutex::new(Vec::new()),
         }
     }
@@ -175,6 +532,10 @@ impl TrackingObserver {
         if other_dirs_count > 0 {
             self.dirs_count.fetch_add(other_dirs_count, Ordering::Relaxed);
         }
+        record_errors(&self.errors, other.path_errors());
+        for path in other.skipped_paths() {
+            self.path_skipped(&path);
+        }
         Ok(())
     }
 }
@@ -204,6 +565,39 @@ impl SearchObserver for TrackingObserver {
     fn directories_count(&self) -> usize {
         self.dirs_count.load(Ordering::Relaxed)
     }
+    fn path_error(&self, path: &Path, error: &dyn Display) {
+        record_errors(&self.errors, vec![PathError {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        }]);
+    }
+    fn errors_count(&self) -> usize {
+        self.path_errors().len()
+    }
+    fn path_errors(&self) -> Vec<PathError> {
+        match self.errors.lock() {
+            Ok(errors) => errors.clone(),
+            Err(_e) => {
+                warn!("Failed to acquire lock for path errors, returning empty vector");
+                Vec::new()
+            }
+        }
+    }
+    fn path_skipped(&self, path: &Path) {
+        match self.skipped.lock() {
+            Ok(mut skipped) => skipped.push(path.to_path_buf()),
+            Err(_e) => warn!("Failed to record skipped path {}: poisoned lock", path.display()),
+        }
+    }
+    fn skipped_paths(&self) -> Vec<PathBuf> {
+        match self.skipped.lock() {
+            Ok(skipped) => skipped.clone(),
+            Err(_e) => {
+                warn!("Failed to acquire lock for skipped paths, returning empty vector");
+                Vec::new()
+            }
+        }
+    }
     fn as_any(&self) -> &dyn Any { self }
 }
 impl Clone for TrackingObserver {
@@ -217,13 +611,101 @@ impl Clone for TrackingObserver {
         if dirs_count > 0 {
             new_observer.dirs_count.store(dirs_count, Ordering::Relaxed);
         }
+        record_errors(&new_observer.errors, self.path_errors());
+        for path in self.skipped_paths() {
+            new_observer.path_skipped(&path);
+        }
         new_observer
     }
 }
 
-/// Create an appropriate observer based on whether progress should be shown
-pub fn create_observer(show_progress: bool) -> Box<dyn SearchObserver> {
-    if show_progress {
+/// Observer that forwards every found file over a bounded channel
+///
+/// Sending blocks while the channel is full, which throttles the search to the
+/// consumer's pace. Once the receiver is dropped, further matches are discarded.
+#[derive(Debug)]
+pub struct StreamingObserver {
+    sender: SyncSender<FileMatch>,
+    files_count: AtomicUsize,
+    dirs_count: AtomicUsize,
+}
+impl StreamingObserver {
+    pub fn new(sender: SyncSender<FileMatch>) -> Self {
+        StreamingObserver {
+            sender,
+            files_count: AtomicUsize::new(0),
+            dirs_count: AtomicUsize::new(0),
+        }
+    }
+}
+impl SearchObserver for StreamingObserver {
+    fn file_found(&self, file_path: &Path) {
+        if self.sender.send(FileMatch::from(file_path.to_path_buf())).is_ok() {
+            self.files_count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    fn directory_processed(&self, _dir_path: &Path) {
+        self.dirs_count.fetch_add(1, Ordering::Relaxed);
+    }
+    fn files_count(&self) -> usize {
+        self.files_count.load(Ordering::Relaxed)
+    }
+    fn directories_count(&self) -> usize {
+        self.dirs_count.load(Ordering::Relaxed)
+    }
+    fn as_any(&self) -> &dyn Any { self }
+}
+
+// Append path errors to a shared list, tolerating a poisoned lock
+fn record_errors(errors: &Mutex<Vec<PathError>>, new_errors: Vec<PathError>) {
+    match errors.lock() {
+        Ok(mut errors) => errors.extend(new_errors),
+        Err(_e) => warn!("Failed to record path errors: poisoned lock"),
+    }
+}
+
+// Time left as `45s`, `3m 20s` or `2h 05m`
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3_600 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m", seconds / 3_600, seconds % 3_600 / 60),
+    }
+}
+
+// Directories read per second so far, as `120 dirs/s`
+fn format_rate(directories: usize, elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs_f64();
+    if seconds < 0.1 {
+        return "- dirs/s".to_string();
+    }
+    format!("{:.0} dirs/s", directories as f64 / seconds)
+}
+
+/// Create the observer that draws progress on a terminal in the chosen style
+///
+/// A `total` counted before the walk, as with `--prescan`, is used for its percentage
+/// and time left instead of earlier runs or estimates.
+pub fn progress_observer(display: ProgressDisplay, root: &Path, total: Option<usize>) -> Box<dyn SearchObserver> {
+    match (display, total) {
+        (ProgressDisplay::Line, None) => Box::new(ProgressReporter::for_root(root)),
+        (ProgressDisplay::Line, Some(total)) => Box::new(ProgressReporter::for_root(root).with_total(total)),
+        (ProgressDisplay::Bar, None) => Box::new(ProgressBarObserver::for_root(root)),
+        (ProgressDisplay::Bar, Some(total)) => Box::new(ProgressBarObserver::for_root(root).with_total(total)),
+    }
+}
+
+/// Create an appropriate observer based on whether and how progress should be shown
+///
+/// Progress is only drawn for people watching a terminal, so scripts see no change.
+pub fn create_observer(config: &FileSearchConfig) -> Box<dyn SearchObserver> {
+    if config.show_progress && io::stderr().is_terminal() {
+        let root = Path::new(config.get_path());
+        let total = (config.prescan && config.recursive)
+            .then(|| count_directories(root, config.hidden, config.follow_symlinks));
+        progress_observer(config.progress, root, total)
+    } else if config.show_progress {
         Box::new(TrackingObserver::new())
     } else {
         Box::new(SilentObserver::new())
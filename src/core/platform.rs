@@ -1,6 +1,14 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
 use log::debug;
 
+use crate::core::file_id::FileId;
+
+// Case sensitivity of each volume probed so far, by volume
+static CASE_INSENSITIVE_VOLUMES: Mutex<Option<HashMap<u64, bool>>> = Mutex::new(None);
+
 /// Platform-specific functionality for the search utility
 /// 
 /// This module provides platform-specific implementations for various
@@ -52,6 +60,51 @@ impl Platform {
         }
     }
     
+    /// Check if a path resolves to the root of a filesystem, such as `/`, `/..` or `D:\`
+    pub fn is_filesystem_root(path: &Path) -> bool {
+        path.canonicalize().is_ok_and(|path| path.parent().is_none())
+    }
+    
+    /// Key identifying a file regardless of how its path was spelled
+    ///
+    /// The path is made absolute and, on volumes that ignore case such as the
+    /// defaults on Windows and macOS, lowercased so that `Foo.txt` and `foo.txt`
+    /// give the same key. Paths that cannot be resolved are keyed as given.
+    pub fn path_key(path: &Path) -> PathBuf {
+        let Ok(canonical) = fs::canonicalize(path) else {
+            return path.to_path_buf();
+        };
+        if Self::is_case_insensitive(&canonical) {
+            PathBuf::from(canonical.to_string_lossy().to_lowercase())
+        } else {
+            canonical
+        }
+    }
+    
+    /// Check whether the volume holding an existing path treats names differing only in case as the same
+    ///
+    /// The answer is probed once per volume by looking the path up with the case of
+    /// one of its names flipped. When no name has letters to flip the platform
+    /// default is assumed: case-insensitive on Windows and macOS.
+    pub fn is_case_insensitive(path: &Path) -> bool {
+        let Ok(id) = FileId::of(path) else {
+            return cfg!(any(target_os = "windows", target_os = "macos"));
+        };
+        let volume = id.volume();
+        
+        let mut volumes = CASE_INSENSITIVE_VOLUMES.lock().unwrap_or_else(|e| e.into_inner());
+        let volumes = volumes.get_or_insert_with(HashMap::new);
+        if let Some(&insensitive) = volumes.get(&volume) {
+            return insensitive;
+        }
+        
+        let insensitive = probe_case_insensitive(path, id)
+            .unwrap_or(cfg!(any(target_os = "windows", target_os = "macos")));
+        debug!("Volume {} is case-{}", volume, if insensitive { "insensitive" } else { "sensitive" });
+        volumes.insert(volume, insensitive);
+        insensitive
+    }
+    
     /// Get a list of common system directories to search based on the platform
     pub fn common_search_paths() -> Vec<PathBuf> {
         #[cfg(target_os = "linux")]
@@ -103,3 +156,24 @@ impl Platform {
         std::env::current_dir().ok()
     }
 }
+
+// Look up a path with the case of its last name that has letters flipped; None if no name has any
+fn probe_case_insensitive(path: &Path, id: FileId) -> Option<bool> {
+    let path = fs::canonicalize(path).ok()?;
+    let components: Vec<Component> = path.components().collect();
+    let (index, flipped) = components.iter().enumerate().rev().find_map(|(index, component)| {
+        let Component::Normal(name) = component else { return None };
+        let name = name.to_string_lossy();
+        let flipped: String = name.chars()
+            .map(|c| if c.is_uppercase() { c.to_lowercase().next().unwrap_or(c) } else { c.to_uppercase().next().unwrap_or(c) })
+            .collect();
+        (flipped != name).then_some((index, flipped))
+    })?;
+    
+    let mut probe: PathBuf = components[..index].iter().collect();
+    probe.push(flipped);
+    probe.extend(&components[index + 1..]);
+    
+    // The flipped name may also exist as a different file on a case-sensitive volume
+    Some(FileId::of(&probe).is_ok_and(|found| found == id))
+}
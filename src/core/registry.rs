@@ -3,6 +3,7 @@ use std::{
     collections::HashMap,
     fmt,
     path::Path,
+    time::Duration,
     sync::{Arc, RwLock, RwLockReadGuard},
 };
 
@@ -10,7 +11,7 @@ use anyhow::Result;
 use log::warn;
 
 use crate::{
-    core::observer::{NullObserver, SearchObserver},
+    core::observer::{NullObserver, PathError, SearchEvent, SearchObserver, SearchStats},
     filters::{Filter, FilterResult},
 };
 
@@ -64,6 +65,22 @@ impl FilterRegistry {
         }
         FilterResult::Accept
     }
+
+    /// Apply all filters to a path offered as a search result, directories included
+    pub fn apply_all_to_entry(&self, path: &Path) -> FilterResult {
+        for filter in self.filters.values() {
+            let result = filter.filter_entry(path);
+            if result != FilterResult::Accept {
+                return result;
+            }
+        }
+        FilterResult::Accept
+    }
+
+    /// Check whether any filter prunes a directory, so that it is not entered
+    pub fn prunes(&self, path: &Path) -> bool {
+        self.filters.values().any(|filter| filter.filter(path) == FilterResult::Prune)
+    }
 }
 
 impl Default for FilterRegistry {
@@ -73,6 +90,9 @@ impl Default for FilterRegistry {
 }
 
 /// Registry for observers that can be notified of search events
+///
+/// Acts as the event bus of a search: every [`SearchEvent`] published on it is
+/// delivered to each registered observer in turn.
 pub struct ObserverRegistry {
     observers: RwLock<Vec<Arc<dyn SearchObserver>>>,
 }
@@ -152,41 +172,18 @@ impl ObserverRegistry {
             .map_err(|_| anyhow::anyhow!("Failed to acquire read lock: poisoned lock"))
     }
 
-    /// Notify all observers that a file was found
-    pub fn notify_file_found(&self, path: &Path) {
+    /// Publish an event to every registered observer
+    pub fn publish(&self, event: SearchEvent<'_>) {
         let observers = match self.read_observers() {
             Ok(obs) => obs,
             Err(e) => {
-                warn!("Failed to notify observers of file found: {}", e);
+                warn!("Failed to publish {:?}: {}", event, e);
                 return;
             }
         };
-        
-        if observers.is_empty() {
-            return;
-        }
 
         for observer in observers.iter() {
-            observer.file_found(path);
-        }
-    }
-
-    /// Notify all observers that a directory was processed
-    pub fn notify_directory_processed(&self, path: &Path) {
-        let observers = match self.read_observers() {
-            Ok(obs) => obs,
-            Err(e) => {
-                warn!("Failed to notify observers of directory processed: {}", e);
-                return;
-            }
-        };
-        
-        if observers.is_empty() {
-            return;
-        }
-
-        for observer in observers.iter() {
-            observer.directory_processed(path);
+            observer.on_event(&event);
         }
     }
 
@@ -224,6 +221,53 @@ impl ObserverRegistry {
         observers.iter().map(|o| o.directories_count()).sum()
     }
 
+    /// Get the largest error count reported by any observer
+    ///
+    /// Every observer sees the same errors, so summing would count them repeatedly.
+    pub fn errors_count(&self) -> usize {
+        match self.read_observers() {
+            Ok(observers) => observers.iter().map(|o| o.errors_count()).max().unwrap_or(0),
+            Err(e) => {
+                warn!("Failed to get error count: {}", e);
+                0
+            }
+        }
+    }
+
+    /// Totals of a search as seen by the observer that counted the most
+    ///
+    /// Every observer sees the same events, so summing would count them repeatedly.
+    pub fn stats(&self, elapsed: Duration) -> SearchStats {
+        let observers = match self.read_observers() {
+            Ok(obs) => obs,
+            Err(e) => {
+                warn!("Failed to get search stats: {}", e);
+                return SearchStats { elapsed, ..SearchStats::default() };
+            }
+        };
+
+        SearchStats {
+            files: observers.iter().map(|o| o.files_count()).max().unwrap_or(0),
+            directories: observers.iter().map(|o| o.directories_count()).max().unwrap_or(0),
+            errors: observers.iter().map(|o| o.errors_count()).max().unwrap_or(0),
+            elapsed,
+        }
+    }
+
+    /// Get the path errors recorded by the observer that saw the most of them
+    pub fn path_errors(&self) -> Vec<PathError> {
+        match self.read_observers() {
+            Ok(observers) => observers.iter()
+                .map(|o| o.path_errors())
+                .max_by_key(|errors| errors.len())
+                .unwrap_or_default(),
+            Err(e) => {
+                warn!("Failed to get path errors: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
     /// Get an observer of a specific type
     /// 
     /// Returns the first observer that matches the specified type
@@ -1,7 +1,47 @@
-use std::{fmt, path::Path};
+use std::{borrow::Cow, collections::HashMap, fmt, fs, path::{Path, PathBuf}, sync::{Arc, Mutex}};
+use ignore::{gitignore::{Gitignore, GitignoreBuilder}, Match};
+use log::warn;
 use serde::{Serialize, Deserialize};
 
+use crate::filters::{Filter, FilterResult};
+
 /// Strategy for traversing directories
+///
+/// Decides which directories the walk enters and which files reach the filters,
+/// before any metadata is read. Embedders can implement their own and add it to a
+/// finder with [`FileFinderBuilder::with_additional_traversal_strategy`], which
+/// keeps the built-in ones, or replace them with
+/// [`with_traversal_strategy`](FileFinderBuilder::with_traversal_strategy). For
+/// example, to leave out the placeholders a cloud drive keeps for files that
+/// have not been downloaded:
+///
+/// ```
+/// use std::path::Path;
+/// use oqab::core::FinderFactory;
+/// use oqab::core::config::FileSearchConfig;
+/// use oqab::core::traversal::TraversalStrategy;
+///
+/// struct SkipPlaceholders;
+///
+/// impl TraversalStrategy for SkipPlaceholders {
+///     fn should_process_directory(&self, _path: &Path) -> bool {
+///         true
+///     }
+///
+///     fn should_process_file(&self, path: &Path) -> bool {
+///         path.extension().is_none_or(|extension| extension != "icloud")
+///     }
+/// }
+///
+/// let config = FileSearchConfig::new();
+/// let finder = FinderFactory::builder_from_search_config(&config)?
+///     .with_additional_traversal_strategy(Box::new(SkipPlaceholders))
+///     .build();
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// [`FileFinderBuilder::with_additional_traversal_strategy`]: crate::core::FileFinderBuilder::with_additional_traversal_strategy
+/// [`with_traversal_strategy`]: crate::core::FileFinderBuilder::with_traversal_strategy
 pub trait TraversalStrategy: Send + Sync {
     /// Check if the given directory should be processed
     fn should_process_directory(&self, path: &Path) -> bool;
@@ -10,6 +50,16 @@ pub trait TraversalStrategy: Send + Sync {
     fn should_process_file(&self, path: &Path) -> bool;
 }
 
+impl<T: TraversalStrategy + ?Sized> TraversalStrategy for Arc<T> {
+    fn should_process_directory(&self, path: &Path) -> bool {
+        (**self).should_process_directory(path)
+    }
+
+    fn should_process_file(&self, path: &Path) -> bool {
+        (**self).should_process_file(path)
+    }
+}
+
 /// Default strategy that processes everything except hidden files and directories
 #[derive(Debug, Clone)]
 pub struct DefaultTraversalStrategy {
@@ -123,9 +173,10 @@ impl RegexTraversalStrategy {
     }
 }
 
-impl TraversalStrategy for RegexTraversalStrategy {
-    fn should_process_directory(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
+impl RegexTraversalStrategy {
+    /// Check a path against the include and exclude patterns
+    fn is_match(&self, path: &Path) -> bool {
+        let path_str = normalize_separators(path);
         
         if let Some(ref exclude) = self.exclude_pattern {
             if exclude.is_match(&path_str) {
@@ -139,26 +190,250 @@ impl TraversalStrategy for RegexTraversalStrategy {
             true
         }
     }
+}
+
+impl TraversalStrategy for RegexTraversalStrategy {
+    fn should_process_directory(&self, path: &Path) -> bool {
+        self.is_match(path)
+    }
     
     fn should_process_file(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
-        
-        if let Some(ref exclude) = self.exclude_pattern {
-            if exclude.is_match(&path_str) {
-                return false;
+        self.is_match(path)
+    }
+}
+
+/// Traversal strategy that skips what git ignores, reading ignore files as the walk reaches them
+///
+/// The `.gitignore` of each directory applies to everything below it, with deeper
+/// files taking precedence, and the repository's `.git/info/exclude` applies
+/// throughout. Nothing outside a git repository is ignored, and a nested repository
+/// only follows its own rules. The `.git` directories themselves are always skipped.
+///
+/// It is also a [`Filter`], pruning ignored directories, for searches that do not
+/// go through a finder.
+pub struct GitAwareTraversalStrategy {
+    root: PathBuf,
+    canonical_root: PathBuf,
+    levels: Mutex<HashMap<PathBuf, Arc<IgnoreLevel>>>,
+}
+
+// The ignore rules in effect in one directory
+struct IgnoreLevel {
+    // The directory's own ignore files, the one taking precedence first
+    matchers: Vec<Gitignore>,
+    // None where the rules of the directories above stop applying
+    parent: Option<Arc<IgnoreLevel>>,
+    in_repository: bool,
+}
+
+impl IgnoreLevel {
+    // Whether the rules here or above ignore a path, the deepest match deciding
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let mut level = Some(self);
+        while let Some(current) = level {
+            for matcher in &current.matchers {
+                match matcher.matched(path, is_dir) {
+                    Match::Ignore(_) => return true,
+                    Match::Whitelist(_) => return false,
+                    Match::None => {}
+                }
             }
+            level = current.parent.as_deref();
+        }
+        false
+    }
+}
+
+impl fmt::Debug for GitAwareTraversalStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GitAwareTraversalStrategy")
+            .field("root", &self.root)
+            .field("directories_read", &self.levels.lock().map_or(0, |levels| levels.len()))
+            .finish()
+    }
+}
+
+impl GitAwareTraversalStrategy {
+    /// Create a strategy for a walk of `root`, which may lie anywhere in a repository
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        let root = root.as_ref().to_path_buf();
+        let canonical_root = fs::canonicalize(&root).unwrap_or_else(|_| root.clone());
+        GitAwareTraversalStrategy { root, canonical_root, levels: Mutex::new(HashMap::new()) }
+    }
+    
+    /// Check whether git would ignore a path
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if path.file_name().is_some_and(|name| name == ".git") {
+            return true;
         }
+        // Ancestors of the root are found through its canonical path, so the
+        // rules of a repository are found from a relative root such as `.`
+        let path = match path.strip_prefix(&self.root) {
+            Ok(relative) => self.canonical_root.join(relative),
+            Err(_) => std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()),
+        };
+        let Some(dir) = path.parent() else {
+            return false;
+        };
         
-        if let Some(ref include) = self.include_pattern {
-            include.is_match(&path_str)
-        } else {
-            true
+        let level = self.level(dir);
+        level.in_repository && level.is_ignored(&path, is_dir)
+    }
+    
+    // The rules in effect in a directory, reading its ignore files the first time
+    fn level(&self, dir: &Path) -> Arc<IgnoreLevel> {
+        if let Some(level) = self.levels.lock().ok().and_then(|levels| levels.get(dir).cloned()) {
+            return level;
+        }
+        
+        let top = dir.join(".git").exists();
+        let parent = if top { None } else { dir.parent().map(|parent| self.level(parent)) };
+        // Within a directory, its .gitignore wins over the repository's exclude file
+        let exclude = top.then(|| read_ignore_file(dir, &dir.join(".git").join("info").join("exclude"))).flatten();
+        let level = Arc::new(IgnoreLevel {
+            matchers: read_ignore_file(dir, &dir.join(".gitignore")).into_iter().chain(exclude).collect(),
+            in_repository: top || parent.as_ref().is_some_and(|parent| parent.in_repository),
+            parent,
+        });
+        if let Ok(mut levels) = self.levels.lock() {
+            levels.insert(dir.to_path_buf(), Arc::clone(&level));
         }
+        level
+    }
+}
+
+/// Traversal strategy applying the `.oqabignore` files of the search root and the directories below it
+///
+/// The files use gitignore syntax, with patterns relative to the directory holding
+/// them, and deeper files take precedence. They let a project keep its exclusions
+/// without passing `--exclude` on every search.
+pub struct IgnoreFileTraversalStrategy {
+    root: PathBuf,
+    levels: Mutex<HashMap<PathBuf, Arc<IgnoreLevel>>>,
+}
+
+impl fmt::Debug for IgnoreFileTraversalStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IgnoreFileTraversalStrategy")
+            .field("root", &self.root)
+            .field("directories_read", &self.levels.lock().map_or(0, |levels| levels.len()))
+            .finish()
+    }
+}
+
+impl IgnoreFileTraversalStrategy {
+    /// Name of the per-project ignore files
+    pub const FILE_NAME: &'static str = ".oqabignore";
+    
+    /// Create a strategy for a walk of `root`
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        IgnoreFileTraversalStrategy { root: root.as_ref().to_path_buf(), levels: Mutex::new(HashMap::new()) }
+    }
+    
+    /// Check whether the ignore files of the directories above a path ignore it
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        path.parent()
+            .and_then(|dir| self.level(dir))
+            .is_some_and(|level| level.is_ignored(path, is_dir))
+    }
+    
+    // The rules in effect in a directory below the root, reading its ignore file the first time
+    fn level(&self, dir: &Path) -> Option<Arc<IgnoreLevel>> {
+        if !dir.starts_with(&self.root) {
+            return None;
+        }
+        if let Some(level) = self.levels.lock().ok().and_then(|levels| levels.get(dir).cloned()) {
+            return Some(level);
+        }
+        
+        let parent = if dir == self.root { None } else { dir.parent().and_then(|parent| self.level(parent)) };
+        let level = Arc::new(IgnoreLevel {
+            matchers: read_ignore_file(dir, &dir.join(Self::FILE_NAME)).into_iter().collect(),
+            parent,
+            in_repository: false,
+        });
+        if let Ok(mut levels) = self.levels.lock() {
+            levels.insert(dir.to_path_buf(), Arc::clone(&level));
+        }
+        Some(level)
+    }
+}
+
+impl TraversalStrategy for IgnoreFileTraversalStrategy {
+    fn should_process_directory(&self, path: &Path) -> bool {
+        !self.is_ignored(path, true)
+    }
+    
+    fn should_process_file(&self, path: &Path) -> bool {
+        !self.is_ignored(path, false)
+    }
+}
+
+impl Filter for IgnoreFileTraversalStrategy {
+    fn filter(&self, path: &Path) -> FilterResult {
+        let is_dir = path.is_dir();
+        match self.is_ignored(path, is_dir) {
+            true if is_dir => FilterResult::Prune,
+            true => FilterResult::Reject,
+            false => FilterResult::Accept,
+        }
+    }
+}
+
+// Compile an ignore file whose patterns are relative to `dir`, if it exists
+fn read_ignore_file(dir: &Path, path: &Path) -> Option<Gitignore> {
+    if !path.is_file() {
+        return None;
+    }
+    let mut builder = GitignoreBuilder::new(dir);
+    if let Some(e) = builder.add(path) {
+        warn!("Skipping invalid lines in {}: {}", path.display(), e);
+    }
+    match builder.build() {
+        Ok(matcher) if !matcher.is_empty() => Some(matcher),
+        Ok(_) => None,
+        Err(e) => {
+            warn!("Ignoring {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+impl TraversalStrategy for GitAwareTraversalStrategy {
+    fn should_process_directory(&self, path: &Path) -> bool {
+        !self.is_ignored(path, true)
+    }
+    
+    fn should_process_file(&self, path: &Path) -> bool {
+        !self.is_ignored(path, false)
+    }
+}
+
+impl Filter for GitAwareTraversalStrategy {
+    fn filter(&self, path: &Path) -> FilterResult {
+        let is_dir = path.is_dir();
+        match self.is_ignored(path, is_dir) {
+            true if is_dir => FilterResult::Prune,
+            true => FilterResult::Reject,
+            false => FilterResult::Accept,
+        }
+    }
+}
+
+/// Render a path with forward slashes so regex patterns such as `src/.*\.rs`
+/// match on every platform, including Windows where the native separator is `\`
+pub fn normalize_separators(path: &Path) -> Cow<'_, str> {
+    let path_str = path.to_string_lossy();
+    
+    if std::path::MAIN_SEPARATOR == '/' {
+        path_str
+    } else {
+        Cow::Owned(path_str.replace(std::path::MAIN_SEPARATOR, "/"))
     }
 }
 
 /// Check if a path is hidden (starts with "." on Unix or has hidden attribute on Windows)
-fn is_hidden(path: &Path) -> bool {
+pub(crate) fn is_hidden(path: &Path) -> bool {
     path.file_name()
         .and_then(|name| name.to_str())
         .map(|name| name.starts_with('.'))
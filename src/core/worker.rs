@@ -6,7 +6,7 @@ use std::{
         Arc, Mutex,
     },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use log::{debug, warn};
@@ -22,9 +22,29 @@ pub enum WorkerMessage {
     Done,
 }
 
+/// What one worker thread did during a search
+///
+/// Time spent idle means the worker was starved of work, while time spent waiting
+/// for the queues means it was contending with other workers for them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkerStats {
+    /// Index of the worker in its pool
+    pub id: usize,
+    /// Directories the worker processed
+    pub directories: usize,
+    /// Files the worker processed
+    pub files: usize,
+    /// Time spent processing directories and files
+    pub busy: Duration,
+    /// Time spent sleeping because both queues were empty
+    pub idle: Duration,
+    /// Time spent waiting for access to the queues
+    pub queue_wait: Duration,
+}
+
 /// Thread pool for processing directories and files
 pub struct WorkerPool {
-    workers: Vec<thread::JoinHandle<()>>,
+    workers: Vec<thread::JoinHandle<WorkerStats>>,
     directory_tx: Sender<WorkerMessage>,
     file_tx: Sender<WorkerMessage>,
     stopped: Arc<AtomicBool>,
@@ -61,13 +81,17 @@ impl WorkerPool {
                 thread::spawn(move || {
                     debug!("Worker thread {} started", id);
                     
+                    let mut stats = WorkerStats { id, ..WorkerStats::default() };
                     let timeout = Duration::from_millis(100);
                     
                     while !stopped.load(Ordering::Relaxed) {
                         let mut processed_message = false;
                         
                         // Process directories first with timeout
-                        let dir_msg = match directory_rx.lock() {
+                        let waiting = Instant::now();
+                        let directory_lock = directory_rx.lock();
+                        stats.queue_wait += waiting.elapsed();
+                        let dir_msg = match directory_lock {
                             Ok(rx) => {
                                 match rx.try_recv() {
                                     Ok(msg) => Some(msg),
@@ -87,7 +111,10 @@ impl WorkerPool {
                         if let Some(message) = dir_msg {
                             match message {
                                 WorkerMessage::Directory(dir) => {
+                                    let started = Instant::now();
                                     directory_consumer(dir);
+                                    stats.busy += started.elapsed();
+                                    stats.directories += 1;
                                     processed_message = true;
                                 }
                                 WorkerMessage::File(file) => {
@@ -107,7 +134,10 @@ impl WorkerPool {
                         }
 
                         // Then process files
-                        let file_msg = match file_rx.lock() {
+                        let waiting = Instant::now();
+                        let file_lock = file_rx.lock();
+                        stats.queue_wait += waiting.elapsed();
+                        let file_msg = match file_lock {
                             Ok(rx) => {
                                 match rx.try_recv() {
                                     Ok(msg) => Some(msg),
@@ -127,7 +157,10 @@ impl WorkerPool {
                         if let Some(message) = file_msg {
                             match message {
                                 WorkerMessage::File(file) => {
+                                    let started = Instant::now();
                                     file_consumer(file);
+                                    stats.busy += started.elapsed();
+                                    stats.files += 1;
                                     processed_message = true;
                                 }
                                 WorkerMessage::Directory(dir) => {
@@ -148,11 +181,14 @@ impl WorkerPool {
                         
                         // If no messages were processed this cycle, yield to other threads
                         if !processed_message {
+                            let sleeping = Instant::now();
                             thread::sleep(timeout);
+                            stats.idle += sleeping.elapsed();
                         }
                     }
                     
                     debug!("Worker thread {} shutting down", id);
+                    stats
                 })
             })
             .collect();
@@ -211,18 +247,24 @@ impl WorkerPool {
         }
     }
     
-    /// Wait for all worker threads to complete
-    pub fn join(mut self) {
+    /// Wait for all worker threads to complete and collect what each one did
+    ///
+    /// Workers drain the queued work and exit once they reach the `Done` message;
+    /// stopping them here would abandon directories that were not picked up yet.
+    /// Threads that panicked are left out of the returned statistics.
+    pub fn join(mut self) -> Vec<WorkerStats> {
         debug!("Waiting for all worker threads to complete");
-        self.stopped.store(true, Ordering::Relaxed);
         self.complete();
 
-        while let Some(worker) = self.workers.pop() {
-            if let Err(e) = worker.join() {
-                warn!("Worker thread panicked: {:?}", e);
+        let mut stats = Vec::with_capacity(self.workers.len());
+        for worker in self.workers.drain(..) {
+            match worker.join() {
+                Ok(worker_stats) => stats.push(worker_stats),
+                Err(e) => warn!("Worker thread panicked: {:?}", e),
             }
         }
         debug!("All worker threads joined successfully");
+        stats
     }
 }
 
@@ -0,0 +1,225 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::filters::{Filter, FilterResult};
+
+/// How serious an audit finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Low => "LOW",
+            Severity::Medium => "MEDIUM",
+            Severity::High => "HIGH",
+        })
+    }
+}
+
+/// Built-in security checks run by `oqab audit`
+///
+/// Each rule is backed by a filter that accepts the paths it flags, so rules can
+/// also be registered on a finder like any other filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditRule {
+    /// Files, and directories without the sticky bit, that anyone may write to
+    WorldWritable,
+    /// Executables running with the privileges of their owner or group
+    Setuid,
+    /// Symbolic links whose target does not exist
+    DanglingSymlink,
+    /// Files owned by a user that no longer exists
+    OrphanedOwner,
+    /// Keys and configuration under `.ssh` readable or writable by others
+    SshPermissions,
+}
+
+impl AuditRule {
+    /// Every built-in rule
+    pub const ALL: [AuditRule; 5] = [
+        AuditRule::WorldWritable,
+        AuditRule::Setuid,
+        AuditRule::DanglingSymlink,
+        AuditRule::OrphanedOwner,
+        AuditRule::SshPermissions,
+    ];
+
+    /// Name used on the command line and in reports
+    pub fn name(self) -> &'static str {
+        match self {
+            AuditRule::WorldWritable => "world-writable",
+            AuditRule::Setuid => "setuid",
+            AuditRule::DanglingSymlink => "dangling-symlink",
+            AuditRule::OrphanedOwner => "orphaned-owner",
+            AuditRule::SshPermissions => "ssh-permissions",
+        }
+    }
+
+    /// Severity of the rule's findings
+    pub fn severity(self) -> Severity {
+        match self {
+            AuditRule::WorldWritable => Severity::Medium,
+            AuditRule::Setuid => Severity::High,
+            AuditRule::DanglingSymlink => Severity::Low,
+            AuditRule::OrphanedOwner => Severity::Medium,
+            AuditRule::SshPermissions => Severity::High,
+        }
+    }
+
+    /// Explanation printed with each finding
+    pub fn message(self) -> &'static str {
+        match self {
+            AuditRule::WorldWritable => "writable by every user",
+            AuditRule::Setuid => "runs with the privileges of its owner or group (setuid/setgid)",
+            AuditRule::DanglingSymlink => "symbolic link to a missing target",
+            AuditRule::OrphanedOwner => "owned by a user that does not exist",
+            AuditRule::SshPermissions => "SSH file accessible to other users",
+        }
+    }
+
+    /// Filter accepting the paths this rule flags
+    pub fn filter(self) -> Box<dyn Filter> {
+        match self {
+            AuditRule::WorldWritable => Box::new(WorldWritableFilter),
+            AuditRule::Setuid => Box::new(SetuidFilter),
+            AuditRule::DanglingSymlink => Box::new(DanglingSymlinkFilter),
+            AuditRule::OrphanedOwner => Box::new(OrphanedOwnerFilter::from_system()),
+            AuditRule::SshPermissions => Box::new(SshPermissionsFilter),
+        }
+    }
+}
+
+/// Permission bits and owner of a path, without following symbolic links
+#[cfg(unix)]
+fn mode_and_owner(path: &Path) -> Option<(fs::Metadata, u32, u32)> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = fs::symlink_metadata(path).ok()?;
+    let (mode, uid) = (metadata.mode(), metadata.uid());
+    Some((metadata, mode, uid))
+}
+
+#[cfg(not(unix))]
+fn mode_and_owner(_path: &Path) -> Option<(fs::Metadata, u32, u32)> {
+    None
+}
+
+fn flag(flagged: bool) -> FilterResult {
+    if flagged { FilterResult::Accept } else { FilterResult::Reject }
+}
+
+/// Filter accepting regular files and non-sticky directories writable by everyone
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WorldWritableFilter;
+
+impl Filter for WorldWritableFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        let Some((metadata, mode, _)) = mode_and_owner(path) else {
+            return FilterResult::Reject;
+        };
+        // Shared directories like /tmp are world-writable by design but sticky
+        let sticky = mode & 0o1000 != 0;
+        let relevant = metadata.is_file() || (metadata.is_dir() && !sticky);
+        flag(relevant && mode & 0o002 != 0)
+    }
+}
+
+/// Filter accepting regular files with the setuid or setgid bit
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SetuidFilter;
+
+impl Filter for SetuidFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        let Some((metadata, mode, _)) = mode_and_owner(path) else {
+            return FilterResult::Reject;
+        };
+        flag(metadata.is_file() && mode & 0o6000 != 0)
+    }
+}
+
+/// Filter accepting symbolic links that point nowhere
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DanglingSymlinkFilter;
+
+impl Filter for DanglingSymlinkFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        let is_link = fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink());
+        flag(is_link && fs::metadata(path).is_err())
+    }
+}
+
+/// Filter accepting paths whose owner is not a known user
+///
+/// Users are read from `/etc/passwd`; accounts only known to a directory
+/// service are not seen, so the filter flags nothing if that file is missing.
+#[derive(Debug, Clone, Default)]
+pub struct OrphanedOwnerFilter {
+    uids: Option<HashSet<u32>>,
+}
+
+impl OrphanedOwnerFilter {
+    /// Create a filter from the system's user database
+    pub fn from_system() -> Self {
+        let uids = fs::read_to_string("/etc/passwd").ok().map(|passwd| Self::parse_passwd(&passwd));
+        Self { uids }
+    }
+
+    /// Create a filter that knows only the given user ids
+    pub fn with_uids<I: IntoIterator<Item = u32>>(uids: I) -> Self {
+        Self { uids: Some(uids.into_iter().collect()) }
+    }
+
+    // User ids from the third field of each passwd line
+    fn parse_passwd(passwd: &str) -> HashSet<u32> {
+        passwd.lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split(':').nth(2)?.parse().ok())
+            .collect()
+    }
+}
+
+impl Filter for OrphanedOwnerFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        let (Some(uids), Some((_, _, uid))) = (&self.uids, mode_and_owner(path)) else {
+            return FilterResult::Reject;
+        };
+        flag(!uids.contains(&uid))
+    }
+}
+
+/// Filter accepting `.ssh` directories and their contents with overly open permissions
+///
+/// Public keys and `known_hosts` may be readable by others but not writable;
+/// everything else, including the directory itself, must be private to its owner.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SshPermissionsFilter;
+
+impl Filter for SshPermissionsFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        let is_ssh_dir = path.file_name().is_some_and(|name| name == ".ssh");
+        let in_ssh_dir = path.parent().and_then(Path::file_name).is_some_and(|name| name == ".ssh");
+        if !is_ssh_dir && !in_ssh_dir {
+            return FilterResult::Reject;
+        }
+        let Some((metadata, mode, _)) = mode_and_owner(path) else {
+            return FilterResult::Reject;
+        };
+        if metadata.file_type().is_symlink() {
+            return FilterResult::Reject;
+        }
+
+        let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        let public = !is_ssh_dir && (name.ends_with(".pub") || name.starts_with("known_hosts"));
+        let forbidden = if public { 0o022 } else { 0o077 };
+        flag(mode & forbidden != 0)
+    }
+}
@@ -30,10 +30,9 @@ impl CompositeFilter {
         self.filters.push(Box::new(filter));
         self
     }
-}
 
-impl Filter for CompositeFilter {
-    fn filter(&self, path: &Path) -> FilterResult {
+    // Combine what each filter makes of a path
+    fn combine(&self, judge: impl Fn(&dyn Filter) -> FilterResult) -> FilterResult {
         if self.filters.is_empty() {
             return FilterResult::Accept;
         }
@@ -43,7 +42,7 @@ impl Filter for CompositeFilter {
                 let mut result = FilterResult::Accept;
                 
                 for filter in &self.filters {
-                    match filter.filter(path) {
+                    match judge(filter.as_ref()) {
                         FilterResult::Accept => continue,
                         FilterResult::Reject => {
                             result = FilterResult::Reject;
@@ -64,7 +63,7 @@ impl Filter for CompositeFilter {
                 let mut found_prune = false;
                 
                 for filter in &self.filters {
-                    match filter.filter(path) {
+                    match judge(filter.as_ref()) {
                         FilterResult::Accept => {
                             found_accept = true;
                             break;
@@ -88,6 +87,16 @@ impl Filter for CompositeFilter {
     }
 }
 
+impl Filter for CompositeFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        self.combine(|filter| filter.filter(path))
+    }
+
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        self.combine(|filter| filter.filter_entry(path))
+    }
+}
+
 /// A type-safe composite filter using generics
 #[derive(Debug)]
 pub struct TypedCompositeFilter<F1, F2>
@@ -113,33 +122,105 @@ where
             operation,
         }
     }
-}
 
-impl<F1, F2> Filter for TypedCompositeFilter<F1, F2>
-where
-    F1: Filter,
-    F2: Filter,
-{
-    fn filter(&self, path: &Path) -> FilterResult {
+    // Combine what both filters make of a path, asking the second only when needed
+    fn combine(&self, judge: impl Fn(&dyn Filter) -> FilterResult) -> FilterResult {
         match self.operation {
             FilterOperation::And => {
-                match self.filter1.filter(path) {
-                    FilterResult::Accept => self.filter2.filter(path),
+                match judge(&self.filter1) {
+                    FilterResult::Accept => judge(&self.filter2),
                     other => other,
                 }
             }
             FilterOperation::Or => {
-                match self.filter1.filter(path) {
+                match judge(&self.filter1) {
                     FilterResult::Accept => FilterResult::Accept,
                     FilterResult::Prune => {
-                        match self.filter2.filter(path) {
+                        match judge(&self.filter2) {
                             FilterResult::Accept => FilterResult::Accept,
                             _ => FilterResult::Prune,
                         }
                     }
-                    FilterResult::Reject => self.filter2.filter(path),
+                    FilterResult::Reject => judge(&self.filter2),
                 }
             }
         }
     }
-} 
\ No newline at end of file
+}
+
+impl<F1, F2> Filter for TypedCompositeFilter<F1, F2>
+where
+    F1: Filter,
+    F2: Filter,
+{
+    fn filter(&self, path: &Path) -> FilterResult {
+        self.combine(|filter| filter.filter(path))
+    }
+
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        self.combine(|filter| filter.filter_entry(path))
+    }
+}
+
+/// Filter inverting another: accepted paths are rejected and rejected ones accepted
+///
+/// Pruned directories stay pruned, since nothing below them was looked at.
+#[derive(Debug)]
+pub struct NotFilter<F: Filter> {
+    filter: F,
+}
+
+impl<F: Filter> NotFilter<F> {
+    /// Create a filter accepting what `filter` rejects
+    pub fn new(filter: F) -> Self {
+        NotFilter { filter }
+    }
+}
+
+impl<F: Filter> NotFilter<F> {
+    fn invert(result: FilterResult) -> FilterResult {
+        match result {
+            FilterResult::Accept => FilterResult::Reject,
+            FilterResult::Reject => FilterResult::Accept,
+            FilterResult::Prune => FilterResult::Prune,
+        }
+    }
+}
+
+impl<F: Filter> Filter for NotFilter<F> {
+    fn filter(&self, path: &Path) -> FilterResult {
+        Self::invert(self.filter.filter(path))
+    }
+
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        Self::invert(self.filter.filter_entry(path))
+    }
+}
+
+/// Fluent combinators for building filter trees without spelling out composite types
+///
+/// ```
+/// use oqab::filters::{ExtensionFilter, FilterExt, NameFilter, SizeFilter};
+///
+/// let filter = ExtensionFilter::new("rs")
+///     .and(NameFilter::contains("test").or(SizeFilter::min(1 << 20)))
+///     .and(NameFilter::contains("generated").not());
+/// ```
+pub trait FilterExt: Filter + Sized {
+    /// Accept paths that both filters accept
+    fn and<F: Filter>(self, other: F) -> TypedCompositeFilter<Self, F> {
+        TypedCompositeFilter::new(self, other, FilterOperation::And)
+    }
+
+    /// Accept paths that either filter accepts
+    fn or<F: Filter>(self, other: F) -> TypedCompositeFilter<Self, F> {
+        TypedCompositeFilter::new(self, other, FilterOperation::Or)
+    }
+
+    /// Accept paths this filter rejects
+    fn not(self) -> NotFilter<Self> {
+        NotFilter::new(self)
+    }
+}
+
+impl<F: Filter> FilterExt for F {}
@@ -0,0 +1,63 @@
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::filters::{Filter, FilterResult};
+use crate::utils::content::{is_binary, read_sample};
+
+/// Whether a file holds text or binary data, as chosen with `--text-only` or `--binary-only`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentKind {
+    /// Content grep would search line by line
+    Text,
+    /// Content grep skips as binary
+    Binary,
+}
+
+/// Filter accepting files whose content is text, or binary
+///
+/// Files are classified from their first bytes by the same detector grep uses
+/// to skip binary files, so the two always agree. Empty files count as text.
+#[derive(Debug, Clone, Copy)]
+pub struct ContentFilter {
+    kind: ContentKind,
+}
+
+impl ContentFilter {
+    /// Create a filter for files with the given kind of content
+    pub fn new(kind: ContentKind) -> Self {
+        Self { kind }
+    }
+
+    /// Create a filter for text files
+    pub fn text() -> Self {
+        Self::new(ContentKind::Text)
+    }
+
+    /// Create a filter for binary files
+    pub fn binary() -> Self {
+        Self::new(ContentKind::Binary)
+    }
+}
+
+impl Filter for ContentFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        // Directories are always accepted for traversal
+        if path.is_dir() {
+            return FilterResult::Accept;
+        }
+        self.filter_entry(path)
+    }
+
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        // Only regular files have content to classify
+        if !fs::metadata(path).is_ok_and(|metadata| metadata.is_file()) {
+            return FilterResult::Reject;
+        }
+        match read_sample(path) {
+            Ok(sample) if is_binary(&sample) == (self.kind == ContentKind::Binary) => FilterResult::Accept,
+            _ => FilterResult::Reject,
+        }
+    }
+}
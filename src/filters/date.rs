@@ -1,69 +1,194 @@
+use std::fs;
+use std::io;
 use std::path::Path;
-use std::time::{UNIX_EPOCH};
-use chrono::{NaiveDate};
+use std::time::{SystemTime, UNIX_EPOCH};
+use chrono::{DateTime, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone};
+use serde::{Deserialize, Serialize};
 
 use crate::filters::{Filter, FilterResult};
 
-/// Filter that matches files by their modification date
+/// Zone in which dates and times written without an offset are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateZone {
+    /// The time zone of the machine running the search
+    #[default]
+    Local,
+    /// Coordinated Universal Time
+    Utc,
+}
+
+/// Timestamp of a file that a date filter compares
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Timestamp {
+    /// When the content was last modified
+    #[default]
+    Modified,
+    /// When the file was created, where the platform and filesystem record it
+    Created,
+    /// When the file was last read
+    Accessed,
+}
+
+impl Timestamp {
+    /// Read this timestamp from a file's metadata
+    ///
+    /// Creation times are unsupported on some platforms and filesystems, and
+    /// reading them fails with [`io::ErrorKind::Unsupported`] there.
+    pub fn of(self, metadata: &fs::Metadata) -> io::Result<SystemTime> {
+        match self {
+            Timestamp::Modified => metadata.modified(),
+            Timestamp::Created => metadata.created(),
+            Timestamp::Accessed => metadata.accessed(),
+        }
+    }
+}
+
+/// Filter that matches files by their modification date, or another of their timestamps
 #[derive(Debug)]
 pub struct DateFilter {
     /// Files must be newer than this timestamp (in seconds since UNIX epoch)
     newer_than: Option<i64>,
     /// Files must be older than this timestamp (in seconds since UNIX epoch)
     older_than: Option<i64>,
+    /// Files must have been modified strictly after this time
+    modified_after: Option<SystemTime>,
+    /// Files must have been modified strictly before this time
+    modified_before: Option<SystemTime>,
+    /// Timestamp compared against the bounds
+    timestamp: Timestamp,
 }
 
 impl DateFilter {
     /// Create a new date filter
     pub fn new(newer_than: Option<i64>, older_than: Option<i64>) -> Self {
-        Self { newer_than, older_than }
+        Self { newer_than, older_than, modified_after: None, modified_before: None, timestamp: Timestamp::Modified }
+    }
+    
+    /// Compare another timestamp than the modification time, such as the creation time
+    ///
+    /// Files whose timestamp cannot be read, as where creation times are not
+    /// recorded, are rejected.
+    pub fn with_timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+    
+    /// Create a filter from already parsed bounds, either of which may be open
+    pub fn between(newer_than: Option<SystemTime>, older_than: Option<SystemTime>) -> Self {
+        Self::new(newer_than.map(epoch_seconds), older_than.map(epoch_seconds))
+    }
+    
+    /// Create a filter for files modified more recently than a reference file, like `find -newer`
+    pub fn newer_than_file(reference: &Path) -> io::Result<Self> {
+        Self::between_files(Some(reference), None)
     }
     
-    /// Create a filter for files newer than the given date string (YYYY-MM-DD)
+    /// Create a filter for files modified before a reference file
+    pub fn older_than_file(reference: &Path) -> io::Result<Self> {
+        Self::between_files(None, Some(reference))
+    }
+    
+    /// Create a filter for files modified between two reference files, either of which may be absent
+    ///
+    /// The modification times of the references are read once, when the filter is
+    /// created, and compared at full precision: a file modified in the same instant
+    /// as a reference matches neither bound.
+    pub fn between_files(newer_than: Option<&Path>, older_than: Option<&Path>) -> io::Result<Self> {
+        let mut filter = Self::new(None, None);
+        filter.modified_after = newer_than.map(modified_time).transpose()?;
+        filter.modified_before = older_than.map(modified_time).transpose()?;
+        Ok(filter)
+    }
+    
+    /// Create a filter for files newer than the given date, read in local time if it has no offset
     pub fn newer_than(date_str: &str) -> Result<Self, chrono::ParseError> {
-        let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")?;
-        let datetime = date.and_hms_opt(0, 0, 0).unwrap();
-        // Convert to UTC and get timestamp
-        let timestamp = datetime.and_utc().timestamp();
-        
-        Ok(Self {
-            newer_than: Some(timestamp),
-            older_than: None,
-        })
+        Ok(Self::between(Some(start_of(date_str, DateZone::Local)?), None))
     }
     
-    /// Create a filter for files older than the given date string (YYYY-MM-DD)
+    /// Create a filter for files older than the given date, read in local time if it has no offset
     pub fn older_than(date_str: &str) -> Result<Self, chrono::ParseError> {
-        let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")?;
-        let datetime = date.and_hms_opt(23, 59, 59).unwrap();
-        // Convert to UTC and get timestamp
-        let timestamp = datetime.and_utc().timestamp();
-        
-        Ok(Self {
-            newer_than: None,
-            older_than: Some(timestamp),
-        })
+        Ok(Self::between(None, Some(end_of(date_str, DateZone::Local)?)))
     }
     
-    /// Create a filter for files within a date range (YYYY-MM-DD)
+    /// Create a filter for files within a date range, read in local time where there is no offset
     pub fn date_range(
         newer_than: &str,
         older_than: &str,
     ) -> Result<Self, chrono::ParseError> {
-        let newer_date = NaiveDate::parse_from_str(newer_than, "%Y-%m-%d")?;
-        let newer_datetime = newer_date.and_hms_opt(0, 0, 0).unwrap();
-        // Convert to UTC and get timestamp
-        let newer_timestamp = newer_datetime.and_utc().timestamp();
-        
-        let older_date = NaiveDate::parse_from_str(older_than, "%Y-%m-%d")?;
-        let older_datetime = older_date.and_hms_opt(23, 59, 59).unwrap();
-        // Convert to UTC and get timestamp
-        let older_timestamp = older_datetime.and_utc().timestamp();
-        
-        Ok(Self {
-            newer_than: Some(newer_timestamp),
-            older_than: Some(older_timestamp),
-        })
+        Ok(Self::between(Some(start_of(newer_than, DateZone::Local)?), Some(end_of(older_than, DateZone::Local)?)))
+    }
+}
+
+/// Parse a date or time as the earliest instant it covers
+///
+/// Accepts RFC 3339 timestamps such as `2024-06-01T14:30:00+02:00`, which name an
+/// exact instant, and `YYYY-MM-DD` dates or `YYYY-MM-DDTHH:MM[:SS]` times without an
+/// offset, which are read in `zone`. A bare date stands for the start of that day.
+pub fn start_of(date_str: &str, zone: DateZone) -> Result<SystemTime, chrono::ParseError> {
+    parse_date_at(date_str, zone, NaiveTime::MIN)
+}
+
+/// Parse a date or time as the latest instant it covers
+///
+/// Accepts the same forms as [`start_of`]; a bare date stands for the last second of that day.
+pub fn end_of(date_str: &str, zone: DateZone) -> Result<SystemTime, chrono::ParseError> {
+    parse_date_at(date_str, zone, NaiveTime::from_hms_opt(23, 59, 59).unwrap())
+}
+
+/// Parse a date as the first second of that day in UTC, or a time as given
+pub fn start_of_day(date_str: &str) -> Result<SystemTime, chrono::ParseError> {
+    start_of(date_str, DateZone::Utc)
+}
+
+/// Parse a date as the last second of that day in UTC, or a time as given
+pub fn end_of_day(date_str: &str) -> Result<SystemTime, chrono::ParseError> {
+    end_of(date_str, DateZone::Utc)
+}
+
+fn parse_date_at(date_str: &str, zone: DateZone, time: NaiveTime) -> Result<SystemTime, chrono::ParseError> {
+    let date_str = date_str.trim();
+    if let Ok(instant) = DateTime::parse_from_rfc3339(date_str) {
+        return Ok(SystemTime::from(instant));
+    }
+    let datetime = ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"].iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(date_str, format).ok());
+    let datetime = match datetime {
+        Some(datetime) => datetime,
+        // Report errors against the plain date form, the one most people write
+        None => NaiveDate::parse_from_str(date_str, "%Y-%m-%d")?.and_time(time),
+    };
+    Ok(in_zone(datetime, zone))
+}
+
+// Instant a wall-clock time names in a zone
+fn in_zone(datetime: NaiveDateTime, zone: DateZone) -> SystemTime {
+    match zone {
+        DateZone::Utc => SystemTime::from(datetime.and_utc()),
+        DateZone::Local => match Local.from_local_datetime(&datetime) {
+            // Times repeated when clocks go back are read as their first occurrence
+            LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => SystemTime::from(time),
+            // Times skipped when clocks go forward are read with the offset in force after the change
+            LocalResult::None => {
+                let offset = Local.offset_from_utc_datetime(&datetime).fix().local_minus_utc();
+                SystemTime::from(datetime.and_utc() - Duration::seconds(i64::from(offset)))
+            }
+        },
+    }
+}
+
+// Modification time of a reference file, naming the file if it cannot be read
+fn modified_time(reference: &Path) -> io::Result<SystemTime> {
+    fs::metadata(reference)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", reference.display(), e)))
+}
+
+fn epoch_seconds(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
     }
 }
 
@@ -75,12 +200,19 @@ impl Filter for DateFilter {
             Err(_) => return FilterResult::Reject,
         };
         
-        // Get modification time
-        let modified = match metadata.modified() {
+        // Get modification time, or whichever timestamp is compared
+        let modified = match self.timestamp.of(&metadata) {
             Ok(time) => time,
             Err(_) => return FilterResult::Reject,
         };
         
+        // Compare against reference files before rounding to seconds
+        if self.modified_after.is_some_and(|after| modified <= after)
+            || self.modified_before.is_some_and(|before| modified >= before)
+        {
+            return FilterResult::Reject;
+        }
+        
         // Convert to timestamp
         let modified_secs = match modified.duration_since(UNIX_EPOCH) {
             Ok(duration) => duration.as_secs() as i64,
@@ -0,0 +1,68 @@
+use std::path::{Path, PathBuf};
+use crate::filters::{Filter, FilterResult};
+
+/// Filter limiting results to a range of depths below the search root, like `find -mindepth -maxdepth`
+///
+/// Entries directly inside the root are at depth 1. Directories beyond the maximum
+/// depth are pruned, while those above the minimum depth are walked through without
+/// being reported. Paths outside the root, such as the targets of followed links,
+/// are accepted.
+#[derive(Debug, Clone)]
+pub struct DepthFilter {
+    root: PathBuf,
+    min_depth: Option<usize>,
+    max_depth: Option<usize>,
+}
+
+impl DepthFilter {
+    /// Create a filter for depths between the given bounds, both inclusive
+    pub fn new<P: AsRef<Path>>(root: P, min_depth: Option<usize>, max_depth: Option<usize>) -> Self {
+        Self { root: root.as_ref().to_path_buf(), min_depth, max_depth }
+    }
+
+    /// Create a filter reporting nothing deeper than the given depth
+    pub fn max<P: AsRef<Path>>(root: P, depth: usize) -> Self {
+        Self::new(root, None, Some(depth))
+    }
+
+    /// Create a filter reporting nothing shallower than the given depth
+    pub fn min<P: AsRef<Path>>(root: P, depth: usize) -> Self {
+        Self::new(root, Some(depth), None)
+    }
+
+    /// Depth of a path below the root, if it is inside it
+    pub fn depth(&self, path: &Path) -> Option<usize> {
+        path.strip_prefix(&self.root).ok().map(|relative| relative.components().count())
+    }
+
+    // Judge a path offered as a result at a known depth
+    fn judge(&self, depth: usize) -> FilterResult {
+        if self.max_depth.is_some_and(|max_depth| depth > max_depth) {
+            return FilterResult::Prune;
+        }
+        if self.min_depth.is_some_and(|min_depth| depth < min_depth) {
+            return FilterResult::Reject;
+        }
+        FilterResult::Accept
+    }
+}
+
+impl Filter for DepthFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        let Some(depth) = self.depth(path) else {
+            return FilterResult::Accept;
+        };
+        match self.judge(depth) {
+            // Directories too shallow to report may still lead to deeper results
+            FilterResult::Reject if path.is_dir() => FilterResult::Accept,
+            result => result,
+        }
+    }
+
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        match self.depth(path) {
+            Some(depth) => self.judge(depth),
+            None => FilterResult::Accept,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::io;
+use std::path::Path;
+use crate::core::file_id::FileId;
+use crate::filters::{Filter, FilterResult};
+
+/// Filter keeping a search on the filesystem of its root, like `find -xdev`
+///
+/// Directories on another device or volume, mount points included, are pruned
+/// rather than entered, and files reached on one through followed links are
+/// rejected. Entries whose device cannot be read are left to the other filters.
+#[derive(Debug, Clone, Copy)]
+pub struct SameFileSystemFilter {
+    volume: u64,
+}
+
+impl SameFileSystemFilter {
+    /// Create a filter for the filesystem holding `root`
+    pub fn new(root: &Path) -> io::Result<Self> {
+        Ok(Self { volume: FileId::of(root)?.volume() })
+    }
+    
+    /// Whether a path lies on the root's filesystem, following symbolic links
+    pub fn contains(&self, path: &Path) -> Option<bool> {
+        FileId::of(path).ok().map(|id| id.volume() == self.volume)
+    }
+}
+
+impl Filter for SameFileSystemFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        match self.contains(path) {
+            Some(false) if path.is_dir() => FilterResult::Prune,
+            Some(false) => FilterResult::Reject,
+            _ => FilterResult::Accept,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::fs;
+use std::path::Path;
+use crate::filters::{Filter, FilterResult};
+
+/// Filter that matches zero-byte files and, as results, directories with no entries
+///
+/// Every directory is accepted for traversal, since an empty file may be found
+/// anywhere below it. A directory is only judged empty when offered as a result,
+/// once the search asks for directories with `--type d`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EmptyFilter;
+
+impl EmptyFilter {
+    /// Create a filter matching empty files and directories
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Whether a directory holds no entries at all, hidden ones included
+pub fn is_empty_dir(path: &Path) -> bool {
+    fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_none())
+}
+
+impl Filter for EmptyFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        match fs::metadata(path) {
+            Ok(metadata) if metadata.is_dir() => FilterResult::Accept,
+            Ok(metadata) if metadata.is_file() && metadata.len() == 0 => FilterResult::Accept,
+            _ => FilterResult::Reject,
+        }
+    }
+
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        match fs::metadata(path) {
+            Ok(metadata) if metadata.is_dir() && is_empty_dir(path) => FilterResult::Accept,
+            // Pipes and sockets have no length either, but are never empty files
+            Ok(metadata) if metadata.is_file() && metadata.len() == 0 => FilterResult::Accept,
+            _ => FilterResult::Reject,
+        }
+    }
+}
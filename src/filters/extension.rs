@@ -5,15 +5,37 @@ use crate::filters::{Filter, FilterResult};
 #[derive(Debug, Clone)]
 pub struct ExtensionFilter {
     extension: String,
+    case_sensitive: bool,
 }
 
 impl ExtensionFilter {
-    /// Create a new ExtensionFilter
+    /// Create a new case-insensitive ExtensionFilter
     pub fn new(extension: &str) -> Self {
         // Normalize extension by removing leading dots
         let extension = extension.trim_start_matches('.');
         ExtensionFilter {
             extension: extension.to_string(),
+            case_sensitive: false,
+        }
+    }
+    
+    /// Create a new ExtensionFilter that compares extensions exactly
+    pub fn case_sensitive(extension: &str) -> Self {
+        Self::new(extension).with_case_sensitive(true)
+    }
+    
+    /// Set whether extensions are compared case-sensitively
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+    
+    /// Check whether a file extension matches this filter's extension
+    fn matches(&self, ext: &str) -> bool {
+        if self.case_sensitive {
+            ext == self.extension
+        } else {
+            ext.to_lowercase() == self.extension.to_lowercase()
         }
     }
 }
@@ -23,9 +45,12 @@ impl Filter for ExtensionFilter {
         if path.is_dir() {
             return FilterResult::Accept;
         }
+        self.filter_entry(path)
+    }
 
+    fn filter_entry(&self, path: &Path) -> FilterResult {
         match path.extension() {
-            Some(ext) if ext.to_string_lossy() == self.extension || self.extension == "*" => {
+            Some(ext) if self.extension == "*" || self.matches(&ext.to_string_lossy()) => {
                 FilterResult::Accept
             }
             None if self.extension.is_empty() => {
@@ -35,4 +60,4 @@ impl Filter for ExtensionFilter {
             _ => FilterResult::Reject
         }
     }
-} 
\ No newline at end of file
+}
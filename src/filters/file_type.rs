@@ -0,0 +1,101 @@
+use std::fs::{self, FileType};
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::filters::{Filter, FilterResult};
+
+/// Kind of directory entry a search can return, as chosen with `--type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryType {
+    /// Regular file
+    File,
+    /// Directory
+    Directory,
+    /// Symbolic link, whatever it points to
+    Symlink,
+    /// Unix domain socket
+    Socket,
+    /// Named pipe
+    Fifo,
+}
+
+impl EntryType {
+    /// Kind of an entry from its type, or none for devices and other special files
+    ///
+    /// Symbolic links are only reported as such by types read without following them,
+    /// like those of `fs::symlink_metadata` and directory entries.
+    pub fn of(file_type: &FileType) -> Option<Self> {
+        if file_type.is_symlink() {
+            return Some(EntryType::Symlink);
+        }
+        if file_type.is_file() {
+            return Some(EntryType::File);
+        }
+        if file_type.is_dir() {
+            return Some(EntryType::Directory);
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+            if file_type.is_socket() {
+                return Some(EntryType::Socket);
+            }
+            if file_type.is_fifo() {
+                return Some(EntryType::Fifo);
+            }
+        }
+        None
+    }
+}
+
+/// Filter accepting entries of the chosen kinds, judged without following symbolic links
+///
+/// The finder also asks it which entries to offer as results at all, so that
+/// directories, links, sockets and pipes are only looked at when asked for.
+/// Without any kinds given, only regular files are accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeFilter {
+    types: Vec<EntryType>,
+}
+
+impl TypeFilter {
+    /// Create a filter accepting entries of any of the given kinds
+    pub fn new<I: IntoIterator<Item = EntryType>>(types: I) -> Self {
+        let mut types: Vec<EntryType> = types.into_iter().collect();
+        if types.is_empty() {
+            types.push(EntryType::File);
+        }
+        Self { types }
+    }
+
+    /// Create a filter accepting regular files only, as searches do by default
+    pub fn files() -> Self {
+        Self::new([EntryType::File])
+    }
+
+    /// Whether entries of this kind are accepted
+    pub fn includes(&self, entry_type: EntryType) -> bool {
+        self.types.contains(&entry_type)
+    }
+
+    /// Whether an entry with this type is accepted
+    pub fn accepts(&self, file_type: &FileType) -> bool {
+        EntryType::of(file_type).is_some_and(|entry_type| self.includes(entry_type))
+    }
+}
+
+impl Default for TypeFilter {
+    fn default() -> Self {
+        Self::files()
+    }
+}
+
+impl Filter for TypeFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        match fs::symlink_metadata(path) {
+            Ok(metadata) if self.accepts(&metadata.file_type()) => FilterResult::Accept,
+            _ => FilterResult::Reject,
+        }
+    }
+}
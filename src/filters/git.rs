@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+use crate::filters::{Filter, FilterResult};
+
+/// Errors that can occur while querying git for candidate files
+#[derive(Error, Debug)]
+pub enum GitFilterError {
+    #[error("Failed to run git: {0}")]
+    Command(#[from] io::Error),
+    
+    #[error("Not a git repository: {0}")]
+    NotARepository(String),
+    
+    #[error("git {0} failed: {1}")]
+    GitFailed(String, String),
+}
+
+/// Filter that only accepts files known to git
+#[derive(Debug, Clone)]
+pub struct GitFilter {
+    files: HashSet<PathBuf>,
+}
+
+impl GitFilter {
+    /// Create a filter accepting files tracked in the repository containing `root`
+    pub fn tracked(root: &Path) -> Result<Self, GitFilterError> {
+        let top_level = Self::top_level(root)?;
+        let files = Self::run_git(root, &["ls-files", "-z", "--full-name"])?;
+        
+        Ok(Self::from_relative_paths(&top_level, files))
+    }
+    
+    /// Create a filter accepting tracked files with staged or unstaged modifications
+    pub fn modified(root: &Path) -> Result<Self, GitFilterError> {
+        let top_level = Self::top_level(root)?;
+        let mut files = Self::run_git(root, &["ls-files", "-z", "--full-name", "--modified"])?;
+        files.extend(Self::run_git(root, &["diff", "--cached", "--name-only", "-z"])?);
+        
+        Ok(Self::from_relative_paths(&top_level, files))
+    }
+    
+    /// Create a filter accepting no files, for a selection git could not be asked about
+    pub fn none() -> Self {
+        GitFilter { files: HashSet::new() }
+    }
+    
+    /// Number of files known to this filter
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+    
+    /// Check whether git reported no files at all
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+    
+    fn from_relative_paths(top_level: &Path, files: Vec<String>) -> Self {
+        let files = files
+            .into_iter()
+            .map(|file| top_level.join(file))
+            .collect();
+        
+        GitFilter { files }
+    }
+    
+    /// Resolve the canonical top-level directory of the repository containing `root`
+    fn top_level(root: &Path) -> Result<PathBuf, GitFilterError> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["rev-parse", "--show-toplevel"])
+            .output()?;
+        
+        if !output.status.success() {
+            return Err(GitFilterError::NotARepository(root.display().to_string()));
+        }
+        
+        let top_level = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim_end());
+        Ok(top_level.canonicalize().unwrap_or(top_level))
+    }
+    
+    /// Run a git command in `root` and split its NUL-delimited output
+    fn run_git(root: &Path, args: &[&str]) -> Result<Vec<String>, GitFilterError> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(args)
+            .output()?;
+        
+        if !output.status.success() {
+            return Err(GitFilterError::GitFailed(
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        
+        Ok(output.stdout
+            .split(|&byte| byte == 0)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| String::from_utf8_lossy(entry).into_owned())
+            .collect())
+    }
+}
+
+impl Filter for GitFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        if path.is_dir() {
+            return FilterResult::Accept;
+        }
+        
+        let canonical = match path.canonicalize() {
+            Ok(canonical) => canonical,
+            Err(_) => return FilterResult::Reject,
+        };
+        
+        if self.files.contains(&canonical) {
+            FilterResult::Accept
+        } else {
+            FilterResult::Reject
+        }
+    }
+}
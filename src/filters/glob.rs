@@ -0,0 +1,142 @@
+use std::path::Path;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use crate::core::traversal::normalize_separators;
+use crate::filters::{Filter, FilterResult};
+
+/// Filter based on a shell-style glob such as `*.rs` or `src/**/test_*.py`
+///
+/// Brace alternatives, including nested ones like `*.{rs,{c,h}pp}`, are
+/// expanded first. Alternatives without a `/` are matched against the file name
+/// only; those with one are matched against the whole path.
+#[derive(Debug, Clone)]
+pub struct GlobFilter {
+    names: GlobSet,
+    paths: GlobSet,
+}
+
+impl GlobFilter {
+    /// Create a new GlobFilter
+    pub fn new(pattern: &str) -> Result<Self, globset::Error> {
+        let mut names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+        for alternative in expand_braces(pattern) {
+            let glob = Glob::new(&alternative)?;
+            if alternative.contains('/') {
+                paths.add(glob);
+            } else {
+                names.add(glob);
+            }
+        }
+        Ok(GlobFilter {
+            names: names.build()?,
+            paths: paths.build()?,
+        })
+    }
+
+    /// Check a path against the glob without touching the filesystem
+    pub fn is_match(&self, path: &Path) -> bool {
+        path.file_name().is_some_and(|name| self.names.is_match(name))
+            || (!self.paths.is_empty() && self.paths.is_match(normalize_separators(path).as_ref()))
+    }
+}
+
+impl Filter for GlobFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        // Always allow directory traversal
+        if path.is_dir() {
+            return FilterResult::Accept;
+        }
+        self.filter_entry(path)
+    }
+
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        if self.is_match(path) {
+            FilterResult::Accept
+        } else {
+            FilterResult::Reject
+        }
+    }
+}
+
+/// Expand brace alternatives the way shells do: `a{b,c{d,e}}` gives `ab`, `acd` and `ace`
+///
+/// Braces escaped with a backslash or inside `[...]` classes are left alone, and a
+/// pattern with unbalanced braces or a group without a comma is returned unchanged
+/// for the glob compiler to interpret.
+pub fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some((open, close, commas)) = find_group(pattern) else {
+        return vec![pattern.to_string()];
+    };
+
+    let (prefix, suffix) = (&pattern[..open], &pattern[close + 1..]);
+    let mut bounds = vec![open];
+    bounds.extend(commas);
+    bounds.push(close);
+
+    // Expand the suffix too, since it can hold further groups
+    let suffixes = expand_braces(suffix);
+    bounds.windows(2)
+        .flat_map(|bound| expand_braces(&pattern[bound[0] + 1..bound[1]]))
+        .flat_map(|alternative| suffixes.iter().map(move |suffix| format!("{}{}{}", prefix, alternative, suffix)))
+        .collect()
+}
+
+/// Find the first brace group with alternatives, returning its braces and top-level commas
+fn find_group(pattern: &str) -> Option<(usize, usize, Vec<usize>)> {
+    let bytes = pattern.as_bytes();
+    let mut start = 0;
+    while let Some(open) = next_unescaped(bytes, start, b'{') {
+        let mut depth = 0;
+        let mut commas = Vec::new();
+        let mut i = open + 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 1,
+                b'[' => i = class_end(bytes, i),
+                b'{' => depth += 1,
+                b'}' if depth == 0 => {
+                    if !commas.is_empty() {
+                        return Some((open, i, commas));
+                    }
+                    break;
+                }
+                b'}' => depth -= 1,
+                b',' if depth == 0 => commas.push(i),
+                _ => {}
+            }
+            i += 1;
+        }
+        start = open + 1;
+    }
+    None
+}
+
+/// Position of the next unescaped `target` outside character classes, from `start`
+fn next_unescaped(bytes: &[u8], start: usize, target: u8) -> Option<usize> {
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'[' => i = class_end(bytes, i),
+            byte if byte == target => return Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Position of the `]` closing a character class opened at `open`, or `open` if it is not closed
+fn class_end(bytes: &[u8], open: usize) -> usize {
+    // A `]` right after `[` or `[!` is part of the class
+    let mut i = open + 1;
+    if matches!(bytes.get(i), Some(b'!' | b'^')) {
+        i += 1;
+    }
+    if bytes.get(i) == Some(&b']') {
+        i += 1;
+    }
+    bytes[i.min(bytes.len())..].iter()
+        .position(|&byte| byte == b']')
+        .map_or(open, |offset| i + offset)
+}
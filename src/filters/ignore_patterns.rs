@@ -0,0 +1,56 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+
+use crate::filters::{Filter, FilterResult};
+
+/// Filter applying gitignore-style patterns
+///
+/// Ignored directories are pruned and ignored files rejected; `!pattern` lines
+/// re-include what earlier lines ignored, as in a `.gitignore`. Patterns
+/// containing a `/` are anchored at the root the filter was created for.
+#[derive(Debug, Clone)]
+pub struct IgnoreFilter {
+    matcher: Gitignore,
+}
+
+impl IgnoreFilter {
+    /// Compile patterns, one per line as they would appear in an ignore file
+    pub fn new<S: AsRef<str>>(root: &Path, patterns: &[S]) -> Result<Self, ignore::Error> {
+        let mut builder = GitignoreBuilder::new(root);
+        for pattern in patterns {
+            builder.add_line(None, pattern.as_ref())?;
+        }
+        Ok(Self { matcher: builder.build()? })
+    }
+
+    /// Location of the user's global ignore file, `oqab/ignore` in the config directory
+    pub fn global_location() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("oqab").join("ignore"))
+    }
+
+    /// Read the lines of the global ignore file, or none if there is no such file
+    pub fn load_global() -> io::Result<Vec<String>> {
+        let Some(location) = Self::global_location() else {
+            return Ok(Vec::new());
+        };
+        match fs::read_to_string(&location) {
+            Ok(contents) => Ok(contents.lines().map(str::to_string).collect()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(io::Error::new(e.kind(), format!("{}: {}", location.display(), e))),
+        }
+    }
+}
+
+impl Filter for IgnoreFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        let is_dir = path.is_dir();
+        match self.matcher.matched(path, is_dir) {
+            Match::Ignore(_) if is_dir => FilterResult::Prune,
+            Match::Ignore(_) => FilterResult::Reject,
+            Match::Whitelist(_) | Match::None => FilterResult::Accept,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::fs::{self, Metadata};
+use std::path::Path;
+use crate::filters::{Filter, FilterResult};
+
+/// Filter that matches entries by their number of hard links, like `find -links`
+///
+/// A regular file normally has a single link; more mean the same data is reachable
+/// under other names. Directories are always walked, and only judged by their
+/// count when offered as results. Counts are only known on Unix, so elsewhere
+/// nothing matches.
+#[derive(Debug, Clone, Copy)]
+pub struct LinksFilter {
+    min_links: Option<u64>,
+    max_links: Option<u64>,
+}
+
+impl LinksFilter {
+    /// Create a filter for link counts between the given bounds, both inclusive
+    pub fn new(min_links: Option<u64>, max_links: Option<u64>) -> Self {
+        Self { min_links, max_links }
+    }
+    
+    /// Create a filter for exactly the given number of links
+    pub fn exactly(count: u64) -> Self {
+        Self::new(Some(count), Some(count))
+    }
+    
+    /// Create a filter for at least the given number of links
+    pub fn at_least(count: u64) -> Self {
+        Self::new(Some(count), None)
+    }
+    
+    fn judge(&self, metadata: &Metadata) -> FilterResult {
+        match link_count(metadata) {
+            Some(count) if self.min_links.is_none_or(|min| count >= min)
+                && self.max_links.is_none_or(|max| count <= max) => FilterResult::Accept,
+            _ => FilterResult::Reject,
+        }
+    }
+}
+
+/// Number of hard links to an entry, where the platform records it
+#[cfg(unix)]
+pub fn link_count(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.nlink())
+}
+
+#[cfg(not(unix))]
+pub fn link_count(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+impl Filter for LinksFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        match fs::metadata(path) {
+            Ok(metadata) if metadata.is_dir() => FilterResult::Accept,
+            Ok(metadata) => self.judge(&metadata),
+            Err(_) => FilterResult::Reject,
+        }
+    }
+    
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        match fs::metadata(path) {
+            Ok(metadata) => self.judge(&metadata),
+            Err(_) => FilterResult::Reject,
+        }
+    }
+}
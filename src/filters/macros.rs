@@ -0,0 +1,74 @@
+//! The [`filter!`](crate::filter!) macro for writing filter trees as expressions
+
+/// Size units accepted by [`filter!`](crate::filter!) after a number, as in `(size > 1 mb)`
+#[allow(non_upper_case_globals)]
+pub mod units {
+    /// Bytes
+    pub const b: u64 = 1;
+    /// Kibibytes
+    pub const kb: u64 = 1 << 10;
+    /// Mebibytes
+    pub const mb: u64 = 1 << 20;
+    /// Gibibytes
+    pub const gb: u64 = 1 << 30;
+    /// Tebibytes
+    pub const tb: u64 = 1 << 40;
+}
+
+/// Build a filter from a small expression language
+///
+/// Atoms are `(ext "rs")`, `(name "test")` (a case-insensitive substring, like
+/// `--name`), size comparisons such as `(size > 1 mb)` or `(size <= 4096)`, and
+/// any other filter written as a parenthesized expression. Atoms combine with
+/// `and`, `or` and `not`; a chain may not mix `and` with `or`, so groups must be
+/// parenthesized to say which binds first.
+///
+/// ```
+/// use oqab::filter;
+/// use oqab::filters::GlobFilter;
+///
+/// let filter = filter!((ext "rs") and ((name "test") or (size > 1 mb)));
+/// let filter = filter!((not (ext "lock")) and (GlobFilter::new("**/src/**").unwrap()));
+/// ```
+#[macro_export]
+macro_rules! filter {
+    (ext $extension:literal) => {
+        $crate::filters::ExtensionFilter::new($extension)
+    };
+    (name $name:literal) => {
+        $crate::filters::NameFilter::contains($name)
+    };
+    (size $op:tt $size:literal $unit:ident) => {
+        $crate::filter!(@size $op ($size * $crate::filters::macros::units::$unit))
+    };
+    (size $op:tt $size:literal) => {
+        $crate::filter!(@size $op ($size))
+    };
+    (@size > $bytes:expr) => {
+        $crate::filters::SizeFilter::from($crate::filters::SizeConstraint::LargerThan($bytes))
+    };
+    (@size < $bytes:expr) => {
+        $crate::filters::SizeFilter::from($crate::filters::SizeConstraint::SmallerThan($bytes))
+    };
+    (@size >= $bytes:expr) => {
+        $crate::filters::SizeFilter::min($bytes)
+    };
+    (@size <= $bytes:expr) => {
+        $crate::filters::SizeFilter::max($bytes)
+    };
+    (not $operand:tt) => {
+        $crate::filters::NotFilter::new($crate::filter! $operand)
+    };
+    (( $($inner:tt)* )) => {
+        $crate::filter!($($inner)*)
+    };
+    ($first:tt and $second:tt $(and $rest:tt)*) => {
+        $crate::filters::FilterExt::and($crate::filter! $first, $crate::filter!($second $(and $rest)*))
+    };
+    ($first:tt or $second:tt $(or $rest:tt)*) => {
+        $crate::filters::FilterExt::or($crate::filter! $first, $crate::filter!($second $(or $rest)*))
+    };
+    ($filter:expr) => {
+        $filter
+    };
+}
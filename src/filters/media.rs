@@ -0,0 +1,229 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::SystemTime;
+use chrono::{NaiveDate, TimeZone, Utc};
+use log::debug;
+
+use crate::filters::{Filter, FilterResult};
+
+/// Metadata embedded in a photo or audio file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MediaMetadata {
+    /// When the photo was taken or the recording made, read as UTC
+    pub taken: Option<SystemTime>,
+    /// Camera model that took the photo
+    pub camera_model: Option<String>,
+    /// Performing artist of a recording
+    pub artist: Option<String>,
+}
+
+/// Reads embedded metadata from the file types it understands
+pub trait MetadataExtractor: Send + Sync {
+    /// Lowercase extensions of the files the extractor reads
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Read the metadata of a file, or `None` if it has none or cannot be read
+    fn extract(&self, path: &Path) -> Option<MediaMetadata>;
+}
+
+/// EXIF metadata of JPEG, TIFF, HEIF, PNG and WebP images
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExifExtractor;
+
+impl MetadataExtractor for ExifExtractor {
+    fn extensions(&self) -> &'static [&'static str] {
+        &["jpg", "jpeg", "tif", "tiff", "heic", "heif", "png", "webp"]
+    }
+
+    fn extract(&self, path: &Path) -> Option<MediaMetadata> {
+        let file = File::open(path).ok()?;
+        let exif = exif::Reader::new()
+            .read_from_container(&mut BufReader::new(file))
+            .inspect_err(|e| debug!("No EXIF data in {}: {}", path.display(), e))
+            .ok()?;
+
+        let ascii = |tag: exif::Tag| -> Option<Vec<u8>> {
+            match &exif.get_field(tag, exif::In::PRIMARY)?.value {
+                exif::Value::Ascii(values) => values.first().cloned(),
+                _ => None,
+            }
+        };
+
+        // Prefer when the shutter fired over when the file was last edited
+        let taken = ascii(exif::Tag::DateTimeOriginal)
+            .or_else(|| ascii(exif::Tag::DateTime))
+            .and_then(|value| exif::DateTime::from_ascii(&value).ok())
+            .and_then(|time| utc_time(
+                time.year.into(), time.month.into(), time.day.into(),
+                time.hour.into(), time.minute.into(), time.second.into(),
+            ));
+        let camera_model = ascii(exif::Tag::Model)
+            .map(|value| String::from_utf8_lossy(&value).trim().to_string())
+            .filter(|model| !model.is_empty());
+
+        Some(MediaMetadata { taken, camera_model, artist: None })
+    }
+}
+
+/// ID3 tags of MP3, AIFF and WAV audio
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Id3Extractor;
+
+impl MetadataExtractor for Id3Extractor {
+    fn extensions(&self) -> &'static [&'static str] {
+        &["mp3", "aiff", "aif", "wav"]
+    }
+
+    fn extract(&self, path: &Path) -> Option<MediaMetadata> {
+        use id3::TagLike;
+
+        // Detects the AIFF and WAV containers by itself
+        let tag = id3::Tag::read_from_path(path)
+            .inspect_err(|e| debug!("No ID3 tag in {}: {}", path.display(), e))
+            .ok()?;
+
+        let taken = tag.date_recorded()
+            .and_then(|time| utc_time(
+                time.year,
+                time.month.unwrap_or(1).into(),
+                time.day.unwrap_or(1).into(),
+                time.hour.unwrap_or(0).into(),
+                time.minute.unwrap_or(0).into(),
+                time.second.unwrap_or(0).into(),
+            ))
+            .or_else(|| tag.year().and_then(|year| utc_time(year, 1, 1, 0, 0, 0)));
+
+        Some(MediaMetadata {
+            taken,
+            camera_model: None,
+            artist: tag.artist().map(str::to_string),
+        })
+    }
+}
+
+// Embedded timestamps rarely carry a time zone, so they are all read as UTC
+fn utc_time(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<SystemTime> {
+    let time = NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(hour, minute, second)?;
+    Some(Utc.from_utc_datetime(&time).into())
+}
+
+/// Extractors to try for each file, chosen by extension
+pub struct ExtractorRegistry {
+    extractors: Vec<Box<dyn MetadataExtractor>>,
+}
+
+impl ExtractorRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self { extractors: Vec::new() }
+    }
+
+    /// Create a registry with the built-in EXIF and ID3 extractors
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(ExifExtractor);
+        registry.register(Id3Extractor);
+        registry
+    }
+
+    /// Add an extractor, tried after those already registered
+    pub fn register<E: MetadataExtractor + 'static>(&mut self, extractor: E) -> &mut Self {
+        self.extractors.push(Box::new(extractor));
+        self
+    }
+
+    /// Read a file's metadata with the first extractor that supports it
+    pub fn extract(&self, path: &Path) -> Option<MediaMetadata> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        self.extractors.iter()
+            .filter(|extractor| extractor.extensions().contains(&extension.as_str()))
+            .find_map(|extractor| extractor.extract(path))
+    }
+}
+
+impl Default for ExtractorRegistry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+/// Filter that matches media files by their embedded metadata
+///
+/// Files without the metadata a criterion asks for are rejected, as are files
+/// no extractor can read.
+pub struct MediaFilter {
+    registry: ExtractorRegistry,
+    taken_after: Option<SystemTime>,
+    taken_before: Option<SystemTime>,
+    camera_model: Option<String>,
+    artist: Option<String>,
+}
+
+impl MediaFilter {
+    /// Create a filter without criteria using the built-in extractors
+    pub fn new() -> Self {
+        Self::with_registry(ExtractorRegistry::with_defaults())
+    }
+
+    /// Create a filter without criteria using the given extractors
+    pub fn with_registry(registry: ExtractorRegistry) -> Self {
+        Self { registry, taken_after: None, taken_before: None, camera_model: None, artist: None }
+    }
+
+    /// Only match files taken or recorded at or after this time
+    pub fn taken_after(mut self, time: SystemTime) -> Self {
+        self.taken_after = Some(time);
+        self
+    }
+
+    /// Only match files taken or recorded before this time
+    pub fn taken_before(mut self, time: SystemTime) -> Self {
+        self.taken_before = Some(time);
+        self
+    }
+
+    /// Only match photos whose camera model contains this text, ignoring case
+    pub fn camera_model(mut self, model: &str) -> Self {
+        self.camera_model = Some(model.to_lowercase());
+        self
+    }
+
+    /// Only match recordings whose artist contains this text, ignoring case
+    pub fn artist(mut self, artist: &str) -> Self {
+        self.artist = Some(artist.to_lowercase());
+        self
+    }
+
+    fn matches(&self, metadata: &MediaMetadata) -> bool {
+        let contains = |value: &Option<String>, wanted: &Option<String>| match wanted {
+            Some(wanted) => value.as_ref().is_some_and(|value| value.to_lowercase().contains(wanted)),
+            None => true,
+        };
+
+        self.taken_after.is_none_or(|after| metadata.taken.is_some_and(|taken| taken >= after))
+            && self.taken_before.is_none_or(|before| metadata.taken.is_some_and(|taken| taken < before))
+            && contains(&metadata.camera_model, &self.camera_model)
+            && contains(&metadata.artist, &self.artist)
+    }
+}
+
+impl Default for MediaFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Filter for MediaFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        // Directories are always accepted for traversal
+        if path.is_dir() {
+            return FilterResult::Accept;
+        }
+
+        match self.registry.extract(path) {
+            Some(metadata) if self.matches(&metadata) => FilterResult::Accept,
+            _ => FilterResult::Reject,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs;
+use std::path::Path;
+
+use crate::filters::{Filter, FilterResult};
+use crate::utils::mime;
+
+/// Filter accepting files whose content is of any of the given MIME types
+///
+/// Types are recognised from the first bytes of each file, so renamed files and
+/// files without an extension are still found. Patterns are compared ignoring
+/// case and may name a whole family, as `image/*` or just `image` do.
+#[derive(Debug, Clone)]
+pub struct MimeFilter {
+    patterns: Vec<String>,
+}
+
+impl MimeFilter {
+    /// Create a filter for the given MIME types or families
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        let patterns = patterns.iter()
+            .map(|pattern| pattern.as_ref().trim().to_lowercase())
+            .map(|pattern| if pattern.contains('/') { pattern } else { format!("{}/*", pattern) })
+            .collect();
+        Self { patterns }
+    }
+
+    /// Whether a MIME type is selected by any of the patterns
+    pub fn matches(&self, mime: &str) -> bool {
+        self.patterns.iter().any(|pattern| match pattern.strip_suffix("/*") {
+            Some("*") => true,
+            Some(family) => mime.split('/').next() == Some(family),
+            None => pattern == mime,
+        })
+    }
+}
+
+impl Filter for MimeFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        // Directories are always accepted for traversal
+        if path.is_dir() {
+            return FilterResult::Accept;
+        }
+        self.filter_entry(path)
+    }
+
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        // Only regular files have content to recognise
+        if !fs::metadata(path).is_ok_and(|metadata| metadata.is_file()) {
+            return FilterResult::Reject;
+        }
+        match mime::sniff(path) {
+            Ok(mime) if self.matches(mime) => FilterResult::Accept,
+            _ => FilterResult::Reject,
+        }
+    }
+}
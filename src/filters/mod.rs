@@ -15,6 +15,15 @@ pub enum FilterResult {
 pub trait Filter: Send + Sync {
     /// Filter a path
     fn filter(&self, path: &Path) -> FilterResult;
+
+    /// Filter a path offered as a search result, whatever kind of entry it is
+    ///
+    /// `filter` accepts directories so that the walk can enter them. Filters judging
+    /// paths by name override this to judge directories the same way as files, for
+    /// searches returning directories.
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        self.filter(path)
+    }
 }
 
 /// Operation to apply to combined filters
@@ -26,15 +35,50 @@ pub enum FilterOperation {
     Or,
 }
 
+pub mod audit;
 pub mod name;
 pub mod extension;
+pub mod file_type;
 pub mod regex;
 pub mod size;
 pub mod composite;
+pub mod content;
+pub mod macros;
+pub mod mime;
 pub mod date;
+pub mod depth;
+pub mod device;
+pub mod empty;
+pub mod git;
+pub mod links;
+#[cfg(feature = "media")]
+pub mod media;
+pub mod glob;
+pub mod ignore_patterns;
+pub mod prune;
+pub mod type_group;
+#[cfg(feature = "scripting")]
+pub mod script;
 
 pub use name::NameFilter;
 pub use extension::ExtensionFilter;
-pub use regex::RegexFilter;
-pub use size::SizeFilter;
-pub use composite::{CompositeFilter, TypedCompositeFilter}; 
\ No newline at end of file
+pub use file_type::{EntryType, TypeFilter};
+pub use content::{ContentFilter, ContentKind};
+pub use date::{DateZone, Timestamp};
+pub use depth::DepthFilter;
+pub use device::SameFileSystemFilter;
+pub use empty::EmptyFilter;
+pub use regex::{RegexFilter, RegexSyntax};
+pub use size::{SizeConstraint, SizeFilter};
+pub use git::GitFilter;
+pub use links::LinksFilter;
+#[cfg(feature = "media")]
+pub use media::MediaFilter;
+pub use glob::GlobFilter;
+pub use mime::MimeFilter;
+pub use ignore_patterns::IgnoreFilter;
+pub use prune::PruneFilter;
+pub use type_group::TypeGroupFilter;
+#[cfg(feature = "scripting")]
+pub use script::{Script, ScriptFilter};
+pub use composite::{CompositeFilter, FilterExt, NotFilter, TypedCompositeFilter}; 
\ No newline at end of file
@@ -1,19 +1,56 @@
+use std::borrow::Cow;
 use std::path::Path;
+use crate::core::traversal::normalize_separators;
 use crate::filters::{Filter, FilterResult};
 
 /// Filter based on file name
 #[derive(Debug, Clone)]
 pub struct NameFilter {
     name: String,
+    // What names are compared to: the name, lowercased once when case is ignored
+    pattern: String,
+    substring: bool,
+    case_sensitive: bool,
+    full_path: bool,
 }
 
 impl NameFilter {
-    /// Create a new NameFilter
+    /// Create a new NameFilter matching the exact file name
     pub fn new(name: &str) -> Self {
         NameFilter {
             name: name.to_string(),
+            pattern: name.to_string(),
+            substring: false,
+            case_sensitive: true,
+            full_path: false,
         }
     }
+    
+    /// Create a NameFilter matching names that contain `name`, ignoring case
+    ///
+    /// This is how `--name` behaves on the command line.
+    pub fn contains(name: &str) -> Self {
+        NameFilter {
+            name: name.to_string(),
+            pattern: name.to_lowercase(),
+            substring: true,
+            case_sensitive: false,
+            full_path: false,
+        }
+    }
+    
+    /// Set whether names are compared case-sensitively
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self.pattern = if case_sensitive { self.name.clone() } else { self.name.to_lowercase() };
+        self
+    }
+    
+    /// Match against the whole path, with forward slashes, instead of the file name only
+    pub fn with_full_path(mut self, full_path: bool) -> Self {
+        self.full_path = full_path;
+        self
+    }
 }
 
 impl Filter for NameFilter {
@@ -22,16 +59,25 @@ impl Filter for NameFilter {
         if path.is_dir() {
             return FilterResult::Accept;
         }
+        self.filter_entry(path)
+    }
 
-        // Get the file name
-        match path.file_name() {
-            Some(name) => match name.to_str() {
-                Some(name_str) if name_str == self.name || self.name == "*" => {
-                    FilterResult::Accept
-                }
-                _ => FilterResult::Reject
-            },
-            None => FilterResult::Reject
-        }
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        // Get the file name, or the whole path
+        let name = if self.full_path {
+            path.to_str().map(|_| normalize_separators(path))
+        } else {
+            path.file_name().and_then(|name| name.to_str()).map(Cow::Borrowed)
+        };
+        let Some(name_str) = name else {
+            return FilterResult::Reject;
+        };
+        let name_str = if self.case_sensitive { name_str } else { Cow::Owned(name_str.to_lowercase()) };
+        let matches = self.name == "*" || if self.substring {
+            name_str.contains(self.pattern.as_str())
+        } else {
+            name_str == self.pattern
+        };
+        if matches { FilterResult::Accept } else { FilterResult::Reject }
     }
 } 
\ No newline at end of file
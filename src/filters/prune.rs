@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::filters::{Filter, FilterResult};
+
+/// Directories skipped by default: dependencies, build output, caches and version control data
+pub const DEFAULT_EXCLUDES: &[&str] = &[
+    ".git",
+    ".hg",
+    ".svn",
+    "node_modules",
+    "bower_components",
+    "target",
+    "__pycache__",
+    ".venv",
+    ".tox",
+    ".mypy_cache",
+    ".pytest_cache",
+    ".gradle",
+    ".next",
+];
+
+/// Filter that prunes directories with any of the given names, along with everything below them
+///
+/// Files are always accepted, so a file named like an excluded directory is still found.
+#[derive(Debug, Clone)]
+pub struct PruneFilter {
+    names: HashSet<String>,
+}
+
+impl PruneFilter {
+    /// Create a filter pruning directories with these exact names
+    pub fn new<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self { names: names.into_iter().map(Into::into).collect() }
+    }
+
+    /// Create a filter pruning the [`DEFAULT_EXCLUDES`]
+    pub fn default_excludes() -> Self {
+        Self::new(DEFAULT_EXCLUDES.iter().copied())
+    }
+}
+
+impl Filter for PruneFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        // Compare names first so that most paths need no metadata lookup
+        let named = path.file_name().is_some_and(|name| self.names.contains(name.to_string_lossy().as_ref()));
+        if named && path.is_dir() {
+            FilterResult::Prune
+        } else {
+            FilterResult::Accept
+        }
+    }
+}
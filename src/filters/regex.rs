@@ -1,17 +1,44 @@
+use std::iter::Peekable;
 use std::path::Path;
+use std::str::Chars;
+use serde::{Serialize, Deserialize};
+use crate::core::traversal::normalize_separators;
 use crate::filters::{Filter, FilterResult};
 
 /// Filter based on regular expression
 #[derive(Debug)]
 pub struct RegexFilter {
     regex: regex::Regex,
+    file_name_only: bool,
 }
 
 impl RegexFilter {
-    /// Create a new RegexFilter
+    /// Create a new RegexFilter matching against the whole path
     pub fn new(pattern: &str) -> Result<Self, regex::Error> {
-        let regex = regex::Regex::new(pattern)?;
-        Ok(RegexFilter { regex })
+        Self::with_syntax(pattern, RegexSyntax::Default)
+    }
+    
+    /// Create a RegexFilter matching against the whole path, written in the given syntax
+    pub fn with_syntax(pattern: &str, syntax: RegexSyntax) -> Result<Self, regex::Error> {
+        let regex = regex::Regex::new(&syntax.translate(pattern))?;
+        Ok(RegexFilter { regex, file_name_only: false })
+    }
+    
+    /// Create a RegexFilter matching against the file name only
+    pub fn file_name(pattern: &str) -> Result<Self, regex::Error> {
+        Self::file_name_with_syntax(pattern, RegexSyntax::Default)
+    }
+    
+    /// Create a RegexFilter matching against the file name only, written in the given syntax
+    pub fn file_name_with_syntax(pattern: &str, syntax: RegexSyntax) -> Result<Self, regex::Error> {
+        let regex = regex::Regex::new(&syntax.translate(pattern))?;
+        Ok(RegexFilter { regex, file_name_only: true })
+    }
+    
+    /// Match against the whole path, with forward slashes, instead of the file name only
+    pub fn with_full_path(mut self, full_path: bool) -> Self {
+        self.file_name_only = !full_path;
+        self
     }
 }
 
@@ -20,12 +47,168 @@ impl Filter for RegexFilter {
         if path.is_dir() {
             return FilterResult::Accept;
         }
+        self.filter_entry(path)
+    }
 
-        let path_str = path.to_string_lossy();
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        let path_str = if self.file_name_only {
+            path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default()
+        } else {
+            normalize_separators(path)
+        };
         if self.regex.is_match(&path_str) {
             FilterResult::Accept
         } else {
             FilterResult::Reject
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Dialect in which user-supplied regular expressions are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RegexSyntax {
+    /// Rust `regex` crate syntax
+    #[default]
+    Default,
+    /// The pattern is matched as plain text
+    Literal,
+    /// POSIX basic regular expressions as used by `grep` and `sed`
+    /// (`\(`, `\{`, `\|`, `\+` and `\?` are operators; bare ones are literal)
+    Basic,
+    /// POSIX extended regular expressions as used by `grep -E`
+    Extended,
+}
+
+impl RegexSyntax {
+    /// Rewrite a pattern written in this syntax into Rust `regex` syntax
+    pub fn translate(self, pattern: &str) -> String {
+        match self {
+            RegexSyntax::Default => pattern.to_string(),
+            RegexSyntax::Literal => regex::escape(pattern),
+            RegexSyntax::Basic => translate_posix(pattern, true),
+            RegexSyntax::Extended => translate_posix(pattern, false),
+        }
+    }
+}
+
+// Translate a POSIX regular expression, tracking where `*` and `^` are literal
+fn translate_posix(pattern: &str, basic: bool) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    // Whether the next token starts an expression (start, after a group or alternation)
+    let mut at_start = true;
+    
+    while let Some(c) = chars.next() {
+        let mut starts_expression = false;
+        match c {
+            '[' => translate_bracket(&mut chars, &mut out),
+            '\\' => match chars.next() {
+                Some(op @ ('(' | '|')) if basic => {
+                    out.push(op);
+                    starts_expression = true;
+                }
+                Some(op @ (')' | '{' | '}' | '+' | '?')) if basic => out.push(op),
+                Some(escaped) => {
+                    out.push('\\');
+                    out.push(escaped);
+                }
+                None => out.push_str("\\\\"),
+            },
+            '(' | '|' if !basic => {
+                out.push(c);
+                starts_expression = true;
+            }
+            '(' | ')' | '{' | '}' | '|' | '+' | '?' if basic => {
+                out.push('\\');
+                out.push(c);
+            }
+            // An interval that is not well formed is a literal brace
+            '{' if !is_interval(chars.clone()) => out.push_str("\\{"),
+            '*' | '+' | '?' if at_start => {
+                out.push('\\');
+                out.push(c);
+            }
+            '^' if basic && !at_start => out.push_str("\\^"),
+            '^' => {
+                out.push('^');
+                starts_expression = true;
+            }
+            '$' if basic && !at_end(chars.clone()) => out.push_str("\\$"),
+            _ => out.push(c),
+        }
+        at_start = starts_expression;
+    }
+    
+    out
+}
+
+// Copy a bracket expression, escaping characters that Rust treats specially inside classes
+fn translate_bracket(chars: &mut Peekable<Chars<'_>>, out: &mut String) {
+    out.push('[');
+    if chars.peek() == Some(&'^') {
+        out.push('^');
+        chars.next();
+    }
+    // A leading `]` is part of the set
+    if chars.peek() == Some(&']') {
+        out.push_str("\\]");
+        chars.next();
+    }
+    
+    while let Some(c) = chars.next() {
+        match c {
+            ']' => {
+                out.push(']');
+                return;
+            }
+            '[' if chars.peek() == Some(&':') => {
+                // Character classes such as [:alpha:] are supported as-is
+                out.push('[');
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if c == ']' {
+                        break;
+                    }
+                }
+            }
+            '[' | '\\' | '&' | '~' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+}
+
+// Whether the text after `{` forms a valid `{n}`, `{n,}` or `{n,m}` interval
+fn is_interval(chars: Peekable<Chars<'_>>) -> bool {
+    let mut body = String::new();
+    let mut closed = false;
+    for c in chars {
+        if c == '}' {
+            closed = true;
+            break;
+        }
+        body.push(c);
+    }
+    if !closed {
+        return false;
+    }
+    
+    let mut bounds = body.splitn(2, ',');
+    let min = bounds.next().unwrap_or("");
+    let max = bounds.next().unwrap_or("");
+    !min.is_empty()
+        && min.chars().all(|c| c.is_ascii_digit())
+        && max.chars().all(|c| c.is_ascii_digit())
+}
+
+// Whether a basic `$` is an anchor: at the end of the pattern, a group or an alternative
+fn at_end(mut chars: Peekable<Chars<'_>>) -> bool {
+    match chars.next() {
+        None => true,
+        Some('\\') => matches!(chars.next(), Some(')' | '|')),
+        Some(_) => false,
+    }
+}
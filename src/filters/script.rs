@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use log::warn;
+use rhai::{CallFnOptions, Dynamic, Engine, FuncArgs, Scope, AST};
+use crate::filters::{Filter, FilterResult};
+
+/// Operations a single script call may perform before it is aborted
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A user script written in Rhai defining search hooks
+///
+/// The script may define any of these functions:
+///
+/// - `filter(path)`, returning whether a file should be part of the results
+/// - `on_match(path)`, run for every matching file of a file search
+/// - `on_match(path, line_number, line)`, run for every matching line of a content search
+///
+/// Helpers `file_name(path)`, `extension(path)` and `file_size(path)` are
+/// available to scripts. Top-level statements run once when the script is loaded.
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+    path: PathBuf,
+}
+
+impl Script {
+    /// Compile the script at `path` and run its top-level statements
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.register_fn("file_name", |path: &str| -> String {
+            Path::new(path).file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default()
+        });
+        engine.register_fn("extension", |path: &str| -> String {
+            Path::new(path).extension().map(|ext| ext.to_string_lossy().to_string()).unwrap_or_default()
+        });
+        engine.register_fn("file_size", |path: &str| -> i64 {
+            std::fs::metadata(path).map(|metadata| metadata.len() as i64).unwrap_or(-1)
+        });
+        
+        let ast = engine.compile_file(path.clone())
+            .map_err(|e| anyhow!("Failed to compile script {}: {}", path.display(), e))?;
+        engine.run_ast(&ast)
+            .map_err(|e| anyhow!("Failed to run script {}: {}", path.display(), e))?;
+        
+        Ok(Self { engine, ast, path })
+    }
+    
+    /// Whether the script defines a function with the given name and number of parameters
+    pub fn defines(&self, name: &str, params: usize) -> bool {
+        self.ast.iter_functions().any(|function| function.name == name && function.params.len() == params)
+    }
+    
+    /// Ask the script's `filter` function whether to keep a file
+    pub fn accepts(&self, path: &Path) -> Result<bool> {
+        self.call("filter", (path.to_string_lossy().to_string(),))?
+            .as_bool()
+            .map_err(|found| anyhow!("Script {} returned {} from filter, expected a bool", self.path.display(), found))
+    }
+    
+    /// Run the script's `on_match` action for a matching file
+    pub fn on_file(&self, path: &Path) -> Result<()> {
+        self.call("on_match", (path.to_string_lossy().to_string(),))
+            .map(|_| ())
+    }
+    
+    /// Run the script's `on_match` action for a matching line
+    pub fn on_line(&self, path: &Path, line_number: usize, line: &str) -> Result<()> {
+        self.call("on_match", (path.to_string_lossy().to_string(), line_number as i64, line.to_string()))
+            .map(|_| ())
+    }
+    
+    fn call(&self, name: &str, args: impl FuncArgs) -> Result<Dynamic> {
+        // Top-level statements already ran when the script was loaded
+        let options = CallFnOptions::new().eval_ast(false);
+        self.engine.call_fn_with_options(options, &mut Scope::new(), &self.ast, name, args)
+            .map_err(|e| anyhow!("Script {} failed in {}: {}", self.path.display(), name, e))
+    }
+}
+
+/// Filter that delegates to the `filter` function of a script
+pub struct ScriptFilter {
+    script: Arc<Script>,
+}
+
+impl ScriptFilter {
+    /// Create a filter from a loaded script
+    pub fn new(script: Arc<Script>) -> Self {
+        Self { script }
+    }
+}
+
+impl Filter for ScriptFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        // Directories are always accepted for traversal
+        if path.is_dir() || !self.script.defines("filter", 1) {
+            return FilterResult::Accept;
+        }
+        
+        match self.script.accepts(path) {
+            Ok(true) => FilterResult::Accept,
+            Ok(false) => FilterResult::Reject,
+            // A failing script must not stop the search, so the file is left out
+            Err(e) => {
+                warn!("{:#}", e);
+                FilterResult::Reject
+            }
+        }
+    }
+}
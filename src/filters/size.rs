@@ -1,3 +1,4 @@
+use std::fs::Metadata;
 use std::path::Path;
 use crate::filters::{Filter, FilterResult};
 
@@ -6,27 +7,88 @@ use crate::filters::{Filter, FilterResult};
 pub struct SizeFilter {
     min_size: Option<u64>,
     max_size: Option<u64>,
+    on_disk: bool,
 }
 
 impl SizeFilter {
     /// Create a new size filter with the given size in bytes
     pub fn new(min_size: Option<u64>, max_size: Option<u64>) -> Self {
-        Self { min_size, max_size }
+        Self { min_size, max_size, on_disk: false }
     }
     
     /// Create a size filter with just a minimum size
     pub fn min(size: u64) -> Self {
-        Self { min_size: Some(size), max_size: None }
+        Self::new(Some(size), None)
     }
     
     /// Create a size filter with just a maximum size
     pub fn max(size: u64) -> Self {
-        Self { min_size: None, max_size: Some(size) }
+        Self::new(None, Some(size))
     }
     
     /// Create a size filter with both minimum and maximum size
     pub fn range(min: u64, max: u64) -> Self {
-        Self { min_size: Some(min), max_size: Some(max) }
+        Self::new(Some(min), Some(max))
+    }
+    
+    /// Measure files by the space allocated to them rather than their length
+    pub fn with_disk_size(mut self, on_disk: bool) -> Self {
+        self.on_disk = on_disk;
+        self
+    }
+}
+
+/// Size of a file in bytes, either its length or the space allocated to it
+///
+/// Allocated space is what `du` reports: less than the length for sparse files,
+/// and rounded up to whole blocks otherwise. It is read from the block count on
+/// Unix; other platforms report the length instead.
+pub fn file_size(metadata: &Metadata, on_disk: bool) -> u64 {
+    if on_disk { allocated_size(metadata) } else { metadata.len() }
+}
+
+#[cfg(unix)]
+fn allocated_size(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    // st_blocks counts 512-byte units whatever the block size of the filesystem
+    metadata.blocks().saturating_mul(512)
+}
+
+#[cfg(not(unix))]
+fn allocated_size(metadata: &Metadata) -> u64 {
+    metadata.len()
+}
+
+/// A find-style size constraint such as `+10M`, `-1G` or `4k`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeConstraint {
+    /// Strictly larger than the given size in bytes (`+N`)
+    LargerThan(u64),
+    /// Strictly smaller than the given size in bytes (`-N`)
+    SmallerThan(u64),
+    /// Exactly the given size in bytes (`N`)
+    Exactly(u64),
+}
+
+impl SizeConstraint {
+    /// Inclusive minimum and maximum sizes equivalent to this constraint
+    pub fn bounds(self) -> (Option<u64>, Option<u64>) {
+        match self {
+            SizeConstraint::LargerThan(size) => (Some(size.saturating_add(1)), None),
+            // Nothing is smaller than zero bytes, so `-0` matches no file at all
+            SizeConstraint::SmallerThan(size) => match size.checked_sub(1) {
+                Some(max) => (None, Some(max)),
+                None => (Some(1), Some(0)),
+            },
+            SizeConstraint::Exactly(size) => (Some(size), Some(size)),
+        }
+    }
+}
+
+impl From<SizeConstraint> for SizeFilter {
+    fn from(constraint: SizeConstraint) -> Self {
+        let (min_size, max_size) = constraint.bounds();
+        Self::new(min_size, max_size)
     }
 }
 
@@ -44,7 +106,7 @@ impl Filter for SizeFilter {
         }
         
         // Get file size
-        let file_size = metadata.len();
+        let file_size = file_size(&metadata, self.on_disk);
         
         // Check against minimum size if specified
         if let Some(min_size) = self.min_size {
@@ -0,0 +1,95 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use crate::filters::{Filter, FilterResult};
+
+/// Built-in groups of extensions selectable with `--type-group`
+pub const TYPE_GROUPS: &[(&str, &[&str])] = &[
+    ("image", &["jpg", "jpeg", "png", "gif", "bmp", "tif", "tiff", "webp", "heic", "heif", "svg", "ico", "raw", "cr2", "nef", "arw", "dng", "psd"]),
+    ("video", &["mp4", "m4v", "mkv", "mov", "avi", "wmv", "flv", "webm", "mpg", "mpeg", "3gp", "ogv"]),
+    ("audio", &["mp3", "wav", "flac", "aac", "m4a", "ogg", "oga", "opus", "wma", "aiff", "aif", "mid", "midi"]),
+    ("archive", &["zip", "tar", "gz", "tgz", "bz2", "xz", "zst", "7z", "rar", "iso", "dmg", "jar", "deb", "rpm"]),
+    ("code", &[
+        "rs", "c", "h", "cc", "cpp", "hpp", "cs", "go", "java", "kt", "kts", "scala", "swift", "m",
+        "py", "rb", "php", "pl", "lua", "js", "mjs", "cjs", "ts", "tsx", "jsx", "vue", "svelte",
+        "sh", "bash", "zsh", "ps1", "sql", "hs", "ml", "ex", "exs", "erl", "clj", "dart", "zig",
+    ]),
+    ("doc", &["pdf", "doc", "docx", "odt", "rtf", "txt", "md", "rst", "tex", "epub", "xls", "xlsx", "ods", "ppt", "pptx", "odp"]),
+];
+
+/// A type group that is neither built in nor defined in the configuration
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown type group '{name}' (known groups: {known})")]
+pub struct UnknownTypeGroup {
+    /// Name that was asked for
+    pub name: String,
+    /// Comma-separated names of the groups that exist
+    pub known: String,
+}
+
+/// Extensions of a group, preferring a definition from the configuration over the built-in one
+pub fn group_extensions(name: &str, custom: &BTreeMap<String, Vec<String>>) -> Option<Vec<String>> {
+    let name = name.trim().to_lowercase();
+    custom.get(&name).cloned().or_else(|| {
+        TYPE_GROUPS.iter()
+            .find(|(group, _)| *group == name)
+            .map(|(_, extensions)| extensions.iter().map(|extension| extension.to_string()).collect())
+    })
+}
+
+/// Names of every built-in and configured group, sorted
+pub fn group_names(custom: &BTreeMap<String, Vec<String>>) -> Vec<String> {
+    let mut names: Vec<String> = TYPE_GROUPS.iter()
+        .map(|(group, _)| group.to_string())
+        .chain(custom.keys().cloned())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Filter accepting files whose extension belongs to any of the selected groups
+///
+/// Extensions are compared ignoring case.
+#[derive(Debug, Clone)]
+pub struct TypeGroupFilter {
+    extensions: HashSet<String>,
+}
+
+impl TypeGroupFilter {
+    /// Create a filter for the built-in groups of the given names
+    pub fn new<S: AsRef<str>>(groups: &[S]) -> Result<Self, UnknownTypeGroup> {
+        Self::with_custom(groups, &BTreeMap::new())
+    }
+
+    /// Create a filter for groups that may also be defined or overridden in the configuration
+    pub fn with_custom<S: AsRef<str>>(groups: &[S], custom: &BTreeMap<String, Vec<String>>) -> Result<Self, UnknownTypeGroup> {
+        let mut extensions = HashSet::new();
+        for group in groups {
+            let group = group.as_ref();
+            let group_extensions = group_extensions(group, custom).ok_or_else(|| UnknownTypeGroup {
+                name: group.to_string(),
+                known: group_names(custom).join(", "),
+            })?;
+            extensions.extend(group_extensions.iter()
+                .map(|extension| extension.trim_start_matches('.').to_lowercase()));
+        }
+        Ok(Self { extensions })
+    }
+}
+
+impl Filter for TypeGroupFilter {
+    fn filter(&self, path: &Path) -> FilterResult {
+        if path.is_dir() {
+            return FilterResult::Accept;
+        }
+        self.filter_entry(path)
+    }
+
+    fn filter_entry(&self, path: &Path) -> FilterResult {
+        match path.extension() {
+            Some(extension) if self.extensions.contains(&extension.to_string_lossy().to_lowercase()) => FilterResult::Accept,
+            _ => FilterResult::Reject,
+        }
+    }
+}
@@ -0,0 +1,460 @@
+pub mod trigram;
+
+pub use trigram::{trigrams, TrigramQuery};
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use anyhow::{bail, Context, Result};
+use log::debug;
+use rayon::prelude::*;
+use rusqlite::{params, Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::content::{is_binary, BINARY_SAMPLE_SIZE};
+use crate::utils::hash::{fnv1a, FNV_OFFSET};
+
+/// Schema of a content index
+///
+/// `files` records the size and modification time each file had when it was
+/// indexed; `trigrams` holds one posting per trigram occurring in a file.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS meta (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS files (
+        id INTEGER PRIMARY KEY,
+        path TEXT NOT NULL UNIQUE,
+        size INTEGER NOT NULL,
+        mtime INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS trigrams (
+        trigram INTEGER NOT NULL,
+        file_id INTEGER NOT NULL REFERENCES files(id),
+        PRIMARY KEY (trigram, file_id)
+    ) WITHOUT ROWID;
+    CREATE INDEX IF NOT EXISTS idx_trigrams_file_id ON trigrams(file_id);
+";
+
+/// Files whose trigrams are extracted before they are written out together
+const BATCH_SIZE: usize = 512;
+
+/// Name written at the top of exported indexes
+const EXPORT_FORMAT: &str = "oqab-index";
+
+/// Version of the export format; bumped whenever its records change
+const EXPORT_VERSION: u32 = 1;
+
+/// Largest value a packed trigram can have
+const MAX_TRIGRAM: u32 = 0x00ff_ffff;
+
+/// First line of an exported index
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportHeader {
+    format: String,
+    version: u32,
+    files: usize,
+}
+
+/// One indexed file of an exported index, with its path relative to the root
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportedFile {
+    path: String,
+    size: i64,
+    mtime: i64,
+    trigrams: Vec<u32>,
+}
+
+/// Counts reported after updating an index
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexStats {
+    /// Text files covered by the index after the update
+    pub files: usize,
+    /// Files that were new or changed and had to be read
+    pub updated: usize,
+    /// Files dropped because they disappeared or became binary or unreadable
+    pub removed: usize,
+}
+
+/// Trigram index of file contents below a root directory
+///
+/// Lets content searches skip files that cannot contain a pattern. Files that
+/// changed since they were indexed, or are not indexed at all, are always searched,
+/// so a stale index only costs speed, never results.
+pub struct ContentIndex {
+    conn: Connection,
+    root: PathBuf,
+}
+
+impl ContentIndex {
+    /// Default index location for a root directory, inside the user's cache directory
+    pub fn default_location(root: &Path) -> Option<PathBuf> {
+        let root = fs::canonicalize(root).ok()?;
+        let name = format!("{:016x}.db", fnv1a(FNV_OFFSET, root.to_string_lossy().as_bytes()));
+        Some(dirs::cache_dir()?.join("oqab").join("index").join(name))
+    }
+
+    /// Open the index at `path` covering `root`, creating it if needed
+    pub fn open(path: &Path, root: &Path) -> Result<Self> {
+        let root = fs::canonicalize(root)
+            .with_context(|| format!("Failed to resolve index root: {}", root.display()))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create index directory: {}", parent.display()))?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open index: {}", path.display()))?;
+        conn.execute_batch(SCHEMA)
+            .context("Failed to create index schema")?;
+        conn.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('root', ?1)",
+            params![root.to_string_lossy()],
+        )?;
+
+        Ok(Self { conn, root })
+    }
+
+    /// Open an existing index without changing it, reading its root from the file
+    pub fn open_existing(path: &Path) -> Result<Self> {
+        Self::open_existing_with(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+    }
+
+    /// Open an existing index for maintenance such as compaction, reading its root from the file
+    pub fn open_existing_writable(path: &Path) -> Result<Self> {
+        Self::open_existing_with(path, OpenFlags::default().difference(OpenFlags::SQLITE_OPEN_CREATE))
+    }
+
+    fn open_existing_with(path: &Path, flags: OpenFlags) -> Result<Self> {
+        let conn = Connection::open_with_flags(path, flags)
+            .with_context(|| format!("Failed to open index: {}", path.display()))?;
+        let root: String = conn.query_row("SELECT value FROM meta WHERE key = 'root'", [], |row| row.get(0))
+            .with_context(|| format!("Not a content index: {}", path.display()))?;
+
+        Ok(Self { conn, root: PathBuf::from(root) })
+    }
+
+    /// Find the index covering a directory, looking at its ancestors' default locations
+    pub fn find_for(dir: &Path) -> Result<Option<Self>> {
+        let dir = match fs::canonicalize(dir) {
+            Ok(dir) => dir,
+            Err(_) => return Ok(None),
+        };
+        for ancestor in dir.ancestors() {
+            if let Some(location) = Self::default_location(ancestor) {
+                if location.exists() {
+                    debug!("Using content index {} for {}", location.display(), ancestor.display());
+                    return Self::open_existing(&location).map(Some);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Directory whose files the index covers
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Bring the index up to date with the given files, which should lie below the root
+    ///
+    /// Unchanged files keep their postings; entries for files that are not listed
+    /// any more are dropped.
+    pub fn update(&mut self, files: &[PathBuf]) -> Result<IndexStats> {
+        let known = self.indexed_files()?;
+
+        // Work out which files are new or changed, relative to the root
+        let mut stats = IndexStats::default();
+        let mut seen = HashSet::new();
+        let mut stale = Vec::new();
+        for file in files {
+            let Ok(absolute) = fs::canonicalize(file) else { continue };
+            let Ok(relative) = absolute.strip_prefix(&self.root) else { continue };
+            let Some((size, mtime)) = file_stamp(&absolute) else { continue };
+            let key = relative.to_string_lossy().to_string();
+            let unchanged = matches!(known.get(&key), Some(&(_, s, m)) if s == size && m == mtime);
+            if !unchanged {
+                stale.push((key.clone(), absolute, size, mtime));
+            }
+            seen.insert(key);
+        }
+
+        let tx = self.conn.transaction()?;
+        for batch in stale.chunks(BATCH_SIZE) {
+            // Reading and splitting files is the expensive part, so do it in parallel
+            let extracted: Vec<_> = batch.par_iter()
+                .map(|(key, absolute, size, mtime)| {
+                    let content = fs::read(absolute).ok()
+                        .filter(|content| !is_binary(&content[..content.len().min(BINARY_SAMPLE_SIZE)]));
+                    (key, *size, *mtime, content.map(|content| trigrams(&content)))
+                })
+                .collect();
+
+            for (key, size, mtime, trigram_set) in extracted {
+                // Binary and unreadable files are left for the removal pass below
+                let Some(trigram_set) = trigram_set else {
+                    seen.remove(key);
+                    continue;
+                };
+                if let Some(&(id, _, _)) = known.get(key) {
+                    tx.execute("DELETE FROM trigrams WHERE file_id = ?1", params![id])?;
+                    tx.execute("DELETE FROM files WHERE id = ?1", params![id])?;
+                }
+
+                tx.execute(
+                    "INSERT INTO files (path, size, mtime) VALUES (?1, ?2, ?3)",
+                    params![key, size, mtime],
+                )?;
+                let id = tx.last_insert_rowid();
+                let mut insert = tx.prepare_cached("INSERT INTO trigrams (trigram, file_id) VALUES (?1, ?2)")?;
+                for trigram in trigram_set {
+                    insert.execute(params![trigram, id])?;
+                }
+                stats.updated += 1;
+            }
+        }
+
+        for (key, (id, _, _)) in &known {
+            if !seen.contains(key) {
+                tx.execute("DELETE FROM trigrams WHERE file_id = ?1", params![id])?;
+                tx.execute("DELETE FROM files WHERE id = ?1", params![id])?;
+                stats.removed += 1;
+            }
+        }
+        tx.commit().context("Failed to write content index")?;
+
+        stats.files = seen.len();
+        Ok(stats)
+    }
+
+    /// Drop the files that the index proves cannot match the query
+    ///
+    /// `search_root` is the directory the candidate paths were found under, as given
+    /// to the finder; candidates outside the index or changed since indexing are kept.
+    pub fn prune(&self, files: Vec<PathBuf>, search_root: &Path, query: &TrigramQuery) -> Result<Vec<PathBuf>> {
+        if query.is_unrestricted() {
+            return Ok(files);
+        }
+        let Ok(search_root_absolute) = fs::canonicalize(search_root) else {
+            return Ok(files);
+        };
+        let Ok(prefix) = search_root_absolute.strip_prefix(&self.root) else {
+            return Ok(files);
+        };
+
+        let possible = self.possible_files(query)?;
+        let entries = self.indexed_files()?;
+
+        let before = files.len();
+        let kept: Vec<PathBuf> = files.into_iter()
+            .filter(|file| {
+                let Ok(relative) = file.strip_prefix(search_root) else { return true };
+                let key = prefix.join(relative).to_string_lossy().to_string();
+                match entries.get(&key) {
+                    Some(&(id, size, mtime)) => {
+                        possible.contains(&id) || file_stamp(file) != Some((size, mtime))
+                    }
+                    None => true,
+                }
+            })
+            .collect();
+        debug!("Content index ruled out {} of {} files", before - kept.len(), before);
+
+        Ok(kept)
+    }
+
+    // Ids of indexed files containing every trigram of at least one alternative
+    fn possible_files(&self, query: &TrigramQuery) -> Result<HashSet<i64>> {
+        let mut statement = self.conn.prepare_cached("SELECT file_id FROM trigrams WHERE trigram = ?1")?;
+        let mut possible = HashSet::new();
+
+        for required in query.alternatives() {
+            let mut matching: Option<HashSet<i64>> = None;
+            for trigram in required {
+                let postings: HashSet<i64> = statement
+                    .query_map(params![trigram], |row| row.get(0))?
+                    .collect::<rusqlite::Result<_>>()?;
+                let narrowed = match matching {
+                    Some(previous) => previous.intersection(&postings).copied().collect(),
+                    None => postings,
+                };
+                let done = narrowed.is_empty();
+                matching = Some(narrowed);
+                if done {
+                    break;
+                }
+            }
+            possible.extend(matching.unwrap_or_default());
+        }
+
+        Ok(possible)
+    }
+
+    /// Write the index in a portable format, returning the number of files written
+    ///
+    /// The export is JSON Lines: a header naming the format and the number of files,
+    /// then one record per file with its path relative to the root, its size and
+    /// modification time, and its trigrams. It can be imported on another machine
+    /// under a different root.
+    pub fn export(&self, mut writer: impl Write) -> Result<usize> {
+        let files: Vec<(i64, String, i64, i64)> = self.conn
+            .prepare("SELECT id, path, size, mtime FROM files ORDER BY path")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let header = ExportHeader { format: EXPORT_FORMAT.to_string(), version: EXPORT_VERSION, files: files.len() };
+        serde_json::to_writer(&mut writer, &header)?;
+        writeln!(writer)?;
+
+        let mut statement = self.conn.prepare_cached("SELECT trigram FROM trigrams WHERE file_id = ?1 ORDER BY trigram")?;
+        for (id, path, size, mtime) in &files {
+            let trigrams = statement
+                .query_map(params![id], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+            let record = ExportedFile { path: path.clone(), size: *size, mtime: *mtime, trigrams };
+            serde_json::to_writer(&mut writer, &record)?;
+            writeln!(writer)?;
+        }
+        writer.flush()?;
+
+        Ok(files.len())
+    }
+
+    /// Replace the contents of the index with an export, returning the number of files read
+    ///
+    /// The whole export is checked before anything is written, so a truncated or
+    /// damaged file leaves the index as it was.
+    pub fn import(&mut self, reader: impl BufRead) -> Result<usize> {
+        let mut lines = reader.lines();
+        let header: ExportHeader = match lines.next() {
+            Some(line) => serde_json::from_str(&line?).context("Export does not start with an index header")?,
+            None => bail!("Export is empty"),
+        };
+        if header.format != EXPORT_FORMAT {
+            bail!("Not an exported index: format is {:?}", header.format);
+        }
+        if header.version != EXPORT_VERSION {
+            bail!("Unsupported index export version {} (expected {})", header.version, EXPORT_VERSION);
+        }
+
+        let mut files = Vec::with_capacity(header.files);
+        let mut paths = HashSet::new();
+        for (number, line) in lines.enumerate() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            // The header is line 1
+            let record: ExportedFile = serde_json::from_str(&line)
+                .with_context(|| format!("Invalid file record on line {}", number + 2))?;
+            let relative = Path::new(&record.path);
+            if !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+                bail!("File record on line {} has a path outside the root: {}", number + 2, record.path);
+            }
+            if record.trigrams.iter().any(|&trigram| trigram > MAX_TRIGRAM) {
+                bail!("File record on line {} has an invalid trigram", number + 2);
+            }
+            if !paths.insert(record.path.clone()) {
+                bail!("File {} appears more than once", record.path);
+            }
+            files.push(record);
+        }
+        if files.len() != header.files {
+            bail!("Export is incomplete: header lists {} files but {} were found", header.files, files.len());
+        }
+
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM trigrams", [])?;
+        tx.execute("DELETE FROM files", [])?;
+        for file in &files {
+            tx.execute(
+                "INSERT INTO files (path, size, mtime) VALUES (?1, ?2, ?3)",
+                params![file.path, file.size, file.mtime],
+            )?;
+            let id = tx.last_insert_rowid();
+            let mut insert = tx.prepare_cached("INSERT OR IGNORE INTO trigrams (trigram, file_id) VALUES (?1, ?2)")?;
+            for trigram in &file.trigrams {
+                insert.execute(params![trigram, id])?;
+            }
+        }
+        tx.commit().context("Failed to write content index")?;
+
+        Ok(files.len())
+    }
+
+    /// Drop postings of files that are no longer indexed and give unused space back
+    ///
+    /// Returns the number of postings dropped. Incremental updates leave free pages
+    /// behind, so the file only shrinks once it is compacted.
+    pub fn compact(&mut self) -> Result<usize> {
+        let dropped = self.conn.execute(
+            "DELETE FROM trigrams WHERE file_id NOT IN (SELECT id FROM files)",
+            [],
+        )?;
+        self.conn.execute_batch("VACUUM").context("Failed to compact content index")?;
+        Ok(dropped)
+    }
+
+    /// Look for damage to the index, returning a description of each problem found
+    pub fn check(&self) -> Result<Vec<String>> {
+        let mut problems: Vec<String> = self.conn
+            .prepare("PRAGMA integrity_check")?
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|message| message != "ok")
+            .collect();
+
+        let orphans: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM trigrams WHERE file_id NOT IN (SELECT id FROM files)",
+            [],
+            |row| row.get(0),
+        )?;
+        if orphans > 0 {
+            problems.push(format!("{} trigram postings refer to files that are not indexed", orphans));
+        }
+
+        let invalid: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM trigrams WHERE trigram < 0 OR trigram > ?1",
+            params![MAX_TRIGRAM],
+            |row| row.get(0),
+        )?;
+        if invalid > 0 {
+            problems.push(format!("{} trigram postings hold invalid trigrams", invalid));
+        }
+
+        Ok(problems)
+    }
+
+    /// Number of files in the index
+    pub fn len(&self) -> Result<usize> {
+        let count: i64 = self.conn.query_row("SELECT COUNT(*) FROM files", [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    /// Whether the index covers no files
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    // Every indexed file by relative path, with its id, size and modification time
+    fn indexed_files(&self) -> Result<HashMap<String, (i64, i64, i64)>> {
+        let mut statement = self.conn.prepare("SELECT path, id, size, mtime FROM files")?;
+        let rows = statement.query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?, row.get(3)?))))?;
+        let mut files = HashMap::new();
+        for row in rows {
+            let (path, entry) = row?;
+            files.insert(path, entry);
+        }
+        Ok(files)
+    }
+}
+
+// Size and modification time used to tell whether a file changed since indexing
+fn file_stamp(path: &Path) -> Option<(i64, i64)> {
+    let metadata = fs::metadata(path).ok()?;
+    let mtime = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((metadata.len() as i64, mtime.as_nanos() as i64))
+}
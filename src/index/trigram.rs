@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+use regex_syntax::hir::literal::Extractor;
+use regex_syntax::ParserBuilder;
+
+/// Pack three bytes into a trigram key, folding ASCII case
+fn pack(window: &[u8]) -> u32 {
+    let [a, b, c] = [window[0], window[1], window[2]].map(|byte| byte.to_ascii_lowercase());
+    (a as u32) << 16 | (b as u32) << 8 | c as u32
+}
+
+/// Collect the case-folded trigrams occurring in some content
+pub fn trigrams(content: &[u8]) -> HashSet<u32> {
+    content.windows(3).map(pack).collect()
+}
+
+/// Trigrams a file must contain to possibly match a content pattern
+///
+/// Every match of the pattern starts with one of a set of literals; a file can
+/// only match if it contains every trigram of at least one of those literals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrigramQuery {
+    /// One set of required trigrams per literal, or `None` if nothing is required
+    alternatives: Option<Vec<Vec<u32>>>,
+}
+
+impl TrigramQuery {
+    /// Plan a query for a pattern in Rust regex syntax
+    ///
+    /// Patterns that cannot be parsed or have no usable literals produce an
+    /// unrestricted query, so the index never hides a file that could match.
+    pub fn from_regex(pattern: &str, ignore_case: bool) -> Self {
+        // Literals are extracted case-sensitively and folded like the index,
+        // which yields a superset of the files a case-insensitive search can match
+        let hir = match ParserBuilder::new().build().parse(pattern) {
+            Ok(hir) => hir,
+            Err(_) => return Self::unrestricted(),
+        };
+        let literals = match Extractor::new().extract(&hir).literals() {
+            Some(literals) => literals.to_vec(),
+            None => return Self::unrestricted(),
+        };
+
+        let mut alternatives: Vec<Vec<u32>> = Vec::with_capacity(literals.len());
+        for literal in &literals {
+            let mut required: Vec<u32> = literal.as_bytes()
+                .windows(3)
+                .filter(|window| !ignore_case || window.iter().all(|&byte| folds_to_ascii_only(byte)))
+                .map(pack)
+                .collect();
+            // A literal that requires nothing lets every file through
+            if required.is_empty() {
+                return Self::unrestricted();
+            }
+            required.sort_unstable();
+            required.dedup();
+            if !alternatives.contains(&required) {
+                alternatives.push(required);
+            }
+        }
+
+        if alternatives.is_empty() {
+            return Self::unrestricted();
+        }
+        Self { alternatives: Some(alternatives) }
+    }
+
+    /// A query that every file satisfies
+    pub fn unrestricted() -> Self {
+        Self { alternatives: None }
+    }
+
+    /// Whether the query lets every file through
+    pub fn is_unrestricted(&self) -> bool {
+        self.alternatives.is_none()
+    }
+
+    /// Sets of trigrams of which a matching file contains at least one in full
+    pub fn alternatives(&self) -> &[Vec<u32>] {
+        self.alternatives.as_deref().unwrap_or_default()
+    }
+}
+
+// Under Unicode case folding, `k` and `s` also match non-ASCII characters
+// (KELVIN SIGN and LONG S), and non-ASCII bytes are not folded by the index
+fn folds_to_ascii_only(byte: u8) -> bool {
+    byte.is_ascii() && !matches!(byte.to_ascii_lowercase(), b'k' | b's')
+}
@@ -2,6 +2,10 @@ pub mod commands;
 pub mod core;
 pub mod cli;
 pub mod filters;
+pub mod index;
+pub mod output;
+#[cfg(feature = "tui")]
+pub mod tui;
 pub mod utils;
 
 // Re-export main types
@@ -15,8 +19,12 @@ pub use core::{
     SearchObserver,
     NullObserver,
     ProgressReporter,
+    ProgressBarObserver,
     SilentObserver,
     TraversalMode,
     TraversalStrategy,
+    CompositeTraversalStrategy,
+    DefaultTraversalStrategy,
+    RegexTraversalStrategy,
 };
 pub use filters::{Filter, FilterResult}; 
\ No newline at end of file
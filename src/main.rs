@@ -1,10 +1,14 @@
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
 use std::process;
-use anyhow::{Context, Result};
-use env_logger::Env;
-use log::{error, info, warn, LevelFilter};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+use log::{info, warn, LevelFilter};
 
+use oqab::cli::args::{CliCommand, IndexAction};
+use oqab::cli::exit_code;
 use oqab::core::{ConfigManager, FileSearchConfig, Platform};
-use oqab::commands::{Command, HelpCommand, SearchCommand, GrepCommand, FuzzyCommand};
+use oqab::commands::{AuditCommand, Command, HelpCommand, SearchCommand, GrepCommand, FuzzyCommand, IndexCommand, IndexOperation, PrintConfigCommand, RecordCommand, ScheduleCommand, SimilarCommand, SizeReportCommand, Outcome};
 
 fn main() {
     // Parse command line arguments
@@ -12,20 +16,27 @@ fn main() {
         Ok(args) => args,
         Err(err) => {
             eprintln!("Error parsing arguments: {}", err);
-            process::exit(1);
+            process::exit(exit_code::USAGE);
         }
     };
     
-    // Initialize logger with custom environment based on verbosity flags
-    let log_level = if args.silent || args.quiet {
-        LevelFilter::Warn
-    } else {
-        std::env::var("RUST_LOG")
-            .map(|level| level.parse().unwrap_or(LevelFilter::Info))
-            .unwrap_or(LevelFilter::Info)
-    };
+    // Initialize logger: explicit verbosity flags win, then RUST_LOG, then warnings only
+    let mut builder = env_logger::Builder::new();
+    match args.log_level() {
+        Some(level) => {
+            builder.filter_level(level);
+        }
+        None => match std::env::var("RUST_LOG") {
+            Ok(filters) => {
+                builder.parse_filters(&filters);
+            }
+            Err(_) => {
+                builder.filter_level(LevelFilter::Warn);
+            }
+        },
+    }
     
-    env_logger::Builder::from_env(Env::default().default_filter_or("warn"))
+    builder
         .format_timestamp(None)
         .format(|buf, record| {
             use std::io::Write;
@@ -36,30 +47,56 @@ fn main() {
                 writeln!(buf, "{}: {}", record.level(), record.args())
             }
         })
-        .filter(None, log_level)
         .init();
     
-    // Run the application and handle errors
-    if let Err(err) = run(&args) {
-        error!("Application error: {:#}", err);
-        process::exit(1);
+    // Exit with the documented code when interrupted rather than dying on the signal
+    // A running pager is waited for, so it does not fight the shell for the terminal
+    if let Err(err) = ctrlc::set_handler(|| {
+        let _ = oqab::output::finish_pager();
+        process::exit(exit_code::INTERRUPTED)
+    }) {
+        warn!("Failed to install Ctrl-C handler: {}", err);
     }
     
-    process::exit(0);
+    // Run the application and handle errors, reporting fatal errors even when silent
+    let result = run(&args);
+    let paged = oqab::output::finish_pager();
+    match result.and_then(|outcome| paged.map(|()| outcome)) {
+        Ok(outcome) => process::exit(exit_code::for_outcome(outcome)),
+        Err(err) => {
+            let code = exit_code::for_error(&err);
+            // A reader like `head` closing the pipe early is not worth complaining about
+            if code != exit_code::BROKEN_PIPE {
+                eprintln!("Error: {:#}", err);
+            }
+            process::exit(code);
+        }
+    }
 }
 
-fn run(args: &oqab::cli::args::Args) -> Result<()> {
+fn run(args: &oqab::cli::args::Args) -> Result<Outcome> {
     // Process arguments into a configuration
     let mut config = args.process()
         .context("Failed to process arguments into a valid configuration")?;
     
+    // Printing the configuration is all that is done when asked for
+    if let Some(format) = args.print_config {
+        let sources = args.config_sources(&config)?;
+        return PrintConfigCommand::new(&config, &sources, format.into()).execute();
+    }
+    
+    // Subcommands bypass the one-off search flow entirely
+    if let Some(command) = &args.command {
+        return run_subcommand(command, &config);
+    }
+    
     // Check if help is requested
-    let showing_help = args.help || (config.file_extension.is_none() && config.file_name.is_none() && config.pattern.is_none());
+    let showing_help = args.help || !config.has_criteria();
     
-    // Set root directory as default search path if none specified (but not when showing help)
-    if config.path.is_none() && !showing_help {
+    // Without a path the current directory is searched, unless the whole machine was asked for
+    if config.path.is_none() && config.files_from.is_none() && config.system && !showing_help {
         let root_path = Platform::root_directory().to_string_lossy().to_string();
-        warn!("No path specified. Searching from root directory ({}). This may take a long time and require elevated permissions.", root_path);
+        warn!("Searching from root directory ({}). This may take a long time and require elevated permissions.", root_path);
         config.path = Some(root_path);
     } else if let Some(path) = &config.path {
         if Platform::is_root_path(path) && !showing_help {
@@ -67,6 +104,15 @@ fn run(args: &oqab::cli::args::Args) -> Result<()> {
         }
     }
     
+    // A scan of the whole machine can run for hours, so make sure it was meant
+    let scans_filesystem = config.files_from.is_none()
+        && config.path.as_deref().is_some_and(|path| Platform::is_filesystem_root(Path::new(path)));
+    if scans_filesystem && !showing_help && !args.yes && !confirm_filesystem_search()? {
+        bail!("Search of the entire filesystem cancelled");
+    }
+    
+    log_active_filters(&config);
+    
     // Initialize the singleton configuration manager
     ConfigManager::instance().initialize(config.clone());
     
@@ -77,21 +123,139 @@ fn run(args: &oqab::cli::args::Args) -> Result<()> {
         info!("Configuration saved successfully");
     }
     
+    // Colors are settled before anything styled is printed
+    config.color.apply();
+    oqab::output::set_hyperlinks(config.hyperlinks);
+    
+    // Page long result lists on interactive terminals, with the built-in prompt if a page size is given
+    if let Some(lines) = config.page_size {
+        oqab::output::set_page_size(lines);
+    } else if config.pager && !config.interactive && !config.select {
+        oqab::output::enable_pager();
+    }
+    
     // Create and execute the appropriate command
     create_command(&config)?
         .execute()
-        .context("Command execution failed")?;
+        .context("Command execution failed")
+}
+
+/// Ask on the terminal whether to search the entire filesystem
+///
+/// Scripts and pipelines have nobody to answer, so only interactive runs are asked.
+fn confirm_filesystem_search() -> Result<bool> {
+    if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+        return Ok(true);
+    }
     
-    Ok(())
+    eprint!("Search the entire filesystem? [y/N] ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).context("Failed to read answer")?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Describe the filters in effect at informational verbosity
+fn log_active_filters(config: &FileSearchConfig) {
+    if let Some(path) = &config.path {
+        info!("Search root: {}", path);
+    }
+    if let Some(ext) = &config.file_extension {
+        info!("Filter: extension = {}", ext);
+    }
+    if let Some(name) = &config.file_name {
+        info!("Filter: name contains '{}'", name);
+    }
+    if let Some(glob) = &config.name_glob {
+        info!("Filter: name matches glob '{}'", glob);
+    }
+    if let Some(name_regex) = &config.name_regex {
+        info!("Filter: name matches regex '{}'", name_regex);
+    }
+    if let Some(pattern) = &config.pattern {
+        info!("Filter: content matches '{}'", pattern);
+    }
+    if let Some(min_size) = config.min_size {
+        info!("Filter: size >= {} bytes", min_size);
+    }
+    if let Some(max_size) = config.max_size {
+        info!("Filter: size <= {} bytes", max_size);
+    }
+    if let Some(newer_than) = config.newer_than {
+        info!("Filter: modified after {}", DateTime::<Local>::from(newer_than));
+    }
+    if let Some(older_than) = config.older_than {
+        info!("Filter: modified before {}", DateTime::<Local>::from(older_than));
+    }
+    if config.git_tracked {
+        info!("Filter: tracked by git");
+    }
+    if config.git_modified {
+        info!("Filter: modified in git");
+    }
+}
+
+/// Run a subcommand with the processed configuration as its defaults
+fn run_subcommand(command: &CliCommand, config: &FileSearchConfig) -> Result<Outcome> {
+    match command {
+        #[cfg(feature = "http")]
+        CliCommand::Serve { http } => oqab::commands::ServeCommand::new(config, http)
+            .execute()
+            .context("HTTP server failed"),
+        #[cfg(not(feature = "http"))]
+        CliCommand::Serve { .. } => {
+            anyhow::bail!("oqab was built without HTTP support; rebuild with `--features http`")
+        }
+        CliCommand::Index { action } => {
+            let operation = match action {
+                IndexAction::Build { content: false } => {
+                    anyhow::bail!("Only content indexes are supported; use `oqab index build --content`");
+                }
+                IndexAction::Build { content: true } => IndexOperation::Build,
+                IndexAction::Export { file } => IndexOperation::Export(file.into()),
+                IndexAction::Import { file } => IndexOperation::Import(file.into()),
+                IndexAction::Compact => IndexOperation::Compact,
+                IndexAction::Check => IndexOperation::Check,
+            };
+            let description = operation.description();
+            IndexCommand::new(config, operation)
+                .execute()
+                .with_context(|| format!("Failed to {} content index", description))
+        }
+        CliCommand::Record { paths } => RecordCommand::new(paths).execute(),
+        CliCommand::Schedule { file, daemon } => ScheduleCommand::new(file, *daemon).execute(),
+        CliCommand::Audit { path, rules } => {
+            let root = path.as_deref().unwrap_or(config.get_path());
+            let rules: Vec<_> = rules.iter().map(|&rule| rule.into()).collect();
+            AuditCommand::new(config, std::path::Path::new(root), &rules).execute()
+        }
+        CliCommand::Similar { file, threshold } => {
+            SimilarCommand::new(config, std::path::Path::new(file), *threshold).execute()
+        }
+    }
 }
 
 /// Create the appropriate command based on the configuration
 fn create_command(config: &FileSearchConfig) -> Result<Box<dyn Command + '_>> {
     // Display help if explicitly requested or if no search criteria provided
-    if config.help || (config.file_extension.is_none() && config.file_name.is_none() && config.pattern.is_none()) {
+    if config.help || !config.has_criteria() {
         return Ok(Box::new(HelpCommand::new()));
     }
     
+    // The browser takes over the terminal and shows results as they arrive
+    if config.interactive {
+        #[cfg(feature = "tui")]
+        return Ok(Box::new(oqab::commands::InteractiveCommand::new(config)));
+        #[cfg(not(feature = "tui"))]
+        anyhow::bail!("oqab was built without the results browser; rebuild with `--features tui`");
+    }
+    
+    // A size report aggregates the matches instead of listing them
+    if let Some(top) = config.du {
+        info!("Using size report mode");
+        return Ok(Box::new(SizeReportCommand::new(config, top)));
+    }
+    
     // If a pattern is specified, use the GrepCommand for text search
     if config.pattern.is_some() {
         info!("Using text pattern search mode");
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::filters::size::file_size;
+
+/// Keys that results can be aggregated by instead of being listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    /// Lowercased file extension
+    Ext,
+    /// Directory containing the file
+    Dir,
+}
+
+/// Number and total size of the results sharing a key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Group {
+    /// Extension or directory the results share; empty for files without an extension
+    pub key: String,
+    /// Number of results in the group
+    pub count: usize,
+    /// Sum of the sizes of the results in bytes
+    pub size: u64,
+}
+
+impl GroupBy {
+    /// Key of a path under this grouping
+    pub fn key(self, path: &Path) -> String {
+        match self {
+            GroupBy::Ext => path.extension()
+                .map(|extension| extension.to_string_lossy().to_lowercase())
+                .unwrap_or_default(),
+            GroupBy::Dir => path.parent()
+                .map(|parent| parent.display().to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Aggregate results into groups, largest total size first and ties by key
+///
+/// Paths whose metadata cannot be read count towards their group with no size.
+pub fn group_results(files: &[PathBuf], group_by: GroupBy, on_disk: bool) -> Vec<Group> {
+    let mut groups: HashMap<String, Group> = HashMap::new();
+    for file in files {
+        let key = group_by.key(file);
+        let size = fs::metadata(file).map(|metadata| file_size(&metadata, on_disk)).unwrap_or(0);
+        let group = groups.entry(key.clone()).or_insert_with(|| Group { key, count: 0, size: 0 });
+        group.count += 1;
+        group.size += size;
+    }
+
+    let mut groups: Vec<Group> = groups.into_values().collect();
+    groups.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.key.cmp(&b.key)));
+    groups
+}
+
+/// Format a byte count with a binary unit, as in `1.5 MiB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
@@ -0,0 +1,129 @@
+use std::fs::Metadata;
+use std::time::{Duration, SystemTime};
+use serde::{Deserialize, Serialize};
+
+use crate::output::group::format_size;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+
+/// Upper bounds of the size buckets in bytes; larger files go to a last open bucket
+const SIZE_BOUNDS: &[u64] = &[1, 1 << 10, 16 << 10, 256 << 10, 1 << 20, 16 << 20, 256 << 20, 1 << 30];
+
+/// Upper bounds of the age buckets in seconds; older files go to a last open bucket
+const AGE_BOUNDS: &[u64] = &[HOUR, DAY, 7 * DAY, 30 * DAY, 365 * DAY];
+
+/// Width in characters of the longest bar of a text chart
+const BAR_WIDTH: usize = 40;
+
+/// File properties that statistics can be broken down by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistogramKind {
+    /// File size in bytes
+    Size,
+    /// Time since the file was last modified
+    Mtime,
+}
+
+/// Files whose value lies in `[min, max)`; `max` is absent for the last bucket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Bucket {
+    /// Inclusive lower bound, in bytes for sizes and seconds of age for modification times
+    pub min: u64,
+    /// Exclusive upper bound in the same unit
+    pub max: Option<u64>,
+    /// Number of files in the bucket
+    pub count: usize,
+}
+
+/// Counts of files by size or age, filled in one file at a time as a search goes
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Histogram {
+    /// Property the files are counted by
+    pub kind: HistogramKind,
+    /// Buckets in increasing order
+    pub buckets: Vec<Bucket>,
+    /// Ages are measured from this time
+    #[serde(skip)]
+    now: SystemTime,
+}
+
+impl Histogram {
+    /// Empty histogram measuring ages from the current time
+    pub fn new(kind: HistogramKind) -> Self {
+        Self::at(kind, SystemTime::now())
+    }
+
+    /// Empty histogram measuring ages from `now`
+    pub fn at(kind: HistogramKind, now: SystemTime) -> Self {
+        let bounds = match kind {
+            HistogramKind::Size => SIZE_BOUNDS,
+            HistogramKind::Mtime => AGE_BOUNDS,
+        };
+        let buckets = std::iter::once(0).chain(bounds.iter().copied())
+            .zip(bounds.iter().copied().map(Some).chain(std::iter::once(None)))
+            .map(|(min, max)| Bucket { min, max, count: 0 })
+            .collect();
+        Self { kind, buckets, now }
+    }
+
+    /// Count a file
+    ///
+    /// Files without a modification time are left out of age histograms, and files
+    /// modified after `now` count as new.
+    pub fn add(&mut self, metadata: &Metadata) {
+        let value = match self.kind {
+            HistogramKind::Size => metadata.len(),
+            HistogramKind::Mtime => match metadata.modified() {
+                Ok(modified) => self.now.duration_since(modified).unwrap_or(Duration::ZERO).as_secs(),
+                Err(_) => return,
+            },
+        };
+        if let Some(bucket) = self.buckets.iter_mut().find(|bucket| bucket.max.is_none_or(|max| value < max)) {
+            bucket.count += 1;
+        }
+    }
+
+    /// Total number of files counted
+    pub fn total(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.count).sum()
+    }
+
+    /// Text bar chart with one line per bucket, bars scaled to the fullest bucket
+    pub fn chart(&self) -> Vec<String> {
+        let labels: Vec<String> = self.buckets.iter().map(|bucket| self.label(bucket)).collect();
+        let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+        let fullest = self.buckets.iter().map(|bucket| bucket.count).max().unwrap_or(0).max(1);
+
+        self.buckets.iter().zip(labels)
+            .map(|(bucket, label)| {
+                // Round up so that no non-empty bucket looks empty
+                let bar = (bucket.count * BAR_WIDTH).div_ceil(fullest);
+                format!("{:<label_width$}  {:<BAR_WIDTH$} {}", label, "#".repeat(bar), bucket.count)
+            })
+            .collect()
+    }
+
+    fn label(&self, bucket: &Bucket) -> String {
+        let format = match self.kind {
+            HistogramKind::Size => format_size,
+            HistogramKind::Mtime => format_age,
+        };
+        match (self.kind, bucket.max) {
+            (HistogramKind::Size, Some(1)) => "empty".to_string(),
+            (_, Some(max)) => format!("< {}", format(max)),
+            (_, None) => format!(">= {}", format(bucket.min)),
+        }
+    }
+}
+
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        s if s >= 365 * DAY => format!("{} year", s / (365 * DAY)),
+        s if s >= 30 * DAY => format!("{} month", s / (30 * DAY)),
+        s if s >= 7 * DAY => format!("{} week", s / (7 * DAY)),
+        s if s >= DAY => format!("{} day", s / DAY),
+        s => format!("{} hour", s / HOUR),
+    }
+}
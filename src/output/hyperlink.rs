@@ -0,0 +1,49 @@
+use std::fmt::Write;
+use std::io::{self, IsTerminal};
+use std::path::{self, Path};
+use std::sync::atomic::{AtomicBool, Ordering};
+use console::Style;
+
+// Whether paths are written as OSC 8 hyperlinks
+static HYPERLINKS: AtomicBool = AtomicBool::new(false);
+
+/// Write result paths as clickable `file://` links when asked to
+///
+/// Links are only written to a terminal, and not to one declaring itself dumb, so
+/// piped output and files never contain the escape sequences.
+pub fn set_hyperlinks(requested: bool) {
+    let supported = io::stdout().is_terminal() && std::env::var_os("TERM").is_none_or(|term| term != "dumb");
+    HYPERLINKS.store(requested && supported, Ordering::Relaxed);
+}
+
+/// Path styled for a result listing, wrapped in a hyperlink to the file when links are on
+pub fn styled_path(style: &Style, path: &Path) -> String {
+    let text = style.apply_to(path.display()).to_string();
+    if !HYPERLINKS.load(Ordering::Relaxed) {
+        return text;
+    }
+    match file_url(path) {
+        Some(url) => format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text),
+        None => text,
+    }
+}
+
+/// `file://` URL of a path, made absolute and percent-encoded
+pub fn file_url(path: &Path) -> Option<String> {
+    let absolute = path::absolute(path).ok()?;
+    let mut url = String::from("file://");
+    for component in absolute.to_string_lossy().split(path::MAIN_SEPARATOR).filter(|part| !part.is_empty()) {
+        url.push('/');
+        for byte in component.bytes() {
+            if byte.is_ascii_alphanumeric() || b"-._~:".contains(&byte) {
+                url.push(byte as char);
+            } else {
+                let _ = write!(url, "%{:02X}", byte);
+            }
+        }
+    }
+    if url.len() == "file://".len() {
+        url.push('/');
+    }
+    Some(url)
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::fs::{self, Metadata};
+use std::path::PathBuf;
+use chrono::{DateTime, Local};
+use console::Style;
+
+use crate::output::hyperlink::styled_path;
+
+/// Details of one result shown by `--long`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LongEntry {
+    /// Path of the result
+    pub path: PathBuf,
+    /// File type and permissions as `ls -l` shows them, such as `-rw-r--r--`
+    pub mode: String,
+    /// Name of the owning user, or its id when it has no name
+    pub owner: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Modification time as `YYYY-MM-DD HH:MM` in local time
+    pub modified: String,
+}
+
+/// Look up the details of every result, without following symbolic links
+///
+/// Results that can no longer be read are still listed, with `?` for what is unknown.
+pub fn long_entries(files: &[PathBuf]) -> Vec<LongEntry> {
+    let owners = Owners::load();
+    files.iter().map(|path| match fs::symlink_metadata(path) {
+        Ok(metadata) => LongEntry {
+            path: path.clone(),
+            mode: mode_string(&metadata),
+            owner: owners.name(&metadata),
+            size: metadata.len(),
+            modified: metadata.modified()
+                .map(|modified| DateTime::<Local>::from(modified).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|_| "?".to_string()),
+        },
+        Err(_) => LongEntry {
+            path: path.clone(),
+            mode: "?".repeat(10),
+            owner: "?".to_string(),
+            size: 0,
+            modified: "?".to_string(),
+        },
+    }).collect()
+}
+
+/// Format entries in columns aligned across all of them, as `ls -l` does
+pub fn long_lines(entries: &[LongEntry], path_style: &Style) -> Vec<String> {
+    let owner_width = entries.iter().map(|entry| entry.owner.len()).max().unwrap_or(0);
+    let size_width = entries.iter().map(|entry| entry.size.to_string().len()).max().unwrap_or(0);
+    entries.iter().map(|entry| {
+        format!("{} {:<owner_width$} {:>size_width$} {} {}",
+            entry.mode, entry.owner, entry.size, entry.modified, styled_path(path_style, &entry.path))
+    }).collect()
+}
+
+// File type character followed by the rwx triplets
+#[cfg(unix)]
+fn mode_string(metadata: &Metadata) -> String {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    let file_type = metadata.file_type();
+    let kind = if file_type.is_dir() {
+        'd'
+    } else if file_type.is_symlink() {
+        'l'
+    } else if file_type.is_fifo() {
+        'p'
+    } else if file_type.is_socket() {
+        's'
+    } else if file_type.is_char_device() {
+        'c'
+    } else if file_type.is_block_device() {
+        'b'
+    } else {
+        '-'
+    };
+
+    let mode = metadata.permissions().mode();
+    let mut text = String::with_capacity(10);
+    text.push(kind);
+    for (shift, special, set_char) in [(6, 0o4000, 's'), (3, 0o2000, 's'), (0, 0o1000, 't')] {
+        let bits = (mode >> shift) & 0o7;
+        text.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        text.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        text.push(match (bits & 0o1 != 0, mode & special != 0) {
+            (true, true) => set_char,
+            (false, true) => set_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    text
+}
+
+// Only the read-only attribute is known elsewhere
+#[cfg(not(unix))]
+fn mode_string(metadata: &Metadata) -> String {
+    let kind = if metadata.is_dir() { 'd' } else if metadata.file_type().is_symlink() { 'l' } else { '-' };
+    let write = if metadata.permissions().readonly() { '-' } else { 'w' };
+    format!("{kind}r{write}-r{write}-r{write}-")
+}
+
+/// Names of the users owning files, read once from the user database
+struct Owners {
+    names: HashMap<u32, String>,
+}
+
+impl Owners {
+    #[cfg(unix)]
+    fn load() -> Self {
+        // Lines are name:password:uid:...; unreadable databases leave numeric ids
+        let names = fs::read_to_string("/etc/passwd")
+            .map(|passwd| passwd.lines()
+                .filter_map(|line| {
+                    let mut fields = line.split(':');
+                    let name = fields.next()?;
+                    let uid = fields.nth(1)?.parse().ok()?;
+                    Some((uid, name.to_string()))
+                })
+                .collect())
+            .unwrap_or_default();
+        Self { names }
+    }
+
+    #[cfg(not(unix))]
+    fn load() -> Self {
+        Self { names: HashMap::new() }
+    }
+
+    #[cfg(unix)]
+    fn name(&self, metadata: &Metadata) -> String {
+        use std::os::unix::fs::MetadataExt;
+        let uid = metadata.uid();
+        self.names.get(&uid).cloned().unwrap_or_else(|| uid.to_string())
+    }
+
+    #[cfg(not(unix))]
+    fn name(&self, _metadata: &Metadata) -> String {
+        "-".to_string()
+    }
+}
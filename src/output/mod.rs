@@ -0,0 +1,59 @@
+pub mod group;
+pub mod histogram;
+pub mod hyperlink;
+pub mod long;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod report;
+pub mod sort;
+pub mod sqlite;
+pub mod stdout;
+pub mod template;
+pub mod theme;
+pub mod tree;
+
+pub use group::{group_results, GroupBy};
+pub use histogram::{Histogram, HistogramKind};
+pub use hyperlink::{set_hyperlinks, styled_path};
+pub use long::{long_entries, long_lines, LongEntry};
+#[cfg(feature = "parquet")]
+pub use self::parquet::write_parquet;
+pub use report::{render_report, ReportFormat, ReportSummary};
+pub use sort::{natural_cmp, sort_by_mtime, sort_results};
+pub use sqlite::SqliteExporter;
+pub use stdout::{enable_pager, finish_pager, flush_pending, set_page_size, BrokenPipeError};
+pub(crate) use stdout::{outln, outrec};
+pub use template::Template;
+pub use theme::{ColorChoice, Theme, ThemePreset};
+pub use tree::render_tree;
+
+use std::path::PathBuf;
+use anyhow::{Context, Result};
+
+use crate::core::config::{FileSearchConfig, OutputFormat};
+
+/// Write results to the configured output file for file-based formats
+///
+/// Returns `true` if the format is file-based, in which case results must not
+/// also be printed to stdout.
+pub fn write_file_output(config: &FileSearchConfig, files: &[PathBuf]) -> Result<bool> {
+    match config.output_format {
+        OutputFormat::Parquet => {
+            let output = config.output_file.as_deref()
+                .context("--format parquet requires --output <FILE>")?;
+            write_parquet_output(output, files)?;
+            Ok(true)
+        }
+        OutputFormat::Text | OutputFormat::Vimgrep | OutputFormat::Json => Ok(false),
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet_output(output: &str, files: &[PathBuf]) -> Result<()> {
+    write_parquet(output, files)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn write_parquet_output(_output: &str, _files: &[PathBuf]) -> Result<()> {
+    anyhow::bail!("oqab was built without Parquet support; rebuild with `--features parquet`")
+}
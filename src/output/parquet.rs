@@ -0,0 +1,126 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::{Context, Result};
+use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+
+/// Parquet schema for exported results, one row per matched file
+const SCHEMA: &str = "
+    message oqab_result {
+        REQUIRED BYTE_ARRAY path (UTF8);
+        OPTIONAL BYTE_ARRAY extension (UTF8);
+        OPTIONAL INT64 size;
+        OPTIONAL INT64 modified (TIMESTAMP(MILLIS, true));
+        OPTIONAL INT64 created (TIMESTAMP(MILLIS, true));
+        OPTIONAL INT64 accessed (TIMESTAMP(MILLIS, true));
+    }
+";
+
+/// Metadata collected for a single result row
+struct FileRow {
+    path: String,
+    extension: Option<String>,
+    size: Option<i64>,
+    modified: Option<i64>,
+    created: Option<i64>,
+    accessed: Option<i64>,
+}
+
+impl FileRow {
+    fn new(path: &Path) -> Self {
+        let metadata = std::fs::metadata(path).ok();
+        let timestamp = |time: std::io::Result<SystemTime>| {
+            time.ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_millis() as i64)
+        };
+        
+        Self {
+            path: path.to_string_lossy().to_string(),
+            extension: path.extension().map(|ext| ext.to_string_lossy().to_string()),
+            size: metadata.as_ref().map(|m| m.len() as i64),
+            modified: metadata.as_ref().and_then(|m| timestamp(m.modified())),
+            created: metadata.as_ref().and_then(|m| timestamp(m.created())),
+            accessed: metadata.as_ref().and_then(|m| timestamp(m.accessed())),
+        }
+    }
+}
+
+/// Write matched files as a Parquet file with path, extension, size and timestamps
+pub fn write_parquet<P: AsRef<Path>>(path: P, files: &[PathBuf]) -> Result<()> {
+    let path = path.as_ref();
+    let rows: Vec<FileRow> = files.iter().map(|file| FileRow::new(file)).collect();
+    
+    let schema = Arc::new(parse_message_type(SCHEMA).context("Invalid Parquet schema")?);
+    let properties = Arc::new(WriterProperties::builder().build());
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create Parquet file: {}", path.display()))?;
+    let mut writer = SerializedFileWriter::new(file, schema, properties)?;
+    
+    let mut row_group = writer.next_row_group()?;
+    let mut column_index = 0;
+    while let Some(mut column) = row_group.next_column()? {
+        match column_index {
+            0 => {
+                let values: Vec<ByteArray> = rows.iter()
+                    .map(|row| ByteArray::from(row.path.as_str()))
+                    .collect();
+                column.typed::<ByteArrayType>().write_batch(&values, None, None)?;
+            }
+            1 => write_optional_strings(&mut column, rows.iter().map(|row| row.extension.as_deref()))?,
+            2 => write_optional_ints(&mut column, rows.iter().map(|row| row.size))?,
+            3 => write_optional_ints(&mut column, rows.iter().map(|row| row.modified))?,
+            4 => write_optional_ints(&mut column, rows.iter().map(|row| row.created))?,
+            5 => write_optional_ints(&mut column, rows.iter().map(|row| row.accessed))?,
+            _ => unreachable!("schema has six columns"),
+        }
+        column.close()?;
+        column_index += 1;
+    }
+    row_group.close()?;
+    writer.close()?;
+    
+    Ok(())
+}
+
+type ColumnWriter<'a> = parquet::file::writer::SerializedColumnWriter<'a>;
+
+fn write_optional_strings<'a>(
+    column: &mut ColumnWriter<'_>,
+    values: impl Iterator<Item = Option<&'a str>>,
+) -> Result<()> {
+    let mut present = Vec::new();
+    let mut definition_levels = Vec::new();
+    
+    for value in values {
+        definition_levels.push(value.is_some() as i16);
+        if let Some(value) = value {
+            present.push(ByteArray::from(value));
+        }
+    }
+    
+    column.typed::<ByteArrayType>().write_batch(&present, Some(&definition_levels), None)?;
+    Ok(())
+}
+
+fn write_optional_ints(
+    column: &mut ColumnWriter<'_>,
+    values: impl Iterator<Item = Option<i64>>,
+) -> Result<()> {
+    let mut present = Vec::new();
+    let mut definition_levels = Vec::new();
+    
+    for value in values {
+        definition_levels.push(value.is_some() as i16);
+        if let Some(value) = value {
+            present.push(value);
+        }
+    }
+    
+    column.typed::<Int64Type>().write_batch(&present, Some(&definition_levels), None)?;
+    Ok(())
+}
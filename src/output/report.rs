@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::output::group::{format_size, group_results, GroupBy};
+use crate::output::long::long_entries;
+
+/// Document formats a search report can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportFormat {
+    /// GitHub-flavored Markdown with pipe tables
+    #[serde(rename = "md")]
+    Markdown,
+    /// A standalone HTML page
+    #[serde(rename = "html")]
+    Html,
+}
+
+/// Figures about a search run shown at the top of its report
+#[derive(Debug, Clone)]
+pub struct ReportSummary {
+    /// Directory the search started from
+    pub root: PathBuf,
+    /// Number of directories visited
+    pub directories_scanned: usize,
+    /// Number of paths that could not be read
+    pub errors: usize,
+    /// Time the search took
+    pub elapsed: Duration,
+}
+
+// Table with a header row; numeric columns are right-aligned
+struct Table {
+    headers: Vec<&'static str>,
+    numeric: Vec<bool>,
+    rows: Vec<Vec<String>>,
+}
+
+/// Render a report of a search: summary figures, a breakdown by extension and every result
+///
+/// Sizes are allocated disk space when `on_disk` is set, as with `--disk-size`.
+pub fn render_report(format: ReportFormat, summary: &ReportSummary, files: &[PathBuf], on_disk: bool) -> String {
+    let groups = group_results(files, GroupBy::Ext, on_disk);
+    let total_size: u64 = groups.iter().map(|group| group.size).sum();
+
+    let overview = Table {
+        headers: vec!["Measure", "Value"],
+        numeric: vec![false, false],
+        rows: vec![
+            vec!["Root".to_string(), summary.root.display().to_string()],
+            vec!["Generated".to_string(), Local::now().format("%Y-%m-%d %H:%M").to_string()],
+            vec!["Files matched".to_string(), files.len().to_string()],
+            vec!["Total size".to_string(), format_size(total_size)],
+            vec!["Directories scanned".to_string(), summary.directories_scanned.to_string()],
+            vec!["Unreadable paths".to_string(), summary.errors.to_string()],
+            vec!["Time taken".to_string(), format!("{:.2} s", summary.elapsed.as_secs_f64())],
+        ],
+    };
+    let extensions = Table {
+        headers: vec!["Extension", "Files", "Size"],
+        numeric: vec![false, true, true],
+        rows: groups.iter().map(|group| vec![
+            if group.key.is_empty() { "(none)".to_string() } else { group.key.clone() },
+            group.count.to_string(),
+            format_size(group.size),
+        ]).collect(),
+    };
+    let results = Table {
+        headers: vec!["Path", "Size", "Modified"],
+        numeric: vec![false, true, false],
+        rows: long_entries(files).into_iter().map(|entry| vec![
+            entry.path.display().to_string(),
+            format_size(entry.size),
+            entry.modified,
+        ]).collect(),
+    };
+
+    let sections = [("Summary", overview), ("By extension", extensions), ("Results", results)];
+    match format {
+        ReportFormat::Markdown => markdown(&summary.root, &sections),
+        ReportFormat::Html => html(&summary.root, &sections),
+    }
+}
+
+fn markdown(root: &Path, sections: &[(&str, Table)]) -> String {
+    let cell = |text: &str| text.replace('|', "\\|");
+    let mut out = format!("# Search report for `{}`\n", root.display());
+    for (title, table) in sections {
+        out.push_str(&format!("\n## {}\n\n", title));
+        out.push_str(&format!("| {} |\n", table.headers.join(" | ")));
+        let separators: Vec<&str> = table.numeric.iter().map(|&numeric| if numeric { "---:" } else { "---" }).collect();
+        out.push_str(&format!("| {} |\n", separators.join(" | ")));
+        for row in &table.rows {
+            let cells: Vec<String> = row.iter().map(|text| cell(text)).collect();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+    }
+    out
+}
+
+fn html(root: &Path, sections: &[(&str, Table)]) -> String {
+    let title = format!("Search report for {}", escape_html(&root.display().to_string()));
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>body {{ font-family: sans-serif; }} table {{ border-collapse: collapse; }} \
+         th, td {{ border: 1px solid #ccc; padding: 2px 8px; text-align: left; }} .num {{ text-align: right; }}</style>\n\
+         </head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    for (title, table) in sections {
+        out.push_str(&format!("<h2>{}</h2>\n<table>\n", title));
+        out.push_str("<tr>");
+        for header in &table.headers {
+            out.push_str(&format!("<th>{}</th>", header));
+        }
+        out.push_str("</tr>\n");
+        for row in &table.rows {
+            out.push_str("<tr>");
+            for (text, &numeric) in row.iter().zip(&table.numeric) {
+                let class = if numeric { " class=\"num\"" } else { "" };
+                out.push_str(&format!("<td{}>{}</td>", class, escape_html(text)));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
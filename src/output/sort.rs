@@ -0,0 +1,85 @@
+use std::cmp::{Ordering, Reverse};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use log::warn;
+
+use crate::core::config::SortOrder;
+use crate::utils::frecency::FrecencyStore;
+
+/// Order search results as requested, keeping the search order for ties
+pub fn sort_results(files: &mut [PathBuf], order: SortOrder) -> Result<()> {
+    match order {
+        SortOrder::None => {}
+        SortOrder::Path => files.sort(),
+        SortOrder::Natural => files.sort_by(|a, b| natural_cmp(a, b)),
+        SortOrder::Frecency => {
+            let store = match FrecencyStore::open_default() {
+                Ok(store) => store,
+                // Without a usable store every score is zero, which keeps the search order
+                Err(e) => {
+                    warn!("Ignoring frecency store: {:#}", e);
+                    return Ok(());
+                }
+            };
+            // Highest score first; the sort is stable, so unscored files stay in order
+            let mut scored: Vec<(f64, PathBuf)> = files.iter()
+                .map(|file| (store.score(file), file.clone()))
+                .collect();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+            for (file, (_, sorted)) in files.iter_mut().zip(scored) {
+                *file = sorted;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Order files newest first by modification time, keeping path order for ties
+///
+/// Files whose modification time cannot be read go last.
+pub fn sort_by_mtime(files: &mut [PathBuf]) {
+    files.sort_by_cached_key(|file| Reverse(fs::metadata(file).and_then(|metadata| metadata.modified()).ok()));
+}
+
+/// Compare paths the way people read them: `file2.txt` before `file10.txt`
+///
+/// Runs of digits compare by their value and text compares ignoring case, falling
+/// back to the plain path order when two paths only differ in case or leading zeros.
+pub fn natural_cmp(a: &Path, b: &Path) -> Ordering {
+    let (a, b) = (a.to_string_lossy(), b.to_string_lossy());
+    natural_str_cmp(&a, &b).then_with(|| a.cmp(&b))
+}
+
+fn natural_str_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(x), Some(y)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+
+        if x.is_ascii_digit() && y.is_ascii_digit() {
+            let (a_digits, a_rest) = split_digits(a);
+            let (b_digits, b_rest) = split_digits(b);
+            // Compare by value without parsing, so that numbers of any length work
+            let (a_value, b_value) = (a_digits.trim_start_matches('0'), b_digits.trim_start_matches('0'));
+            let ordering = a_value.len().cmp(&b_value.len()).then_with(|| a_value.cmp(b_value));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            (a, b) = (a_rest, b_rest);
+            continue;
+        }
+
+        let ordering = x.to_lowercase().cmp(y.to_lowercase());
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        (a, b) = (&a[x.len_utf8()..], &b[y.len_utf8()..]);
+    }
+}
+
+fn split_digits(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(end)
+}
@@ -0,0 +1,107 @@
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection};
+
+/// Marker stored in `PRAGMA application_id` of databases written by oqab ("oqab" in ASCII)
+const APPLICATION_ID: i32 = 0x6f71_6162;
+
+/// Schema for exported results
+///
+/// `files` holds one row per matched file; `matches` holds one row per matched
+/// line for content searches and references the file it was found in.
+const SCHEMA: &str = "
+    DROP TABLE IF EXISTS matches;
+    DROP TABLE IF EXISTS files;
+    CREATE TABLE files (
+        id INTEGER PRIMARY KEY,
+        path TEXT NOT NULL UNIQUE,
+        extension TEXT,
+        size INTEGER,
+        mtime INTEGER
+    );
+    CREATE TABLE matches (
+        id INTEGER PRIMARY KEY,
+        file_id INTEGER NOT NULL REFERENCES files(id),
+        line_number INTEGER NOT NULL,
+        line TEXT NOT NULL
+    );
+    CREATE INDEX idx_files_extension ON files(extension);
+    CREATE INDEX idx_files_size ON files(size);
+    CREATE INDEX idx_files_mtime ON files(mtime);
+    CREATE INDEX idx_matches_file_id ON matches(file_id);
+";
+
+/// Writes search results into a SQLite database for follow-up analysis
+///
+/// Previous results in the database are replaced, and all rows are written in
+/// a single transaction that is committed by `finish`. Only new databases and
+/// those written by an earlier export are used, so tables of other databases
+/// that happen to be called `files` or `matches` are never dropped.
+pub struct SqliteExporter {
+    conn: Connection,
+}
+
+impl SqliteExporter {
+    /// Open (or create) the database and prepare a fresh schema
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open SQLite database: {}", path.display()))?;
+        
+        conn.execute_batch("BEGIN;")?;
+        let tables: i64 = conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get(0))
+            .with_context(|| format!("Failed to read SQLite database: {}", path.display()))?;
+        let application_id: i32 = conn.query_row("PRAGMA application_id", [], |row| row.get(0))?;
+        if tables > 0 && application_id != APPLICATION_ID {
+            bail!("Refusing to overwrite {}: it is not a database written by oqab", path.display());
+        }
+        
+        conn.pragma_update(None, "application_id", APPLICATION_ID)?;
+        conn.execute_batch(SCHEMA)
+            .context("Failed to create SQLite schema")?;
+        
+        Ok(Self { conn })
+    }
+    
+    /// Record a matched file, returning its row id
+    pub fn add_file(&self, path: &Path) -> Result<i64> {
+        let metadata = std::fs::metadata(path).ok();
+        let size = metadata.as_ref().map(|m| m.len() as i64);
+        let mtime = metadata
+            .and_then(|m| m.modified().ok())
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs() as i64);
+        let extension = path.extension().map(|ext| ext.to_string_lossy().to_string());
+        
+        self.conn.execute(
+            "INSERT OR IGNORE INTO files (path, extension, size, mtime) VALUES (?1, ?2, ?3, ?4)",
+            params![path.to_string_lossy(), extension, size, mtime],
+        ).with_context(|| format!("Failed to export file: {}", path.display()))?;
+        
+        let id = self.conn.query_row(
+            "SELECT id FROM files WHERE path = ?1",
+            params![path.to_string_lossy()],
+            |row| row.get(0),
+        )?;
+        
+        Ok(id)
+    }
+    
+    /// Record a matched line within a previously added file
+    pub fn add_match(&self, file_id: i64, line_number: usize, line: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO matches (file_id, line_number, line) VALUES (?1, ?2, ?3)",
+            params![file_id, line_number as i64, line],
+        ).context("Failed to export match")?;
+        
+        Ok(())
+    }
+    
+    /// Commit all exported rows
+    pub fn finish(self) -> Result<()> {
+        self.conn.execute_batch("COMMIT;")
+            .context("Failed to commit SQLite export")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+use std::env;
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use anyhow::{Context, Result};
+use console::{style, Key, Term};
+use log::warn;
+
+/// Standard output was closed by its reader, as when piping into `head`
+///
+/// Commands stop searching as soon as they see it, and the process exits
+/// quietly instead of reporting an error.
+#[derive(Debug, thiserror::Error)]
+#[error("standard output was closed")]
+pub struct BrokenPipeError;
+
+// Lines per page and lines shown since the last prompt; a page size of zero disables paging
+static PAGER: Mutex<(usize, usize)> = Mutex::new((0, 0));
+
+/// Pause for a key press after every `lines` lines of results
+///
+/// Paging only happens when both standard output and standard input are
+/// terminals, so redirected output and pipelines are never held up.
+pub fn set_page_size(lines: usize) {
+    if io::stdout().is_terminal() && io::stdin().is_terminal() {
+        *PAGER.lock().unwrap_or_else(|e| e.into_inner()) = (lines, 0);
+    }
+}
+
+// Output held back until it is known to need the external pager, or the running pager
+static EXTERNAL_PAGER: Mutex<Option<ExternalPager>> = Mutex::new(None);
+
+// Output waiting to fill a screen, then the pager program it is piped into
+enum ExternalPager {
+    Buffering { command: String, height: usize, buffer: Vec<u8>, lines: usize },
+    Running(Child),
+}
+
+/// Pipe results longer than the screen into `$PAGER`, as git does
+///
+/// Results are held back until they fill the screen, so short listings are printed
+/// directly and no pager is started for them. Paging only happens when both
+/// standard output and standard input are terminals, and never when `$PAGER` is
+/// empty or `cat`. Without `$PAGER`, `less` is used. Call [`finish_pager`] once
+/// all results are written.
+pub fn enable_pager() {
+    if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+        return;
+    }
+    let command = env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    if command.trim().is_empty() || command.trim() == "cat" {
+        return;
+    }
+    let (height, _) = Term::stdout().size();
+    *EXTERNAL_PAGER.lock().unwrap_or_else(|e| e.into_inner()) = Some(ExternalPager::Buffering {
+        command,
+        // One line is left for the shell prompt that follows
+        height: usize::from(height).saturating_sub(1).max(1),
+        buffer: Vec::new(),
+        lines: 0,
+    });
+}
+
+/// Print any output still held back for the pager, or wait for the user to quit it
+pub fn finish_pager() -> Result<()> {
+    match EXTERNAL_PAGER.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        None => Ok(()),
+        Some(ExternalPager::Buffering { buffer, .. }) => write_stdout(&buffer),
+        Some(ExternalPager::Running(mut child)) => {
+            // Closing its input lets the pager know the output is complete
+            drop(child.stdin.take());
+            child.wait().context("Failed to wait for the pager")?;
+            Ok(())
+        }
+    }
+}
+
+/// Print output held back for the pager, giving up on paging it
+///
+/// Summaries go straight to standard error, so commands call this before writing
+/// them, or short results would only show up after the summary. Output already
+/// piped into a running pager stays there.
+pub fn flush_pending() -> Result<()> {
+    let mut external = EXTERNAL_PAGER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(ExternalPager::Buffering { buffer, .. }) = external.as_ref() {
+        let written = write_stdout(buffer);
+        *external = None;
+        return written;
+    }
+    Ok(())
+}
+
+// Start the pager program through the shell, so `$PAGER` may carry arguments
+fn spawn_pager(command: &str) -> io::Result<Child> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    // Like git, let less pass colors through and keep the output on screen after quitting
+    if env::var_os("LESS").is_none() {
+        shell.env("LESS", "FRX");
+    }
+    shell.arg(command).stdin(Stdio::piped()).spawn()
+}
+
+/// Write one line of results to standard output
+///
+/// A closed pipe is reported as [`BrokenPipeError`] rather than panicking the way
+/// `println!` does. With paging enabled, a full page first waits for the user, and
+/// quitting at the prompt, or quitting the external pager, is reported the same way.
+pub fn write_line(line: fmt::Arguments<'_>) -> Result<()> {
+    write_record(line, '\n')
+}
+
+/// Write one record of results followed by `terminator`, such as NUL for `--null-data`
+pub fn write_record(record: fmt::Arguments<'_>, terminator: char) -> Result<()> {
+    write_paged(format_args!("{}{}", record, terminator))
+}
+
+/// Write one result exactly as given, such as a filled-in `--printf` template
+pub fn write_text(text: &str) -> Result<()> {
+    write_paged(format_args!("{}", text))
+}
+
+// Write one result, counting it as a line of the current page
+fn write_paged(output: fmt::Arguments<'_>) -> Result<()> {
+    let mut pager = PAGER.lock().unwrap_or_else(|e| e.into_inner());
+    let (page_size, shown) = &mut *pager;
+    if *page_size > 0 {
+        if *shown >= *page_size {
+            *shown = more(*page_size)?;
+        }
+        *shown += 1;
+    }
+    
+    let mut external = EXTERNAL_PAGER.lock().unwrap_or_else(|e| e.into_inner());
+    match external.as_mut() {
+        None => io::stdout().lock().write_fmt(output).map_err(write_error),
+        Some(ExternalPager::Running(child)) => match child.stdin.as_mut() {
+            // The pager closing its input means the user quit it
+            Some(stdin) => stdin.write_fmt(output).map_err(write_error),
+            None => Err(BrokenPipeError.into()),
+        },
+        Some(ExternalPager::Buffering { command, height, buffer, lines }) => {
+            let start = buffer.len();
+            buffer.write_fmt(output).context("Failed to buffer output")?;
+            *lines += buffer[start..].iter().filter(|&&byte| byte == b'\n').count();
+            if *lines < *height {
+                return Ok(());
+            }
+            let buffer = std::mem::take(buffer);
+            match spawn_pager(command) {
+                Ok(mut child) => {
+                    let written = match child.stdin.as_mut() {
+                        Some(stdin) => stdin.write_all(&buffer).map_err(write_error),
+                        None => Err(BrokenPipeError.into()),
+                    };
+                    *external = Some(ExternalPager::Running(child));
+                    written
+                }
+                Err(e) => {
+                    warn!("Failed to start pager '{}': {}", command, e);
+                    *external = None;
+                    write_stdout(&buffer)
+                }
+            }
+        }
+    }
+}
+
+fn write_stdout(bytes: &[u8]) -> Result<()> {
+    io::stdout().lock().write_all(bytes).map_err(write_error)
+}
+
+// A closed pipe ends the output quietly; anything else is a real failure
+fn write_error(e: io::Error) -> anyhow::Error {
+    match e.kind() {
+        io::ErrorKind::BrokenPipe => BrokenPipeError.into(),
+        _ => anyhow::Error::new(e).context("Failed to write to standard output"),
+    }
+}
+
+/// Prompt for more output, returning how many lines of the next page count as already shown
+fn more(page_size: usize) -> Result<usize> {
+    let term = Term::stdout();
+    term.write_str(&style("-- more: space for a page, enter for a line, q to quit --").reverse().to_string())
+        .context("Failed to write to standard output")?;
+    let key = term.read_key().context("Failed to read from the terminal")?;
+    term.clear_line().context("Failed to write to standard output")?;
+    match key {
+        Key::Char('q') | Key::Char('Q') | Key::Escape => Err(BrokenPipeError.into()),
+        Key::Enter => Ok(page_size - 1),
+        _ => Ok(0),
+    }
+}
+
+/// Print a line of results like `println!`, failing once standard output is closed
+macro_rules! outln {
+    () => {
+        $crate::output::stdout::write_line(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::stdout::write_line(format_args!($($arg)*))
+    };
+}
+
+/// Print a record of results ending in the given terminator, failing once standard output is closed
+macro_rules! outrec {
+    ($terminator:expr, $($arg:tt)*) => {
+        $crate::output::stdout::write_record(format_args!($($arg)*), $terminator)
+    };
+}
+
+pub(crate) use outln;
+pub(crate) use outrec;
@@ -0,0 +1,170 @@
+use std::fmt::Write;
+use std::fs::{self, Metadata};
+use std::path::Path;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Local};
+
+/// Result fields a `--printf` template can print
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    /// `%p`: path of the file
+    Path,
+    /// `%f`: file name without its directory
+    Name,
+    /// `%h`: directory containing the file
+    Dir,
+    /// `%e`: extension, without the dot
+    Ext,
+    /// `%s`: size in bytes
+    Size,
+    /// `%t`: modification time in local time
+    Mtime,
+    /// `%m`: permission bits in octal
+    Mode,
+    /// `%n`: number of the matching line
+    LineNumber,
+    /// `%l`: text of the matching line
+    Line,
+}
+
+impl Field {
+    fn from_directive(directive: char) -> Option<Self> {
+        Some(match directive {
+            'p' => Field::Path,
+            'f' => Field::Name,
+            'h' => Field::Dir,
+            'e' => Field::Ext,
+            's' => Field::Size,
+            't' => Field::Mtime,
+            'm' => Field::Mode,
+            'n' => Field::LineNumber,
+            'l' => Field::Line,
+            _ => return None,
+        })
+    }
+
+    fn needs_metadata(self) -> bool {
+        matches!(self, Field::Size | Field::Mtime | Field::Mode)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Field(Field),
+}
+
+/// Output template given with `--printf`, in the manner of `find -printf`
+///
+/// `%p` prints the path, `%f` the file name, `%h` its directory, `%e` its extension,
+/// `%s` its size in bytes, `%t` its modification time, `%m` its permissions in octal,
+/// and for content searches `%n` and `%l` the number and text of the matching line.
+/// `%%` is a percent sign and `\n`, `\t`, `\0` and `\\` the usual escapes. Nothing
+/// is added after each result, so templates normally end in `\n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// Parse a template, rejecting unknown directives and escapes
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '%' => match chars.next() {
+                    Some('%') => literal.push('%'),
+                    Some(directive) => {
+                        let Some(field) = Field::from_directive(directive) else {
+                            bail!("Unknown directive '%{}' in --printf template", directive);
+                        };
+                        if !literal.is_empty() {
+                            parts.push(Part::Literal(std::mem::take(&mut literal)));
+                        }
+                        parts.push(Part::Field(field));
+                    }
+                    None => bail!("--printf template ends in an incomplete '%' directive"),
+                },
+                '\\' => literal.push(match chars.next() {
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some('0') => '\0',
+                    Some('\\') => '\\',
+                    Some(other) => bail!("Unknown escape '\\{}' in --printf template", other),
+                    None => bail!("--printf template ends in an incomplete '\\' escape"),
+                }),
+                _ => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Whether the template prints matching lines, and so is applied once per line rather than per file
+    pub fn uses_lines(&self) -> bool {
+        self.parts.iter().any(|part| matches!(part, Part::Field(Field::LineNumber | Field::Line)))
+    }
+
+    /// Fill in the template for a file and, for content searches, one of its matching lines
+    ///
+    /// Fields that do not apply, such as the size of a file that has gone away, print nothing.
+    pub fn render(&self, path: &Path, line: Option<(usize, &str)>) -> String {
+        let needs_metadata = self.parts.iter().any(|part| matches!(part, Part::Field(field) if field.needs_metadata()));
+        let metadata = if needs_metadata { fs::metadata(path).ok() } else { None };
+
+        let mut output = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => output.push_str(text),
+                Part::Field(field) => render_field(&mut output, *field, path, metadata.as_ref(), line),
+            }
+        }
+        output
+    }
+}
+
+fn render_field(output: &mut String, field: Field, path: &Path, metadata: Option<&Metadata>, line: Option<(usize, &str)>) {
+    // Writing to a String cannot fail
+    let _ = match field {
+        Field::Path => write!(output, "{}", path.display()),
+        Field::Name => write!(output, "{}", path.file_name().unwrap_or_default().to_string_lossy()),
+        Field::Dir => write!(output, "{}", path.parent().unwrap_or(Path::new("")).display()),
+        Field::Ext => write!(output, "{}", path.extension().unwrap_or_default().to_string_lossy()),
+        Field::Size => match metadata {
+            Some(metadata) => write!(output, "{}", metadata.len()),
+            None => Ok(()),
+        },
+        Field::Mtime => match metadata.and_then(|metadata| metadata.modified().ok()) {
+            Some(modified) => write!(output, "{}", DateTime::<Local>::from(modified).format("%Y-%m-%d %H:%M:%S")),
+            None => Ok(()),
+        },
+        Field::Mode => match metadata {
+            Some(metadata) => write!(output, "{:o}", mode(metadata)),
+            None => Ok(()),
+        },
+        Field::LineNumber => match line {
+            Some((number, _)) => write!(output, "{}", number),
+            None => Ok(()),
+        },
+        Field::Line => match line {
+            Some((_, text)) => write!(output, "{}", text),
+            None => Ok(()),
+        },
+    };
+}
+
+#[cfg(unix)]
+fn mode(metadata: &Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+// Only the read-only attribute is known elsewhere, so report what it implies for everyone
+#[cfg(not(unix))]
+fn mode(metadata: &Metadata) -> u32 {
+    if metadata.permissions().readonly() { 0o444 } else { 0o666 }
+}
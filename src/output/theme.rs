@@ -0,0 +1,147 @@
+use anyhow::{bail, Result};
+use console::Style;
+use serde::{Deserialize, Serialize};
+
+/// Built-in color schemes, selected with `--theme` or the `preset` of a theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemePreset {
+    /// The standard colors
+    #[default]
+    Default,
+    /// Bright colors and a highlighted background for matches, for low-vision use
+    HighContrast,
+    /// The standard colors with underlines in place of bold text
+    NoBold,
+}
+
+impl ThemePreset {
+    // Styles for paths, line numbers, matches and headings, in that order
+    fn styles(self) -> [&'static str; 4] {
+        match self {
+            ThemePreset::Default => ["", "green", "red.bold", "cyan.bold"],
+            ThemePreset::HighContrast => ["white.bright", "yellow.bright", "black.on_yellow", "white.bright.bold.underlined"],
+            ThemePreset::NoBold => ["", "green", "red.underlined", "cyan.underlined"],
+        }
+    }
+}
+
+/// When text output is colored, selected with `--color`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Color output written to a terminal, unless the `NO_COLOR` environment variable is set
+    #[default]
+    Auto,
+    /// Always color output, even when it is piped or redirected
+    Always,
+    /// Never color output
+    Never,
+}
+
+impl ColorChoice {
+    /// Turn colors on or off for everything written to standard output and standard error
+    pub fn apply(self) {
+        let enabled = match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            // See https://no-color.org: any non-empty value turns colors off
+            ColorChoice::Auto if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) => false,
+            // Otherwise the terminal detection of `console` decides
+            ColorChoice::Auto => return,
+        };
+        console::set_colors_enabled(enabled);
+        console::set_colors_enabled_stderr(enabled);
+    }
+}
+
+/// Colors of text output, as set in the `theme` section of a configuration file
+///
+/// Each color is a dotted style such as `"red.bold"`, `"black.on_yellow"` or a
+/// 256-color number like `"208"`; colors that are not given come from the preset.
+///
+/// ```json
+/// "theme": { "preset": "no-bold", "line_number": "magenta" }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Theme {
+    /// Scheme the other colors override
+    #[serde(default)]
+    pub preset: ThemePreset,
+
+    /// Paths in result listings
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Line numbers of content matches
+    #[serde(default)]
+    pub line_number: Option<String>,
+
+    /// Matched text within a line
+    #[serde(default)]
+    pub matched: Option<String>,
+
+    /// File headings above grouped content matches
+    #[serde(default)]
+    pub heading: Option<String>,
+}
+
+impl Theme {
+    /// Create a theme using only the colors of a preset
+    pub fn preset(preset: ThemePreset) -> Self {
+        Self { preset, ..Self::default() }
+    }
+
+    /// Style of paths in result listings
+    pub fn path(&self) -> Style {
+        Self::style(&self.path, self.preset.styles()[0])
+    }
+
+    /// Style of line numbers
+    pub fn line_number(&self) -> Style {
+        Self::style(&self.line_number, self.preset.styles()[1])
+    }
+
+    /// Style of matched text
+    pub fn matched(&self) -> Style {
+        Self::style(&self.matched, self.preset.styles()[2])
+    }
+
+    /// Style of file headings
+    pub fn heading(&self) -> Style {
+        Self::style(&self.heading, self.preset.styles()[3])
+    }
+
+    /// Check that every color names a known style, since unknown words would be ignored
+    pub fn validate(&self) -> Result<()> {
+        let colors = [
+            ("path", &self.path),
+            ("line_number", &self.line_number),
+            ("matched", &self.matched),
+            ("heading", &self.heading),
+        ];
+        for (name, color) in colors {
+            let Some(color) = color else { continue };
+            if let Some(part) = color.split('.').find(|part| !is_style_part(part)) {
+                bail!("Unknown style '{}' in theme color {}: '{}'", part, name, color);
+            }
+        }
+        Ok(())
+    }
+
+    fn style(custom: &Option<String>, preset: &str) -> Style {
+        Style::from_dotted_str(custom.as_deref().unwrap_or(preset))
+    }
+}
+
+// Whether a dotted component is understood by `Style::from_dotted_str`
+fn is_style_part(part: &str) -> bool {
+    const COLORS: [&str; 9] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white", "bright"];
+    const ATTRIBUTES: [&str; 8] = ["bold", "dim", "underlined", "blink", "blink_fast", "reverse", "hidden", "strikethrough"];
+
+    let color = part.strip_prefix("on_").unwrap_or(part);
+    part.is_empty()
+        || COLORS.contains(&color)
+        || color.parse::<u8>().is_ok()
+        || ATTRIBUTES.contains(&part)
+}
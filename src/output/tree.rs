@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use console::Style;
+
+/// Entry in a tree of results, holding the subdirectories and matched files below it by name
+#[derive(Debug, Default)]
+struct Node {
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn insert(&mut self, components: &[String]) {
+        if let Some((name, rest)) = components.split_first() {
+            self.children.entry(name.clone()).or_default().insert(rest);
+        }
+    }
+}
+
+/// Render results as an indented tree under the directory they were found in, like `tree`
+///
+/// Files are grouped under their parent directories, with directories and files in
+/// name order and only the directories leading to results shown. Results outside
+/// `root` hang off the root by their full path.
+pub fn render_tree(root: &Path, files: &[PathBuf], directory_style: &Style, file_style: &Style) -> Vec<String> {
+    let mut tree = Node::default();
+    for file in files {
+        let relative = file.strip_prefix(root).unwrap_or(file);
+        let components: Vec<String> = relative.components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        tree.insert(&components);
+    }
+
+    let mut lines = vec![directory_style.apply_to(root.display()).to_string()];
+    render_children(&tree, "", directory_style, file_style, &mut lines);
+    lines
+}
+
+fn render_children(node: &Node, prefix: &str, directory_style: &Style, file_style: &Style, lines: &mut Vec<String>) {
+    let count = node.children.len();
+    for (index, (name, child)) in node.children.iter().enumerate() {
+        let last = index + 1 == count;
+        let (branch, indent) = if last { ("└── ", "    ") } else { ("├── ", "│   ") };
+        let style = if child.children.is_empty() { file_style } else { directory_style };
+        lines.push(format!("{}{}{}", prefix, branch, style.apply_to(name)));
+        render_children(child, &format!("{}{}", prefix, indent), directory_style, file_style, lines);
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+
+/// State of the results browser, kept apart from drawing so it can be driven directly
+///
+/// Results are appended as the search finds them. Typing a filter narrows the list
+/// to paths containing it, ignoring case, and the cursor stays on the same result
+/// where possible while the list changes underneath it.
+#[derive(Debug, Default)]
+pub struct Browser {
+    results: Vec<PathBuf>,
+    filter: String,
+    // Indexes into `results` of the paths passing the filter, in order found
+    visible: Vec<usize>,
+    cursor: usize,
+    finished: bool,
+}
+
+impl Browser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a result found by the search
+    pub fn push(&mut self, path: PathBuf) {
+        if self.matches(&path) {
+            self.visible.push(self.results.len());
+        }
+        self.results.push(path);
+    }
+
+    /// Note that the search is over and no more results will arrive
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Whether the search is over
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Every result found so far
+    pub fn results(&self) -> &[PathBuf] {
+        &self.results
+    }
+
+    /// Up to `len` results passing the filter from position `start`, in the order they were found
+    pub fn visible(&self, start: usize, len: usize) -> impl Iterator<Item = &Path> {
+        self.visible.iter().skip(start).take(len).map(|&index| self.results[index].as_path())
+    }
+
+    /// Number of results passing the filter
+    pub fn visible_count(&self) -> usize {
+        self.visible.len()
+    }
+
+    /// Position of the cursor among the visible results
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Result under the cursor, if any result is visible
+    pub fn selected(&self) -> Option<&Path> {
+        self.visible.get(self.cursor).map(|&index| self.results[index].as_path())
+    }
+
+    /// Text the results are filtered by
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Replace the filter, keeping the cursor on the selected result if it still passes
+    pub fn set_filter(&mut self, filter: &str) {
+        let selected = self.visible.get(self.cursor).copied();
+        self.filter = filter.to_string();
+        self.visible = (0..self.results.len())
+            .filter(|&index| self.matches(&self.results[index]))
+            .collect();
+        self.cursor = selected
+            .and_then(|selected| self.visible.iter().position(|&index| index == selected))
+            .unwrap_or(0);
+    }
+
+    /// Move the cursor by `offset` visible results, stopping at either end
+    pub fn move_by(&mut self, offset: isize) {
+        let last = self.visible.len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(offset).min(last);
+    }
+
+    /// Move the cursor to the first visible result
+    pub fn move_to_start(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Move the cursor to the last visible result
+    pub fn move_to_end(&mut self) {
+        self.cursor = self.visible.len().saturating_sub(1);
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        self.filter.is_empty()
+            || path.to_string_lossy().to_lowercase().contains(&self.filter.to_lowercase())
+    }
+}
@@ -0,0 +1,343 @@
+//! Interactive results browser drawn in the terminal
+//!
+//! Results stream in from a running search and can be filtered, previewed, opened
+//! with the system's default application or copied to the clipboard. Paths opened,
+//! copied or chosen are recorded in the frecency store for `--sort frecency`.
+
+mod browser;
+
+pub use browser::Browser;
+
+use std::fs::File;
+use std::io::{self, Read, Stderr, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::Duration;
+use anyhow::{Context, Result};
+use log::warn;
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::core::finder::FileMatch;
+use crate::utils::frecency::FrecencyStore;
+
+/// How long to wait for a key press before taking in more results
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Most results taken from the search between two redraws
+const RESULTS_PER_FRAME: usize = 2000;
+
+/// Bytes of a file read for its preview
+const PREVIEW_BYTES: u64 = 64 * 1024;
+
+/// What came of browsing the results
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    /// Number of results found, including those that arrived while filtered out
+    pub found: usize,
+    /// Paths chosen with Enter in select mode, empty if the user quit instead
+    pub chosen: Vec<PathBuf>,
+}
+
+/// Browse the results arriving on `results` until the user quits
+///
+/// With `select`, Enter chooses the selected result and ends the session instead of
+/// opening it. The search keeps running in the background while the user looks around.
+pub fn browse(results: Receiver<FileMatch>, select: bool) -> Result<Session> {
+    let mut terminal = init_terminal().context("Failed to set up the terminal")?;
+    let outcome = App::new(results, select).run(&mut terminal);
+    restore_terminal();
+    outcome
+}
+
+// The browser is drawn on standard error, so that standard output carries nothing
+// but the chosen paths and can be captured as in `vim "$(oqab -I --select)"`
+type Terminal = ratatui::Terminal<CrosstermBackend<Stderr>>;
+
+fn init_terminal() -> io::Result<Terminal> {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        hook(info);
+    }));
+    terminal::enable_raw_mode()?;
+    execute!(io::stderr(), EnterAlternateScreen)?;
+    Terminal::new(CrosstermBackend::new(io::stderr()))
+}
+
+fn restore_terminal() {
+    // Raw mode goes first, as it has more side effects than the alternate screen
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(io::stderr(), LeaveAlternateScreen);
+}
+
+// Whether keys edit the filter or act on the results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Browse,
+    Filter,
+}
+
+struct App {
+    browser: Browser,
+    results: Receiver<FileMatch>,
+    mode: Mode,
+    select: bool,
+    chosen: Vec<PathBuf>,
+    preview: bool,
+    status: String,
+    // Preview of the last file shown, so it is not read again every frame
+    previewed: Option<(PathBuf, String)>,
+    // First visible result drawn, moved only as far as needed to keep the cursor in view
+    offset: usize,
+}
+
+impl App {
+    fn new(results: Receiver<FileMatch>, select: bool) -> Self {
+        Self {
+            browser: Browser::new(),
+            results,
+            mode: Mode::Browse,
+            select,
+            chosen: Vec::new(),
+            preview: false,
+            status: String::new(),
+            previewed: None,
+            offset: 0,
+        }
+    }
+
+    fn run(mut self, terminal: &mut Terminal) -> Result<Session> {
+        loop {
+            self.receive();
+            terminal.draw(|frame| self.draw(frame)).context("Failed to draw")?;
+            if !event::poll(POLL_INTERVAL)? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !self.handle_key(key) {
+                    break;
+                }
+            }
+        }
+        Ok(Session { found: self.browser.results().len(), chosen: self.chosen })
+    }
+
+    // Take in the results that arrived since the last frame
+    fn receive(&mut self) {
+        for _ in 0..RESULTS_PER_FRAME {
+            match self.results.try_recv() {
+                Ok(found) => self.browser.push(found.path),
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.browser.finish();
+                    return;
+                }
+            }
+        }
+    }
+
+    // Act on a key press, returning false once the user quits
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.status.clear();
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return false;
+        }
+        match self.mode {
+            Mode::Filter => match key.code {
+                KeyCode::Enter => self.mode = Mode::Browse,
+                KeyCode::Esc => {
+                    self.browser.set_filter("");
+                    self.mode = Mode::Browse;
+                }
+                KeyCode::Backspace => {
+                    let mut filter = self.browser.filter().to_string();
+                    filter.pop();
+                    self.browser.set_filter(&filter);
+                }
+                KeyCode::Char(c) => {
+                    let filter = format!("{}{}", self.browser.filter(), c);
+                    self.browser.set_filter(&filter);
+                }
+                KeyCode::Up => self.browser.move_by(-1),
+                KeyCode::Down => self.browser.move_by(1),
+                _ => {}
+            },
+            Mode::Browse => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return false,
+                KeyCode::Char('/') => self.mode = Mode::Filter,
+                KeyCode::Up | KeyCode::Char('k') => self.browser.move_by(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.browser.move_by(1),
+                KeyCode::PageUp => self.browser.move_by(-20),
+                KeyCode::PageDown => self.browser.move_by(20),
+                KeyCode::Home | KeyCode::Char('g') => self.browser.move_to_start(),
+                KeyCode::End | KeyCode::Char('G') => self.browser.move_to_end(),
+                KeyCode::Char('p') => self.preview = !self.preview,
+                KeyCode::Enter if self.select => return !self.choose_selected(),
+                KeyCode::Enter | KeyCode::Char('o') => self.open_selected(),
+                KeyCode::Char('y') => self.copy_selected(),
+                _ => {}
+            },
+        }
+        true
+    }
+
+    // Choose the selected result to end the session with, returning false if there is none
+    fn choose_selected(&mut self) -> bool {
+        let Some(path) = self.browser.selected().map(Path::to_path_buf) else {
+            return false;
+        };
+        // The terminal is about to be handed back, so a failure is only logged
+        if let Err(e) = record(&path) {
+            warn!("Failed to record {}: {:#}", path.display(), e);
+        }
+        self.chosen.push(path);
+        true
+    }
+
+    fn open_selected(&mut self) {
+        let Some(path) = self.browser.selected().map(Path::to_path_buf) else {
+            return;
+        };
+        self.status = match open(&path) {
+            Ok(()) => match record(&path) {
+                Ok(()) => format!("Opened {}", path.display()),
+                Err(e) => format!("Opened {}, but failed to record it: {:#}", path.display(), e),
+            },
+            Err(e) => format!("Failed to open {}: {}", path.display(), e),
+        };
+    }
+
+    fn copy_selected(&mut self) {
+        let Some(path) = self.browser.selected().map(Path::to_path_buf) else {
+            return;
+        };
+        self.status = match copy_to_clipboard(&path.to_string_lossy()) {
+            Ok(()) => match record(&path) {
+                Ok(()) => format!("Copied {}", path.display()),
+                Err(e) => format!("Copied {}, but failed to record it: {:#}", path.display(), e),
+            },
+            Err(e) => format!("Failed to copy {}: {}", path.display(), e),
+        };
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [list_area, preview_area] = if self.preview {
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(main)
+        } else {
+            [main, Default::default()]
+        };
+
+        // Only the rows that fit are built, however many results there are
+        let height = list_area.height.saturating_sub(2) as usize;
+        let cursor = self.browser.cursor();
+        if cursor < self.offset {
+            self.offset = cursor;
+        } else if height > 0 && cursor >= self.offset + height {
+            self.offset = cursor + 1 - height;
+        }
+        let items: Vec<ListItem> = self.browser.visible(self.offset, height)
+            .map(|path| ListItem::new(path.display().to_string()))
+            .collect();
+        let searching = if self.browser.is_finished() { "" } else { ", searching…" };
+        let title = format!(" {}/{} results{} ", self.browser.visible_count(), self.browser.results().len(), searching);
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default()
+            .with_selected((self.browser.visible_count() > 0).then(|| cursor - self.offset));
+        frame.render_stateful_widget(list, list_area, &mut state);
+
+        if self.preview {
+            let (title, text) = match self.browser.selected().map(Path::to_path_buf) {
+                Some(path) => {
+                    if self.previewed.as_ref().is_none_or(|(previewed, _)| *previewed != path) {
+                        self.previewed = Some((path.clone(), preview(&path)));
+                    }
+                    let text = self.previewed.as_ref().map(|(_, text)| text.clone()).unwrap_or_default();
+                    (format!(" {} ", path.display()), text)
+                }
+                None => (String::new(), String::new()),
+            };
+            let paragraph = Paragraph::new(text)
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .wrap(Wrap { trim: false });
+            frame.render_widget(paragraph, preview_area);
+        }
+
+        let footer_text = match self.mode {
+            Mode::Filter => format!("/{}", self.browser.filter()),
+            Mode::Browse if !self.status.is_empty() => self.status.clone(),
+            Mode::Browse if self.select => "↑↓ move  / filter  p preview  enter choose  o open  y copy  q quit".to_string(),
+            Mode::Browse => "↑↓ move  / filter  p preview  enter open  y copy  q quit".to_string(),
+        };
+        frame.render_widget(Line::from(footer_text), footer);
+    }
+}
+
+// Record a path the user acted on, so that `--sort frecency` ranks it higher next time
+fn record(path: &Path) -> Result<()> {
+    FrecencyStore::record_chosen(&[path])
+}
+
+// Start of a file as text, or a note saying why it cannot be shown
+fn preview(path: &Path) -> String {
+    let mut content = Vec::new();
+    match File::open(path).and_then(|file| file.take(PREVIEW_BYTES).read_to_end(&mut content)) {
+        Ok(_) if content.contains(&0) => "(binary file)".to_string(),
+        Ok(_) => String::from_utf8_lossy(&content).into_owned(),
+        Err(e) => format!("(cannot read: {})", e),
+    }
+}
+
+// Open a file with the default application, without waiting for it
+fn open(path: &Path) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command.arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+// Ask the terminal to put text on the clipboard with an OSC 52 sequence, which also
+// works over SSH; terminals that do not support it ignore the sequence
+fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stderr = io::stderr();
+    write!(stderr, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    stderr.flush()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| group | u32::from(byte) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
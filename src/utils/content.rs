@@ -0,0 +1,34 @@
+use std::fs::File;
+use std::io::{self, BufRead, Read};
+use std::path::Path;
+
+/// Number of leading bytes sampled when classifying a file as binary
+pub const BINARY_SAMPLE_SIZE: usize = 8 * 1024;
+
+/// Check whether a sample of file content looks binary
+///
+/// A NUL byte anywhere in the sample marks the content as binary, the same
+/// heuristic used by grep and ripgrep. So does a sample of which more than a
+/// quarter fails to decode as UTF-8, while text in legacy encodings, with only
+/// the odd accented letter out of place, still counts as text.
+pub fn is_binary(sample: &[u8]) -> bool {
+    if sample.contains(&0) {
+        return true;
+    }
+    let invalid: usize = sample.utf8_chunks().map(|chunk| chunk.invalid().len()).sum();
+    invalid * 4 > sample.len()
+}
+
+/// Check whether a reader's upcoming content looks binary without consuming it
+pub fn starts_binary<R: BufRead>(reader: &mut R) -> io::Result<bool> {
+    let buffer = reader.fill_buf()?;
+    let sample = &buffer[..buffer.len().min(BINARY_SAMPLE_SIZE)];
+    Ok(is_binary(sample))
+}
+
+/// Read the leading bytes of a file that classify its content
+pub fn read_sample(path: &Path) -> io::Result<Vec<u8>> {
+    let mut sample = Vec::with_capacity(BINARY_SAMPLE_SIZE);
+    File::open(path)?.take(BINARY_SAMPLE_SIZE as u64).read_to_end(&mut sample)?;
+    Ok(sample)
+}
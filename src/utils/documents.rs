@@ -0,0 +1,191 @@
+use std::path::Path;
+use anyhow::Result;
+
+use crate::core::config::{DocType, FileSearchConfig};
+
+/// Turns a document format into plain text that content search can scan
+pub trait TextExtractor: Send + Sync {
+    /// Document type the extractor handles
+    fn doc_type(&self) -> DocType;
+    
+    /// Lowercase extensions of the files the extractor reads
+    fn extensions(&self) -> &'static [&'static str];
+    
+    /// Extract the text of a document, with one line per paragraph where possible
+    fn extract(&self, path: &Path) -> Result<String>;
+}
+
+/// Text of PDF documents
+#[cfg(feature = "pdf")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PdfExtractor;
+
+#[cfg(feature = "pdf")]
+impl TextExtractor for PdfExtractor {
+    fn doc_type(&self) -> DocType {
+        DocType::Pdf
+    }
+    
+    fn extensions(&self) -> &'static [&'static str] {
+        &["pdf"]
+    }
+    
+    fn extract(&self, path: &Path) -> Result<String> {
+        use anyhow::Context;
+        
+        let content = std::fs::read(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        // The PDF parser panics on some malformed files; treat that like any other failure
+        std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(&content))
+            .map_err(|_| anyhow::anyhow!("Malformed PDF: {}", path.display()))?
+            .with_context(|| format!("Failed to extract text from {}", path.display()))
+    }
+}
+
+/// Text of Word (Office Open XML) documents
+#[cfg(feature = "docx")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DocxExtractor;
+
+#[cfg(feature = "docx")]
+impl TextExtractor for DocxExtractor {
+    fn doc_type(&self) -> DocType {
+        DocType::Docx
+    }
+    
+    fn extensions(&self) -> &'static [&'static str] {
+        &["docx"]
+    }
+    
+    fn extract(&self, path: &Path) -> Result<String> {
+        use std::io::Read;
+        use anyhow::Context;
+        
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .with_context(|| format!("Not a DOCX document: {}", path.display()))?;
+        let mut xml = String::new();
+        archive.by_name("word/document.xml")
+            .with_context(|| format!("Not a DOCX document: {}", path.display()))?
+            .read_to_string(&mut xml)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        
+        Ok(docx_text(&xml))
+    }
+}
+
+// Text runs of a WordprocessingML body, with paragraphs on their own lines
+#[cfg(feature = "docx")]
+fn docx_text(xml: &str) -> String {
+    let mut text = String::new();
+    let mut rest = xml;
+    let mut in_text = false;
+    
+    while let Some(start) = rest.find('<') {
+        if in_text {
+            text.push_str(&unescape_xml(&rest[..start]));
+        }
+        let Some(end) = rest[start..].find('>') else { break };
+        let tag = &rest[start + 1..start + end];
+        let name = tag.trim_start_matches('/').split([' ', '/']).next().unwrap_or("");
+        match name {
+            "w:t" => in_text = !tag.starts_with('/') && !tag.ends_with('/'),
+            "w:tab" => text.push('\t'),
+            "w:br" | "w:cr" => text.push('\n'),
+            "w:p" if tag.starts_with('/') => text.push('\n'),
+            _ => {}
+        }
+        rest = &rest[start + end + 1..];
+    }
+    
+    text
+}
+
+#[cfg(feature = "docx")]
+fn unescape_xml(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find(';') else { break };
+        let entity = &rest[start + 1..start + end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity.strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => unescaped.push(c),
+            None => unescaped.push_str(&rest[start..start + end + 1]),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// The text extractors a content search runs, chosen by file extension
+#[derive(Default)]
+pub struct DocumentExtractors {
+    extractors: Vec<Box<dyn TextExtractor>>,
+}
+
+impl DocumentExtractors {
+    /// Document types this build can extract text from
+    pub fn available() -> Vec<DocType> {
+        Self::all().extractors.iter().map(|extractor| extractor.doc_type()).collect()
+    }
+    
+    /// Every extractor compiled into this build
+    pub fn all() -> Self {
+        let extractors: Vec<Box<dyn TextExtractor>> = vec![
+            #[cfg(feature = "pdf")]
+            Box::new(PdfExtractor),
+            #[cfg(feature = "docx")]
+            Box::new(DocxExtractor),
+        ];
+        Self { extractors }
+    }
+    
+    /// Extractors for the given document types that this build supports
+    pub fn for_types(doc_types: &[DocType]) -> Self {
+        let mut all = Self::all();
+        all.extractors.retain(|extractor| doc_types.contains(&extractor.doc_type()));
+        all
+    }
+    
+    /// Extractors selected by `--doc-types`, or all of them if it was not given
+    pub fn for_config(config: &FileSearchConfig) -> Self {
+        match &config.doc_types {
+            Some(doc_types) => Self::for_types(doc_types),
+            None => Self::all(),
+        }
+    }
+    
+    /// Extractor for a file, if it is a document of a selected type
+    pub fn find(&self, path: &Path) -> Option<&dyn TextExtractor> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        self.extractors.iter()
+            .find(|extractor| extractor.extensions().contains(&extension.as_str()))
+            .map(|extractor| extractor.as_ref())
+    }
+    
+    /// Extractor of a document type, if it is selected
+    pub fn find_type(&self, doc_type: DocType) -> Option<&dyn TextExtractor> {
+        self.extractors.iter()
+            .find(|extractor| extractor.doc_type() == doc_type)
+            .map(|extractor| extractor.as_ref())
+    }
+    
+    /// Whether no extractor is selected
+    pub fn is_empty(&self) -> bool {
+        self.extractors.is_empty()
+    }
+}
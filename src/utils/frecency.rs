@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::core::data_file::{data_location, DataFile};
+use crate::core::Platform;
+
+/// Name of the store inside the user's data directory
+const FILE_NAME: &str = "frecency.json";
+
+/// What the store is called in error messages
+const WHAT: &str = "frecency store";
+
+/// Total selection count above which all entries are aged
+const MAX_TOTAL_COUNT: f64 = 10_000.0;
+
+/// Factor applied to every count when the store is aged
+const AGING_FACTOR: f64 = 0.9;
+
+/// How often and how recently a path was chosen from results
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    count: f64,
+    last_selected: u64,
+}
+
+/// Small on-disk store of the paths users pick from search results
+///
+/// Scores combine how often a path was selected with how recently, in the style
+/// of zoxide: a selection in the last hour weighs four times as much as one a
+/// day ago. Counts are periodically aged so that old habits fade out.
+#[derive(Debug)]
+pub struct FrecencyStore {
+    entries: DataFile<HashMap<PathBuf, Entry>>,
+}
+
+impl FrecencyStore {
+    /// Default store location inside the user's data directory
+    pub fn default_location() -> Option<PathBuf> {
+        data_location(FILE_NAME)
+    }
+    
+    /// Open the store at the default location
+    pub fn open_default() -> Result<Self> {
+        Ok(Self { entries: DataFile::open_default(FILE_NAME, WHAT)? })
+    }
+    
+    /// Open the store at `location`, starting empty if it does not exist yet
+    pub fn open<P: AsRef<Path>>(location: P) -> Result<Self> {
+        Ok(Self { entries: DataFile::open(location, WHAT)? })
+    }
+    
+    /// Record in the default store that `paths` were chosen, and save it
+    pub fn record_chosen<P: AsRef<Path>>(paths: &[P]) -> Result<()> {
+        let mut store = Self::open_default()?;
+        for path in paths {
+            store.record(path.as_ref());
+        }
+        store.save()
+    }
+    
+    /// Record that a path was chosen from the results
+    pub fn record(&mut self, path: &Path) {
+        let now = unix_now();
+        let entries = self.entries.contents_mut();
+        let entry = entries.entry(canonical(path)).or_insert(Entry { count: 0.0, last_selected: now });
+        entry.count += 1.0;
+        entry.last_selected = now;
+        
+        let total: f64 = entries.values().map(|entry| entry.count).sum();
+        if total > MAX_TOTAL_COUNT {
+            for entry in entries.values_mut() {
+                entry.count *= AGING_FACTOR;
+            }
+            entries.retain(|_, entry| entry.count >= 1.0);
+        }
+    }
+    
+    /// Score of a path, or zero if it was never selected
+    pub fn score(&self, path: &Path) -> f64 {
+        let Some(entry) = self.entries.contents().get(&canonical(path)) else {
+            return 0.0;
+        };
+        
+        let age = unix_now().saturating_sub(entry.last_selected);
+        let weight = match age {
+            0..3_600 => 4.0,
+            3_600..86_400 => 2.0,
+            86_400..604_800 => 0.5,
+            _ => 0.25,
+        };
+        entry.count * weight
+    }
+    
+    /// Write the store back to disk
+    pub fn save(&self) -> Result<()> {
+        self.entries.save()
+    }
+}
+
+// Paths are stored absolute, and case-folded on volumes that ignore case, so that
+// scores apply however a path is spelled
+fn canonical(path: &Path) -> PathBuf {
+    Platform::path_key(path)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,96 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use anyhow::{bail, Context, Result};
+
+use crate::core::config::{DocType, FileSearchConfig, Handler};
+use crate::utils::documents::{DocumentExtractors, TextExtractor};
+use crate::utils::mime;
+
+/// Handlers that content search consults before reading a file
+///
+/// Keys containing a `/` are MIME types, either exact like `application/gzip` or
+/// covering a whole family like `text/*`; other keys are extensions. Extensions take
+/// precedence, then exact MIME types, then families.
+#[derive(Default)]
+pub struct HandlerRegistry {
+    by_extension: HashMap<String, Handler>,
+    by_mime: HashMap<String, Handler>,
+    extractors: DocumentExtractors,
+}
+
+impl HandlerRegistry {
+    /// Registry of the given handlers, checking that each one can run in this build
+    pub fn new(handlers: &BTreeMap<String, Handler>) -> Result<Self> {
+        let mut registry = Self { extractors: DocumentExtractors::all(), ..Self::default() };
+        for (key, handler) in handlers {
+            match handler {
+                Handler::Extract(doc_type) if registry.extractors.find_type(*doc_type).is_none() => {
+                    bail!("Handler for {} extracts {:?} documents, which this build does not support", key, doc_type);
+                }
+                Handler::Command(command) if command.is_empty() => {
+                    bail!("Handler for {} has an empty command", key);
+                }
+                _ => {}
+            }
+
+            let key = key.to_lowercase();
+            if key.contains('/') {
+                registry.by_mime.insert(key, handler.clone());
+            } else {
+                registry.by_extension.insert(key.trim_start_matches('.').to_string(), handler.clone());
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Registry of the handlers in a configuration
+    pub fn for_config(config: &FileSearchConfig) -> Result<Self> {
+        Self::new(&config.handlers)
+    }
+
+    /// Handler configured for a file, if any
+    pub fn find(&self, path: &Path) -> Option<&Handler> {
+        if let Some(extension) = path.extension() {
+            if let Some(handler) = self.by_extension.get(&extension.to_string_lossy().to_lowercase()) {
+                return Some(handler);
+            }
+        }
+        if self.by_mime.is_empty() {
+            return None;
+        }
+
+        let mime = mime::from_path(path)?;
+        let family = mime.split('/').next().map(|family| format!("{}/*", family));
+        self.by_mime.get(mime)
+            .or_else(|| family.and_then(|family| self.by_mime.get(&family)))
+    }
+
+    /// Extractor behind an `extract` handler
+    pub fn extractor(&self, doc_type: DocType) -> Option<&dyn TextExtractor> {
+        self.extractors.find_type(doc_type)
+    }
+
+    /// Whether no handler is configured
+    pub fn is_empty(&self) -> bool {
+        self.by_extension.is_empty() && self.by_mime.is_empty()
+    }
+}
+
+/// Run a handler command on a file and return what it wrote to standard output
+pub fn run_command(command: &[String], path: &Path) -> Result<String> {
+    let Some((program, args)) = command.split_first() else {
+        bail!("Empty handler command for {}", path.display());
+    };
+    let output = Command::new(program)
+        .args(args)
+        .arg(path)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .with_context(|| format!("Failed to run handler {} on {}", program, path.display()))?;
+    if !output.status.success() {
+        bail!("Handler {} failed on {} ({})", program, path.display(), output.status);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
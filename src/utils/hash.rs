@@ -0,0 +1,12 @@
+/// Starting state of a 64-bit FNV-1a hash
+pub const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Continue a 64-bit FNV-1a hash over `bytes`, starting from `FNV_OFFSET`
+///
+/// Unlike the standard library's hashers the result is the same on every run and
+/// platform, so it can name files on disk and be compared across processes.
+pub fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
@@ -0,0 +1,363 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use serde_json::json;
+
+use crate::core::{config::FileSearchConfig, observer::SearchObserver};
+use crate::utils::standard_search::search_directory;
+
+/// Most requests served at once; further connections wait to be accepted
+const MAX_CONNECTIONS: usize = 16;
+
+/// Longest a client may take to send its request or to take a piece of the response
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Minimal HTTP/1.1 server answering `GET /search` with streamed JSON results
+///
+/// Supported query parameters are `path` (relative to the served root),
+/// `ext`, `name` and `q` (the same smart query accepted on the command line, so
+/// a glob, `re:` regular expression, `.ext` or file name; `ext` and `name` win
+/// over what it sets). Results are written with chunked transfer
+/// encoding as soon as they are found, so clients can start consuming them
+/// before the search completes.
+pub struct HttpServer {
+    listener: TcpListener,
+    base_config: FileSearchConfig,
+    root: PathBuf,
+}
+
+impl HttpServer {
+    /// Bind the server to the given address, serving searches below the configured path
+    pub fn bind(address: &str, base_config: FileSearchConfig) -> Result<Self> {
+        let root = Path::new(base_config.get_path())
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve search root: {}", base_config.get_path()))?;
+        
+        let listener = TcpListener::bind(address)
+            .with_context(|| format!("Failed to bind HTTP server to {}", address))?;
+        
+        Ok(Self { listener, base_config, root })
+    }
+    
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+    
+    /// Accept connections forever, handling each one on its own thread
+    ///
+    /// At most `MAX_CONNECTIONS` are handled at once, others waiting in the listen
+    /// queue, and clients that stall are dropped after `IO_TIMEOUT`, so slow or
+    /// numerous clients cannot pile up threads.
+    pub fn serve(&self) -> Result<()> {
+        info!("Serving searches below {}", self.root.display());
+        
+        let slots = Arc::new(ConnectionSlots::default());
+        loop {
+            let slot = slots.acquire();
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    warn!("Failed to accept connection: {}", e);
+                    continue;
+                }
+            };
+            if let Err(e) = stream.set_read_timeout(Some(IO_TIMEOUT)).and_then(|()| stream.set_write_timeout(Some(IO_TIMEOUT))) {
+                debug!("Failed to set connection timeouts: {}", e);
+                continue;
+            }
+            
+            let base_config = self.base_config.clone();
+            let root = self.root.clone();
+            thread::spawn(move || {
+                let _slot = slot;
+                if let Err(e) = handle_connection(stream, &base_config, &root) {
+                    debug!("Connection error: {:#}", e);
+                }
+            });
+        }
+    }
+}
+
+/// Counts the requests being served, so that no more than `MAX_CONNECTIONS` are
+#[derive(Default)]
+struct ConnectionSlots {
+    active: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl ConnectionSlots {
+    /// Wait until fewer than `MAX_CONNECTIONS` requests are being served, and take a slot
+    fn acquire(self: &Arc<Self>) -> ConnectionSlot {
+        let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+        while *active >= MAX_CONNECTIONS {
+            active = self.freed.wait(active).unwrap_or_else(|e| e.into_inner());
+        }
+        *active += 1;
+        ConnectionSlot(Arc::clone(self))
+    }
+}
+
+/// Slot of a request being served, given back when dropped
+struct ConnectionSlot(Arc<ConnectionSlots>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        *self.0.active.lock().unwrap_or_else(|e| e.into_inner()) -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+/// Parse a single request and write the response
+fn handle_connection(stream: TcpStream, base_config: &FileSearchConfig, root: &Path) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut stream = stream;
+    
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    
+    // Drain the headers, none of which influence the response
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+    
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let target = parts.next().unwrap_or("");
+    debug!("HTTP {} {}", method, target);
+    
+    if method != "GET" {
+        return write_error(&mut stream, "405 Method Not Allowed", "only GET is supported");
+    }
+    
+    let (route, query) = target.split_once('?').unwrap_or((target, ""));
+    if route != "/search" {
+        return write_error(&mut stream, "404 Not Found", "unknown endpoint");
+    }
+    
+    let params = parse_query(query);
+    let config = match search_config(base_config, root, &params) {
+        Ok(config) => config,
+        Err(message) => return write_error(&mut stream, "400 Bad Request", &message),
+    };
+    
+    stream_results(stream, &config)
+}
+
+/// Build the search configuration for a request from the server defaults
+fn search_config(
+    base_config: &FileSearchConfig,
+    root: &Path,
+    params: &HashMap<String, String>,
+) -> Result<FileSearchConfig, String> {
+    let mut config = base_config.clone();
+    
+    // Requests may only search inside the served root
+    let search_root = match params.get("path") {
+        Some(path) => root.join(path.trim_start_matches('/')),
+        None => root.to_path_buf(),
+    };
+    let search_root = search_root
+        .canonicalize()
+        .map_err(|e| format!("invalid path: {}", e))?;
+    if !search_root.starts_with(root) || !search_root.is_dir() {
+        return Err("path must be a directory inside the served root".to_string());
+    }
+    config.path = Some(search_root.to_string_lossy().to_string());
+    
+    if let Some(query) = params.get("q") {
+        config.apply_query(query, false, !params.contains_key("name"), !params.contains_key("ext"));
+    }
+    if let Some(ext) = params.get("ext") {
+        config.file_extension = Some(ext.clone());
+    }
+    if let Some(name) = params.get("name") {
+        config.file_name = Some(name.clone());
+    }
+    
+    Ok(config)
+}
+
+/// Run the search, writing each match to the client as soon as it is found
+fn stream_results(mut stream: TcpStream, config: &FileSearchConfig) -> Result<()> {
+    let start_time = Instant::now();
+    
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\n\
+          Content-Type: application/json\r\n\
+          Transfer-Encoding: chunked\r\n\
+          Connection: close\r\n\r\n",
+    )?;
+    
+    let mut writer = ChunkedWriter::new(stream);
+    writer.write_chunk(b"{\"results\":[")?;
+    
+    let observer = StreamingObserver::new(writer);
+    let search_root = PathBuf::from(config.get_path());
+    let outcome = search_directory(&search_root, config, &observer);
+    
+    let mut writer = observer.into_writer();
+    let mut trailer = json!({
+        "count": outcome.as_ref().map(|files| files.len()).unwrap_or(0),
+        "elapsed_ms": start_time.elapsed().as_millis() as u64,
+    });
+    if let Err(e) = &outcome {
+        trailer["error"] = json!(format!("{:#}", e));
+    }
+    
+    // Splice the trailer fields into the streamed object after the results array
+    let trailer = trailer.to_string();
+    writer.write_chunk(format!("],{}", &trailer[1..]).as_bytes())?;
+    writer.finish()
+}
+
+/// Write a JSON error response with the given status line
+fn write_error(stream: &mut TcpStream, status: &str, message: &str) -> Result<()> {
+    let body = json!({ "error": message }).to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    Ok(())
+}
+
+/// Parse `key=value&...` pairs, decoding percent escapes and `+`
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                match (hex_value(bytes[i + 1]), hex_value(bytes[i + 2])) {
+                    (Some(high), Some(low)) => {
+                        decoded.push(high << 4 | low);
+                        i += 2;
+                    }
+                    _ => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|digit| digit as u8)
+}
+
+/// Writer producing HTTP/1.1 chunked transfer encoding
+struct ChunkedWriter {
+    stream: TcpStream,
+}
+
+impl ChunkedWriter {
+    fn new(stream: TcpStream) -> Self {
+        Self { stream }
+    }
+    
+    fn write_chunk(&mut self, data: &[u8]) -> io::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        write!(self.stream, "{:x}\r\n", data.len())?;
+        self.stream.write_all(data)?;
+        self.stream.write_all(b"\r\n")?;
+        self.stream.flush()
+    }
+    
+    fn finish(mut self) -> Result<()> {
+        self.stream.write_all(b"0\r\n\r\n")?;
+        self.stream.flush()?;
+        Ok(())
+    }
+}
+
+/// Observer that forwards every found file to the client as a JSON object
+struct StreamingObserver {
+    writer: Mutex<ChunkedWriter>,
+    files_count: AtomicUsize,
+    dirs_count: AtomicUsize,
+    disconnected: AtomicBool,
+}
+
+impl StreamingObserver {
+    fn new(writer: ChunkedWriter) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+            files_count: AtomicUsize::new(0),
+            dirs_count: AtomicUsize::new(0),
+            disconnected: AtomicBool::new(false),
+        }
+    }
+    
+    fn into_writer(self) -> ChunkedWriter {
+        self.writer
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl SearchObserver for StreamingObserver {
+    fn file_found(&self, file_path: &Path) {
+        let index = self.files_count.fetch_add(1, Ordering::Relaxed);
+        if self.disconnected.load(Ordering::Relaxed) {
+            return;
+        }
+        
+        let size = std::fs::metadata(file_path).map(|m| m.len()).ok();
+        let entry = json!({ "path": file_path.to_string_lossy(), "size": size }).to_string();
+        let chunk = if index == 0 { entry } else { format!(",{}", entry) };
+        
+        if let Ok(mut writer) = self.writer.lock() {
+            if writer.write_chunk(chunk.as_bytes()).is_err() {
+                debug!("Client disconnected, dropping remaining results");
+                self.disconnected.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+    
+    fn directory_processed(&self, _dir_path: &Path) {
+        self.dirs_count.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    fn files_count(&self) -> usize {
+        self.files_count.load(Ordering::Relaxed)
+    }
+    
+    fn directories_count(&self) -> usize {
+        self.dirs_count.load(Ordering::Relaxed)
+    }
+    
+    fn as_any(&self) -> &dyn Any { self }
+}
@@ -1,3 +1,13 @@
+pub mod content;
+pub mod documents;
+pub mod frecency;
+pub mod handlers;
+pub mod hash;
+pub mod mime;
+pub mod schedule;
+pub mod similarity;
 pub mod standard_search;
+#[cfg(feature = "http")]
+pub mod http_server;
 
-pub use standard_search::search_directory; 
\ No newline at end of file
+pub use standard_search::{collect_files, search_directory}; 
\ No newline at end of file
@@ -0,0 +1,261 @@
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::FileSearchConfig;
+use crate::core::observer::SilentObserver;
+use crate::utils::collect_files;
+
+/// Longest a webhook may take to accept a connection, take a report or respond
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Searches to run periodically, loaded from a JSON schedule file
+///
+/// ```json
+/// {
+///   "jobs": [{
+///     "name": "stale-data",
+///     "interval": "1d",
+///     "min_age": "90d",
+///     "search": { "path": "/data" },
+///     "output": { "file": "/var/log/oqab/stale-data.jsonl" }
+///   }]
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Jobs to run, each on its own interval
+    pub jobs: Vec<ScheduledJob>,
+}
+
+impl Schedule {
+    /// Load and validate a schedule file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read schedule file: {}", path.display()))?;
+        let schedule: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse schedule file: {}", path.display()))?;
+
+        if schedule.jobs.is_empty() {
+            bail!("Schedule file defines no jobs: {}", path.display());
+        }
+        for job in &schedule.jobs {
+            job.interval()?;
+            job.age_bounds(SystemTime::now())?;
+        }
+
+        Ok(schedule)
+    }
+}
+
+/// A search run on an interval, with its report sent to a sink
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    /// Name identifying the job in its reports
+    pub name: String,
+
+    /// Time between runs, such as `30m`, `6h` or `1d`
+    pub interval: String,
+
+    /// Only report files last modified at least this long ago, such as `90d`
+    #[serde(default)]
+    pub min_age: Option<String>,
+
+    /// Only report files last modified at most this long ago
+    #[serde(default)]
+    pub max_age: Option<String>,
+
+    /// File criteria of the search, as in a configuration file
+    pub search: FileSearchConfig,
+
+    /// Where to send reports (standard output if not given)
+    #[serde(default)]
+    pub output: Option<ReportSink>,
+}
+
+impl ScheduledJob {
+    /// Time between runs of the job
+    pub fn interval(&self) -> Result<Duration> {
+        let interval = parse_interval(&self.interval)
+            .with_context(|| format!("Invalid interval for job '{}'", self.name))?;
+        // A daemon would otherwise rerun the job without ever pausing
+        if interval.is_zero() {
+            bail!("Invalid interval for job '{}': it must be longer than zero", self.name);
+        }
+        Ok(interval)
+    }
+
+    /// Run the search once and build its report
+    pub fn run(&self) -> Result<Report> {
+        let now = SystemTime::now();
+        let mut config = self.search.clone();
+        let (newer_than, older_than) = self.age_bounds(now)?;
+        config.newer_than = newer_than.or(config.newer_than);
+        config.older_than = older_than.or(config.older_than);
+
+        let files = collect_files(&config, &SilentObserver::new())
+            .with_context(|| format!("Search for job '{}' failed in: {}", self.name, config.get_path()))?;
+
+        Ok(Report {
+            job: self.name.clone(),
+            generated_at: DateTime::<Utc>::from(now).to_rfc3339(),
+            path: config.get_path().to_string(),
+            count: files.len(),
+            files: files.into_iter().map(ReportFile::new).collect(),
+        })
+    }
+
+    // Modification time bounds equivalent to the configured ages at `now`
+    fn age_bounds(&self, now: SystemTime) -> Result<(Option<SystemTime>, Option<SystemTime>)> {
+        let bound = |age: &Option<String>| -> Result<Option<SystemTime>> {
+            match age {
+                Some(age) => {
+                    let age = parse_interval(age)
+                        .with_context(|| format!("Invalid age for job '{}'", self.name))?;
+                    Ok(Some(now.checked_sub(age).unwrap_or(SystemTime::UNIX_EPOCH)))
+                }
+                None => Ok(None),
+            }
+        };
+        Ok((bound(&self.max_age)?, bound(&self.min_age)?))
+    }
+}
+
+/// Destination of a job's reports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportSink {
+    /// Append each report as a JSON line to a file
+    File(PathBuf),
+    /// POST each report as JSON to an `http://` URL
+    Webhook(String),
+}
+
+impl ReportSink {
+    /// Send a report to the sink
+    pub fn deliver(&self, report: &Report) -> Result<()> {
+        let body = serde_json::to_string(report).context("Failed to serialize report")?;
+        match self {
+            ReportSink::File(path) => {
+                if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+                }
+                let mut file = OpenOptions::new().create(true).append(true).open(path)
+                    .with_context(|| format!("Failed to open report file: {}", path.display()))?;
+                writeln!(file, "{}", body)
+                    .with_context(|| format!("Failed to write report file: {}", path.display()))?;
+            }
+            ReportSink::Webhook(url) => post_json(url, &body)
+                .with_context(|| format!("Failed to deliver report to {}", url))?,
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of one run of a scheduled job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    /// Name of the job
+    pub job: String,
+    /// When the search ran, in RFC 3339 format
+    pub generated_at: String,
+    /// Directory that was searched
+    pub path: String,
+    /// Number of matching files
+    pub count: usize,
+    /// Matching files
+    pub files: Vec<ReportFile>,
+}
+
+/// A file listed in a report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportFile {
+    /// Path of the file
+    pub path: PathBuf,
+    /// Size in bytes, if it could be read
+    pub size: Option<u64>,
+    /// Last modification time in RFC 3339 format, if it could be read
+    pub modified: Option<String>,
+}
+
+impl ReportFile {
+    fn new(path: PathBuf) -> Self {
+        let metadata = fs::metadata(&path).ok();
+        Self {
+            size: metadata.as_ref().map(|metadata| metadata.len()),
+            modified: metadata
+                .and_then(|metadata| metadata.modified().ok())
+                .map(|time| DateTime::<Utc>::from(time).to_rfc3339()),
+            path,
+        }
+    }
+}
+
+/// Parse an interval such as `90s`, `15m`, `6h`, `1d` or `2w` (plain numbers are seconds)
+pub fn parse_interval(spec: &str) -> Result<Duration> {
+    let spec = spec.trim();
+    let digits = spec.find(|c: char| !c.is_ascii_digit()).unwrap_or(spec.len());
+    let (value, unit) = spec.split_at(digits);
+    let value: u64 = value.parse()
+        .with_context(|| format!("expected a number followed by s, m, h, d or w: '{}'", spec))?;
+
+    let seconds = match unit.trim() {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        other => bail!("unknown interval unit '{}' in '{}'", other, spec),
+    };
+    Ok(Duration::from_secs(value.saturating_mul(seconds)))
+}
+
+// Minimal HTTP/1.1 POST, enough for internal webhooks without pulling in a client
+fn post_json(url: &str, body: &str) -> Result<()> {
+    let Some(rest) = url.strip_prefix("http://") else {
+        bail!("only http:// webhooks are supported");
+    };
+    let (authority, target) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let address = if authority.contains(':') { authority.to_string() } else { format!("{}:80", authority) };
+
+    let mut stream = connect(&address)
+        .with_context(|| format!("Failed to connect to {}", address))?;
+    stream.set_read_timeout(Some(WEBHOOK_TIMEOUT))?;
+    stream.set_write_timeout(Some(WEBHOOK_TIMEOUT))?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        target, authority, body.len(), body
+    )?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let status = response.split_whitespace().nth(1).unwrap_or("");
+    if !status.starts_with('2') {
+        bail!("webhook responded with status {}", if status.is_empty() { "(none)" } else { status });
+    }
+    Ok(())
+}
+
+// Connect to the first address the host resolves to that answers in time, so that
+// an unreachable webhook cannot hang a scheduled run
+fn connect(address: &str) -> std::io::Result<TcpStream> {
+    let mut last_error = None;
+    for resolved in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&resolved, WEBHOOK_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "host has no addresses")))
+}
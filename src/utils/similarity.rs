@@ -0,0 +1,78 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use anyhow::{bail, Context, Result};
+
+use crate::utils::content::{is_binary, BINARY_SAMPLE_SIZE};
+use crate::utils::hash::{fnv1a, FNV_OFFSET};
+
+/// Number of hash functions in a MinHash signature
+const SIGNATURE_SIZE: usize = 128;
+
+/// Number of consecutive words forming a shingle
+const SHINGLE_WORDS: usize = 4;
+
+/// MinHash signature of a text file's content
+///
+/// Content is split into overlapping shingles of consecutive words, so the
+/// share of equal signature entries between two files estimates the Jaccard
+/// similarity of their shingle sets while ignoring whitespace and layout changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    signature: [u64; SIGNATURE_SIZE],
+}
+
+impl Fingerprint {
+    /// Fingerprint some content, or `None` if it is binary or has no words
+    pub fn from_content(content: &[u8]) -> Option<Self> {
+        if is_binary(&content[..content.len().min(BINARY_SAMPLE_SIZE)]) {
+            return None;
+        }
+        
+        let text = String::from_utf8_lossy(content);
+        let words: Vec<&str> = text.split_whitespace().collect();
+        if words.is_empty() {
+            return None;
+        }
+        
+        // Short files still get a single shingle of all their words
+        let shingles: HashSet<u64> = words.windows(SHINGLE_WORDS.min(words.len()))
+            .map(|window| window.iter().fold(FNV_OFFSET, |hash, word| fnv1a(fnv1a(hash, word.as_bytes()), b" ")))
+            .collect();
+        
+        let mut signature = [u64::MAX; SIGNATURE_SIZE];
+        for shingle in shingles {
+            for (seed, slot) in signature.iter_mut().enumerate() {
+                *slot = (*slot).min(splitmix64(shingle ^ (seed as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)));
+            }
+        }
+        
+        Some(Self { signature })
+    }
+    
+    /// Fingerprint a file, failing if it is binary or has no words
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+        match Self::from_content(&content) {
+            Some(fingerprint) => Ok(fingerprint),
+            None => bail!("{} is binary or empty and cannot be compared", path.display()),
+        }
+    }
+    
+    /// Estimated similarity between 0.0 (nothing shared) and 1.0 (same shingles)
+    pub fn similarity(&self, other: &Self) -> f64 {
+        let equal = self.signature.iter()
+            .zip(&other.signature)
+            .filter(|(a, b)| a == b)
+            .count();
+        equal as f64 / SIGNATURE_SIZE as f64
+    }
+}
+
+// Finalizer of SplitMix64, spreading a seeded shingle hash over all 64 bits
+fn splitmix64(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
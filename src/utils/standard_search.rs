@@ -1,12 +1,21 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
-use log::{debug, warn};
+use log::{debug, info, warn};
 use anyhow::{Context, Result};
 
 use crate::core::{
     config::FileSearchConfig,
-    observer::SearchObserver,
+    estimate::{Probe, TreeSizeSampler},
+    file_id::FileId,
+    finder::is_ancestor,
+    observer::{self, Heartbeat, SearchEvent, SearchObserver, HEARTBEAT_INTERVAL},
+    registry::FilterRegistry,
+    traversal::{is_hidden, normalize_separators, GitAwareTraversalStrategy, IgnoreFileTraversalStrategy},
 };
+use crate::filters::{date::DateFilter, size::file_size, ContentFilter, DepthFilter, EmptyFilter, EntryType, FilterResult, GitFilter, GlobFilter, IgnoreFilter, LinksFilter, MimeFilter, PruneFilter, SameFileSystemFilter, RegexFilter, Timestamp, TypeFilter, TypeGroupFilter};
 
 /// Search statistics for performance tracking
 #[derive(Debug, Clone)]
@@ -39,15 +48,25 @@ pub fn search_directory(
         return Err(anyhow::anyhow!("Path is not a directory: {}", root_dir.display()));
     }
     
+    // Build the filters that cannot be expressed through simple config checks
+    let filters = build_filters(root_dir, config)?;
+    
     // Call the recursive search function
     let mut result = Vec::new();
-    if let Err(e) = walk_directory(root_dir, config, observer, &mut result) {
+    let mut pulse = Pulse::new(config);
+    if let Err(e) = walk_directory(root_dir, config, &filters, observer, &mut pulse, Probe::root(), &mut Vec::new(), &mut result) {
         warn!("Error during directory walk: {}", e);
     }
-    
     let elapsed = start_time.elapsed();
     let file_count = observer.files_count();
     let dir_count = observer.directories_count();
+    observer.on_event(&SearchEvent::Completed(&observer::SearchStats {
+        files: file_count,
+        directories: dir_count,
+        errors: observer.errors_count(),
+        elapsed,
+    }));
+    
     let files_per_sec = if elapsed.as_secs_f32() > 0.0 {
         file_count as f32 / elapsed.as_secs_f32()
     } else {
@@ -67,71 +86,381 @@ pub fn search_directory(
     Ok(result)
 }
 
+/// Collect candidate files for the configuration
+///
+/// Reads an explicit list when `files_from` is set, bypassing traversal entirely,
+/// and otherwise walks the configured search path.
+pub fn collect_files(config: &FileSearchConfig, observer: &dyn SearchObserver) -> Result<Vec<PathBuf>> {
+    match &config.files_from {
+        Some(source) => {
+            let paths = read_path_list(source)?;
+            search_paths(&paths, config, observer)
+        }
+        None => search_directory(Path::new(config.get_path()), config, observer),
+    }
+}
+
+/// Apply the configured filters to an externally provided list of files
+pub fn search_paths(
+    paths: &[PathBuf],
+    config: &FileSearchConfig,
+    observer: &dyn SearchObserver
+) -> Result<Vec<PathBuf>> {
+    debug!("Filtering {} provided paths", paths.len());
+    let start_time = Instant::now();
+    
+    let filters = build_filters(Path::new(config.get_path()), config)?;
+    let mut result = Vec::new();
+    let mut reported = Reported::new(config);
+    
+    for path in paths {
+        if limit_reached(config, &result) {
+            break;
+        }
+        
+        if !path.is_file() {
+            debug!("Skipping provided path that is not a file: {}", path.display());
+            continue;
+        }
+        
+        if match_file(path, config, &filters, observer) && let Some(path) = reported.identify(path.clone()) {
+            observer.on_event(&SearchEvent::FileFound(&path));
+            result.push(path);
+        }
+    }
+    observer.on_event(&SearchEvent::Completed(&observer::SearchStats {
+        files: observer.files_count(),
+        directories: 0,
+        errors: observer.errors_count(),
+        elapsed: start_time.elapsed(),
+    }));
+    
+    Ok(result)
+}
+
+/// Read a list of paths from a file, or from standard input when `source` is "-"
+///
+/// Entries are separated by newlines, or by NUL bytes if the input contains any
+/// (as produced by `git ls-files -z` or `find -print0`).
+pub fn read_path_list(source: &str) -> Result<Vec<PathBuf>> {
+    let mut contents = Vec::new();
+    
+    if source == "-" {
+        std::io::stdin().read_to_end(&mut contents)
+            .context("Failed to read file list from standard input")?;
+    } else {
+        contents = std::fs::read(source)
+            .with_context(|| format!("Failed to read file list: {}", source))?;
+    }
+    
+    let separator = if contents.contains(&0) { b'\0' } else { b'\n' };
+    
+    Ok(contents
+        .split(|&byte| byte == separator)
+        .map(|entry| String::from_utf8_lossy(entry))
+        .map(|entry| entry.trim_end_matches('\r').to_string())
+        .filter(|entry| !entry.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Build the registry of filters applied on top of the configured criteria
+fn build_filters(root_dir: &Path, config: &FileSearchConfig) -> Result<FilterRegistry> {
+    let mut filters = FilterRegistry::new();
+    
+    if let Some(glob) = &config.name_glob {
+        let filter = GlobFilter::new(glob)
+            .with_context(|| format!("Invalid glob: {}", glob))?;
+        filters.register("glob", filter);
+    }
+    
+    if let Some(name_regex) = &config.name_regex {
+        let filter = RegexFilter::file_name_with_syntax(name_regex, config.regex_syntax)
+            .with_context(|| format!("Invalid file name regex: {}", name_regex))?;
+        filters.register("name_regex", filter.with_full_path(config.full_path));
+    }
+    
+    if config.git_modified {
+        let filter = GitFilter::modified(root_dir)
+            .context("Failed to list modified files with git")?;
+        debug!("Restricting search to {} modified files", filter.len());
+        filters.register("git", filter);
+    } else if config.git_tracked {
+        let filter = GitFilter::tracked(root_dir)
+            .context("Failed to list tracked files with git")?;
+        debug!("Restricting search to {} tracked files", filter.len());
+        filters.register("git", filter);
+    }
+    
+    if config.one_file_system {
+        let filter = SameFileSystemFilter::new(root_dir)
+            .context("Failed to read the device of the search path")?;
+        filters.register("one_file_system", filter);
+    }
+    
+    if !config.no_default_excludes {
+        filters.register("default_excludes", PruneFilter::default_excludes());
+    }
+    
+    if !config.exclude.is_empty() {
+        let filter = IgnoreFilter::new(root_dir, &config.exclude)
+            .context("Invalid --exclude pattern")?;
+        filters.register("exclude", filter);
+    }
+    
+    if !config.global_ignore.is_empty() {
+        let filter = IgnoreFilter::new(root_dir, &config.global_ignore)
+            .context("Invalid pattern in global ignore file")?;
+        filters.register("global_ignore", filter);
+    }
+    
+    filters.register("ignore_files", IgnoreFileTraversalStrategy::new(root_dir));
+    
+    if config.git_ignore {
+        filters.register("git_ignore", GitAwareTraversalStrategy::new(root_dir));
+    }
+    
+    if config.empty {
+        filters.register("empty", EmptyFilter::new());
+    }
+    
+    if let Some(count) = config.links {
+        filters.register("links", LinksFilter::exactly(count));
+    } else if let Some(count) = config.min_links {
+        filters.register("links", LinksFilter::at_least(count));
+    }
+    
+    if config.max_depth.is_some() || config.min_depth.is_some() {
+        filters.register("depth", DepthFilter::new(root_dir, config.min_depth, config.max_depth));
+    }
+    
+    if let Some(groups) = &config.type_group {
+        filters.register("type_group", TypeGroupFilter::with_custom(groups, &config.type_groups)?);
+    }
+    
+    if let Some(types) = &config.mime {
+        filters.register("mime", MimeFilter::new(types));
+    }
+    
+    if let Some(kind) = config.content_kind {
+        filters.register("content", ContentFilter::new(kind));
+    }
+    
+    if config.created_after.is_some() || config.created_before.is_some() {
+        let filter = DateFilter::between(config.created_after, config.created_before).with_timestamp(Timestamp::Created);
+        filters.register("created", filter);
+    }
+    
+    if config.accessed_after.is_some() || config.accessed_before.is_some() {
+        let filter = DateFilter::between(config.accessed_after, config.accessed_before).with_timestamp(Timestamp::Accessed);
+        filters.register("accessed", filter);
+    }
+    
+    if config.newer_than_file.is_some() || config.older_than_file.is_some() {
+        let filter = DateFilter::between_files(
+            config.newer_than_file.as_deref().map(Path::new),
+            config.older_than_file.as_deref().map(Path::new),
+        ).context("Failed to read reference file")?;
+        filters.register("reference_date", filter);
+    }
+    
+    #[cfg(feature = "media")]
+    if config.has_media_criteria() {
+        let mut filter = crate::filters::MediaFilter::new();
+        if let Some(after) = config.exif_date_after {
+            filter = filter.taken_after(after);
+        }
+        if let Some(before) = config.exif_date_before {
+            filter = filter.taken_before(before);
+        }
+        if let Some(model) = &config.camera_model {
+            filter = filter.camera_model(model);
+        }
+        if let Some(artist) = &config.artist {
+            filter = filter.artist(artist);
+        }
+        filters.register("media", filter);
+    }
+    
+    #[cfg(feature = "scripting")]
+    if let Some(script) = &config.script {
+        let script = crate::filters::Script::load(script)?;
+        filters.register("script", crate::filters::ScriptFilter::new(std::sync::Arc::new(script)));
+    }
+    
+    Ok(filters)
+}
+
+// Files already reported, when each file is only reported once
+struct Reported(Option<HashSet<FileId>>);
+
+impl Reported {
+    fn new(config: &FileSearchConfig) -> Self {
+        Reported(config.unique.then(HashSet::new))
+    }
+    
+    // Path to report a matched file under, or none if another path to the same file was reported
+    //
+    // As in the finder, links and hard links to a reported file are dropped and files
+    // are reported under their canonical paths. Files whose identity cannot be read
+    // are reported as found.
+    fn identify(&mut self, path: PathBuf) -> Option<PathBuf> {
+        let Some(seen) = &mut self.0 else {
+            return Some(path);
+        };
+        if let Ok(id) = FileId::of(&path) && !seen.insert(id) {
+            debug!("Skipping another path to a reported file: {}", path.display());
+            return None;
+        }
+        Some(path.canonicalize().unwrap_or(path))
+    }
+}
+
+// Throttles the heartbeats of a walk, counts the directories it has yet to read
+// and remembers the files it reported
+struct Pulse {
+    last_beat: Instant,
+    pending: usize,
+    sampler: TreeSizeSampler,
+    reported: Reported,
+}
+
+impl Pulse {
+    fn new(config: &FileSearchConfig) -> Self {
+        Pulse { last_beat: Instant::now(), pending: 1, sampler: TreeSizeSampler::default(), reported: Reported::new(config) }
+    }
+    
+    // Count a directory as read, reporting a heartbeat if one is due
+    fn enter(&mut self, dir_path: &Path, observer: &dyn SearchObserver) {
+        // Symbolic links to directories are entered without having been counted
+        self.pending = self.pending.saturating_sub(1);
+        if self.last_beat.elapsed() >= HEARTBEAT_INTERVAL {
+            self.last_beat = Instant::now();
+            observer.on_event(&SearchEvent::Heartbeat(&Heartbeat {
+                directory: dir_path.to_path_buf(),
+                queue_depth: self.pending,
+                estimated_total: self.sampler.estimate(),
+            }));
+        }
+    }
+}
+
 /// Recursively walk directory to find files
+#[allow(clippy::too_many_arguments)]
 fn walk_directory(
     dir_path: &Path, 
     config: &FileSearchConfig,
+    filters: &FilterRegistry,
     observer: &dyn SearchObserver,
+    pulse: &mut Pulse,
+    probe: Probe,
+    ancestors: &mut Vec<FileId>,
     results: &mut Vec<PathBuf>
 ) -> Result<()> {
+    // Directories still waiting once enough matches are found are abandoned
+    if limit_reached(config, results) {
+        return Ok(());
+    }
+    
     // Notify observer that we're processing this directory
-    observer.directory_processed(dir_path);
+    observer.on_event(&SearchEvent::DirEntered(dir_path));
+    pulse.enter(dir_path, observer);
     
-    // Try to read directory entries
-    let entries = match std::fs::read_dir(dir_path) {
-        Ok(entries) => entries,
+    // Try to read directory entries, skipping directories we cannot access
+    // (common when searching from the root directory)
+    let entries: Vec<_> = match std::fs::read_dir(dir_path) {
+        Ok(entries) => entries.collect(),
         Err(e) => {
-            // Silently skip directories we don't have permission to access
-            // This is common when searching from root directory
-            if e.kind() == std::io::ErrorKind::PermissionDenied {
-                debug!("Skipping directory due to permission denied: {}", dir_path.display());
-                return Ok(());
-            }
-            // For other errors, return with context
-            return Err(e).with_context(|| format!("Failed to read directory entries for: {}", dir_path.display()));
+            report_path_error(config, observer, dir_path, &e);
+            return Ok(());
         }
     };
+    let subdirectories = if config.recursive {
+        entries.iter()
+            .filter_map(|entry| entry.as_ref().ok())
+            .filter(|entry| config.hidden || !is_hidden(&entry.path()))
+            .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+            .filter(|entry| !filters.prunes(&entry.path()))
+            .count()
+    } else {
+        0
+    };
+    pulse.pending += subdirectories;
+    if subdirectories == 0 {
+        pulse.sampler.leaf(probe);
+    }
+    let probe = probe.descend(subdirectories);
+    // Only links can lead back to a directory being walked, so plain walks skip the lookup
+    let id = config.follow_symlinks.then(|| FileId::of(dir_path).ok()).flatten();
+    ancestors.extend(id);
+    
+    // Once a directory has enough matches its remaining files are not examined
+    let mut found = 0;
+    let satisfied = |found: usize| config.max_per_dir.is_some_and(|limit| found >= limit);
+    let types = TypeFilter::new(config.entry_types.iter().copied());
     
     for entry_result in entries {
+        if limit_reached(config, results) {
+            break;
+        }
+        
         let entry = match entry_result {
             Ok(entry) => entry,
             Err(e) => {
-                warn!("Failed to read directory entry: {}", e);
+                report_path_error(config, observer, dir_path, &e);
                 continue;
             }
         };
         
         let path = entry.path();
         
+        // Skip hidden files and directories unless --hidden is set
+        if !config.hidden && is_hidden(&path) {
+            continue;
+        }
+        
         let file_type = match entry.file_type() {
             Ok(ft) => ft,
             Err(e) => {
-                warn!("Failed to determine file type for {}: {}", path.display(), e);
+                report_path_error(config, observer, &path, &e);
                 continue;
             }
         };
         
+        // Directories, links, sockets and pipes are only results when asked for
+        if !file_type.is_file() && types.accepts(&file_type) && !satisfied(found) && match_file(&path, config, filters, observer)
+            && let Some(reported) = pulse.reported.identify(path.clone())
+        {
+            observer.on_event(&SearchEvent::FileFound(&reported));
+            results.push(reported);
+            found += 1;
+        }
+        
         // Process based on file type
         if file_type.is_dir() && config.recursive {
             // Skip symbolic links if not following them
             if file_type.is_symlink() && !config.follow_symlinks {
-                debug!("Skipping symbolic link to directory: {}", path.display());
+                info!("Skipping symbolic link to directory: {}", path.display());
+                continue;
+            }
+            
+            if filters.prunes(&path) {
+                debug!("Pruning directory: {}", path.display());
+                observer.on_event(&SearchEvent::Skipped(&path));
                 continue;
             }
             
             // Recursively process subdirectory
-            if let Err(e) = walk_directory(&path, config, observer, results) {
-                // Only log errors that aren't permission related
-                if !e.to_string().contains("permission denied") {
-                    warn!("Error processing subdirectory {}: {}", path.display(), e);
-                }
+            if let Err(e) = walk_directory(&path, config, filters, observer, pulse, probe, ancestors, results) {
+                warn!("Error processing subdirectory {}: {}", path.display(), e);
             }
         } else if file_type.is_file() {
-            let matches = match_file(&path, config);
+            let matches = types.includes(EntryType::File) && !satisfied(found) && match_file(&path, config, filters, observer);
             
-            if matches {
-                observer.file_found(&path);
+            if matches && let Some(path) = pulse.reported.identify(path) {
+                observer.on_event(&SearchEvent::FileFound(&path));
                 results.push(path);
+                found += 1;
             }
         } else if file_type.is_symlink() && config.follow_symlinks {
             // Follow symlinks if enabled
@@ -147,46 +476,92 @@ fn walk_directory(
                     
                     match std::fs::metadata(&target_path) {
                         Ok(metadata) => {
-                            if metadata.is_dir() && config.recursive {
+                            if metadata.is_dir() && filters.prunes(&path) {
+                                debug!("Pruning directory: {}", path.display());
+                                observer.on_event(&SearchEvent::Skipped(&path));
+                            } else if metadata.is_dir() && config.recursive && is_ancestor(&target_path, ancestors) {
+                                info!("Skipping symbolic link cycle: {}", path.display());
+                                observer.on_event(&SearchEvent::Skipped(&path));
+                            } else if metadata.is_dir() && config.recursive {
                                 // Process the directory the symlink points to
-                                if let Err(e) = walk_directory(&target_path, config, observer, results) {
+                                if let Err(e) = walk_directory(&target_path, config, filters, observer, pulse, probe, ancestors, results) {
                                     warn!("Error processing symlinked directory {}: {}", 
                                           target_path.display(), e);
                                 }
                             } else if metadata.is_file() {
                                 // Process the file the symlink points to
-                                let matches = match_file(&target_path, config);
+                                let matches = types.includes(EntryType::File) && !satisfied(found) && match_file(&target_path, config, filters, observer);
                                 
-                                if matches {
-                                    observer.file_found(&target_path);
+                                if matches && let Some(target_path) = pulse.reported.identify(target_path) {
+                                    observer.on_event(&SearchEvent::FileFound(&target_path));
                                     results.push(target_path);
+                                    found += 1;
                                 }
                             }
                         }
                         Err(e) => {
-                            warn!("Failed to get metadata for symlink target {}: {}", 
-                                  target_path.display(), e);
+                            report_path_error(config, observer, &target_path, &e);
                         }
                     }
                 }
                 Err(e) => {
-                    warn!("Failed to read symlink {}: {}", path.display(), e);
+                    report_path_error(config, observer, &path, &e);
                 }
             }
         }
     }
     
+    if id.is_some() {
+        ancestors.pop();
+    }
     Ok(())
 }
 
+// Whether the search has found as many results as it may report, and can stop
+fn limit_reached(config: &FileSearchConfig, results: &[PathBuf]) -> bool {
+    config.max_results.is_some_and(|limit| results.len() >= limit)
+}
 
+/// Count an unreadable path and log it unless `--no-messages` is set
+///
+/// Permission errors are routine when searching system directories, so they are
+/// only shown at informational verbosity.
+pub fn report_path_error(
+    config: &FileSearchConfig,
+    observer: &dyn SearchObserver,
+    path: &Path,
+    error: &std::io::Error
+) {
+    observer.on_event(&SearchEvent::Error { path, error });
+    
+    if config.no_messages {
+        return;
+    }
+    
+    if error.kind() == std::io::ErrorKind::PermissionDenied {
+        info!("Skipping {}: {}", path.display(), error);
+    } else {
+        warn!("Failed to read {}: {}", path.display(), error);
+    }
+}
 
 /// Check if a file matches the configured criteria
-fn match_file(file_path: &Path, config: &FileSearchConfig) -> bool {
+fn match_file(
+    file_path: &Path,
+    config: &FileSearchConfig,
+    filters: &FilterRegistry,
+    observer: &dyn SearchObserver
+) -> bool {
     // Check file extension if specified
     if let Some(ref ext) = config.file_extension {
         if let Some(file_ext) = file_path.extension().and_then(|e| e.to_str()) {
-            if file_ext.to_lowercase() != ext.to_lowercase() {
+            let ext = ext.trim_start_matches('.');
+            let matches = if config.case_sensitive_extension && !config.ignore_case {
+                file_ext == ext
+            } else {
+                file_ext.to_lowercase() == ext.to_lowercase()
+            };
+            if !matches {
                 return false;
             }
         } else {
@@ -195,9 +570,14 @@ fn match_file(file_path: &Path, config: &FileSearchConfig) -> bool {
         }
     }
     
-    // Check file name if specified
+    // Check file name, or the whole path with --full-path, if specified
     if let Some(ref name_pattern) = config.file_name {
-        if let Some(file_name) = file_path.file_name().and_then(|n| n.to_str()) {
+        let file_name = if config.full_path {
+            file_path.to_str().map(|_| normalize_separators(file_path))
+        } else {
+            file_path.file_name().and_then(|n| n.to_str()).map(Cow::Borrowed)
+        };
+        if let Some(file_name) = file_name {
             // Simple case-insensitive contains check
             if !file_name.to_lowercase().contains(&name_pattern.to_lowercase()) {
                 return false;
@@ -212,7 +592,7 @@ fn match_file(file_path: &Path, config: &FileSearchConfig) -> bool {
     if config.min_size.is_some() || config.max_size.is_some() {
         match std::fs::metadata(file_path) {
             Ok(metadata) => {
-                let file_size = metadata.len();
+                let file_size = file_size(&metadata, config.disk_size);
                 
                 // Check minimum size
                 if let Some(min_size) = config.min_size {
@@ -229,7 +609,7 @@ fn match_file(file_path: &Path, config: &FileSearchConfig) -> bool {
                 }
             }
             Err(e) => {
-                warn!("Failed to get metadata for size check on {}: {}", file_path.display(), e);
+                report_path_error(config, observer, file_path, &e);
                 return false;
             }
         }
@@ -237,63 +617,23 @@ fn match_file(file_path: &Path, config: &FileSearchConfig) -> bool {
     
     // Check date constraints if specified
     if config.newer_than.is_some() || config.older_than.is_some() {
-        match std::fs::metadata(file_path) {
-            Ok(metadata) => {
-                // Check newer than constraint
-                if let Some(ref newer_than) = config.newer_than {
-                    match metadata.modified() {
-                        Ok(modified_time) => {
-                            let modified_secs = modified_time
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_secs() as i64;
-                            
-                            if let Ok(newer_time) = newer_than.parse::<i64>() {
-                                if modified_secs < newer_time {
-                                    return false;
-                                }
-                            } else {
-                                warn!("Invalid newer_than value: {}", newer_than);
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to get modified time for {}: {}", file_path.display(), e);
-                            return false;
-                        }
-                    }
-                }
-                
-                // Check older than constraint
-                if let Some(ref older_than) = config.older_than {
-                    match metadata.modified() {
-                        Ok(modified_time) => {
-                            let modified_secs = modified_time
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_secs() as i64;
-                            
-                            if let Ok(older_time) = older_than.parse::<i64>() {
-                                if modified_secs > older_time {
-                                    return false;
-                                }
-                            } else {
-                                warn!("Invalid older_than value: {}", older_than);
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to get modified time for {}: {}", file_path.display(), e);
-                            return false;
-                        }
-                    }
-                }
-            }
+        let modified = match std::fs::metadata(file_path).and_then(|metadata| metadata.modified()) {
+            Ok(modified) => modified,
             Err(e) => {
-                warn!("Failed to get metadata for date check on {}: {}", file_path.display(), e);
+                report_path_error(config, observer, file_path, &e);
                 return false;
             }
+        };
+        
+        if config.newer_than.is_some_and(|newer_than| modified < newer_than) {
+            return false;
+        }
+        
+        if config.older_than.is_some_and(|older_than| modified > older_than) {
+            return false;
         }
     }
     
-    // All checks passed
-    true
+    // Finally apply any additional filters
+    filters.apply_all_to_entry(file_path) == FilterResult::Accept
 }
\ No newline at end of file
@@ -0,0 +1,150 @@
+use clap::Parser;
+use oqab::cli::args::Args;
+
+// Build the search configuration for a command line
+fn config_for(args: &[&str]) -> oqab::core::config::FileSearchConfig {
+    let args = Args::try_parse_from(std::iter::once("oqab").chain(args.iter().copied()))
+        .expect("Failed to parse arguments");
+    args.to_config()
+}
+
+#[test]
+fn test_query_interpretation() {
+    // Globs
+    let config = config_for(&["*.rs"]);
+    assert_eq!(config.name_glob.as_deref(), Some("*.rs"));
+    assert_eq!(config.file_name, None);
+    
+    // Regular expressions
+    let config = config_for(&["re:^main\\.(rs|go)$"]);
+    assert_eq!(config.name_regex.as_deref(), Some("^main\\.(rs|go)$"));
+    
+    // Extension shorthand
+    let config = config_for(&[".rs"]);
+    assert_eq!(config.file_extension.as_deref(), Some("rs"));
+    assert_eq!(config.file_name, None);
+    
+    // Name with extension
+    let config = config_for(&["main.rs"]);
+    assert_eq!(config.file_name.as_deref(), Some("main.rs"));
+    assert_eq!(config.file_extension.as_deref(), Some("rs"));
+    
+    // Plain text
+    let config = config_for(&["config"]);
+    assert_eq!(config.file_name.as_deref(), Some("config"));
+}
+
+#[test]
+fn test_query_literal() {
+    let config = config_for(&["--literal", "*.rs"]);
+    assert_eq!(config.file_name.as_deref(), Some("*.rs"));
+    assert_eq!(config.name_glob, None);
+    
+    let config = config_for(&["--literal", ".rs"]);
+    assert_eq!(config.file_name.as_deref(), Some(".rs"));
+    assert_eq!(config.file_extension, None);
+}
+
+#[test]
+fn test_query_explicit_flags_win() {
+    let config = config_for(&["--name", "lib", "*.rs"]);
+    assert_eq!(config.file_name.as_deref(), Some("lib"));
+    assert_eq!(config.name_glob, None);
+    
+    let config = config_for(&["--ext", "toml", ".rs"]);
+    assert_eq!(config.file_extension.as_deref(), Some("toml"));
+}
+
+#[test]
+fn test_interactive_select() {
+    // The browser chooses among the results itself, so the query still selects them
+    let config = config_for(&["-I", "--select", "*.rs"]);
+    assert!(config.interactive && config.select);
+    assert!(!config.fuzzy);
+    assert_eq!(config.name_glob.as_deref(), Some("*.rs"));
+    assert_eq!(config.fuzzy_query, None);
+    
+    let config = config_for(&["--select", "*.rs"]);
+    assert!(config.fuzzy);
+    assert_eq!(config.fuzzy_query.as_deref(), Some("*.rs"));
+}
+
+#[test]
+fn test_links_flags_conflict() {
+    // An exact count and a minimum would either be redundant or match nothing
+    let args = Args::try_parse_from(["oqab", "--links", "2", "--min-links", "5"]);
+    assert!(args.is_err());
+    assert_eq!(config_for(&["--min-links", "2"]).min_links, Some(2));
+}
+
+#[test]
+fn test_long_conflicts_with_grep() {
+    // Matching lines are printed instead of files, so there is no listing to make long
+    assert!(Args::try_parse_from(["oqab", "-l", "--grep", "TODO"]).is_err());
+    assert!(config_for(&["-l", "*.rs"]).long);
+}
+
+#[test]
+fn test_system_flag() {
+    assert!(!config_for(&["*.rs"]).system);
+    assert!(config_for(&["--system", "*.rs"]).system);
+    
+    // A whole-filesystem search has no use for a path
+    assert!(Args::try_parse_from(["oqab", "--system", "--path", ".", "*.rs"]).is_err());
+}
+
+#[test]
+fn test_type_group_flag() {
+    let config = config_for(&["--type-group", "image,video"]);
+    assert_eq!(config.type_group, Some(vec!["image".to_string(), "video".to_string()]));
+    
+    let args = Args::try_parse_from(["oqab", "--type-group", "spreadsheet"]).unwrap();
+    assert!(args.process().is_err());
+}
+
+#[test]
+fn test_config_sources() {
+    use oqab::core::config::ConfigSource;
+    
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    let config_file = temp_dir.path().join("config.json");
+    std::fs::write(&config_file, r#"{"ignore_case": true, "file_extension": "rs"}"#).unwrap();
+    let config_file = config_file.to_str().unwrap();
+    
+    let args = Args::try_parse_from(["oqab", "-c", config_file, "-e", "md", "--hidden", "--print-config"])
+        .expect("Failed to parse arguments");
+    let config = args.process().expect("Failed to process arguments");
+    let sources = args.config_sources(&config).unwrap();
+    
+    // Flags win over the file, which wins over the defaults
+    assert_eq!(config.file_extension.as_deref(), Some("md"));
+    assert_eq!(sources["file_extension"], ConfigSource::CommandLine);
+    assert_eq!(sources["hidden"], ConfigSource::CommandLine);
+    assert_eq!(sources["ignore_case"], ConfigSource::File(config_file.to_string()));
+    assert_eq!(sources["recursive"], ConfigSource::Default);
+}
+
+#[test]
+fn test_hidden_flags() {
+    assert!(!config_for(&["*.rs"]).hidden);
+    assert!(config_for(&["--hidden", "*.rs"]).hidden);
+    
+    // Whichever of --hidden and --no-hidden comes last wins
+    assert!(!config_for(&["--hidden", "--no-hidden", "*.rs"]).hidden);
+    assert!(config_for(&["--no-hidden", "-H", "*.rs"]).hidden);
+    
+    // --no-hidden overrides a config file that includes hidden entries
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    let config_file = temp_dir.path().join("config.json");
+    std::fs::write(&config_file, r#"{"hidden": true}"#).unwrap();
+    let config_file = config_file.to_str().unwrap();
+    
+    let process = |args: &[&str]| {
+        Args::try_parse_from(["oqab", "-c", config_file].iter().chain(args))
+            .expect("Failed to parse arguments")
+            .process()
+            .expect("Failed to process arguments")
+    };
+    assert!(process(&["*.rs"]).hidden);
+    assert!(!process(&["--no-hidden", "*.rs"]).hidden);
+}
@@ -0,0 +1,112 @@
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::{symlink, MetadataExt, PermissionsExt};
+use std::process::Command;
+use tempfile::TempDir;
+use oqab::filters::audit::{AuditRule, OrphanedOwnerFilter, Severity};
+use oqab::filters::{Filter, FilterResult};
+
+fn set_mode(path: &std::path::Path, mode: u32) {
+    fs::set_permissions(path, fs::Permissions::from_mode(mode)).expect("Failed to set permissions");
+}
+
+#[test]
+fn test_audit_rule_filters() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    
+    let writable = root.join("writable.txt");
+    fs::write(&writable, "").unwrap();
+    set_mode(&writable, 0o666);
+    let private = root.join("private.txt");
+    fs::write(&private, "").unwrap();
+    set_mode(&private, 0o600);
+    let shared = root.join("shared");
+    fs::create_dir(&shared).unwrap();
+    set_mode(&shared, 0o1777);
+    let setuid = root.join("tool");
+    fs::write(&setuid, "").unwrap();
+    set_mode(&setuid, 0o4755);
+    let dangling = root.join("dangling");
+    symlink(root.join("missing"), &dangling).unwrap();
+    let link = root.join("link");
+    symlink(&private, &link).unwrap();
+    
+    let world_writable = AuditRule::WorldWritable.filter();
+    assert_eq!(world_writable.filter(&writable), FilterResult::Accept);
+    assert_eq!(world_writable.filter(&private), FilterResult::Reject);
+    // Sticky shared directories such as /tmp are expected to be world-writable
+    assert_eq!(world_writable.filter(&shared), FilterResult::Reject);
+    
+    let setuid_rule = AuditRule::Setuid.filter();
+    assert_eq!(setuid_rule.filter(&setuid), FilterResult::Accept);
+    assert_eq!(setuid_rule.filter(&private), FilterResult::Reject);
+    
+    let dangling_rule = AuditRule::DanglingSymlink.filter();
+    assert_eq!(dangling_rule.filter(&dangling), FilterResult::Accept);
+    assert_eq!(dangling_rule.filter(&link), FilterResult::Reject);
+    
+    let uid = fs::metadata(&private).unwrap().uid();
+    assert_eq!(OrphanedOwnerFilter::with_uids([uid]).filter(&private), FilterResult::Reject);
+    assert_eq!(OrphanedOwnerFilter::with_uids([uid + 1]).filter(&private), FilterResult::Accept);
+    
+    assert_eq!(AuditRule::Setuid.severity(), Severity::High);
+    assert!(Severity::High > Severity::Low);
+}
+
+#[test]
+fn test_audit_ssh_permissions() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let ssh = temp_dir.path().join(".ssh");
+    fs::create_dir(&ssh).unwrap();
+    set_mode(&ssh, 0o700);
+    let key = ssh.join("id_ed25519");
+    fs::write(&key, "").unwrap();
+    let public = ssh.join("id_ed25519.pub");
+    fs::write(&public, "").unwrap();
+    set_mode(&public, 0o644);
+    let elsewhere = temp_dir.path().join("id_rsa");
+    fs::write(&elsewhere, "").unwrap();
+    set_mode(&elsewhere, 0o644);
+    
+    let rule = AuditRule::SshPermissions.filter();
+    set_mode(&key, 0o600);
+    assert_eq!(rule.filter(&key), FilterResult::Reject);
+    set_mode(&key, 0o640);
+    assert_eq!(rule.filter(&key), FilterResult::Accept);
+    assert_eq!(rule.filter(&public), FilterResult::Reject);
+    assert_eq!(rule.filter(&ssh), FilterResult::Reject);
+    assert_eq!(rule.filter(&elsewhere), FilterResult::Reject);
+}
+
+#[test]
+fn test_audit_command_json() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let writable = temp_dir.path().join("writable.txt");
+    fs::write(&writable, "").unwrap();
+    set_mode(&writable, 0o666);
+    symlink(temp_dir.path().join("missing"), temp_dir.path().join("dangling")).unwrap();
+    
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["--format", "json", "audit", temp_dir.path().to_str().unwrap(), "--rule", "world-writable,dangling-symlink"])
+        .output()
+        .expect("Failed to run oqab");
+    assert_eq!(output.status.code(), Some(0));
+    
+    let records: Vec<serde_json::Value> = String::from_utf8(output.stdout).unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let mut rules: Vec<&str> = records.iter()
+        .filter(|record| record["type"] == "finding")
+        .map(|record| record["rule"].as_str().unwrap())
+        .collect();
+    rules.sort();
+    assert_eq!(rules, ["dangling-symlink", "world-writable"]);
+    
+    let summary = records.last().unwrap();
+    assert_eq!(summary["type"], "summary");
+    assert_eq!(summary["by_severity"]["medium"], 1);
+    assert_eq!(summary["by_severity"]["low"], 1);
+}
@@ -1,27 +1,72 @@
 use std::path::PathBuf;
 use oqab::core::config::{AppConfig, FileSearchConfig};
+use oqab::filters::DateZone;
+use oqab::filters::date::{end_of_day, start_of, start_of_day};
 
 #[test]
 fn test_app_config_defaults() {
     let config = AppConfig {
         root_dir: PathBuf::from("/test/path"),
         extension: None,
+        case_sensitive_extension: None,
         name: None,
+        name_glob: None,
+        name_regex: None,
+        full_path: None,
         pattern: None,
         min_size: None,
         max_size: None,
+        disk_size: None,
+        empty: None,
+        links: None,
+        min_links: None,
         newer_than: None,
         older_than: None,
+        created_after: None,
+        created_before: None,
+        accessed_after: None,
+        accessed_before: None,
+        newer_than_file: None,
+        older_than_file: None,
+        type_group: None,
+        mime: None,
+        content_kind: None,
+        entry_types: Vec::new(),
+        type_groups: Default::default(),
+        exif_date_after: None,
+        exif_date_before: None,
+        camera_model: None,
+        artist: None,
         size: None,
         depth: None,
+        min_depth: None,
+        max_per_dir: None,
+        max_results: None,
         threads: None,
+        walker: None,
         follow_links: None,
+        one_file_system: None,
+        unique: None,
+        hidden: None,
+        regex_syntax: None,
         show_progress: None,
+        progress: None,
+        prescan: None,
+        quiet: None,
+        no_messages: None,
+        no_default_excludes: None,
+        exclude: Vec::new(),
+        global_ignore: Vec::new(),
+        git_ignore: Some(false),
+        git_tracked: None,
+        git_modified: None,
+        script: None,
     };
     
     // Check defaults
     assert_eq!(config.root_dir, PathBuf::from("/test/path"));
     assert_eq!(config.extension, None);
+    assert_eq!(config.case_sensitive_extension, None);
     assert_eq!(config.name, None);
     assert_eq!(config.pattern, None);
     assert_eq!(config.min_size, None);
@@ -33,6 +78,8 @@ fn test_app_config_defaults() {
     assert_eq!(config.threads, None);
     assert_eq!(config.follow_links, None);
     assert_eq!(config.show_progress, None);
+    assert_eq!(config.git_tracked, None);
+    assert_eq!(config.git_modified, None);
 }
 
 #[test]
@@ -49,8 +96,9 @@ fn test_file_search_config() {
         traversal_mode: Default::default(),
         min_size: Some(1000),
         max_size: Some(5000),
-        newer_than: Some(String::from("2023-01-01")),
-        older_than: Some(String::from("2023-12-31")),
+        newer_than: Some(start_of_day("2023-01-01").unwrap()),
+        older_than: Some(end_of_day("2023-12-31").unwrap()),
+        ..Default::default()
     };
     
     // Check values
@@ -64,8 +112,8 @@ fn test_file_search_config() {
     assert!(!config.follow_symlinks);
     assert_eq!(config.min_size, Some(1000));
     assert_eq!(config.max_size, Some(5000));
-    assert_eq!(config.newer_than, Some(String::from("2023-01-01")));
-    assert_eq!(config.older_than, Some(String::from("2023-12-31")));
+    assert_eq!(config.newer_than, Some(start_of_day("2023-01-01").unwrap()));
+    assert_eq!(config.older_than, Some(end_of_day("2023-12-31").unwrap()));
 }
 
 #[test]
@@ -84,6 +132,7 @@ fn test_file_search_config_defaults() {
         max_size: None,
         newer_than: None,
         older_than: None,
+        ..Default::default()
     };
     
     // Check defaults
@@ -99,4 +148,71 @@ fn test_file_search_config_defaults() {
     assert_eq!(config.max_size, None);
     assert_eq!(config.newer_than, None);
     assert_eq!(config.older_than, None);
-} 
\ No newline at end of file
+}
+
+#[test]
+fn test_app_config_from_search_config() {
+    let search_config = FileSearchConfig {
+        path: Some(String::from("/test/path")),
+        file_extension: Some(String::from("rs")),
+        file_name: Some(String::from("main")),
+        thread_count: Some(2),
+        recursive: false,
+        follow_symlinks: true,
+        min_size: Some(10),
+        max_size: Some(100),
+        newer_than: Some(start_of_day("2023-01-01").unwrap()),
+        older_than: Some(end_of_day("2023-12-31").unwrap()),
+        ..Default::default()
+    };
+    
+    let config = AppConfig::from(&search_config);
+    
+    // Every filter should carry over to the runtime configuration
+    assert_eq!(config.root_dir, PathBuf::from("/test/path"));
+    assert_eq!(config.extension, Some(String::from("rs")));
+    assert_eq!(config.name, Some(String::from("main")));
+    assert_eq!(config.min_size, Some(10));
+    assert_eq!(config.max_size, Some(100));
+    assert_eq!(config.newer_than, Some(start_of_day("2023-01-01").unwrap()));
+    assert_eq!(config.older_than, Some(end_of_day("2023-12-31").unwrap()));
+    assert_eq!(config.threads, Some(2));
+    assert_eq!(config.follow_links, Some(true));
+    
+    // Non-recursive searches are limited to the root directory
+    assert_eq!(config.depth, Some(1));
+}
+
+#[test]
+fn test_file_search_config_dates_round_trip() {
+    // Config files store date bounds as plain dates covering the whole day in their zone
+    let config: FileSearchConfig = serde_json::from_str(
+        r#"{"newer_than": "2023-01-01", "older_than": "2023-12-31", "date_zone": "utc"}"#
+    ).expect("Failed to parse config");
+    assert_eq!(config.newer_than, Some(start_of_day("2023-01-01").unwrap()));
+    assert_eq!(config.older_than, Some(end_of_day("2023-12-31").unwrap()));
+    
+    let json = serde_json::to_value(&config).expect("Failed to serialize config");
+    assert_eq!(json["newer_than"], "2023-01-01");
+    assert_eq!(json["older_than"], "2023-12-31");
+    
+    // Local time, as on the command line, unless the file says otherwise
+    let config: FileSearchConfig = serde_json::from_str(r#"{"newer_than": "2023-01-01"}"#).expect("Failed to parse config");
+    assert_eq!(config.newer_than, Some(start_of("2023-01-01", DateZone::Local).unwrap()));
+    assert_eq!(serde_json::to_value(&config).unwrap()["newer_than"], "2023-01-01");
+    
+    // Other times are stored exactly
+    let config = FileSearchConfig {
+        newer_than: Some(start_of_day("2024-06-01T14:30:00+02:00").unwrap()),
+        date_zone: DateZone::Utc,
+        ..Default::default()
+    };
+    let json = serde_json::to_value(&config).expect("Failed to serialize config");
+    assert_eq!(json["newer_than"], "2024-06-01T12:30:00Z");
+    let loaded: FileSearchConfig = serde_json::from_value(json).expect("Failed to parse config");
+    assert_eq!(loaded.newer_than, config.newer_than);
+    
+    // Malformed dates are rejected when the file is loaded
+    assert!(serde_json::from_str::<FileSearchConfig>(r#"{"newer_than": "garbage"}"#).is_err());
+}
+
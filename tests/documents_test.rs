@@ -0,0 +1,89 @@
+use std::path::Path;
+use oqab::core::config::DocType;
+use oqab::utils::documents::DocumentExtractors;
+#[cfg(any(feature = "pdf", feature = "docx"))]
+use {std::fs, std::process::Command, tempfile::TempDir};
+
+// Run the oqab binary and return what it printed
+#[cfg(any(feature = "pdf", feature = "docx"))]
+fn oqab(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(args)
+        .output()
+        .expect("Failed to run oqab");
+    String::from_utf8(output.stdout).expect("Output was not UTF-8")
+}
+
+#[test]
+fn test_document_extractor_selection() {
+    let all = DocumentExtractors::all();
+    let available = DocumentExtractors::available();
+    assert_eq!(all.is_empty(), available.is_empty());
+    assert_eq!(all.find(Path::new("report.pdf")).is_some(), available.contains(&DocType::Pdf));
+    assert!(all.find(Path::new("notes.txt")).is_none());
+    
+    // Only the requested types are extracted
+    let docx_only = DocumentExtractors::for_types(&[DocType::Docx]);
+    assert!(docx_only.find(Path::new("report.pdf")).is_none());
+}
+
+#[cfg(feature = "docx")]
+#[test]
+fn test_grep_searches_docx_text() {
+    use std::io::Write;
+    
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let document = temp_dir.path().join("minutes.docx");
+    let mut writer = zip::ZipWriter::new(fs::File::create(&document).unwrap());
+    writer.start_file("word/document.xml", zip::write::SimpleFileOptions::default()).unwrap();
+    writer.write_all(br#"<?xml version="1.0"?><w:document><w:body>
+        <w:p><w:r><w:t>Agenda</w:t></w:r></w:p>
+        <w:p><w:r><w:t xml:space="preserve">Budget &amp; </w:t></w:r><w:r><w:t>hiring plan</w:t></w:r></w:p>
+    </w:body></w:document>"#).unwrap();
+    writer.finish().unwrap();
+    
+    let path = temp_dir.path().to_str().unwrap();
+    let output = oqab(&["-s", "-p", path, "--grep", "hiring", "--format", "vimgrep"]);
+    assert_eq!(output.trim_end(), format!("{}:2:10:Budget & hiring plan", document.display()));
+    
+    // Without docx in --doc-types the archive is skipped as binary
+    let output = oqab(&["-s", "-p", path, "--grep", "hiring", "--doc-types", "pdf"]);
+    assert!(output.is_empty());
+}
+
+#[cfg(feature = "pdf")]
+#[test]
+fn test_grep_searches_pdf_text() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(temp_dir.path().join("invoice.pdf"), minimal_pdf("Invoice total due")).unwrap();
+    
+    let output = oqab(&["-s", "-p", temp_dir.path().to_str().unwrap(), "--grep", "total", "--files-with-matches"]);
+    assert!(output.trim_end().ends_with("invoice.pdf"));
+}
+
+// A single-page PDF showing one line of text in a standard font
+#[cfg(feature = "pdf")]
+fn minimal_pdf(text: &str) -> Vec<u8> {
+    let stream = format!("BT /F1 12 Tf 72 720 Td ({}) Tj ET", text);
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 4 0 R /Resources << /Font << /F1 5 0 R >> >> >>".to_string(),
+        format!("<< /Length {} >>\nstream\n{}\nendstream", stream.len(), stream),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+    ];
+    
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref).as_bytes());
+    pdf
+}
@@ -0,0 +1,111 @@
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+// Run the oqab binary with the given arguments and return its exit code
+fn oqab(args: &[&str]) -> i32 {
+    Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(args)
+        .output()
+        .expect("Failed to run oqab")
+        .status
+        .code()
+        .expect("oqab was terminated by a signal")
+}
+
+#[test]
+fn test_exit_codes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(temp_dir.path().join("notes.txt"), "TODO: write tests\n").expect("Failed to write file");
+    let path = temp_dir.path().to_str().unwrap();
+    
+    // Matches found
+    assert_eq!(oqab(&["-s", "-p", path, "-e", "txt"]), 0);
+    assert_eq!(oqab(&["-s", "-p", path, "--grep", "TODO"]), 0);
+    
+    // Nothing found
+    assert_eq!(oqab(&["-s", "-p", path, "-e", "rs"]), 1);
+    assert_eq!(oqab(&["-s", "-p", path, "--grep", "FIXME"]), 1);
+    
+    // Usage errors
+    assert_eq!(oqab(&["--no-such-flag"]), 2);
+    assert_eq!(oqab(&["-s", "-p", path, "--grep", "("]), 2);
+    assert_eq!(oqab(&["-s", "-p", path, "-e", "txt", "--newer-than", "garbage"]), 2);
+    assert_eq!(oqab(&["-s", "-p", path, "-e", "txt", "--min-size", "lots"]), 2);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exit_code_partial_results() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(temp_dir.path().join("notes.txt"), "").expect("Failed to write file");
+    std::os::unix::fs::symlink(temp_dir.path().join("gone"), temp_dir.path().join("dangling"))
+        .expect("Failed to create symlink");
+    let path = temp_dir.path().to_str().unwrap();
+    
+    assert_eq!(oqab(&["-s", "-p", path, "-e", "txt", "--follow-symlinks"]), 3);
+    assert_eq!(oqab(&["-s", "-p", path, "-e", "txt", "--follow-symlinks", "--strict"]), 3);
+}
+
+#[test]
+fn test_default_path_is_current_dir() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(temp_dir.path().join("notes.txt"), "").expect("Failed to write file");
+    
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-s", "-e", "txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to run oqab");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("notes.txt"));
+}
+
+#[test]
+fn test_exit_code_broken_pipe() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+    
+    // Enough output to overflow the pipe buffer once the reader is gone
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    for i in 0..2000 {
+        fs::write(temp_dir.path().join(format!("file-with-a-fairly-long-name-{:04}.txt", i)), "")
+            .expect("Failed to write file");
+    }
+    
+    let mut child = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-s", "-p", temp_dir.path().to_str().unwrap(), "-e", "txt"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to run oqab");
+    
+    // Read a single line and hang up, like `head -n 1`
+    let mut first = String::new();
+    BufReader::new(child.stdout.take().unwrap()).read_line(&mut first).expect("Failed to read output");
+    assert!(first.contains("file-with-a-fairly-long-name"));
+    
+    let output = child.wait_with_output().expect("Failed to wait for oqab");
+    assert_eq!(output.status.code(), Some(141));
+    assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_exit_codes_for_every_output_mode() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(temp_dir.path().join("notes.txt"), "TODO: write tests\n").expect("Failed to write file");
+    let path = temp_dir.path().to_str().unwrap();
+    
+    // How results are printed never changes whether the search counts as a match
+    for mode in [&["--tree"][..], &["--long"], &["--group-by", "ext"], &["--print0"], &["--max-results", "1"]] {
+        let found = [&["-s", "-p", path, "-e", "txt"][..], mode].concat();
+        let missing = [&["-s", "-p", path, "-e", "rs"][..], mode].concat();
+        assert_eq!(oqab(&found), 0, "{:?}", mode);
+        assert_eq!(oqab(&missing), 1, "{:?}", mode);
+    }
+    
+    assert_eq!(oqab(&["-s", "-p", path, "--grep", "TODO", "--files-with-matches"]), 0);
+    assert_eq!(oqab(&["-s", "-p", path, "--grep", "FIXME", "--files-with-matches"]), 1);
+    assert_eq!(oqab(&["-s", "-p", path, "-z", "notes"]), 0);
+    assert_eq!(oqab(&["-s", "-p", path, "-z", "qqqqqq"]), 1);
+}
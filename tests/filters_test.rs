@@ -1,4 +1,3 @@
-use std::path::Path;
 use tempfile::TempDir;
 use std::fs::File;
 use std::io::Write;
@@ -123,4 +122,531 @@ fn test_size_filter() {
     
     // Directories are never filtered by size
     assert_eq!(min_filter.filter(temp_dir.path()), FilterResult::Accept);
-} 
\ No newline at end of file
+}
+
+#[test]
+fn test_extension_filter_case_sensitivity() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let upper_file = temp_dir.path().join("main.RS");
+    let lower_file = temp_dir.path().join("lib.rs");
+    
+    File::create(&upper_file).expect("Failed to create upper-case file");
+    File::create(&lower_file).expect("Failed to create lower-case file");
+    
+    // Extensions are matched case-insensitively by default
+    let filter = ExtensionFilter::new("rs");
+    assert_eq!(filter.filter(&upper_file), FilterResult::Accept);
+    assert_eq!(filter.filter(&lower_file), FilterResult::Accept);
+    
+    let upper_filter = ExtensionFilter::new(".RS");
+    assert_eq!(upper_filter.filter(&lower_file), FilterResult::Accept);
+    
+    // Case-sensitive matching requires an exact match
+    let sensitive_filter = ExtensionFilter::case_sensitive("rs");
+    assert_eq!(sensitive_filter.filter(&upper_file), FilterResult::Reject);
+    assert_eq!(sensitive_filter.filter(&lower_file), FilterResult::Accept);
+}
+
+#[test]
+fn test_size_constraint_filter() {
+    use oqab::filters::SizeConstraint;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("data.bin");
+    File::create(&file_path).unwrap().write_all(&[0u8; 4096]).unwrap();
+    
+    let accepts = |constraint| SizeFilter::from(constraint).filter(&file_path) == FilterResult::Accept;
+    
+    assert!(accepts(SizeConstraint::Exactly(4096)));
+    assert!(!accepts(SizeConstraint::Exactly(4095)));
+    assert!(accepts(SizeConstraint::LargerThan(4095)));
+    assert!(!accepts(SizeConstraint::LargerThan(4096)));
+    assert!(accepts(SizeConstraint::SmallerThan(4097)));
+    assert!(!accepts(SizeConstraint::SmallerThan(4096)));
+    assert!(!accepts(SizeConstraint::SmallerThan(0)));
+}
+
+#[test]
+fn test_glob_filter() {
+    use oqab::filters::GlobFilter;
+    use std::path::Path;
+    
+    // Patterns without a separator only look at the file name
+    let filter = GlobFilter::new("test_*.py").unwrap();
+    assert!(filter.is_match(Path::new("project/tests/test_parser.py")));
+    assert!(!filter.is_match(Path::new("project/tests/parser_test.py")));
+    
+    // Patterns with a separator match the whole path
+    let filter = GlobFilter::new("**/src/*.{rs,toml}").unwrap();
+    assert!(filter.is_match(Path::new("project/src/main.rs")));
+    assert!(filter.is_match(Path::new("project/src/Cargo.toml")));
+    assert!(!filter.is_match(Path::new("project/benches/main.rs")));
+    
+    assert!(GlobFilter::new("[").is_err());
+}
+
+#[test]
+fn test_glob_brace_expansion() {
+    use oqab::filters::GlobFilter;
+    use oqab::filters::glob::expand_braces;
+    use std::path::Path;
+    
+    assert_eq!(expand_braces("src/**/*.{rs,toml,md}"), ["src/**/*.rs", "src/**/*.toml", "src/**/*.md"]);
+    assert_eq!(expand_braces("{a,b{c,d}}-{1,2}"), ["a-1", "a-2", "bc-1", "bc-2", "bd-1", "bd-2"]);
+    assert_eq!(expand_braces("x{,.bak}"), ["x", "x.bak"]);
+    
+    // Escaped braces, braces inside classes, single items and unbalanced braces stay as written
+    assert_eq!(expand_braces(r"\{a,b\}"), [r"\{a,b\}"]);
+    assert_eq!(expand_braces("[{,]x"), ["[{,]x"]);
+    assert_eq!(expand_braces("{a}"), ["{a}"]);
+    assert_eq!(expand_braces("{a,b"), ["{a,b"]);
+    
+    // Nested groups, which the glob compiler rejects on its own
+    let filter = GlobFilter::new("*.{rs,{c,h}pp}").unwrap();
+    assert!(filter.is_match(Path::new("src/main.rs")));
+    assert!(filter.is_match(Path::new("src/parser.hpp")));
+    assert!(!filter.is_match(Path::new("src/parser.h")));
+    
+    // Each alternative decides on its own whether it matches names or whole paths
+    let filter = GlobFilter::new("{Makefile,**/src/*.rs}").unwrap();
+    assert!(filter.is_match(Path::new("project/docs/Makefile")));
+    assert!(filter.is_match(Path::new("project/src/lib.rs")));
+    assert!(!filter.is_match(Path::new("project/lib.rs")));
+}
+
+#[test]
+fn test_regex_syntax_translation() {
+    use oqab::filters::RegexSyntax;
+    
+    let matches = |syntax: RegexSyntax, pattern: &str, text: &str| {
+        regex::Regex::new(&syntax.translate(pattern)).unwrap().is_match(text)
+    };
+    
+    // Literal patterns have no metacharacters
+    assert!(matches(RegexSyntax::Literal, "a.b(c)", "xa.b(c)y"));
+    assert!(!matches(RegexSyntax::Literal, "a.b", "axb"));
+    
+    // Basic syntax escapes operators and treats bare ones as text
+    assert!(matches(RegexSyntax::Basic, r"\(ab\)\{2\}", "abab"));
+    assert!(matches(RegexSyntax::Basic, "f(x)+1", "f(x)+1"));
+    assert!(matches(RegexSyntax::Basic, r"cat\|dog", "hotdog"));
+    assert!(matches(RegexSyntax::Basic, "*a^b$c", "*a^b$c"));
+    assert!(matches(RegexSyntax::Basic, "^end$", "end"));
+    
+    // Extended syntax follows grep -E, with POSIX bracket expressions
+    assert!(matches(RegexSyntax::Extended, "(ab){2}|x", "abab"));
+    assert!(matches(RegexSyntax::Extended, r"[\]+", r"a\b"));
+    assert!(matches(RegexSyntax::Extended, "[]a]", "]"));
+    assert!(matches(RegexSyntax::Extended, "[[:digit:]]+", "42"));
+    assert!(matches(RegexSyntax::Extended, "a{,", "a{,"));
+}
+
+#[test]
+fn test_date_filter_reference_files() {
+    use std::time::{Duration, SystemTime};
+    use oqab::filters::date::DateFilter;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let now = SystemTime::now();
+    let touch = |name: &str, age_secs: u64| {
+        let path = temp_dir.path().join(name);
+        let file = File::create(&path).unwrap();
+        file.set_modified(now - Duration::from_secs(age_secs)).unwrap();
+        path
+    };
+    let stamp = touch("build.stamp", 3600);
+    let old = touch("old.rs", 7200);
+    let new = touch("new.rs", 60);
+    
+    let filter = DateFilter::newer_than_file(&stamp).unwrap();
+    assert_eq!(filter.filter(&new), FilterResult::Accept);
+    assert_eq!(filter.filter(&old), FilterResult::Reject);
+    // Like find -newer, the reference itself is not newer than itself
+    assert_eq!(filter.filter(&stamp), FilterResult::Reject);
+    
+    let filter = DateFilter::older_than_file(&stamp).unwrap();
+    assert_eq!(filter.filter(&old), FilterResult::Accept);
+    assert_eq!(filter.filter(&new), FilterResult::Reject);
+    
+    assert!(DateFilter::newer_than_file(&temp_dir.path().join("missing")).is_err());
+}
+
+#[test]
+fn test_date_filter_timestamps() {
+    use std::fs::FileTimes;
+    use std::time::{Duration, SystemTime};
+    use oqab::filters::Timestamp;
+    use oqab::filters::date::DateFilter;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("report.txt");
+    let now = SystemTime::now();
+    let day = Duration::from_secs(24 * 3600);
+    // Read a year ago but modified just now
+    File::create(&path).unwrap()
+        .set_times(FileTimes::new().set_accessed(now - 365 * day).set_modified(now))
+        .unwrap();
+    
+    let last_month = Some(now - 30 * day);
+    assert_eq!(DateFilter::between(last_month, None).filter(&path), FilterResult::Accept);
+    let accessed = DateFilter::between(last_month, None).with_timestamp(Timestamp::Accessed);
+    assert_eq!(accessed.filter(&path), FilterResult::Reject);
+    let accessed = DateFilter::between(None, last_month).with_timestamp(Timestamp::Accessed);
+    assert_eq!(accessed.filter(&path), FilterResult::Accept);
+    
+    // Creation times are only compared where they are recorded
+    let metadata = std::fs::metadata(&path).unwrap();
+    if Timestamp::Created.of(&metadata).is_ok() {
+        let created = DateFilter::between(last_month, None).with_timestamp(Timestamp::Created);
+        assert_eq!(created.filter(&path), FilterResult::Accept);
+    }
+}
+
+#[test]
+fn test_date_zones() {
+    use std::process::Command;
+    use std::time::{Duration, UNIX_EPOCH};
+    use oqab::filters::date::{end_of, start_of, start_of_day, DateZone};
+    
+    // 2024-06-01T12:30:00Z, however it is written
+    let instant = UNIX_EPOCH + Duration::from_secs(1_717_245_000);
+    assert_eq!(start_of("2024-06-01T14:30:00+02:00", DateZone::Local).unwrap(), instant);
+    assert_eq!(start_of("2024-06-01T12:30:00Z", DateZone::Local).unwrap(), instant);
+    assert_eq!(start_of("2024-06-01T12:30", DateZone::Utc).unwrap(), instant);
+    assert_eq!(start_of("2024-06-01", DateZone::Utc).unwrap(), start_of_day("2024-06-01").unwrap());
+    assert_eq!(end_of("2024-06-01", DateZone::Utc).unwrap(), start_of_day("2024-06-01").unwrap() + Duration::from_secs(86_399));
+    assert!(start_of("2024-06-31", DateZone::Utc).is_err());
+    
+    // Bare dates on the command line are local unless --utc is given; in UTC+2
+    // the local day of June 2 starts at 22:00 UTC on June 1
+    let temp_dir = TempDir::new().unwrap();
+    let file = File::create(temp_dir.path().join("late.txt")).unwrap();
+    file.set_modified(UNIX_EPOCH + Duration::from_secs(1_717_282_800)).unwrap(); // 2024-06-01T23:00:00Z
+    let search = |extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .env("TZ", "OQB-2")
+            .args(["-s", "-e", "txt", "-p", temp_dir.path().to_str().unwrap(), "--newer-than", "2024-06-02"])
+            .args(extra)
+            .output()
+            .expect("Failed to run oqab");
+        String::from_utf8_lossy(&output.stdout).lines().count()
+    };
+    assert_eq!(search(&[]), 1);
+    assert_eq!(search(&["--utc"]), 0);
+    
+    // Config files read them the same way, in their own date_zone
+    let config_file = temp_dir.path().join("config.json");
+    let search = |config: &str| {
+        std::fs::write(&config_file, config).unwrap();
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .env("TZ", "OQB-2")
+            .args(["-s", "-e", "txt", "-p", temp_dir.path().to_str().unwrap(), "-c", config_file.to_str().unwrap()])
+            .output()
+            .expect("Failed to run oqab");
+        String::from_utf8_lossy(&output.stdout).lines().count()
+    };
+    assert_eq!(search(r#"{"newer_than": "2024-06-02"}"#), 1);
+    assert_eq!(search(r#"{"newer_than": "2024-06-02", "date_zone": "local"}"#), 1);
+    assert_eq!(search(r#"{"newer_than": "2024-06-02", "date_zone": "utc"}"#), 0);
+}
+
+#[test]
+fn test_type_group_filter() {
+    use std::collections::BTreeMap;
+    use std::path::Path;
+    use oqab::filters::TypeGroupFilter;
+    
+    let filter = TypeGroupFilter::new(&["image", "code"]).unwrap();
+    assert_eq!(filter.filter(Path::new("holiday.JPG")), FilterResult::Accept);
+    assert_eq!(filter.filter(Path::new("main.rs")), FilterResult::Accept);
+    assert_eq!(filter.filter(Path::new("song.mp3")), FilterResult::Reject);
+    assert_eq!(filter.filter(Path::new("Makefile")), FilterResult::Reject);
+    
+    // Groups from the configuration replace built-in ones of the same name
+    let mut custom = BTreeMap::new();
+    custom.insert("image".to_string(), vec!["avif".to_string()]);
+    custom.insert("notebook".to_string(), vec![".ipynb".to_string()]);
+    let filter = TypeGroupFilter::with_custom(&["image", "notebook"], &custom).unwrap();
+    assert_eq!(filter.filter(Path::new("photo.avif")), FilterResult::Accept);
+    assert_eq!(filter.filter(Path::new("photo.jpg")), FilterResult::Reject);
+    assert_eq!(filter.filter(Path::new("analysis.ipynb")), FilterResult::Accept);
+    
+    let error = TypeGroupFilter::new(&["spreadsheet"]).unwrap_err();
+    assert!(error.to_string().contains("image"));
+}
+
+#[test]
+fn test_mime_filter() {
+    use oqab::filters::MimeFilter;
+    use oqab::utils::mime;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let picture = root.join("holiday.dat");
+    let document = root.join("report");
+    let disguised = root.join("notes.png");
+    File::create(&picture).unwrap().write_all(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+    File::create(&document).unwrap().write_all(b"%PDF-1.7\n").unwrap();
+    File::create(&disguised).unwrap().write_all(b"plain text").unwrap();
+    
+    assert_eq!(mime::sniff(&picture).unwrap(), "image/png");
+    assert_eq!(mime::from_content(b"\0\x01\x02"), "application/octet-stream");
+    
+    // Content decides, not the extension
+    let images = MimeFilter::new(&["image/*"]);
+    assert_eq!(images.filter(&picture), FilterResult::Accept);
+    assert_eq!(images.filter(&disguised), FilterResult::Reject);
+    assert_eq!(images.filter(root), FilterResult::Accept);
+    assert_eq!(images.filter_entry(root), FilterResult::Reject);
+    
+    let documents = MimeFilter::new(&["Application/PDF", "text"]);
+    assert_eq!(documents.filter(&document), FilterResult::Accept);
+    assert_eq!(documents.filter(&disguised), FilterResult::Accept);
+    assert_eq!(documents.filter(&picture), FilterResult::Reject);
+}
+
+#[test]
+fn test_content_filter() {
+    use oqab::filters::ContentFilter;
+    use oqab::utils::content::is_binary;
+    
+    // NUL bytes or mostly undecodable bytes mark binary content, a stray Latin-1 letter does not
+    assert!(!is_binary(b"plain text\n"));
+    assert!(!is_binary(b"caf\xe9 au lait\n"));
+    assert!(is_binary(b"text\0more"));
+    assert!(is_binary(b"\x89\xfe\xff\x80\x81ab"));
+    
+    let temp_dir = TempDir::new().unwrap();
+    let text = temp_dir.path().join("notes.txt");
+    let binary = temp_dir.path().join("notes.bin");
+    let empty = temp_dir.path().join("empty");
+    File::create(&text).unwrap().write_all(b"some notes\n").unwrap();
+    File::create(&binary).unwrap().write_all(b"\x7fELF\x02\x01\x01\0\0").unwrap();
+    File::create(&empty).unwrap();
+    
+    assert_eq!(ContentFilter::text().filter(&text), FilterResult::Accept);
+    assert_eq!(ContentFilter::text().filter(&binary), FilterResult::Reject);
+    assert_eq!(ContentFilter::text().filter(&empty), FilterResult::Accept);
+    assert_eq!(ContentFilter::binary().filter(&binary), FilterResult::Accept);
+    assert_eq!(ContentFilter::binary().filter(&text), FilterResult::Reject);
+    assert_eq!(ContentFilter::binary().filter(temp_dir.path()), FilterResult::Accept);
+}
+
+#[test]
+fn test_name_filter_case() {
+    let path = std::path::Path::new("Makefile");
+    
+    // Exact names compare case-sensitively unless asked not to, substrings ignore case
+    assert_eq!(NameFilter::new("makefile").filter(path), FilterResult::Reject);
+    assert_eq!(NameFilter::new("makefile").with_case_sensitive(false).filter(path), FilterResult::Accept);
+    assert_eq!(NameFilter::contains("MAKE").filter(path), FilterResult::Accept);
+    assert_eq!(NameFilter::contains("MAKE").with_case_sensitive(true).filter(path), FilterResult::Reject);
+    assert_eq!(NameFilter::contains("Make").with_case_sensitive(true).filter(path), FilterResult::Accept);
+}
+
+#[test]
+fn test_full_path_matching() {
+    use oqab::filters::RegexFilter;
+    
+    let path = std::path::Path::new("project/tests/unit.rs");
+    
+    // Only the file name is matched by default
+    assert_eq!(NameFilter::contains("TESTS/").filter(path), FilterResult::Reject);
+    assert_eq!(NameFilter::contains("TESTS/").with_full_path(true).filter(path), FilterResult::Accept);
+    assert_eq!(NameFilter::new("project/tests/unit.rs").with_full_path(true).filter(path), FilterResult::Accept);
+    
+    let regex = RegexFilter::file_name(r"^tests/.*\.rs$").unwrap();
+    assert_eq!(regex.filter(path), FilterResult::Reject);
+    let regex = RegexFilter::file_name(r"/tests/.*\.rs$").unwrap().with_full_path(true);
+    assert_eq!(regex.filter(path), FilterResult::Accept);
+}
+
+#[test]
+fn test_depth_filter() {
+    use oqab::filters::DepthFilter;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    std::fs::create_dir_all(root.join("a/b/c")).unwrap();
+    File::create(root.join("top.txt")).unwrap();
+    File::create(root.join("a/b/deep.txt")).unwrap();
+    
+    let filter = DepthFilter::new(root, Some(2), Some(2));
+    assert_eq!(filter.depth(&root.join("a/b")), Some(2));
+    
+    // Directories above the range are walked through without being reported
+    assert_eq!(filter.filter(&root.join("a")), FilterResult::Accept);
+    assert_eq!(filter.filter_entry(&root.join("a")), FilterResult::Reject);
+    assert_eq!(filter.filter(&root.join("top.txt")), FilterResult::Reject);
+    
+    // Directories at the limit are reported, those beyond it pruned
+    assert_eq!(filter.filter_entry(&root.join("a/b")), FilterResult::Accept);
+    assert_eq!(filter.filter(&root.join("a/b/c")), FilterResult::Prune);
+    assert_eq!(filter.filter(&root.join("a/b/deep.txt")), FilterResult::Prune);
+    
+    // Paths outside the root are not judged
+    assert_eq!(DepthFilter::max(root.join("a"), 0).filter(&root.join("top.txt")), FilterResult::Accept);
+}
+
+#[test]
+fn test_empty_filter() {
+    use oqab::filters::EmptyFilter;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let empty_file = root.join("empty.txt");
+    let full_file = root.join("full.txt");
+    let empty_dir = root.join("empty");
+    let full_dir = root.join("full");
+    File::create(&empty_file).unwrap();
+    File::create(&full_file).unwrap().write_all(b"data").unwrap();
+    std::fs::create_dir(&empty_dir).unwrap();
+    std::fs::create_dir(&full_dir).unwrap();
+    File::create(full_dir.join(".keep")).unwrap();
+    
+    let filter = EmptyFilter::new();
+    assert_eq!(filter.filter(&empty_file), FilterResult::Accept);
+    assert_eq!(filter.filter(&full_file), FilterResult::Reject);
+    
+    // Every directory is walked, but only those without entries are results
+    assert_eq!(filter.filter(&full_dir), FilterResult::Accept);
+    assert_eq!(filter.filter_entry(&empty_dir), FilterResult::Accept);
+    assert_eq!(filter.filter_entry(&full_dir), FilterResult::Reject);
+    assert_eq!(filter.filter_entry(&empty_file), FilterResult::Accept);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_type_filter() {
+    use std::os::unix::fs::symlink;
+    use oqab::filters::{EntryType, FilterExt, TypeFilter};
+    
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let file = root.join("notes.txt");
+    let dir = root.join("notes");
+    let link = root.join("latest.txt");
+    File::create(&file).unwrap();
+    std::fs::create_dir(&dir).unwrap();
+    std::fs::create_dir(root.join("other")).unwrap();
+    symlink(&file, &link).unwrap();
+    
+    // Only regular files by default, and links are judged without being followed
+    let files = TypeFilter::default();
+    assert_eq!(files.filter(&file), FilterResult::Accept);
+    assert_eq!(files.filter(&dir), FilterResult::Reject);
+    assert_eq!(files.filter(&link), FilterResult::Reject);
+    
+    let others = TypeFilter::new([EntryType::Directory, EntryType::Symlink]);
+    assert_eq!(others.filter(&file), FilterResult::Reject);
+    assert_eq!(others.filter(&dir), FilterResult::Accept);
+    assert_eq!(others.filter(&link), FilterResult::Accept);
+    
+    // Name filters let every directory through to be walked, but judge directories offered as results
+    let named = NameFilter::contains("notes");
+    assert_eq!(named.filter(&root.join("other")), FilterResult::Accept);
+    assert_eq!(named.filter_entry(&root.join("other")), FilterResult::Reject);
+    assert_eq!(named.filter_entry(&dir), FilterResult::Accept);
+    
+    let no_text = named.and(ExtensionFilter::new("txt").not());
+    assert_eq!(no_text.filter_entry(&dir), FilterResult::Accept);
+    assert_eq!(no_text.filter_entry(&file), FilterResult::Reject);
+}
+
+// Sparse files are only guaranteed to stay unallocated on Unix filesystems
+#[cfg(unix)]
+#[test]
+fn test_size_filter_on_disk() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sparse.img");
+    File::create(&path).unwrap().set_len(16 * 1024 * 1024).unwrap();
+    
+    let apparent = SizeFilter::min(1024 * 1024);
+    assert_eq!(apparent.filter(&path), FilterResult::Accept);
+    
+    let on_disk = SizeFilter::min(1024 * 1024).with_disk_size(true);
+    assert_eq!(on_disk.filter(&path), FilterResult::Reject);
+}
+
+#[test]
+fn test_filter_macro() {
+    use oqab::filter;
+    use std::fs;
+    use oqab::filters::{FilterExt, GlobFilter};
+    
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("parser_test.rs"), "").unwrap();
+    fs::write(root.join("parser.rs"), "").unwrap();
+    fs::write(root.join("big.rs"), vec![0u8; 2 << 20]).unwrap();
+    fs::write(root.join("notes_test.txt"), "").unwrap();
+    fs::write(root.join("Cargo.lock"), "").unwrap();
+    
+    let accepted = |filter: &dyn Filter| -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(root).unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| filter.filter(path) == FilterResult::Accept)
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    };
+    
+    let filter = filter!((ext "rs") and ((name "test") or (size > 1 mb)));
+    assert_eq!(accepted(&filter), ["big.rs", "parser_test.rs"]);
+    
+    // The same tree built with the fluent combinators
+    let fluent = ExtensionFilter::new("rs").and(NameFilter::contains("test").or(filter!(size > 1 mb)));
+    assert_eq!(accepted(&fluent), accepted(&filter));
+    
+    // Chains of one operator, negation and arbitrary filters as atoms
+    let filter = filter!((not (ext "rs")) and (not (ext "lock")) and (GlobFilter::new("*_test.*").unwrap()));
+    assert_eq!(accepted(&filter), ["notes_test.txt"]);
+    assert_eq!(accepted(&filter!((ext "lock") or (ext "txt") or (size >= 2097152))), ["Cargo.lock", "big.rs", "notes_test.txt"]);
+    assert_eq!(accepted(&filter!(size < 1 kb)).len(), 4);
+    assert_eq!(accepted(&filter!(size <= 0)).len(), 4);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_links_filter() {
+    use oqab::filters::LinksFilter;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let single = root.join("single.txt");
+    let shared = root.join("shared.txt");
+    File::create(&single).unwrap();
+    File::create(&shared).unwrap();
+    std::fs::hard_link(&shared, root.join("other-name.txt")).unwrap();
+    
+    assert_eq!(LinksFilter::exactly(1).filter(&single), FilterResult::Accept);
+    assert_eq!(LinksFilter::exactly(1).filter(&shared), FilterResult::Reject);
+    assert_eq!(LinksFilter::at_least(2).filter(&shared), FilterResult::Accept);
+    assert_eq!(LinksFilter::at_least(2).filter(&single), FilterResult::Reject);
+    
+    // Directories are walked whatever their count, and judged by it as results
+    let dir = root.join("dir");
+    std::fs::create_dir(&dir).unwrap();
+    assert_eq!(LinksFilter::exactly(1).filter(&dir), FilterResult::Accept);
+    assert_eq!(LinksFilter::exactly(1).filter_entry(&dir), FilterResult::Reject);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_same_file_system_filter() {
+    use oqab::filters::SameFileSystemFilter;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("file.txt");
+    File::create(&file).unwrap();
+    std::fs::create_dir(temp_dir.path().join("dir")).unwrap();
+    
+    let filter = SameFileSystemFilter::new(temp_dir.path()).unwrap();
+    assert_eq!(filter.filter(&file), FilterResult::Accept);
+    assert_eq!(filter.filter(&temp_dir.path().join("dir")), FilterResult::Accept);
+    
+    // /proc is always a filesystem of its own, so it is pruned rather than entered
+    assert_eq!(filter.filter(std::path::Path::new("/proc")), FilterResult::Prune);
+    assert!(SameFileSystemFilter::new(&temp_dir.path().join("missing")).is_err());
+}
@@ -0,0 +1,369 @@
+use std::fs;
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+// Run the oqab binary and return what it printed
+fn oqab(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(args)
+        .output()
+        .expect("Failed to run oqab");
+    String::from_utf8(output.stdout).expect("Output was not UTF-8")
+}
+
+#[test]
+fn test_grep_uses_finder_filters() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::create_dir(root.join(".cache")).unwrap();
+    fs::write(root.join("notes.txt"), "TODO: visible\n").unwrap();
+    fs::write(root.join("big.txt"), format!("TODO: big\n{}", "x".repeat(4096))).unwrap();
+    fs::write(root.join(".cache/stale.txt"), "TODO: hidden\n").unwrap();
+    
+    let path = root.to_str().unwrap();
+    let output = oqab(&["-s", "-p", path, "--grep", "TODO", "--files-with-matches", "--max-size", "1k"]);
+    let files: Vec<&str> = output.lines().collect();
+    
+    // Hidden directories are pruned and size filters apply, as for file search
+    assert_eq!(files, [root.join("notes.txt").to_str().unwrap()]);
+}
+
+#[test]
+fn test_grep_hidden_flag() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("visible.txt"), "TODO\n").unwrap();
+    fs::write(root.join(".hidden.txt"), "TODO\n").unwrap();
+    
+    let path = root.to_str().unwrap();
+    let output = oqab(&["-s", "-p", path, "--grep", "TODO", "--files-with-matches", "--hidden"]);
+    let mut files: Vec<&str> = output.lines().collect();
+    files.sort();
+    
+    assert_eq!(files, [root.join(".hidden.txt").to_str().unwrap(), root.join("visible.txt").to_str().unwrap()]);
+}
+
+#[test]
+fn test_grep_stats_json() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("a.rs"), "// TODO one\n// TODO two\n").unwrap();
+    fs::write(root.join("b.txt"), "TODO three\n").unwrap();
+    fs::write(root.join("blob.bin"), b"TODO\0binary").unwrap();
+    fs::write(root.join("large.log"), format!("TODO\n{}", "x".repeat(2048))).unwrap();
+    
+    let path = root.to_str().unwrap();
+    let output = oqab(&["-p", path, "--grep", "TODO", "--stats", "--format", "json", "--max-filesize", "1k"]);
+    let records: Vec<serde_json::Value> = output.lines()
+        .map(|line| serde_json::from_str(line).expect("Invalid JSON record"))
+        .collect();
+    
+    // One record per match, then the summary
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[0]["type"], "match");
+    let stats = &records[3]["stats"];
+    assert_eq!(records[3]["type"], "summary");
+    assert_eq!(stats["files_searched"], 2);
+    assert_eq!(stats["matches"], 3);
+    assert_eq!(stats["bytes_scanned"], 35);
+    assert_eq!(stats["skipped"]["binary"], 1);
+    assert_eq!(stats["skipped"]["too_large"], 1);
+    assert_eq!(stats["matches_by_extension"]["rs"], 2);
+    assert_eq!(stats["matches_by_extension"]["txt"], 1);
+}
+
+#[test]
+fn test_grep_stats_histograms() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("small.txt"), "TODO\n").unwrap();
+    fs::write(root.join("medium.txt"), format!("TODO\n{}", "x".repeat(2048))).unwrap();
+    
+    let path = root.to_str().unwrap();
+    let output = oqab(&["-p", path, "--grep", "TODO", "--histogram", "size,mtime", "--format", "json"]);
+    let summary: serde_json::Value = serde_json::from_str(output.lines().last().expect("No output"))
+        .expect("Invalid JSON record");
+    
+    // --histogram implies --stats
+    assert_eq!(summary["type"], "summary");
+    let histograms = summary["stats"]["histograms"].as_array().expect("No histograms");
+    assert_eq!(histograms.len(), 2);
+    
+    let size = &histograms[0];
+    assert_eq!(size["kind"], "size");
+    let counts: Vec<u64> = size["buckets"].as_array().unwrap().iter()
+        .map(|bucket| bucket["count"].as_u64().unwrap())
+        .collect();
+    assert_eq!(counts[..3], [0, 1, 1]);
+    assert_eq!(counts.iter().sum::<u64>(), 2);
+    assert_eq!(size["buckets"][1]["min"], 1);
+    assert_eq!(size["buckets"][1]["max"], 1024);
+    
+    // Both files were just written
+    let mtime = &histograms[1];
+    assert_eq!(mtime["kind"], "mtime");
+    assert_eq!(mtime["buckets"][0]["count"], 2);
+    assert!(mtime["buckets"].as_array().unwrap().last().unwrap()["max"].is_null());
+}
+
+#[test]
+fn test_grep_output_order() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    let mut expected = Vec::new();
+    for i in 0..50 {
+        let file = root.join(format!("file{:02}.txt", i));
+        fs::write(&file, "needle\n".repeat(50 - i)).unwrap();
+        expected.push(file.to_string_lossy().to_string());
+    }
+    
+    // Parallel workers still print files in path order by default
+    let path = root.to_str().unwrap();
+    let output = oqab(&["-s", "-p", path, "--grep", "needle", "--files-with-matches", "-w", "4"]);
+    let files: Vec<String> = output.lines().map(String::from).collect();
+    assert_eq!(files, expected);
+    
+    // --unordered prints the same files in whatever order they finish
+    let output = oqab(&["-s", "-p", path, "--grep", "needle", "--files-with-matches", "-w", "4", "--unordered"]);
+    let mut files: Vec<String> = output.lines().map(String::from).collect();
+    files.sort();
+    assert_eq!(files, expected);
+}
+
+#[test]
+fn test_grep_with_content_index() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path().join("repo");
+    fs::create_dir(&root).unwrap();
+    fs::write(root.join("a.txt"), "needle\n").unwrap();
+    fs::write(root.join("b.txt"), "haystack\n").unwrap();
+    
+    let path = root.to_str().unwrap();
+    let index = temp_dir.path().join("index.db");
+    let index = index.to_str().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-p", path, "--index", index, "index", "build", "--content"])
+        .output()
+        .expect("Failed to run oqab");
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Indexed 2 files"));
+    
+    // The index rules out b.txt, which is reported in the statistics
+    let output = oqab(&["-p", path, "--index", index, "--grep", "needle", "--stats", "--format", "json"]);
+    let summary: serde_json::Value = serde_json::from_str(output.lines().last().unwrap()).unwrap();
+    assert_eq!(summary["stats"]["files_searched"], 1);
+    assert_eq!(summary["stats"]["skipped"]["indexed"], 1);
+    assert_eq!(summary["stats"]["matches"], 1);
+    
+    // With --passthru files without a match are printed too, so the index rules out nothing
+    let output = oqab(&["-s", "-p", path, "--index", index, "--grep", "needle", "--passthru", "--no-filename"]);
+    assert_eq!(output, "needle\nhaystack\n");
+}
+
+#[test]
+fn test_stdout_only_carries_results() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("notes.txt"), "TODO: one\n").unwrap();
+    let path = root.to_str().unwrap();
+    
+    // Summaries and performance figures go to stderr so pipelines only see matches
+    let output = oqab(&["-p", path, "-e", "txt"]);
+    let lines: Vec<&str> = output.lines().filter(|line| !line.is_empty()).collect();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].trim().ends_with("notes.txt"));
+    
+    let output = oqab(&["-p", path, "--grep", "TODO", "--stats"]);
+    assert!(!output.contains("Performance"));
+    assert!(output.contains("TODO: one"));
+}
+
+#[test]
+fn test_grep_null_data() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    // Records that span lines, as written by `git ls-files -z` or `find -print0`
+    fs::write(root.join("records.dat"), b"first\nrecord\0second TODO\nrecord\0third\0").unwrap();
+    
+    let path = root.to_str().unwrap();
+    
+    // Without --null-data the NUL bytes make the file look binary
+    assert_eq!(oqab(&["-s", "-p", path, "--grep", "TODO"]), "");
+    
+    let output = oqab(&["-s", "-p", path, "--grep", "TODO", "--null-data", "--line-number", "--no-filename"]);
+    let records: Vec<&str> = output.split('\0').collect();
+    assert_eq!(records, ["2:second TODO\nrecord", ""]);
+    
+    let output = oqab(&["-s", "-p", path, "--grep", "TODO", "--null-data", "--heading"]);
+    let records: Vec<&str> = output.split('\0').collect();
+    assert_eq!(records, [root.join("records.dat").to_str().unwrap(), "second TODO\nrecord", "", ""]);
+}
+
+#[test]
+fn test_grep_binary_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("blob.bin"), b"\0header\nTODO inside\n").unwrap();
+    let blob = root.join("blob.bin");
+    let blob = blob.to_str().unwrap();
+    
+    let path = root.to_str().unwrap();
+    let grep = |extra: &[&str]| {
+        let mut args = vec!["-s", "-p", path, "--grep", "TODO", "--line-number"];
+        args.extend(extra);
+        oqab(&args)
+    };
+    
+    // Binary files are skipped unless asked for, then reported or searched as text
+    assert_eq!(grep(&[]), "");
+    assert_eq!(grep(&["--binary"]), format!("Binary file {blob} matches\n"));
+    assert_eq!(grep(&["--text"]), format!("{blob}:2:TODO inside\n"));
+    assert_eq!(grep(&["--binary", "--text"]), format!("{blob}:2:TODO inside\n"));
+    
+    let output = oqab(&["-s", "-p", path, "--grep", "TODO", "--binary", "--stats", "--format", "json"]);
+    let summary: serde_json::Value = serde_json::from_str(output.lines().last().unwrap()).unwrap();
+    assert_eq!(summary["stats"]["binary_matched"], 1);
+    assert_eq!(summary["stats"]["skipped"]["binary"], 0);
+}
+
+#[test]
+fn test_grep_color() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(temp_dir.path().join("notes.txt"), "one TODO two\n").unwrap();
+    
+    let path = temp_dir.path().to_str().unwrap();
+    let grep = |color: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .args(["-s", "-p", path, "--grep", "TODO", "--no-filename", "--color", color])
+            .env("NO_COLOR", "1")
+            .output()
+            .expect("Failed to run oqab");
+        String::from_utf8(output.stdout).expect("Output was not UTF-8")
+    };
+    
+    // Only the matched text is highlighted, and asking for colors beats NO_COLOR
+    assert_eq!(grep("always"), "one \u{1b}[31m\u{1b}[1mTODO\u{1b}[0m two\n");
+    assert_eq!(grep("auto"), "one TODO two\n");
+    assert_eq!(grep("never"), "one TODO two\n");
+}
+
+#[test]
+fn test_grep_heading() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("notes.txt"), "TODO one\nnothing\nTODO two\n").unwrap();
+    let file = root.join("notes.txt");
+    let file = file.to_str().unwrap();
+    
+    let path = root.to_str().unwrap();
+    let grep = |extra: &[&str]| {
+        let mut args = vec!["-s", "-p", path, "--grep", "TODO"];
+        args.extend(extra);
+        oqab(&args)
+    };
+    
+    // Piped output gets one grep-style record per match
+    assert_eq!(grep(&[]), format!("{file}:TODO one\n{file}:TODO two\n"));
+    assert_eq!(grep(&["--line-number"]), format!("{file}:1:TODO one\n{file}:3:TODO two\n"));
+    assert_eq!(grep(&["--line-number", "--no-filename"]), "1:TODO one\n3:TODO two\n");
+    
+    // Headings group matches under the file name, as on a terminal
+    assert_eq!(grep(&["--heading"]), format!("{file}\nTODO one\nTODO two\n\n"));
+    assert_eq!(grep(&["--heading", "--no-filename"]), "TODO one\nTODO two\n\n");
+    assert_eq!(grep(&["--heading", "--no-heading"]), format!("{file}:TODO one\n{file}:TODO two\n"));
+}
+
+#[test]
+fn test_grep_passthru() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("a.log"), "TODO one\nnothing\nTODO two\n").unwrap();
+    fs::write(root.join("b.log"), "quiet\n").unwrap();
+    let a = root.join("a.log");
+    let a = a.to_str().unwrap();
+    let b = root.join("b.log");
+    let b = b.to_str().unwrap();
+    
+    let path = root.to_str().unwrap();
+    let grep = |extra: &[&str]| {
+        let mut args = vec!["-s", "-p", path, "--grep", "TODO", "--passthru"];
+        args.extend(extra);
+        oqab(&args)
+    };
+    
+    // Every line of every file comes through, matching ones flagged with `:`
+    assert_eq!(grep(&["--line-number"]), format!("{a}:1:TODO one\n{a}-2-nothing\n{a}:3:TODO two\n{b}-1-quiet\n"));
+    assert_eq!(grep(&["--no-filename"]), "TODO one\nnothing\nTODO two\nquiet\n");
+    assert_eq!(grep(&["--heading", "--line-number"]), format!("{a}\n1: TODO one\n2- nothing\n3: TODO two\n\n{b}\n1- quiet\n\n"));
+}
+
+#[test]
+fn test_grep_sort_input_mtime() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    let now = SystemTime::now();
+    for (name, age_days) in [("a.log", 3), ("b.log", 1), ("c.log", 2)] {
+        let file = root.join(name);
+        fs::write(&file, "ERROR\n").unwrap();
+        let file = fs::File::options().write(true).open(&file).unwrap();
+        file.set_modified(now - Duration::from_secs(age_days * 24 * 60 * 60)).unwrap();
+    }
+    
+    let path = root.to_str().unwrap();
+    let grep = |extra: &[&str]| {
+        let mut args = vec!["-s", "-p", path, "--grep", "ERROR", "--files-with-matches"];
+        args.extend(extra);
+        oqab(&args)
+    };
+    let listing = |names: [&str; 3]| names.iter().map(|name| format!("{}\n", root.join(name).display())).collect::<String>();
+    
+    assert_eq!(grep(&[]), listing(["a.log", "b.log", "c.log"]));
+    assert_eq!(grep(&["--sort-input", "mtime"]), listing(["b.log", "c.log", "a.log"]));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_grep_handlers() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path().join("files");
+    fs::create_dir(&root).unwrap();
+    fs::write(root.join("plain.txt"), "needle\n").unwrap();
+    fs::write(root.join("skipped.log"), "needle\n").unwrap();
+    fs::write(root.join("skipped.md"), "needle\n").unwrap();
+    fs::write(root.join("forced.dat"), "needle\0\x01\x02\n").unwrap();
+    fs::write(root.join("encoded.enc"), "ELDEEN\n").unwrap();
+    
+    let config = temp_dir.path().join("config.json");
+    fs::write(&config, r#"{
+        "handlers": {
+            "log": "skip",
+            "text/markdown": "skip",
+            "dat": "text",
+            "enc": { "command": ["sed", "s/ELDEEN/needle/"] }
+        }
+    }"#).unwrap();
+    
+    let output = oqab(&["-s", "-p", root.to_str().unwrap(), "-c", config.to_str().unwrap(),
+        "--grep", "needle", "--files-with-matches", "--stats", "--format", "json"]);
+    let mut lines: Vec<&str> = output.lines().collect();
+    let summary: serde_json::Value = serde_json::from_str(lines.pop().unwrap()).unwrap();
+    let mut matched: Vec<String> = lines.iter()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["path"].as_str().unwrap().to_string())
+        .map(|path| path.rsplit('/').next().unwrap().to_string())
+        .collect();
+    matched.sort();
+    
+    assert_eq!(matched, ["encoded.enc", "forced.dat", "plain.txt"]);
+    assert_eq!(summary["stats"]["skipped"]["handled"], 2);
+    
+    // A content index holds the raw bytes, so it cannot rule out files a command converts
+    let index = temp_dir.path().join("index.db");
+    let index = index.to_str().unwrap();
+    Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-p", root.to_str().unwrap(), "--index", index, "index", "build", "--content"])
+        .output()
+        .expect("Failed to run oqab");
+    let output = oqab(&["-s", "-p", root.to_str().unwrap(), "-c", config.to_str().unwrap(), "--index", index,
+        "--grep", "needle", "--files-with-matches", "--ext", "enc"]);
+    assert_eq!(output, format!("{}\n", root.join("encoded.enc").display()));
+}
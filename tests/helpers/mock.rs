@@ -1,5 +1,6 @@
-use std::path::{Path, PathBuf};
-use std::fs::Metadata;
+#![allow(dead_code, clippy::wrong_self_convention)]
+
+use std::path::PathBuf;
 use std::io;
 use std::time::{SystemTime, UNIX_EPOCH};
 
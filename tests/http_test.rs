@@ -0,0 +1,84 @@
+#![cfg(feature = "http")]
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+use oqab::core::config::FileSearchConfig;
+use oqab::utils::http_server::HttpServer;
+
+// Helper function to send a GET request and return the raw response
+fn get(address: &str, target: &str) -> String {
+    let mut stream = TcpStream::connect(address).expect("Failed to connect to server");
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target).expect("Failed to send request");
+    
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("Failed to read response");
+    response
+}
+
+#[test]
+fn test_http_search_streams_results() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::create_dir(temp_dir.path().join("src")).expect("Failed to create subdirectory");
+    File::create(temp_dir.path().join("src/main.rs")).expect("Failed to create test file");
+    File::create(temp_dir.path().join("src/notes.txt")).expect("Failed to create test file");
+    
+    let config = FileSearchConfig {
+        path: Some(temp_dir.path().to_string_lossy().to_string()),
+        ..Default::default()
+    };
+    let server = HttpServer::bind("127.0.0.1:0", config).expect("Failed to bind server");
+    let address = server.local_addr().expect("Failed to get address").to_string();
+    thread::spawn(move || server.serve());
+    
+    let response = get(&address, "/search?ext=rs&path=src");
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("Transfer-Encoding: chunked"));
+    assert!(response.contains("main.rs"));
+    assert!(!response.contains("notes.txt"));
+    assert!(response.contains("\"count\":1"));
+    
+    // The query is read like the one on the command line
+    let response = get(&address, "/search?q=*.rs");
+    assert!(response.contains("main.rs"));
+    assert!(response.contains("\"count\":1"));
+    let response = get(&address, "/search?q=.txt");
+    assert!(response.contains("notes.txt"));
+    assert!(response.contains("\"count\":1"));
+    
+    // Searches may not escape the served root
+    let response = get(&address, "/search?path=..%2F..");
+    assert!(response.starts_with("HTTP/1.1 400"));
+    
+    let response = get(&address, "/unknown");
+    assert!(response.starts_with("HTTP/1.1 404"));
+}
+
+#[test]
+fn test_http_limits_concurrent_requests() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    File::create(temp_dir.path().join("main.rs")).expect("Failed to create test file");
+    
+    let config = FileSearchConfig {
+        path: Some(temp_dir.path().to_string_lossy().to_string()),
+        ..Default::default()
+    };
+    let server = HttpServer::bind("127.0.0.1:0", config).expect("Failed to bind server");
+    let address = server.local_addr().expect("Failed to get address").to_string();
+    thread::spawn(move || server.serve());
+    
+    // Clients that never send their request hold on to every slot
+    let idle: Vec<TcpStream> = (0..16)
+        .map(|_| TcpStream::connect(&address).expect("Failed to connect to server"))
+        .collect();
+    let waiting = thread::spawn(move || get(&address, "/search?ext=rs"));
+    thread::sleep(Duration::from_millis(200));
+    assert!(!waiting.is_finished());
+    
+    // The waiting request is served once those clients go away
+    drop(idle);
+    assert!(waiting.join().expect("Request failed").starts_with("HTTP/1.1 200 OK"));
+}
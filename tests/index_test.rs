@@ -0,0 +1,123 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use oqab::index::{ContentIndex, TrigramQuery};
+
+// Helper function to create files with the given contents and return their paths
+fn create_files(root: &std::path::Path, files: &[(&str, &str)]) -> Vec<PathBuf> {
+    files.iter()
+        .map(|(name, content)| {
+            let path = root.join(name);
+            fs::write(&path, content).expect("Failed to write test file");
+            path
+        })
+        .collect()
+}
+
+#[test]
+fn test_trigram_query_planning() {
+    // Literal prefixes become required trigrams
+    assert!(!TrigramQuery::from_regex("handle_request", false).is_unrestricted());
+    assert_eq!(TrigramQuery::from_regex("foo|barbaz", false).alternatives().len(), 2);
+    
+    // Patterns without a usable literal can match any file
+    assert!(TrigramQuery::from_regex(r"\w+", false).is_unrestricted());
+    assert!(TrigramQuery::from_regex("ab", false).is_unrestricted());
+    assert!(TrigramQuery::from_regex("x*", false).is_unrestricted());
+    assert!(TrigramQuery::from_regex("(unclosed", false).is_unrestricted());
+}
+
+#[test]
+fn test_content_index_prunes_candidates() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path().join("repo");
+    fs::create_dir(&root).unwrap();
+    let files = create_files(&root, &[
+        ("server.rs", "fn handle_request() {}\n"),
+        ("client.rs", "fn send_request() {}\n"),
+        ("notes.txt", "HANDLE_REQUEST is documented here\n"),
+    ]);
+    
+    let location = temp_dir.path().join("index.db");
+    let mut index = ContentIndex::open(&location, &root).expect("Failed to open index");
+    let stats = index.update(&files).expect("Failed to build index");
+    assert_eq!((stats.files, stats.updated, stats.removed), (3, 3, 0));
+    
+    // Only files containing the literal survive, with case folded for -i
+    let query = TrigramQuery::from_regex("handle_request", false);
+    let kept = index.prune(files.clone(), &root, &query).unwrap();
+    assert_eq!(kept, vec![files[0].clone(), files[2].clone()]);
+    
+    // A file changed after indexing is always searched
+    fs::write(&files[1], "fn handle_request_later() {}\n// grown\n").unwrap();
+    let kept = index.prune(files.clone(), &root, &query).unwrap();
+    assert_eq!(kept.len(), 3);
+    
+    // Rebuilding only re-reads the changed file and drops deleted ones
+    fs::remove_file(&files[2]).unwrap();
+    let stats = index.update(&files[..2]).expect("Failed to update index");
+    assert_eq!((stats.files, stats.updated, stats.removed), (2, 1, 1));
+}
+
+#[test]
+fn test_content_index_export_import() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let server = temp_dir.path().join("server");
+    let client = temp_dir.path().join("client");
+    fs::create_dir(&server).unwrap();
+    fs::create_dir(&client).unwrap();
+    let files = create_files(&server, &[
+        ("server.rs", "fn handle_request() {}\n"),
+        ("client.rs", "fn send_request() {}\n"),
+    ]);
+    let mut index = ContentIndex::open(&temp_dir.path().join("server.db"), &server).unwrap();
+    index.update(&files).unwrap();
+    
+    let mut export = Vec::new();
+    assert_eq!(index.export(&mut export).unwrap(), 2);
+    
+    // The copy keeps size and modification time, so the imported entries stay valid
+    let copies: Vec<PathBuf> = files.iter()
+        .map(|file| {
+            let copy = client.join(file.file_name().unwrap());
+            fs::copy(file, &copy).unwrap();
+            let modified = fs::metadata(file).unwrap().modified().unwrap();
+            fs::File::options().write(true).open(&copy).unwrap().set_modified(modified).unwrap();
+            copy
+        })
+        .collect();
+    let mut imported = ContentIndex::open(&temp_dir.path().join("client.db"), &client).unwrap();
+    assert_eq!(imported.import(export.as_slice()).unwrap(), 2);
+    let query = TrigramQuery::from_regex("handle_request", false);
+    assert_eq!(imported.prune(copies.clone(), &client, &query).unwrap(), vec![copies[0].clone()]);
+    
+    // A truncated export is rejected without touching the index
+    let truncated = &export[..export.iter().rposition(|&byte| byte == b'{').unwrap()];
+    assert!(imported.import(truncated).is_err());
+    assert_eq!(imported.len().unwrap(), 2);
+    assert!(imported.import(&b"{\"format\":\"other\",\"version\":1,\"files\":0}\n"[..]).is_err());
+}
+
+#[test]
+fn test_content_index_compact_and_check() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path().join("repo");
+    fs::create_dir(&root).unwrap();
+    let files = create_files(&root, &[("a.txt", "alpha beta gamma\n"), ("b.txt", "delta\n")]);
+    let location = temp_dir.path().join("index.db");
+    ContentIndex::open(&location, &root).unwrap().update(&files).unwrap();
+    assert!(ContentIndex::open_existing(&location).unwrap().check().unwrap().is_empty());
+    
+    // Postings left behind by a lost file entry are reported, then dropped by compaction
+    let conn = rusqlite::Connection::open(&location).unwrap();
+    conn.execute_batch("PRAGMA foreign_keys = OFF").unwrap();
+    conn.execute("DELETE FROM files WHERE path = 'a.txt'", []).unwrap();
+    drop(conn);
+    let problems = ContentIndex::open_existing(&location).unwrap().check().unwrap();
+    assert_eq!(problems.len(), 1);
+    
+    let mut index = ContentIndex::open_existing_writable(&location).unwrap();
+    assert!(index.compact().unwrap() > 0);
+    assert!(index.check().unwrap().is_empty());
+    assert_eq!(index.len().unwrap(), 1);
+}
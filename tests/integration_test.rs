@@ -2,9 +2,8 @@ use std::fs::{self, File};
 use std::io::Write;
 use std::path::Path;
 use tempfile::TempDir;
-use oqab::core::config::{AppConfig, FileSearchConfig};
-use oqab::core::FinderFactory;
-use oqab::utils::search_directory;
+use oqab::core::config::FileSearchConfig;
+use oqab::utils::{collect_files, search_directory};
 use oqab::core::observer::TrackingObserver;
 
 // Helper function to create a test directory structure
@@ -58,6 +57,7 @@ fn test_finder_factory_create_standard_finder() {
         max_size: None,
         newer_than: None,
         older_than: None,
+        ..Default::default()
     };
     
     // Use standard search directly since it's easier to test
@@ -95,6 +95,7 @@ fn test_search_directory_with_size_filter() {
         max_size: None,
         newer_than: None,
         older_than: None,
+        ..Default::default()
     };
     
     let observer = TrackingObserver::new();
@@ -133,6 +134,7 @@ fn test_recursive_search() {
         max_size: None,
         newer_than: None,
         older_than: None,
+        ..Default::default()
     };
     
     let observer1 = TrackingObserver::new();
@@ -160,6 +162,7 @@ fn test_recursive_search() {
         max_size: None,
         newer_than: None,
         older_than: None,
+        ..Default::default()
     };
     
     let observer2 = TrackingObserver::new();
@@ -171,4 +174,802 @@ fn test_recursive_search() {
     
     // We should find all 6 files
     assert_eq!(recursive_results.len(), 6);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_search_directory_extension_case() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    create_test_file(&temp_dir.path().join("upper.RS"), 10);
+    create_test_file(&temp_dir.path().join("lower.rs"), 10);
+    
+    let mut config = FileSearchConfig {
+        path: Some(temp_dir.path().to_string_lossy().to_string()),
+        file_extension: Some("rs".to_string()),
+        ..Default::default()
+    };
+    
+    // Both files match when comparing case-insensitively (the default)
+    let results = search_directory(temp_dir.path(), &config, &TrackingObserver::new())
+        .expect("Search operation failed");
+    assert_eq!(results.len(), 2);
+    
+    // Only the exact match is found when case-sensitive matching is requested
+    config.case_sensitive_extension = true;
+    let results = search_directory(temp_dir.path(), &config, &TrackingObserver::new())
+        .expect("Search operation failed");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].file_name().unwrap(), "lower.rs");
+    
+    // Ignoring case wins over case-sensitive extensions, in both search paths
+    config.ignore_case = true;
+    let results = search_directory(temp_dir.path(), &config, &TrackingObserver::new())
+        .expect("Search operation failed");
+    assert_eq!(results.len(), 2);
+    let app_config = oqab::core::AppConfig::from(&config);
+    assert_eq!(app_config.case_sensitive_extension, Some(false));
+}
+
+// Helper function to run a git command inside a test repository
+fn git(dir: &Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=oqab", "-c", "user.email=oqab@example.com"])
+        .args(args)
+        .status()
+        .expect("Failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn test_search_directory_git_selection() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    git(temp_dir.path(), &["init", "-q"]);
+    
+    create_test_file(&temp_dir.path().join("tracked.txt"), 10);
+    create_test_file(&temp_dir.path().join("changed.txt"), 10);
+    git(temp_dir.path(), &["add", "tracked.txt", "changed.txt"]);
+    git(temp_dir.path(), &["commit", "-q", "-m", "initial"]);
+    
+    create_test_file(&temp_dir.path().join("changed.txt"), 20);
+    create_test_file(&temp_dir.path().join("untracked.txt"), 10);
+    
+    let mut config = FileSearchConfig {
+        path: Some(temp_dir.path().to_string_lossy().to_string()),
+        file_extension: Some("txt".to_string()),
+        git_tracked: true,
+        ..Default::default()
+    };
+    
+    // Only files in the index are candidates
+    let mut results = search_directory(temp_dir.path(), &config, &TrackingObserver::new())
+        .expect("Search operation failed");
+    results.sort();
+    let names: Vec<_> = results.iter().map(|p| p.file_name().unwrap().to_owned()).collect();
+    assert_eq!(names, ["changed.txt", "tracked.txt"]);
+    
+    // Only tracked files with uncommitted changes are candidates
+    config.git_tracked = false;
+    config.git_modified = true;
+    let results = search_directory(temp_dir.path(), &config, &TrackingObserver::new())
+        .expect("Search operation failed");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].file_name().unwrap(), "changed.txt");
+}
+
+#[test]
+fn test_standard_finder_git_selection_outside_repository() {
+    use oqab::core::{AppConfig, FinderFactory};
+    
+    // Without a repository to ask, a selection of tracked files selects nothing rather than everything
+    let temp_dir = create_test_directory();
+    let config = AppConfig {
+        root_dir: temp_dir.path().to_path_buf(),
+        extension: Some("txt".to_string()),
+        git_tracked: Some(true),
+        threads: Some(1),
+        ..Default::default()
+    };
+    let finder = FinderFactory::create_standard_finder(&config);
+    let (matches, _) = finder.find(temp_dir.path()).expect("Search operation failed");
+    assert!(matches.is_empty());
+}
+
+#[test]
+fn test_collect_files_from_list() {
+    let temp_dir = create_test_directory();
+    
+    // A NUL-separated list, including an entry that no longer exists
+    let list_path = temp_dir.path().join("files.lst");
+    let entries = [
+        temp_dir.path().join("file1.txt"),
+        temp_dir.path().join("file2.log"),
+        temp_dir.path().join("subdir2/subdir3/config.txt"),
+        temp_dir.path().join("missing.txt"),
+    ];
+    let list: Vec<String> = entries.iter().map(|p| p.to_string_lossy().to_string()).collect();
+    fs::write(&list_path, list.join("\0")).expect("Failed to write file list");
+    
+    let config = FileSearchConfig {
+        file_extension: Some("txt".to_string()),
+        files_from: Some(list_path.to_string_lossy().to_string()),
+        ..Default::default()
+    };
+    
+    // Only listed files are considered, and the usual filters still apply
+    let results = collect_files(&config, &TrackingObserver::new())
+        .expect("Search operation failed");
+    assert_eq!(results, vec![entries[0].clone(), entries[2].clone()]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_search_directory_counts_unreadable_paths() {
+    use oqab::core::observer::SearchObserver;
+    
+    let temp_dir = create_test_directory();
+    std::os::unix::fs::symlink(temp_dir.path().join("gone"), temp_dir.path().join("dangling"))
+        .expect("Failed to create symlink");
+    
+    // Messages are suppressed, but the broken link is still counted
+    let config = FileSearchConfig {
+        follow_symlinks: true,
+        no_messages: true,
+        ..Default::default()
+    };
+    
+    let observer = TrackingObserver::new();
+    let results = search_directory(temp_dir.path(), &config, &observer)
+        .expect("Search operation failed");
+    assert_eq!(results.len(), 6);
+    assert_eq!(observer.errors_count(), 1);
+    
+    // The failure is recorded for --strict reporting
+    let errors = observer.path_errors();
+    assert_eq!(errors[0].path, temp_dir.path().join("gone"));
+}
+
+#[test]
+fn test_search_directory_hidden_entries() {
+    let temp_dir = create_test_directory();
+    fs::create_dir(temp_dir.path().join(".cache")).expect("Failed to create .cache");
+    create_test_file(&temp_dir.path().join(".cache/cached.txt"), 10);
+    create_test_file(&temp_dir.path().join(".env"), 10);
+    
+    // Hidden files and directories are skipped by default
+    let mut config = FileSearchConfig::default();
+    let results = search_directory(temp_dir.path(), &config, &TrackingObserver::new())
+        .expect("Search operation failed");
+    assert_eq!(results.len(), 6);
+    
+    // --hidden includes them
+    config.hidden = true;
+    let results = search_directory(temp_dir.path(), &config, &TrackingObserver::new())
+        .expect("Search operation failed");
+    assert_eq!(results.len(), 8);
+}
+
+#[test]
+fn test_finder_streams_matches() {
+    use oqab::core::{AppConfig, FinderFactory};
+    
+    let temp_dir = create_test_directory();
+    let config = AppConfig {
+        root_dir: temp_dir.path().to_path_buf(),
+        extension: Some("txt".to_string()),
+        threads: Some(1),
+        ..Default::default()
+    };
+    let finder = FinderFactory::create_standard_finder(&config);
+    
+    // A capacity of one forces the walk to wait for the consumer after every match
+    let receiver = finder.find_streaming(temp_dir.path(), 1).expect("Failed to start search");
+    let mut streamed: Vec<_> = receiver.iter().map(|found| found.path).collect();
+    streamed.sort();
+    assert_eq!(streamed.len(), 3);
+    
+    // The finder's own observers saw the same run
+    let observer = finder.get_tracking_observer().expect("Missing tracking observer");
+    let mut tracked = observer.lock_found_files().unwrap().clone();
+    tracked.sort();
+    assert_eq!(streamed, tracked);
+}
+
+#[test]
+fn test_finder_reports_search() {
+    use oqab::core::{AppConfig, FinderFactory};
+    
+    let temp_dir = create_test_directory();
+    let hidden = temp_dir.path().join(".hidden");
+    fs::create_dir(&hidden).expect("Failed to create hidden directory");
+    create_test_file(&hidden.join("secret.txt"), 10);
+    
+    let config = AppConfig {
+        root_dir: temp_dir.path().to_path_buf(),
+        extension: Some("txt".to_string()),
+        threads: Some(1),
+        ..Default::default()
+    };
+    let finder = FinderFactory::create_standard_finder(&config);
+    
+    let (matches, report) = finder.find(temp_dir.path()).expect("Search operation failed");
+    assert_eq!(matches.len(), 3);
+    assert_eq!(report.files_matched, 3);
+    assert_eq!(report.directories_scanned, 4);
+    assert_eq!(report.skipped, vec![hidden]);
+    assert!(report.is_complete());
+    
+    // Each run reports only itself
+    let (_, second) = finder.find(temp_dir.path()).expect("Search operation failed");
+    assert_eq!(second.files_matched, 3);
+}
+
+#[test]
+fn test_finder_from_search_config() {
+    use oqab::core::FinderFactory;
+    
+    let temp_dir = create_test_directory();
+    let mut config = FileSearchConfig::new();
+    config.path = Some(temp_dir.path().to_string_lossy().to_string());
+    config.file_extension = Some("txt".to_string());
+    config.min_size = Some(800);
+    config.thread_count = Some(1);
+    
+    let finder = FinderFactory::from_search_config(&config).expect("Failed to build finder");
+    let (matches, _) = finder.find(temp_dir.path()).expect("Search operation failed");
+    let mut names: Vec<_> = matches.iter()
+        .map(|found| found.path.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["file1.txt", "file3.txt"]);
+    
+    // Criteria that cannot be applied are errors rather than silently dropped
+    config.name_glob = Some("[".to_string());
+    assert!(FinderFactory::from_search_config(&config).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_filesystem_root_detection() {
+    use oqab::core::Platform;
+    
+    assert!(Platform::is_filesystem_root(Path::new("/")));
+    assert!(Platform::is_filesystem_root(Path::new("/tmp/..")));
+    
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    assert!(!Platform::is_filesystem_root(temp_dir.path()));
+    assert!(!Platform::is_filesystem_root(&temp_dir.path().join("missing")));
+}
+
+#[test]
+fn test_default_excludes() {
+    use oqab::core::{AppConfig, FinderFactory};
+    
+    let temp_dir = create_test_directory();
+    let modules = temp_dir.path().join("node_modules/left-pad");
+    fs::create_dir_all(&modules).expect("Failed to create node_modules");
+    create_test_file(&modules.join("readme.txt"), 10);
+    // A file named like an excluded directory is still a match
+    create_test_file(&temp_dir.path().join("target"), 10);
+    
+    let mut config = AppConfig {
+        root_dir: temp_dir.path().to_path_buf(),
+        threads: Some(1),
+        ..Default::default()
+    };
+    let (matches, report) = FinderFactory::create_standard_finder(&config)
+        .find(temp_dir.path())
+        .expect("Search operation failed");
+    assert_eq!(matches.len(), 7);
+    assert_eq!(report.skipped, vec![temp_dir.path().join("node_modules")]);
+    
+    config.no_default_excludes = Some(true);
+    let (matches, _) = FinderFactory::create_standard_finder(&config)
+        .find(temp_dir.path())
+        .expect("Search operation failed");
+    assert_eq!(matches.len(), 8);
+}
+
+// The config directory only follows XDG_CONFIG_HOME on Linux
+#[cfg(target_os = "linux")]
+#[test]
+fn test_global_ignore_file() {
+    use std::process::Command;
+    
+    let temp_dir = create_test_directory();
+    let config_home = TempDir::new().expect("Failed to create config directory");
+    fs::create_dir(config_home.path().join("oqab")).unwrap();
+    fs::write(config_home.path().join("oqab/ignore"), "# build output\n*.log\nsubdir2/\n").unwrap();
+    
+    let search = |extra: &[&str]| -> Vec<String> {
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .env("XDG_CONFIG_HOME", config_home.path())
+            .args(["-s", "-n", ".", "-p", temp_dir.path().to_str().unwrap()])
+            .args(extra)
+            .output()
+            .expect("Failed to run oqab");
+        let mut names: Vec<String> = String::from_utf8_lossy(&output.stdout).lines()
+            .filter_map(|line| Path::new(line.trim()).file_name().map(|name| name.to_string_lossy().to_string()))
+            .collect();
+        names.sort();
+        names
+    };
+    
+    assert_eq!(search(&[]), vec!["file1.txt", "file3.txt", "image.jpg"]);
+    assert_eq!(search(&["--no-ignore-global"]).len(), 6);
+}
+
+#[test]
+fn test_exclude_patterns() {
+    use oqab::core::{AppConfig, FinderFactory};
+    use oqab::core::config::Walker;
+    
+    let temp_dir = create_test_directory();
+    for walker in [Walker::Builtin, Walker::Walkdir, Walker::Ignore] {
+        let config = AppConfig {
+            root_dir: temp_dir.path().to_path_buf(),
+            exclude: vec!["subdir2/".to_string(), "*.jpg".to_string()],
+            walker: Some(walker),
+            ..Default::default()
+        };
+        let (matches, report) = FinderFactory::create_standard_finder(&config)
+            .find(temp_dir.path())
+            .expect("Search operation failed");
+        let mut names: Vec<_> = matches.iter()
+            .map(|found| found.path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["file1.txt", "file2.log", "file3.txt"]);
+        
+        // Excluded directories are pruned rather than walked and filtered
+        assert_eq!(report.skipped, vec![temp_dir.path().join("subdir2")]);
+        assert_eq!(report.directories_scanned, 2);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_entry_types() {
+    use std::os::unix::fs::symlink;
+    use oqab::core::{AppConfig, FinderFactory};
+    use oqab::core::config::Walker;
+    use oqab::filters::EntryType;
+    
+    let temp_dir = create_test_directory();
+    symlink(temp_dir.path().join("file1.txt"), temp_dir.path().join("subdir1/link1.txt")).unwrap();
+    let relative = |paths: Vec<std::path::PathBuf>| {
+        let mut paths: Vec<_> = paths.into_iter()
+            .map(|path| path.strip_prefix(temp_dir.path()).unwrap().to_string_lossy().to_string())
+            .collect();
+        paths.sort();
+        paths
+    };
+    
+    let search = |walker: Walker, types: &[EntryType], name: Option<&str>| {
+        let config = AppConfig {
+            root_dir: temp_dir.path().to_path_buf(),
+            entry_types: types.to_vec(),
+            name: name.map(str::to_string),
+            walker: Some(walker),
+            ..Default::default()
+        };
+        let (matches, _) = FinderFactory::create_standard_finder(&config)
+            .find(temp_dir.path())
+            .expect("Search operation failed");
+        relative(matches.into_iter().map(|found| found.path).collect())
+    };
+    
+    for walker in [Walker::Builtin, Walker::Walkdir, Walker::Ignore] {
+        assert_eq!(search(walker, &[EntryType::Directory], None), ["subdir1", "subdir2", "subdir2/subdir3"]);
+        assert_eq!(search(walker, &[EntryType::Directory], Some("3")), ["subdir2/subdir3"]);
+        assert_eq!(search(walker, &[EntryType::Symlink, EntryType::Directory], Some("1")), ["subdir1", "subdir1/link1.txt"]);
+        assert_eq!(search(walker, &[], Some("1")).len(), 1);
+    }
+    
+    // The standard search honours the same kinds
+    let config = FileSearchConfig {
+        path: Some(temp_dir.path().to_string_lossy().to_string()),
+        entry_types: vec![EntryType::Directory, EntryType::Symlink],
+        ..Default::default()
+    };
+    let files = collect_files(&config, &TrackingObserver::new()).expect("Search operation failed");
+    assert_eq!(relative(files), ["subdir1", "subdir1/link1.txt", "subdir2", "subdir2/subdir3"]);
+}
+
+#[test]
+fn test_depth_limits() {
+    use oqab::core::{AppConfig, FinderFactory};
+    use oqab::core::config::Walker;
+    use oqab::filters::EntryType;
+    
+    let temp_dir = create_test_directory();
+    let relative = |paths: Vec<std::path::PathBuf>| {
+        let mut paths: Vec<_> = paths.into_iter()
+            .map(|path| path.strip_prefix(temp_dir.path()).unwrap().to_string_lossy().to_string())
+            .collect();
+        paths.sort();
+        paths
+    };
+    
+    let search = |walker: Walker, min_depth: Option<usize>, max_depth: Option<usize>| {
+        let config = AppConfig {
+            root_dir: temp_dir.path().to_path_buf(),
+            entry_types: vec![EntryType::File, EntryType::Directory],
+            min_depth,
+            depth: max_depth,
+            walker: Some(walker),
+            ..Default::default()
+        };
+        let (matches, _) = FinderFactory::create_standard_finder(&config)
+            .find(temp_dir.path())
+            .expect("Search operation failed");
+        relative(matches.into_iter().map(|found| found.path).collect())
+    };
+    
+    for walker in [Walker::Builtin, Walker::Walkdir, Walker::Ignore] {
+        assert_eq!(search(walker, None, Some(1)), ["file1.txt", "file2.log", "subdir1", "subdir2"]);
+        assert_eq!(search(walker, Some(2), Some(2)), ["subdir1/file3.txt", "subdir1/image.jpg", "subdir2/document.pdf", "subdir2/subdir3"]);
+        assert_eq!(search(walker, Some(3), None), ["subdir2/subdir3/config.txt"]);
+    }
+    
+    // The standard search honours the same limits
+    let config = FileSearchConfig {
+        path: Some(temp_dir.path().to_string_lossy().to_string()),
+        entry_types: vec![EntryType::File, EntryType::Directory],
+        min_depth: Some(2),
+        max_depth: Some(2),
+        ..Default::default()
+    };
+    let files = collect_files(&config, &TrackingObserver::new()).expect("Search operation failed");
+    assert_eq!(relative(files), ["subdir1/file3.txt", "subdir1/image.jpg", "subdir2/document.pdf", "subdir2/subdir3"]);
+}
+
+#[test]
+fn test_newer_than_reference_file() {
+    use std::time::{Duration, SystemTime};
+    use oqab::core::{AppConfig, FinderFactory};
+    
+    let temp_dir = create_test_directory();
+    let stamp = temp_dir.path().join("build.stamp");
+    let now = SystemTime::now();
+    File::create(&stamp).unwrap().set_modified(now - Duration::from_secs(60)).unwrap();
+    for path in ["file1.txt", "subdir1/file3.txt", "subdir2/subdir3/config.txt"] {
+        let age = if path == "file1.txt" { 0 } else { 3600 };
+        File::options().write(true).open(temp_dir.path().join(path)).unwrap()
+            .set_modified(now - Duration::from_secs(age)).unwrap();
+    }
+    
+    // Only the file touched after the stamp was written is newer, in either search
+    let config = FileSearchConfig {
+        path: Some(temp_dir.path().to_string_lossy().to_string()),
+        file_extension: Some("txt".to_string()),
+        newer_than_file: Some(stamp.to_string_lossy().to_string()),
+        ..Default::default()
+    };
+    let files = collect_files(&config, &TrackingObserver::new()).expect("Search operation failed");
+    assert_eq!(files, [temp_dir.path().join("file1.txt")]);
+    
+    let (matches, _) = FinderFactory::create_standard_finder(&AppConfig::from(&config))
+        .find(temp_dir.path())
+        .expect("Search operation failed");
+    let paths: Vec<_> = matches.into_iter().map(|found| found.path).collect();
+    assert_eq!(paths, [temp_dir.path().join("file1.txt")]);
+    
+    // A missing reference file is an error rather than an empty result
+    let config = FileSearchConfig { newer_than_file: Some("missing.stamp".to_string()), ..config };
+    assert!(collect_files(&config, &TrackingObserver::new()).is_err());
+}
+
+#[test]
+fn test_max_per_dir() {
+    use std::process::Command;
+    use oqab::core::{AppConfig, FinderFactory};
+    
+    let temp_dir = create_test_directory();
+    create_test_file(&temp_dir.path().join("subdir2/subdir3/extra.txt"), 10);
+    create_test_file(&temp_dir.path().join("notes.md"), 10);
+    
+    let config = AppConfig {
+        root_dir: temp_dir.path().to_path_buf(),
+        threads: Some(1),
+        max_per_dir: Some(1),
+        ..Default::default()
+    };
+    let (matches, _) = FinderFactory::create_standard_finder(&config)
+        .find(temp_dir.path())
+        .expect("Search operation failed");
+    let mut parents: Vec<_> = matches.iter().map(|found| found.path.parent().unwrap().to_path_buf()).collect();
+    parents.sort();
+    parents.dedup();
+    // One file from every directory, including those below a satisfied one
+    assert_eq!((matches.len(), parents.len()), (4, 4));
+    
+    let count = |extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .args(["-s", "-n", ".", "-p", temp_dir.path().to_str().unwrap()])
+            .args(extra)
+            .output()
+            .expect("Failed to run oqab");
+        String::from_utf8_lossy(&output.stdout).lines().count()
+    };
+    assert_eq!(count(&[]), 8);
+    assert_eq!(count(&["--one-per-dir"]), 4);
+    assert_eq!(count(&["--one-per-dir", "--advanced"]), 4);
+    assert_eq!(count(&["--max-per-dir", "2"]), 7);
+    assert_eq!(count(&["--max-per-dir", "0"]), 0);
+}
+
+#[test]
+fn test_path_key_case_sensitivity() {
+    use oqab::core::Platform;
+    
+    let temp_dir = create_test_directory();
+    let file = temp_dir.path().join("subdir1/image.jpg");
+    let respelled = temp_dir.path().join("subdir1/IMAGE.JPG");
+    
+    // Whether the names refer to the same file decides whether they share a key
+    let insensitive = Platform::is_case_insensitive(&file);
+    assert_eq!(insensitive, respelled.exists());
+    assert_eq!(Platform::path_key(&file) == Platform::path_key(&respelled), insensitive);
+    
+    // Differently spelled routes to the same file always share a key
+    let dotted = temp_dir.path().join("subdir2/../subdir1/./image.jpg");
+    assert_eq!(Platform::path_key(&dotted), Platform::path_key(&file));
+    
+    // Paths that do not exist are keyed as given
+    let missing = temp_dir.path().join("Missing.txt");
+    assert_eq!(Platform::path_key(&missing), missing);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_cycles_are_skipped() {
+    use std::os::unix::fs::symlink;
+    use oqab::core::{AppConfig, FileId, FinderFactory};
+    
+    let temp_dir = create_test_directory();
+    symlink("..", temp_dir.path().join("subdir2/subdir3/up")).unwrap();
+    fs::hard_link(temp_dir.path().join("file1.txt"), temp_dir.path().join("subdir1/linked.txt")).unwrap();
+    
+    // Hard links share an identity, whatever their names; other files do not
+    let id = |path: &str| FileId::of(&temp_dir.path().join(path)).unwrap();
+    assert_eq!(id("file1.txt"), id("subdir1/linked.txt"));
+    assert_ne!(id("file1.txt"), id("file2.log"));
+    assert_eq!(id("subdir2/subdir3/up"), id("subdir2"));
+    
+    let config = AppConfig {
+        root_dir: temp_dir.path().to_path_buf(),
+        threads: Some(1),
+        follow_links: Some(true),
+        ..Default::default()
+    };
+    let (matches, report) = FinderFactory::create_standard_finder(&config)
+        .find(temp_dir.path())
+        .expect("Search operation failed");
+    assert_eq!(matches.len(), 7);
+    assert_eq!(report.skipped, vec![temp_dir.path().join("subdir2/subdir3/up")]);
+    
+    let config = FileSearchConfig {
+        path: Some(temp_dir.path().to_string_lossy().to_string()),
+        follow_symlinks: true,
+        ..Default::default()
+    };
+    let files = collect_files(&config, &TrackingObserver::new()).expect("Search operation failed");
+    assert_eq!(files.len(), 7);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unique_results() {
+    use std::os::unix::fs::symlink;
+    use oqab::core::{AppConfig, FinderFactory};
+    use oqab::core::config::Walker;
+    
+    let temp_dir = create_test_directory();
+    let root = temp_dir.path();
+    fs::hard_link(root.join("file1.txt"), root.join("subdir1/linked.txt")).unwrap();
+    symlink(root.join("subdir2"), root.join("alias")).unwrap();
+    
+    let search = |unique: bool, walker: Walker| {
+        let config = AppConfig {
+            root_dir: root.to_path_buf(),
+            extension: Some("txt".to_string()),
+            follow_links: Some(true),
+            unique: Some(unique),
+            walker: Some(walker),
+            ..Default::default()
+        };
+        let (matches, _) = FinderFactory::create_standard_finder(&config)
+            .find(root)
+            .expect("Search operation failed");
+        let mut paths: Vec<_> = matches.into_iter().map(|found| found.path).collect();
+        paths.sort();
+        paths
+    };
+    
+    // file1.txt, its hard link, file3.txt and config.txt, reached again through alias
+    assert_eq!(search(false, Walker::Builtin).len(), 5);
+    
+    // Each file is reported once, under its canonical path
+    for walker in [Walker::Builtin, Walker::Walkdir, Walker::Ignore] {
+        let paths = search(true, walker);
+        assert_eq!(paths.len(), 3, "{:?}", paths);
+        assert!(paths.iter().all(|path| path.is_absolute() && *path == path.canonicalize().unwrap()));
+        assert!(paths.iter().all(|path| !path.starts_with(root.canonicalize().unwrap().join("alias"))));
+    }
+    
+    // The default search drops the same paths
+    let config = FileSearchConfig {
+        path: Some(root.to_string_lossy().to_string()),
+        file_extension: Some("txt".to_string()),
+        follow_symlinks: true,
+        ..Default::default()
+    };
+    assert_eq!(collect_files(&config, &TrackingObserver::new()).expect("Search operation failed").len(), 5);
+    let config = FileSearchConfig { unique: true, ..config };
+    let mut paths = collect_files(&config, &TrackingObserver::new()).expect("Search operation failed");
+    paths.sort();
+    assert_eq!(paths, search(true, Walker::Builtin));
+}
+
+#[test]
+fn test_walkers_agree() {
+    use oqab::core::{AppConfig, FinderFactory};
+    use oqab::core::config::Walker;
+    
+    let temp_dir = create_test_directory();
+    fs::create_dir_all(temp_dir.path().join("node_modules/pkg")).unwrap();
+    create_test_file(&temp_dir.path().join("node_modules/pkg/index.txt"), 10);
+    create_test_file(&temp_dir.path().join(".hidden.txt"), 10);
+    create_test_file(&temp_dir.path().join("subdir2/subdir3/extra.txt"), 10);
+    
+    let search = |walker: Walker, depth: Option<usize>, max_per_dir: Option<usize>| {
+        let config = AppConfig {
+            root_dir: temp_dir.path().to_path_buf(),
+            extension: Some("txt".to_string()),
+            threads: Some(2),
+            walker: Some(walker),
+            depth,
+            max_per_dir,
+            ..Default::default()
+        };
+        let (matches, report) = FinderFactory::create_standard_finder(&config)
+            .find(temp_dir.path())
+            .expect("Search operation failed");
+        // Which file comes first in a directory depends on the walker, so only the directories are compared
+        let mut paths: Vec<_> = matches.into_iter()
+            .map(|found| found.path.strip_prefix(temp_dir.path()).unwrap().to_path_buf())
+            .map(|path| if max_per_dir.is_some() { path.parent().unwrap().to_path_buf() } else { path })
+            .collect();
+        paths.sort();
+        (paths, report.directories_scanned, report.skipped)
+    };
+    
+    for (depth, max_per_dir) in [(None, None), (Some(2), None), (None, Some(1))] {
+        let builtin = search(Walker::Builtin, depth, max_per_dir);
+        assert_eq!(search(Walker::Walkdir, depth, max_per_dir), builtin);
+        assert_eq!(search(Walker::Ignore, depth, max_per_dir), builtin);
+    }
+    let (paths, directories, skipped) = search(Walker::Ignore, None, None);
+    assert_eq!(paths.len(), 4);
+    assert_eq!(directories, 4);
+    assert_eq!(skipped, vec![temp_dir.path().join("node_modules")]);
+}
+
+#[test]
+fn test_fuzzy_query_ranks_filtered_candidates() {
+    use std::process::Command;
+    
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    for name in ["report.txt", "report.md", "rprt_2024.txt", "notes.txt"] {
+        create_test_file(&temp_dir.path().join(name), 10);
+    }
+    
+    let search = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .args(["-s", "--ext", "txt", "-p", temp_dir.path().to_str().unwrap()])
+            .args(args)
+            .output()
+            .expect("Failed to run oqab");
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once(" (match quality"))
+            .filter_map(|(path, _)| Path::new(path.trim()).file_name().map(|name| name.to_string_lossy().into_owned()))
+            .collect::<Vec<_>>()
+    };
+    
+    // The query ranks the extension's files without being a name filter itself
+    assert_eq!(search(&["--fuzzy-query", "rpt"]), vec!["rprt_2024.txt", "report.txt"]);
+    // The positional query is the fuzzy query with -z, and so is a legacy --name
+    assert_eq!(search(&["-z", "report"]), vec!["report.txt"]);
+    assert_eq!(search(&["-z", "-n", "report"]), vec!["report.txt"]);
+    
+    // --silent leaves nothing but the results, found or not
+    for query in ["report", "zzz"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .args(["-s", "-z", "-p", temp_dir.path().to_str().unwrap(), query])
+            .output()
+            .expect("Failed to run oqab");
+        assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
+
+#[test]
+fn test_select_prints_only_chosen_path() {
+    use std::process::{Command, Stdio};
+    
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    for name in ["report.txt", "report.md", "notes.txt"] {
+        create_test_file(&temp_dir.path().join(name), 10);
+    }
+    
+    let data_dir = TempDir::new().expect("Failed to create temp directory");
+    let select = |query: &str| {
+        Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .args(["--select", "--ext", "txt", "-p", temp_dir.path().to_str().unwrap(), query])
+            .env("XDG_DATA_HOME", data_dir.path())
+            .stdin(Stdio::null())
+            .output()
+            .expect("Failed to run oqab")
+    };
+    
+    // Without a terminal to choose on, the best match is the only output
+    let output = select("report");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), format!("{}\n", temp_dir.path().join("report.txt").display()));
+    
+    // The choice counts towards --sort frecency
+    #[cfg(target_os = "linux")]
+    {
+        use oqab::utils::frecency::FrecencyStore;
+        let store = FrecencyStore::open(data_dir.path().join("oqab").join("frecency.json"))
+            .expect("Failed to open frecency store");
+        assert!(store.score(&temp_dir.path().join("report.txt")) > 0.0);
+        assert_eq!(store.score(&temp_dir.path().join("notes.txt")), 0.0);
+    }
+    
+    let output = select("zzz");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_max_results_stops_search() {
+    use std::process::Command;
+    use oqab::core::{AppConfig, FinderFactory};
+    
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    for dir in 0..20 {
+        fs::create_dir(temp_dir.path().join(format!("dir{dir}"))).unwrap();
+        for file in 0..3 {
+            create_test_file(&temp_dir.path().join(format!("dir{dir}/file{file}.txt")), 10);
+        }
+    }
+    
+    for threads in [1, 4] {
+        let config = AppConfig {
+            root_dir: temp_dir.path().to_path_buf(),
+            threads: Some(threads),
+            max_results: Some(2),
+            ..Default::default()
+        };
+        let (matches, report) = FinderFactory::create_standard_finder(&config)
+            .find(temp_dir.path())
+            .expect("Search operation failed");
+        assert_eq!(matches.len(), 2);
+        // The walk is abandoned rather than finished and truncated
+        assert!(report.directories_scanned < 21, "read {} directories", report.directories_scanned);
+    }
+    
+    let count = |extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .args(["-s", "-e", "txt", "--max-results", "5", "-p", temp_dir.path().to_str().unwrap()])
+            .args(extra)
+            .output()
+            .expect("Failed to run oqab");
+        String::from_utf8_lossy(&output.stdout).lines().count()
+    };
+    assert_eq!(count(&[]), 5);
+    assert_eq!(count(&["--advanced", "--walker", "ignore", "-w", "3"]), 5);
+    assert_eq!(count(&["--grep", "."]), 5);
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "media")]
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use id3::TagLike;
+use tempfile::TempDir;
+use oqab::filters::media::{ExtractorRegistry, MediaMetadata, MetadataExtractor};
+use oqab::filters::{Filter, FilterResult, MediaFilter};
+
+// Extractor serving fixed metadata for `.fake` files, named after their camera
+struct FakeExtractor;
+
+impl MetadataExtractor for FakeExtractor {
+    fn extensions(&self) -> &'static [&'static str] {
+        &["fake"]
+    }
+    
+    fn extract(&self, path: &Path) -> Option<MediaMetadata> {
+        let model = path.file_stem()?.to_string_lossy().to_string();
+        Some(MediaMetadata {
+            taken: Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000)), // 2020-09-13
+            camera_model: Some(model),
+            artist: None,
+        })
+    }
+}
+
+fn fake_filter() -> MediaFilter {
+    let mut registry = ExtractorRegistry::new();
+    registry.register(FakeExtractor);
+    MediaFilter::with_registry(registry)
+}
+
+#[test]
+fn test_media_filter_criteria() {
+    let photo = Path::new("X-T4.fake");
+    let other = Path::new("EOS R5.fake");
+    let unknown = Path::new("X-T4.jpg");
+    let date = |secs: u64| UNIX_EPOCH + Duration::from_secs(secs);
+    
+    let filter = fake_filter().camera_model("x-t4");
+    assert_eq!(filter.filter(photo), FilterResult::Accept);
+    assert_eq!(filter.filter(other), FilterResult::Reject);
+    // No registered extractor reads the file, so it has no metadata to match
+    assert_eq!(filter.filter(unknown), FilterResult::Reject);
+    
+    assert_eq!(fake_filter().taken_before(date(1_577_836_800)).filter(photo), FilterResult::Reject);
+    assert_eq!(fake_filter().taken_after(date(1_577_836_800)).filter(photo), FilterResult::Accept);
+    // Photos have no artist
+    assert_eq!(fake_filter().artist("anyone").filter(photo), FilterResult::Reject);
+}
+
+#[test]
+fn test_media_filter_reads_id3() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let song = temp_dir.path().join("song.mp3");
+    fs::write(&song, []).unwrap();
+    let mut tag = id3::Tag::new();
+    tag.set_artist("Fairuz");
+    tag.set_date_recorded("1972-05-01".parse().unwrap());
+    tag.write_to_path(&song, id3::Version::Id3v24).expect("Failed to write ID3 tag");
+    let untagged = temp_dir.path().join("untagged.mp3");
+    fs::write(&untagged, [0xff, 0xfb, 0x90, 0x00]).unwrap();
+    
+    let metadata = ExtractorRegistry::with_defaults().extract(&song).expect("No metadata read");
+    assert_eq!(metadata.artist.as_deref(), Some("Fairuz"));
+    assert!(metadata.taken.unwrap() < SystemTime::UNIX_EPOCH + Duration::from_secs(100_000_000));
+    
+    assert_eq!(MediaFilter::new().artist("fair").filter(&song), FilterResult::Accept);
+    assert_eq!(MediaFilter::new().artist("fair").filter(&untagged), FilterResult::Reject);
+    
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-s", "-p", temp_dir.path().to_str().unwrap(), "--exif-date-before", "2000-01-01", "--format", "vimgrep"])
+        .output()
+        .expect("Failed to run oqab");
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.lines().count(), 1);
+    assert!(output.contains("song.mp3"));
+}
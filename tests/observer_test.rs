@@ -1,5 +1,11 @@
+use std::any::Any;
+use std::fs;
 use std::path::{Path, PathBuf};
-use oqab::core::observer::{SearchObserver, TrackingObserver, SilentObserver};
+use std::sync::{atomic::{AtomicUsize, Ordering}, Arc, Mutex};
+use std::time::Duration;
+use oqab::core::estimate::{count_directories, Probe, Progress, ScanTotals, TreeSizeSampler, SAMPLE_LEAVES};
+use oqab::core::observer::{Heartbeat, ProgressBarObserver, ProgressReporter, SearchEvent, SearchObserver, SearchStats, TrackingObserver, SilentObserver};
+use oqab::core::{FileFinderBuilder, ObserverRegistry, WorkerStats};
 
 #[test]
 fn test_tracking_observer() {
@@ -75,4 +81,240 @@ fn test_observer_trait_object() {
     let found_files = tracking_observer.get_found_files();
     assert_eq!(found_files.len(), 1);
     assert_eq!(found_files[0], PathBuf::from("/path/to/file.txt"));
-}
\ No newline at end of file
+}
+
+// Records the heartbeats of a search and how often it finished
+#[derive(Default)]
+struct PulseObserver {
+    heartbeats: Mutex<Vec<Heartbeat>>,
+    finished: AtomicUsize,
+}
+
+impl SearchObserver for PulseObserver {
+    fn file_found(&self, _file_path: &Path) {}
+    fn directory_processed(&self, _dir_path: &Path) {}
+    fn files_count(&self) -> usize { 0 }
+    fn directories_count(&self) -> usize { 0 }
+    fn heartbeat(&self, heartbeat: &Heartbeat) {
+        self.heartbeats.lock().unwrap().push(heartbeat.clone());
+    }
+    fn search_finished(&self) {
+        self.finished.fetch_add(1, Ordering::Relaxed);
+    }
+    fn as_any(&self) -> &dyn Any { self }
+}
+
+#[test]
+fn test_heartbeat_and_search_finished() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    fs::create_dir_all(temp_dir.path().join("a/b")).unwrap();
+    fs::write(temp_dir.path().join("a/b/file.txt"), "content").unwrap();
+    
+    let pulse = Arc::new(PulseObserver::default());
+    let registry = ObserverRegistry::new();
+    registry.register_arc(pulse.clone());
+    let finder = FileFinderBuilder::new()
+        .with_threads(1)
+        .with_observer_registry(registry)
+        .build();
+    finder.find(temp_dir.path()).expect("Search operation failed");
+    
+    // The walk is too quick for a beat to be due, but any that came must be well formed
+    assert_eq!(pulse.finished.load(Ordering::Relaxed), 1);
+    for heartbeat in pulse.heartbeats.lock().unwrap().iter() {
+        assert!(heartbeat.directory.starts_with(temp_dir.path()));
+        assert!(heartbeat.queue_depth <= 3);
+    }
+}
+
+#[test]
+fn test_progress_reporter_records_errors() {
+    let reporter = ProgressReporter::new();
+    reporter.path_error(Path::new("/locked"), &"Permission denied");
+    reporter.search_finished();
+    
+    assert_eq!(reporter.errors_count(), 1);
+    assert_eq!(reporter.path_errors()[0].path, PathBuf::from("/locked"));
+    assert_eq!(reporter.clone().errors_count(), 1);
+}
+
+#[test]
+fn test_progress_bar_observer_counts() {
+    let observer = ProgressBarObserver::new();
+    observer.directory_processed(Path::new("/src"));
+    observer.file_found(Path::new("/src/main.rs"));
+    observer.path_error(Path::new("/locked"), &"Permission denied");
+    // An estimate smaller than what was already read is not shown as over 100%
+    observer.heartbeat(&Heartbeat {
+        directory: PathBuf::from("/src"),
+        queue_depth: 0,
+        estimated_total: Some(0),
+    });
+    observer.directory_processed(Path::new("/src/cli"));
+    observer.search_finished();
+    
+    assert_eq!((observer.files_count(), observer.directories_count(), observer.errors_count()), (1, 2, 1));
+}
+
+#[test]
+fn test_tree_size_estimate() {
+    // In a tree where every directory has four subdirectories, three levels deep,
+    // each path to a leaf sees the same branching factors
+    let leaf = Probe::root().descend(4).descend(4).descend(4);
+    let mut sampler = TreeSizeSampler::default();
+    for _ in 1..SAMPLE_LEAVES {
+        sampler.leaf(leaf);
+    }
+    assert_eq!(sampler.estimate(), None);
+    sampler.leaf(leaf);
+    assert_eq!(sampler.estimate(), Some(1 + 4 + 16 + 64));
+}
+
+#[test]
+fn test_progress_from_counts() {
+    let progress = Progress::from_counts(25, 10, 100, Duration::from_secs(10)).unwrap();
+    assert_eq!(progress.fraction, 0.25);
+    assert_eq!(progress.remaining, Duration::from_secs(30));
+    
+    // A tree that outgrew its estimate is never reported as done
+    let progress = Progress::from_counts(150, 0, 100, Duration::from_secs(10)).unwrap();
+    assert_eq!(progress.fraction, 0.99);
+    assert_eq!(Progress::from_counts(0, 1, 100, Duration::ZERO), None);
+}
+
+#[test]
+fn test_scan_totals_round_trip() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    let location = temp_dir.path().join("scan-totals.json");
+    
+    let mut totals = ScanTotals::open(&location).unwrap();
+    assert_eq!(totals.get(temp_dir.path()), None);
+    totals.record(temp_dir.path(), 1234);
+    totals.save().unwrap();
+    
+    let reopened = ScanTotals::open(&location).unwrap();
+    assert_eq!(reopened.get(temp_dir.path()), Some(1234));
+}
+
+#[test]
+fn test_progress_reporter_uses_estimate() {
+    let reporter = ProgressReporter::new();
+    for _ in 0..10 {
+        reporter.directory_processed(Path::new("/data"));
+    }
+    let mut heartbeat = Heartbeat {
+        directory: PathBuf::from("/data/logs"),
+        queue_depth: 5,
+        estimated_total: None,
+    };
+    assert_eq!(reporter.progress(&heartbeat), None);
+    
+    heartbeat.estimated_total = Some(40);
+    assert_eq!(reporter.progress(&heartbeat).unwrap().fraction, 0.25);
+}
+
+#[test]
+fn test_prescan_total() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    fs::create_dir_all(temp_dir.path().join("src/cli")).unwrap();
+    fs::create_dir_all(temp_dir.path().join(".git/objects")).unwrap();
+    fs::write(temp_dir.path().join("src/main.rs"), "").unwrap();
+    
+    // The root counts, hidden directories only when the search would enter them
+    assert_eq!(count_directories(temp_dir.path(), false, false), 3);
+    assert_eq!(count_directories(temp_dir.path(), true, false), 5);
+    
+    // A counted total wins over the walk's own estimate
+    let reporter = ProgressReporter::new().with_total(20);
+    for _ in 0..10 {
+        reporter.directory_processed(Path::new("/data"));
+    }
+    let heartbeat = Heartbeat {
+        directory: PathBuf::from("/data/logs"),
+        queue_depth: 0,
+        estimated_total: Some(1000),
+    };
+    assert_eq!(reporter.progress(&heartbeat).unwrap().fraction, 0.5);
+}
+
+// Subscribes through the event bus alone, keeping the files and stats it was sent
+#[derive(Default)]
+struct EventLog {
+    files: Mutex<Vec<PathBuf>>,
+    completed: Mutex<Vec<SearchStats>>,
+    workers: Mutex<Vec<WorkerStats>>,
+}
+
+impl SearchObserver for EventLog {
+    fn file_found(&self, _file_path: &Path) {}
+    fn directory_processed(&self, _dir_path: &Path) {}
+    fn files_count(&self) -> usize { 0 }
+    fn directories_count(&self) -> usize { 0 }
+    fn as_any(&self) -> &dyn Any { self }
+    fn on_event(&self, event: &SearchEvent<'_>) {
+        match *event {
+            SearchEvent::FileFound(path) => self.files.lock().unwrap().push(path.to_path_buf()),
+            SearchEvent::Completed(stats) => self.completed.lock().unwrap().push(*stats),
+            SearchEvent::Workers(workers) => self.workers.lock().unwrap().extend_from_slice(workers),
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn test_event_bus_fans_out() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    fs::create_dir(temp_dir.path().join("sub")).unwrap();
+    fs::write(temp_dir.path().join("one.txt"), "1").unwrap();
+    fs::write(temp_dir.path().join("sub/two.txt"), "2").unwrap();
+    
+    let log = Arc::new(EventLog::default());
+    let tracker = Arc::new(TrackingObserver::new());
+    let registry = ObserverRegistry::new();
+    registry.register_arc(log.clone());
+    registry.register_arc(tracker.clone());
+    let finder = FileFinderBuilder::new()
+        .with_threads(1)
+        .with_observer_registry(registry)
+        .build();
+    finder.find(temp_dir.path()).expect("Search operation failed");
+    
+    // Both subscribers saw every file, and the totals count each event once
+    let mut files = log.files.lock().unwrap().clone();
+    files.sort();
+    assert_eq!(files, vec![temp_dir.path().join("one.txt"), temp_dir.path().join("sub/two.txt")]);
+    assert_eq!(tracker.files_count(), 2);
+    
+    let completed = log.completed.lock().unwrap();
+    assert_eq!(completed.len(), 1);
+    assert_eq!((completed[0].files, completed[0].directories, completed[0].errors), (2, 2, 0));
+}
+
+#[test]
+fn test_worker_telemetry() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    fs::create_dir(temp_dir.path().join("sub")).unwrap();
+    fs::write(temp_dir.path().join("sub/one.txt"), "1").unwrap();
+    
+    let log = Arc::new(EventLog::default());
+    let registry = ObserverRegistry::new();
+    registry.register_arc(log.clone());
+    let finder = FileFinderBuilder::new()
+        .with_threads(3)
+        .with_observer_registry(registry)
+        .build();
+    let (_, report) = finder.find(temp_dir.path()).expect("Search operation failed");
+    
+    // Every worker reports, and the root submitted to the pool was processed by one of them
+    let mut ids: Vec<usize> = report.workers.iter().map(|worker| worker.id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![0, 1, 2]);
+    assert_eq!(report.workers.iter().map(|worker| worker.directories).sum::<usize>(), 1);
+    assert!(report.workers.iter().any(|worker| worker.busy > Duration::ZERO));
+    assert_eq!(*log.workers.lock().unwrap(), report.workers);
+    
+    // Single-threaded searches have no pool to report on
+    let (_, report) = FileFinderBuilder::new().with_threads(1).build()
+        .find(temp_dir.path()).expect("Search operation failed");
+    assert!(report.workers.is_empty());
+}
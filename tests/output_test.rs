@@ -0,0 +1,509 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use std::process::Command;
+use oqab::core::config::SortOrder;
+use oqab::output::{group_results, long_entries, long_lines, natural_cmp, render_report, render_tree, sort_results, GroupBy, ReportFormat, ReportSummary, SqliteExporter, Template};
+use oqab::output::group::format_size;
+use oqab::output::hyperlink::file_url;
+use oqab::utils::frecency::FrecencyStore;
+
+#[test]
+fn test_sqlite_export() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let file_path = temp_dir.path().join("main.rs");
+    fs::write(&file_path, "fn main() {}\n// TODO: more\n").expect("Failed to write test file");
+    let database = temp_dir.path().join("results.db");
+    
+    let exporter = SqliteExporter::create(&database).expect("Failed to create database");
+    let file_id = exporter.add_file(&file_path).expect("Failed to export file");
+    exporter.add_match(file_id, 2, "// TODO: more").expect("Failed to export match");
+    
+    // Adding the same file twice reuses its row
+    assert_eq!(exporter.add_file(&file_path).expect("Failed to export file"), file_id);
+    exporter.finish().expect("Failed to commit export");
+    
+    let conn = rusqlite::Connection::open(&database).expect("Failed to open database");
+    let (extension, size): (String, i64) = conn
+        .query_row("SELECT extension, size FROM files WHERE id = ?1", [file_id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .expect("Failed to query files");
+    assert_eq!(extension, "rs");
+    assert_eq!(size, 27);
+    
+    let line_number: i64 = conn
+        .query_row("SELECT line_number FROM matches WHERE file_id = ?1", [file_id], |row| row.get(0))
+        .expect("Failed to query matches");
+    assert_eq!(line_number, 2);
+    
+    // Exporting again replaces the previous results
+    SqliteExporter::create(&database).expect("Failed to recreate database")
+        .finish().expect("Failed to commit export");
+    let count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM files", [], |row| row.get(0))
+        .expect("Failed to count files");
+    assert_eq!(count, 0);
+    
+    // Databases that oqab did not write are left alone
+    let other = temp_dir.path().join("analysis.db");
+    let conn = rusqlite::Connection::open(&other).expect("Failed to open database");
+    conn.execute_batch("CREATE TABLE files (name TEXT); INSERT INTO files VALUES ('kept');")
+        .expect("Failed to create unrelated table");
+    assert!(SqliteExporter::create(&other).is_err());
+    let name: String = conn
+        .query_row("SELECT name FROM files", [], |row| row.get(0))
+        .expect("Unrelated table was dropped");
+    assert_eq!(name, "kept");
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_parquet_export() {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+    
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let with_ext = temp_dir.path().join("data.csv");
+    let without_ext = temp_dir.path().join("Makefile");
+    fs::write(&with_ext, "a,b\n").expect("Failed to write test file");
+    fs::write(&without_ext, "all:\n").expect("Failed to write test file");
+    let output = temp_dir.path().join("results.parquet");
+    
+    oqab::output::write_parquet(&output, &[with_ext.clone(), without_ext])
+        .expect("Failed to write Parquet file");
+    
+    let reader = SerializedFileReader::new(fs::File::open(&output).expect("Failed to open output"))
+        .expect("Failed to read Parquet file");
+    assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+    
+    let rows: Vec<_> = reader.get_row_iter(None).expect("Failed to iterate rows")
+        .map(|row| row.expect("Failed to read row"))
+        .collect();
+    assert_eq!(rows[0].get_string(0).unwrap(), &with_ext.to_string_lossy().to_string());
+    assert_eq!(rows[0].get_string(1).unwrap(), "csv");
+    assert_eq!(rows[0].get_long(2).unwrap(), 4);
+    
+    // Files without an extension store a null
+    assert!(rows[1].get_string(1).is_err());
+}
+
+#[test]
+fn test_frecency_store() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let often = temp_dir.path().join("often.rs");
+    let once = temp_dir.path().join("once.rs");
+    fs::write(&often, "").unwrap();
+    fs::write(&once, "").unwrap();
+    let location = temp_dir.path().join("data").join("frecency.json");
+    
+    let mut store = FrecencyStore::open(&location).expect("Failed to open store");
+    store.record(&often);
+    store.record(&often);
+    store.record(&once);
+    store.save().expect("Failed to save store");
+    
+    // Scores survive a reload and rank more frequent selections higher
+    let store = FrecencyStore::open(&location).expect("Failed to reopen store");
+    assert!(store.score(&often) > store.score(&once));
+    assert!(store.score(&once) > 0.0);
+    assert_eq!(store.score(&temp_dir.path().join("never.rs")), 0.0);
+}
+
+#[test]
+fn test_sort_frecency() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path().join("repo");
+    fs::create_dir(&root).unwrap();
+    for name in ["a.rs", "b.rs", "c.rs"] {
+        fs::write(root.join(name), "").unwrap();
+    }
+    let data = temp_dir.path().join("data");
+    let oqab = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .args(args)
+            .env("XDG_DATA_HOME", &data)
+            .output()
+            .expect("Failed to run oqab");
+        assert!(output.status.success() || output.status.code() == Some(1));
+        String::from_utf8(output.stdout).expect("Output was not UTF-8")
+    };
+    
+    let path = root.to_str().unwrap();
+    oqab(&["record", root.join("c.rs").to_str().unwrap()]);
+    let output = oqab(&["-s", "-p", path, "-e", "rs", "--sort", "frecency", "--format", "vimgrep"]);
+    let files: Vec<&str> = output.lines().collect();
+    assert_eq!(files.len(), 3);
+    assert!(files[0].contains("c.rs"));
+    
+    let output = oqab(&["-s", "-p", path, "-e", "rs", "--sort", "path", "--format", "vimgrep"]);
+    let names: Vec<&str> = output.lines()
+        .map(|line| line.rsplit('/').next().unwrap())
+        .collect();
+    assert_eq!(names, ["a.rs:1:1:", "b.rs:1:1:", "c.rs:1:1:"]);
+}
+
+#[test]
+fn test_sort_natural() {
+    let mut files: Vec<PathBuf> = ["file10.txt", "File2.txt", "file2.txt", "file1.txt", "file02.txt", "a/b9", "a/b10"]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    sort_results(&mut files, SortOrder::Natural).expect("Failed to sort");
+    let names: Vec<&str> = files.iter().map(|file| file.to_str().unwrap()).collect();
+    assert_eq!(names, ["a/b9", "a/b10", "file1.txt", "File2.txt", "file02.txt", "file2.txt", "file10.txt"]);
+    
+    assert_eq!(natural_cmp(Path::new("v1.9"), Path::new("v1.10")), Ordering::Less);
+    assert_eq!(natural_cmp(Path::new("x99999999999999999999999"), Path::new("x100000000000000000000000")), Ordering::Less);
+}
+
+#[test]
+fn test_theme() {
+    use console::Style;
+    use oqab::core::FileSearchConfig;
+    use oqab::output::{Theme, ThemePreset};
+    
+    let theme = Theme::default();
+    assert_eq!(theme.heading(), Style::new().cyan().bold());
+    assert_eq!(Theme::preset(ThemePreset::NoBold).heading(), Style::new().cyan().underlined());
+    assert_eq!(Theme::preset(ThemePreset::HighContrast).matched(), Style::new().black().on_yellow());
+    
+    // Colors from a configuration file override the preset one by one
+    let config: FileSearchConfig = serde_json::from_str(
+        r#"{"theme": {"preset": "no-bold", "line_number": "magenta.bold"}}"#
+    ).expect("Failed to parse configuration");
+    assert_eq!(config.theme.line_number(), Style::new().magenta().bold());
+    assert_eq!(config.theme.matched(), Style::new().red().underlined());
+    assert!(config.theme.validate().is_ok());
+    
+    let theme = Theme { path: Some("blue.sparkly".to_string()), ..Theme::default() };
+    assert!(theme.validate().is_err());
+}
+
+#[test]
+fn test_group_by() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::create_dir(temp_dir.path().join("src")).expect("Failed to create directory");
+    fs::write(temp_dir.path().join("src/main.rs"), "fn main() {}\n").expect("Failed to write test file");
+    fs::write(temp_dir.path().join("src/lib.RS"), "").expect("Failed to write test file");
+    fs::write(temp_dir.path().join("notes.txt"), vec![b'x'; 2048]).expect("Failed to write test file");
+    fs::write(temp_dir.path().join("Makefile"), "all:\n").expect("Failed to write test file");
+    
+    let files = vec![
+        temp_dir.path().join("src/main.rs"),
+        temp_dir.path().join("src/lib.RS"),
+        temp_dir.path().join("notes.txt"),
+        temp_dir.path().join("Makefile"),
+    ];
+    let groups = group_results(&files, GroupBy::Ext, false);
+    let summary: Vec<(&str, usize, u64)> = groups.iter()
+        .map(|group| (group.key.as_str(), group.count, group.size))
+        .collect();
+    assert_eq!(summary, vec![("txt", 1, 2048), ("rs", 2, 13), ("", 1, 5)]);
+    
+    let groups = group_results(&files, GroupBy::Dir, false);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[1].key, temp_dir.path().join("src").display().to_string());
+    assert_eq!(groups[1].count, 2);
+    
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(1536), "1.5 KiB");
+    assert_eq!(format_size(3 * 1024 * 1024), "3.0 MiB");
+    
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-s", "-n", ".", "--group-by", "ext", "--format", "json", "-p"])
+        .arg(temp_dir.path())
+        .output()
+        .expect("Failed to run oqab");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first: serde_json::Value = serde_json::from_str(stdout.lines().next().expect("No output"))
+        .expect("Invalid JSON");
+    assert_eq!(first["type"], "group");
+    assert_eq!(first["key"], "txt");
+    assert_eq!(first["count"], 1);
+}
+
+#[test]
+fn test_page_size() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        fs::write(temp_dir.path().join(name), "").unwrap();
+    }
+    let oqab = |page_size: &str| {
+        Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .args(["-s", "-e", "txt", "--page-size", page_size, "-p"])
+            .arg(temp_dir.path())
+            .output()
+            .expect("Failed to run oqab")
+    };
+    
+    // Output that is not a terminal is never paged
+    let output = oqab("1");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 3);
+    
+    assert_eq!(oqab("0").status.code(), Some(2));
+}
+
+#[test]
+fn test_pager_needs_terminal() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    for index in 0..200 {
+        fs::write(temp_dir.path().join(format!("{}.txt", index)), "").unwrap();
+    }
+    
+    // However long, output that is not a terminal is never piped into the pager
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-s", "-e", "txt", "-p"])
+        .arg(temp_dir.path())
+        .env("PAGER", "sed s/^/paged:/")
+        .output()
+        .expect("Failed to run oqab");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 200);
+    assert!(!stdout.contains("paged:"));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_pager_output_precedes_summary() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(temp_dir.path().join("a.txt"), "needle\n").unwrap();
+    
+    // `script` runs oqab on a pseudo-terminal, where short results are held back for the pager
+    let on_terminal = |args: &str| {
+        let command = format!("stty rows 40 cols 120; {} -p {} {}", env!("CARGO_BIN_EXE_oqab"), temp_dir.path().display(), args);
+        Command::new("script")
+            .args(["-qec", &command, "/dev/null"])
+            .env("PAGER", "less")
+            .output()
+            .ok()
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let Some(output) = on_terminal(".txt") else {
+        return;
+    };
+    let result = output.find("a.txt").expect("No result printed");
+    assert!(result < output.find("Performance:").expect("No summary printed"));
+    
+    let output = on_terminal("--grep needle").expect("Failed to run oqab");
+    let result = output.find("a.txt").expect("No result printed");
+    assert!(result < output.find("Performance:").expect("No summary printed"));
+}
+
+#[test]
+fn test_print0() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("two words.txt"), "TODO\n").unwrap();
+    fs::write(root.join("two\nlines.txt"), "TODO\n").unwrap();
+    let oqab = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .args(["-s", "-0", "-p"])
+            .arg(root)
+            .args(args)
+            .output()
+            .expect("Failed to run oqab");
+        let mut paths: Vec<String> = String::from_utf8_lossy(&output.stdout).split('\0').map(str::to_string).collect();
+        paths.sort();
+        paths
+    };
+    
+    // Bare paths that survive spaces and newlines, each ending in NUL
+    let expected = vec![
+        String::new(),
+        root.join("two\nlines.txt").display().to_string(),
+        root.join("two words.txt").display().to_string(),
+    ];
+    assert_eq!(oqab(&["-e", "txt"]), expected);
+    assert_eq!(oqab(&["--grep", "TODO", "--files-with-matches"]), expected);
+}
+
+#[test]
+fn test_printf_template() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("notes.md"), "intro\nTODO: write\n").unwrap();
+    let file = root.join("notes.md");
+    
+    let template = Template::parse("%f %e %s 100%%\t%n:%l\n").unwrap();
+    assert_eq!(template.render(&file, None), "notes.md md 18 100%\t:\n");
+    assert_eq!(template.render(&file, Some((2, "TODO: write"))), "notes.md md 18 100%\t2:TODO: write\n");
+    assert!(template.uses_lines());
+    assert!(Template::parse("%q").is_err());
+    assert!(Template::parse("50%").is_err());
+    
+    let oqab = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+            .args(["-s", "-p"])
+            .arg(root)
+            .args(args)
+            .output()
+            .expect("Failed to run oqab");
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    
+    assert_eq!(oqab(&["-e", "md", "--printf", "%h|%f\\0"]), format!("{}|notes.md\0", root.display()));
+    assert_eq!(oqab(&["--grep", "TODO", "--printf", "%f:%n: %l\\n"]), "notes.md:2: TODO: write\n");
+    assert_eq!(oqab(&["--grep", "TODO", "--printf", "%f\\n"]), "notes.md\n");
+}
+
+#[test]
+fn test_long_listing() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let small = temp_dir.path().join("small.txt");
+    let large = temp_dir.path().join("large.txt");
+    fs::write(&small, "x").unwrap();
+    fs::write(&large, "x".repeat(1500)).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&small, fs::Permissions::from_mode(0o640)).unwrap();
+    }
+    
+    let entries = long_entries(&[small.clone(), large.clone()]);
+    assert_eq!((entries[0].size, entries[1].size), (1, 1500));
+    #[cfg(unix)]
+    assert_eq!(entries[0].mode, "-rw-r-----");
+    
+    // Sizes are right-aligned so that the paths line up
+    let lines = long_lines(&entries, &console::Style::new());
+    let path_column = |line: &str, path: &Path| line.find(&path.display().to_string()).unwrap();
+    assert_eq!(path_column(&lines[0], &small), path_column(&lines[1], &large));
+    assert!(lines[0].contains("    1 "));
+}
+
+#[test]
+fn test_render_tree() {
+    let root = Path::new("/project");
+    let files = vec![
+        root.join("src/main.rs"),
+        root.join("README.md"),
+        root.join("src/cli/args.rs"),
+    ];
+    
+    // Directories and files are sorted by name, with only the branches leading to results
+    let lines = render_tree(root, &files, &console::Style::new(), &console::Style::new());
+    assert_eq!(lines, vec![
+        "/project",
+        "├── README.md",
+        "└── src",
+        "    ├── cli",
+        "    │   └── args.rs",
+        "    └── main.rs",
+    ]);
+}
+
+#[test]
+fn test_tree_without_path() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::create_dir(temp_dir.path().join("sub")).unwrap();
+    fs::write(temp_dir.path().join("a.txt"), "").unwrap();
+    fs::write(temp_dir.path().join("sub/b.txt"), "").unwrap();
+    
+    // Results are absolute, so the tree is drawn from the resolved current directory
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-s", "--tree", "-e", "txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to run oqab");
+    let root = temp_dir.path().canonicalize().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), format!("{}\n├── a.txt\n└── sub\n    └── b.txt\n", root.display()));
+}
+
+#[test]
+fn test_report_without_path() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(temp_dir.path().join("a.txt"), "").unwrap();
+    
+    // The report is titled after the resolved current directory rather than `.`
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-s", "--report", "md", "-e", "txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to run oqab");
+    let root = temp_dir.path().canonicalize().unwrap();
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with(&format!("# Search report for `{}`\n", root.display())));
+}
+
+#[test]
+fn test_size_report() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("logs/old")).unwrap();
+    fs::write(root.join("logs/old/a.log"), "x".repeat(300)).unwrap();
+    fs::write(root.join("logs/b.log"), "x".repeat(200)).unwrap();
+    fs::write(root.join("c.log"), "x".repeat(100)).unwrap();
+    fs::write(root.join("ignored.txt"), "x".repeat(1000)).unwrap();
+    
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-s", "-e", "log", "--du=2", "--format", "json", "-p"])
+        .arg(root)
+        .output()
+        .expect("Failed to run oqab");
+    let entries: Vec<(String, PathBuf, u64, u64)> = String::from_utf8_lossy(&output.stdout).lines()
+        .map(|line| {
+            let entry: serde_json::Value = serde_json::from_str(line).unwrap();
+            (entry["type"].as_str().unwrap().to_string(), PathBuf::from(entry["path"].as_str().unwrap()),
+             entry["size"].as_u64().unwrap(), entry["count"].as_u64().unwrap())
+        })
+        .collect();
+    
+    // Directory totals cover their whole subtree, and only the two largest of each are listed
+    assert_eq!(entries, vec![
+        ("dir".to_string(), root.to_path_buf(), 600, 3),
+        ("dir".to_string(), root.join("logs"), 500, 2),
+        ("file".to_string(), root.join("logs/old/a.log"), 300, 1),
+        ("file".to_string(), root.join("logs/b.log"), 200, 1),
+    ]);
+}
+
+#[test]
+fn test_render_report() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("a|b.md"), "x".repeat(2048)).unwrap();
+    fs::write(root.join("<notes>.txt"), "hi").unwrap();
+    let files = vec![root.join("a|b.md"), root.join("<notes>.txt")];
+    let summary = ReportSummary {
+        root: root.to_path_buf(),
+        directories_scanned: 1,
+        errors: 0,
+        elapsed: std::time::Duration::from_millis(1500),
+    };
+    
+    let markdown = render_report(ReportFormat::Markdown, &summary, &files, false);
+    assert!(markdown.contains("| Files matched | 2 |"));
+    assert!(markdown.contains("| Time taken | 1.50 s |"));
+    assert!(markdown.contains("| md | 1 | 2.0 KiB |"));
+    // Pipes in paths would otherwise split the cell
+    assert!(markdown.contains("a\\|b.md | 2.0 KiB |"));
+    
+    let html = render_report(ReportFormat::Html, &summary, &files, false);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("&lt;notes&gt;.txt</td><td class=\"num\">2 B</td>"));
+    assert!(!html.contains("<notes>"));
+}
+
+#[test]
+fn test_hyperlinks() {
+    // Paths are made absolute, with anything outside the unreserved characters escaped
+    assert_eq!(file_url(Path::new("/home/me/my notes#1.txt")).as_deref(), Some("file:///home/me/my%20notes%231.txt"));
+    let relative = file_url(Path::new("src/main.rs")).unwrap();
+    assert!(relative.starts_with("file:///") && relative.ends_with("/src/main.rs"));
+    
+    // Piped output never carries the escape sequences, even when links are asked for
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(temp_dir.path().join("main.rs"), "").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-s", "-e", "rs", "--hyperlinks", "--color", "always", "-p"])
+        .arg(temp_dir.path())
+        .output()
+        .expect("Failed to run oqab");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("main.rs"));
+    assert!(!stdout.contains("\x1b]8;;"));
+}
@@ -0,0 +1,110 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+use oqab::utils::schedule::{parse_interval, ReportSink, Schedule};
+
+#[test]
+fn test_parse_interval() {
+    assert_eq!(parse_interval("45").unwrap(), Duration::from_secs(45));
+    assert_eq!(parse_interval("15m").unwrap(), Duration::from_secs(15 * 60));
+    assert_eq!(parse_interval("6h").unwrap(), Duration::from_secs(6 * 3600));
+    assert_eq!(parse_interval("90d").unwrap(), Duration::from_secs(90 * 86400));
+    assert_eq!(parse_interval("2w").unwrap(), Duration::from_secs(14 * 86400));
+    assert!(parse_interval("soon").is_err());
+    assert!(parse_interval("3y").is_err());
+}
+
+#[test]
+fn test_scheduled_job_reports_to_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let data = temp_dir.path().join("data");
+    fs::create_dir(&data).unwrap();
+    fs::write(data.join("old.log"), "x").unwrap();
+    fs::write(data.join("notes.txt"), "x").unwrap();
+    let report_file = temp_dir.path().join("reports").join("logs.jsonl");
+    let schedule_file = temp_dir.path().join("schedule.json");
+    fs::write(&schedule_file, serde_json::json!({
+        "jobs": [{
+            "name": "logs",
+            "interval": "1h",
+            "search": {"path": data, "file_extension": "log"},
+            "output": {"file": report_file},
+        }]
+    }).to_string()).unwrap();
+    
+    let schedule = Schedule::load_from_file(&schedule_file).expect("Failed to load schedule");
+    assert_eq!(schedule.jobs[0].interval().unwrap(), Duration::from_secs(3600));
+    
+    // A job without a pause between runs is refused up front
+    for interval in ["0", "0s"] {
+        let mut job = schedule.jobs[0].clone();
+        job.interval = interval.to_string();
+        assert!(job.interval().is_err());
+    }
+    
+    // Without --daemon every job runs once and the report is appended to its file
+    let status = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["schedule", schedule_file.to_str().unwrap()])
+        .status()
+        .expect("Failed to run oqab");
+    assert!(status.success());
+    
+    let contents = fs::read_to_string(&report_file).expect("Report was not written");
+    let report: serde_json::Value = serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+    assert_eq!(report["job"], "logs");
+    assert_eq!(report["count"], 1);
+    assert!(report["files"][0]["path"].as_str().unwrap().ends_with("old.log"));
+    
+    // Files modified just now are younger than any minimum age
+    let mut job = schedule.jobs[0].clone();
+    job.min_age = Some("1d".to_string());
+    assert_eq!(job.run().unwrap().count, 0);
+}
+
+#[test]
+fn test_scheduled_job_reports_to_webhook() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    fs::write(temp_dir.path().join("a.log"), "x").unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind listener");
+    let address = listener.local_addr().unwrap();
+    
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0; 4096];
+        // Read until the JSON body is complete
+        while !request.ends_with(b"}") {
+            let read = stream.read(&mut buffer).unwrap();
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&buffer[..read]);
+        }
+        stream.write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n").unwrap();
+        String::from_utf8(request).unwrap()
+    });
+    
+    let schedule_file = temp_dir.path().join("schedule.json");
+    fs::write(&schedule_file, serde_json::json!({
+        "jobs": [{
+            "name": "hook",
+            "interval": "5m",
+            "search": {"path": temp_dir.path(), "file_extension": "log"},
+            "output": {"webhook": format!("http://{}/reports", address)},
+        }]
+    }).to_string()).unwrap();
+    
+    let schedule = Schedule::load_from_file(&schedule_file).unwrap();
+    let job = &schedule.jobs[0];
+    assert_eq!(job.output, Some(ReportSink::Webhook(format!("http://{}/reports", address))));
+    job.output.as_ref().unwrap().deliver(&job.run().unwrap()).expect("Failed to deliver report");
+    
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /reports HTTP/1.1\r\n"));
+    assert!(request.contains("\"job\":\"hook\""));
+    assert!(request.contains("\"count\":1"));
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "scripting")]
+
+use std::fs;
+use std::process::Command;
+use std::sync::Arc;
+use tempfile::TempDir;
+use oqab::filters::{Filter, FilterResult, Script, ScriptFilter};
+
+#[test]
+fn test_script_filter() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let script = temp_dir.path().join("rules.rhai");
+    fs::write(&script, r#"
+        fn filter(path) {
+            extension(path) == "rs" && !file_name(path).starts_with("test_")
+        }
+    "#).unwrap();
+    let main = temp_dir.path().join("main.rs");
+    let test = temp_dir.path().join("test_main.rs");
+    let notes = temp_dir.path().join("notes.txt");
+    for file in [&main, &test, &notes] {
+        fs::write(file, "").unwrap();
+    }
+    
+    let script = Script::load(&script).expect("Failed to load script");
+    assert!(script.defines("filter", 1));
+    assert!(!script.defines("on_match", 3));
+    
+    let filter = ScriptFilter::new(Arc::new(script));
+    assert_eq!(filter.filter(&main), FilterResult::Accept);
+    assert_eq!(filter.filter(&test), FilterResult::Reject);
+    assert_eq!(filter.filter(&notes), FilterResult::Reject);
+    // Directories stay open for traversal
+    assert_eq!(filter.filter(temp_dir.path()), FilterResult::Accept);
+}
+
+#[test]
+fn test_script_rejects_invalid_source() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let script = temp_dir.path().join("broken.rhai");
+    fs::write(&script, "fn filter(path) {").unwrap();
+    
+    assert!(Script::load(&script).is_err());
+}
+
+#[test]
+fn test_grep_script_actions() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path().join("repo");
+    fs::create_dir(&root).unwrap();
+    fs::write(root.join("a.txt"), "needle one\nhay\nneedle two\n").unwrap();
+    fs::write(root.join("skip.txt"), "needle three\n").unwrap();
+    let script = temp_dir.path().join("rules.rhai");
+    fs::write(&script, r#"
+        fn filter(path) { !file_name(path).starts_with("skip") }
+        fn on_match(path, line_number, line) { print(`action ${line_number}`); }
+    "#).unwrap();
+    
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-s", "-p", root.to_str().unwrap(), "--grep", "needle", "--files-with-matches",
+            "--script", script.to_str().unwrap()])
+        .output()
+        .expect("Failed to run oqab");
+    let output = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    
+    assert_eq!(lines[..2], ["action 1", "action 3"]);
+    assert_eq!(lines.len(), 3);
+    assert!(lines[2].ends_with("a.txt"));
+}
@@ -0,0 +1,55 @@
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+use oqab::utils::similarity::Fingerprint;
+
+// Deterministic prose-like text made of numbered sentences
+fn document(sentences: std::ops::Range<usize>) -> String {
+    sentences
+        .map(|n| format!("sentence {} talks about topic {} and detail {}.", n, n * 7 % 13, n * 3 % 11))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_fingerprint_similarity() {
+    let original = document(0..200);
+    let edited = original.replace("sentence 150 ", "line 150 ");
+    let reformatted = original.replace('\n', "\n\n    ");
+    let unrelated = document(1000..1200);
+    
+    let original = Fingerprint::from_content(original.as_bytes()).unwrap();
+    let edited = Fingerprint::from_content(edited.as_bytes()).unwrap();
+    let reformatted = Fingerprint::from_content(reformatted.as_bytes()).unwrap();
+    let unrelated = Fingerprint::from_content(unrelated.as_bytes()).unwrap();
+    
+    assert_eq!(original.similarity(&original), 1.0);
+    // Whitespace and layout do not change the shingles
+    assert_eq!(original.similarity(&reformatted), 1.0);
+    assert!(original.similarity(&edited) > 0.9);
+    assert!(original.similarity(&unrelated) < 0.2);
+    
+    assert!(Fingerprint::from_content(b"\0\x01binary").is_none());
+    assert!(Fingerprint::from_content(b"  \n ").is_none());
+}
+
+#[test]
+fn test_similar_command() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    fs::write(root.join("original.md"), document(0..200)).unwrap();
+    fs::write(root.join("copy.md"), document(0..200).replace("sentence 10 ", "line 10 ")).unwrap();
+    fs::write(root.join("other.md"), document(500..700)).unwrap();
+    
+    let output = Command::new(env!("CARGO_BIN_EXE_oqab"))
+        .args(["-p", root.to_str().unwrap(), "similar", root.join("original.md").to_str().unwrap()])
+        .output()
+        .expect("Failed to run oqab");
+    assert_eq!(output.status.code(), Some(0));
+    
+    // The reference itself is not reported
+    let output = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].ends_with("copy.md"));
+}
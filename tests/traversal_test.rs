@@ -0,0 +1,146 @@
+use std::path::Path;
+use oqab::core::traversal::{normalize_separators, RegexTraversalStrategy, TraversalStrategy};
+use oqab::core::FinderFactory;
+use oqab::core::config::FileSearchConfig;
+
+#[test]
+fn test_regex_traversal_forward_slash_patterns() {
+    let strategy = RegexTraversalStrategy::new(Some(r"src/.*\.rs$"), Some(r"/target/"))
+        .expect("Failed to compile traversal patterns");
+    
+    let source_file = Path::new("project").join("src").join("core").join("main.rs");
+    let build_file = Path::new("project").join("target").join("src").join("main.rs");
+    let other_file = Path::new("project").join("docs").join("guide.md");
+    
+    // Forward-slash patterns match regardless of the native separator
+    assert!(strategy.should_process_file(&source_file));
+    assert!(!strategy.should_process_file(&build_file));
+    assert!(!strategy.should_process_file(&other_file));
+}
+
+#[test]
+fn test_normalize_separators() {
+    let path = Path::new("a").join("b").join("c.txt");
+    assert_eq!(normalize_separators(&path), "a/b/c.txt");
+}
+
+// Leaves out the placeholders cloud drives keep for files that are not downloaded
+struct SkipPlaceholders;
+
+impl TraversalStrategy for SkipPlaceholders {
+    fn should_process_directory(&self, _path: &Path) -> bool {
+        true
+    }
+    
+    fn should_process_file(&self, path: &Path) -> bool {
+        path.extension().is_none_or(|extension| extension != "icloud")
+    }
+}
+
+#[test]
+fn test_additional_traversal_strategy() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    std::fs::write(root.join("report.txt"), "text").unwrap();
+    std::fs::write(root.join("photo.jpg.icloud"), "placeholder").unwrap();
+    std::fs::write(root.join(".hidden.txt"), "text").unwrap();
+    
+    let mut config = FileSearchConfig::new();
+    config.path = Some(root.to_string_lossy().to_string());
+    let finder = FinderFactory::builder_from_search_config(&config)
+        .expect("Failed to configure finder")
+        .with_additional_traversal_strategy(Box::new(SkipPlaceholders))
+        .build();
+    let (found, _) = finder.find(root).expect("Search operation failed");
+    
+    // The built-in strategy still skips hidden files alongside the added one
+    let names: Vec<_> = found.iter().map(|found| found.path.file_name().unwrap().to_owned()).collect();
+    assert_eq!(names, ["report.txt"]);
+}
+
+#[test]
+fn test_git_aware_traversal() {
+    use oqab::core::traversal::GitAwareTraversalStrategy;
+    use oqab::core::SilentObserver;
+    use oqab::utils::standard_search;
+    
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path();
+    std::fs::create_dir_all(root.join(".git").join("info")).unwrap();
+    std::fs::create_dir_all(root.join("build")).unwrap();
+    std::fs::create_dir_all(root.join("src").join("generated")).unwrap();
+    std::fs::write(root.join(".gitignore"), "build/\n*.log\n!keep.log\n").unwrap();
+    std::fs::write(root.join(".git").join("info").join("exclude"), "notes.txt\n").unwrap();
+    std::fs::write(root.join("src").join(".gitignore"), "generated/\n").unwrap();
+    for file in ["build/out.txt", "debug.log", "keep.log", "notes.txt", "main.txt", "src/lib.txt", "src/generated/code.txt"] {
+        std::fs::write(root.join(file), "text").unwrap();
+    }
+    
+    let strategy = GitAwareTraversalStrategy::new(root);
+    assert!(!strategy.should_process_directory(&root.join("build")));
+    assert!(!strategy.should_process_directory(&root.join("src").join("generated")));
+    assert!(!strategy.should_process_file(&root.join("debug.log")));
+    assert!(!strategy.should_process_file(&root.join("notes.txt")));
+    assert!(strategy.should_process_file(&root.join("keep.log")));
+    assert!(strategy.should_process_file(&root.join("src").join("lib.txt")));
+    
+    // Outside a repository nothing is ignored
+    let outside = tempfile::tempdir().expect("Failed to create temp directory");
+    std::fs::write(outside.path().join(".gitignore"), "*.log\n").unwrap();
+    assert!(GitAwareTraversalStrategy::new(outside.path()).should_process_file(&outside.path().join("debug.log")));
+    
+    // Finders and the standard search skip the same files
+    let mut config = FileSearchConfig::new();
+    config.path = Some(root.to_string_lossy().to_string());
+    config.git_ignore = true;
+    let finder = FinderFactory::from_search_config(&config).expect("Failed to configure finder");
+    let (found, _) = finder.find(root).expect("Search operation failed");
+    let mut found: Vec<_> = found.into_iter().map(|found| found.path).collect();
+    found.sort();
+    let expected = [root.join("keep.log"), root.join("main.txt"), root.join("src").join("lib.txt")];
+    assert_eq!(found, expected);
+    
+    let mut found = standard_search::search_directory(root, &config, &SilentObserver::new()).expect("Search operation failed");
+    found.sort();
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn test_ignore_file_traversal() {
+    use oqab::core::traversal::IgnoreFileTraversalStrategy;
+    use oqab::core::SilentObserver;
+    use oqab::utils::standard_search;
+    
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp directory");
+    let root = temp_dir.path().join("project");
+    std::fs::create_dir_all(root.join("dist")).unwrap();
+    std::fs::create_dir_all(root.join("web").join("vendor")).unwrap();
+    std::fs::write(temp_dir.path().join(IgnoreFileTraversalStrategy::FILE_NAME), "*.txt\n").unwrap();
+    std::fs::write(root.join(IgnoreFileTraversalStrategy::FILE_NAME), "dist/\n*.tmp\n").unwrap();
+    std::fs::write(root.join("web").join(IgnoreFileTraversalStrategy::FILE_NAME), "!keep.tmp\nvendor/\n").unwrap();
+    for file in ["dist/app.js", "scratch.tmp", "main.txt", "web/keep.tmp", "web/draft.tmp", "web/vendor/lib.js", "web/page.js"] {
+        std::fs::write(root.join(file), "text").unwrap();
+    }
+    
+    // Deeper files take precedence, and those above the root do not apply
+    let strategy = IgnoreFileTraversalStrategy::new(&root);
+    assert!(!strategy.should_process_directory(&root.join("dist")));
+    assert!(!strategy.should_process_directory(&root.join("web").join("vendor")));
+    assert!(!strategy.should_process_file(&root.join("web").join("draft.tmp")));
+    assert!(strategy.should_process_file(&root.join("web").join("keep.tmp")));
+    assert!(strategy.should_process_file(&root.join("main.txt")));
+    
+    // Finders and the standard search skip the same files without being asked to
+    let mut config = FileSearchConfig::new();
+    config.path = Some(root.to_string_lossy().to_string());
+    let finder = FinderFactory::from_search_config(&config).expect("Failed to configure finder");
+    let (found, _) = finder.find(&root).expect("Search operation failed");
+    let mut found: Vec<_> = found.into_iter().map(|found| found.path).collect();
+    found.sort();
+    let expected = [root.join("main.txt"), root.join("web").join("keep.tmp"), root.join("web").join("page.js")];
+    assert_eq!(found, expected);
+    
+    let mut found = standard_search::search_directory(&root, &config, &SilentObserver::new()).expect("Search operation failed");
+    found.sort();
+    assert_eq!(found, expected);
+}
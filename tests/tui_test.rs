@@ -0,0 +1,39 @@
+#![cfg(feature = "tui")]
+
+use std::path::{Path, PathBuf};
+use oqab::tui::Browser;
+
+#[test]
+fn test_browser_filter_keeps_selection() {
+    let mut browser = Browser::new();
+    for path in ["/src/main.rs", "/src/cli/args.rs", "/README.md", "/src/cli/mod.rs"] {
+        browser.push(PathBuf::from(path));
+    }
+    browser.move_by(1);
+    assert_eq!(browser.selected(), Some(Path::new("/src/cli/args.rs")));
+    
+    // Filtering ignores case and keeps the cursor on the same result
+    browser.set_filter("CLI");
+    assert_eq!(browser.visible_count(), 2);
+    assert_eq!(browser.selected(), Some(Path::new("/src/cli/args.rs")));
+    
+    // Results arriving later are filtered too
+    browser.push(PathBuf::from("/src/cli/help.rs"));
+    browser.push(PathBuf::from("/Cargo.toml"));
+    let visible: Vec<&Path> = browser.visible(0, 10).collect();
+    assert_eq!(visible, [Path::new("/src/cli/args.rs"), Path::new("/src/cli/mod.rs"), Path::new("/src/cli/help.rs")]);
+    assert_eq!(browser.results().len(), 6);
+    
+    // The cursor stops at either end
+    browser.move_by(10);
+    assert_eq!(browser.cursor(), 2);
+    browser.move_by(-10);
+    assert_eq!(browser.cursor(), 0);
+    
+    // A selection filtered out sends the cursor back to the top
+    browser.move_to_end();
+    browser.set_filter("args");
+    assert_eq!(browser.selected(), Some(Path::new("/src/cli/args.rs")));
+    browser.set_filter("nothing");
+    assert_eq!(browser.selected(), None);
+}